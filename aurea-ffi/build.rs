@@ -41,6 +41,7 @@ fn main() {
         "native/platform/windows/utils.c",
         "native/platform/windows/window.c",
         "native/platform/windows/menu.c",
+        "native/platform/windows/keyboard.c",
         "native/platform/windows/elements/common.c",
         "native/platform/windows/elements/button.c",
         "native/platform/windows/elements/label.c",
@@ -86,6 +87,7 @@ fn main() {
         "native/platform/macos/macos_ops.m",
         "native/platform/macos/window.m",
         "native/platform/macos/menu.m",
+        "native/platform/macos/keyboard.m",
         "native/platform/macos/utils.m",
         "native/platform/macos/elements/button.m",
         "native/platform/macos/elements/label.m",
@@ -111,7 +113,19 @@ fn main() {
         "native/platform/linux/linux_ops.c",
         "native/platform/linux/utils.c",
         "native/platform/linux/window.c",
+        "native/platform/linux/window_state.c",
+        "native/platform/linux/clipboard.c",
+        "native/platform/linux/cursor.c",
+        "native/platform/linux/dialog.c",
+        "native/platform/linux/dnd.c",
+        "native/platform/linux/ime.c",
+        "native/platform/linux/keyboard.c",
+        "native/platform/linux/pointer.c",
         "native/platform/linux/menu.c",
+        "native/platform/linux/tray.c",
+        "native/platform/linux/notification.c",
+        "native/platform/linux/accessibility.c",
+        "native/platform/linux/focus.c",
         "native/platform/linux/elements/button.c",
         "native/platform/linux/elements/label.c",
         "native/platform/linux/elements/box.c",
@@ -238,6 +252,7 @@ fn main() {
         "native/platform/windows/utils.h",
         "native/platform/windows/window.h",
         "native/platform/windows/menu.h",
+        "native/platform/windows/keyboard.h",
         "native/platform/windows/elements.h",
     ];
 
@@ -245,6 +260,7 @@ fn main() {
         "native/platform/macos.h",
         "native/platform/macos/window.h",
         "native/platform/macos/menu.h",
+        "native/platform/macos/keyboard.h",
         "native/platform/macos/utils.h",
         "native/platform/macos/elements.h",
     ];
@@ -253,7 +269,19 @@ fn main() {
         "native/platform/linux.h",
         "native/platform/linux/utils.h",
         "native/platform/linux/window.h",
+        "native/platform/linux/window_state.h",
+        "native/platform/linux/clipboard.h",
+        "native/platform/linux/cursor.h",
+        "native/platform/linux/dialog.h",
+        "native/platform/linux/dnd.h",
+        "native/platform/linux/ime.h",
+        "native/platform/linux/keyboard.h",
+        "native/platform/linux/pointer.h",
         "native/platform/linux/menu.h",
+        "native/platform/linux/tray.h",
+        "native/platform/linux/notification.h",
+        "native/platform/linux/accessibility.h",
+        "native/platform/linux/focus.h",
         "native/platform/linux/elements.h",
     ];
 
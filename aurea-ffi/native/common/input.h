@@ -88,11 +88,155 @@
 #define NG_KEY_PERIOD 76u        /* . */
 #define NG_KEY_SLASH 77u         /* / */
 
+#define NG_KEY_CAPS_LOCK 78u
+#define NG_KEY_NUM_LOCK 79u
+#define NG_KEY_SCROLL_LOCK 80u
+#define NG_KEY_PRINT_SCREEN 81u
+#define NG_KEY_PAUSE 82u
+#define NG_KEY_CONTEXT_MENU 83u
+
+/* Keypad digits and operators (Num Lock on). */
+#define NG_KEY_NUMPAD_0 84u
+#define NG_KEY_NUMPAD_1 85u
+#define NG_KEY_NUMPAD_2 86u
+#define NG_KEY_NUMPAD_3 87u
+#define NG_KEY_NUMPAD_4 88u
+#define NG_KEY_NUMPAD_5 89u
+#define NG_KEY_NUMPAD_6 90u
+#define NG_KEY_NUMPAD_7 91u
+#define NG_KEY_NUMPAD_8 92u
+#define NG_KEY_NUMPAD_9 93u
+#define NG_KEY_NUMPAD_ADD 94u
+#define NG_KEY_NUMPAD_SUBTRACT 95u
+#define NG_KEY_NUMPAD_MULTIPLY 96u
+#define NG_KEY_NUMPAD_DIVIDE 97u
+#define NG_KEY_NUMPAD_DECIMAL 98u
+#define NG_KEY_NUMPAD_ENTER 99u
+#define NG_KEY_NUMPAD_EQUALS 100u
+
+#define NG_KEY_F13 101u
+#define NG_KEY_F14 102u
+#define NG_KEY_F15 103u
+#define NG_KEY_F16 104u
+#define NG_KEY_F17 105u
+#define NG_KEY_F18 106u
+#define NG_KEY_F19 107u
+#define NG_KEY_F20 108u
+#define NG_KEY_F21 109u
+#define NG_KEY_F22 110u
+#define NG_KEY_F23 111u
+#define NG_KEY_F24 112u
+
+#define NG_KEY_MEDIA_PLAY_PAUSE 113u
+#define NG_KEY_MEDIA_STOP 114u
+#define NG_KEY_MEDIA_NEXT_TRACK 115u
+#define NG_KEY_MEDIA_PREVIOUS_TRACK 116u
+#define NG_KEY_VOLUME_UP 117u
+#define NG_KEY_VOLUME_DOWN 118u
+#define NG_KEY_VOLUME_MUTE 119u
+
 #define NG_KEY_UNKNOWN 0xFFFFFFFFu
 
+/* Physical keys are USB HID usages (keyboard page 0x07; consumer page keys
+ * as 0x000C0000 | usage). Keys without a mapping pass the platform scancode
+ * with this bit set. */
+#define NG_PHYSICAL_UNIDENTIFIED 0x80000000u
+
 #define NG_MOD_SHIFT 0x1u
 #define NG_MOD_CTRL 0x2u
 #define NG_MOD_ALT 0x4u
 #define NG_MOD_META 0x8u
+/* Lock state rather than a held key. */
+#define NG_MOD_CAPS_LOCK 0x10u
+#define NG_MOD_NUM_LOCK 0x20u
+/* Held modifiers only; shortcut matching ignores lock state. */
+#define NG_MOD_HELD_MASK 0xFu
+
+#define NG_SCROLL_DELTA_LINE 0u
+#define NG_SCROLL_DELTA_PIXEL 1u
+
+#define NG_SCROLL_PHASE_DISCRETE 0u
+#define NG_SCROLL_PHASE_BEGAN 1u
+#define NG_SCROLL_PHASE_CHANGED 2u
+#define NG_SCROLL_PHASE_ENDED 3u
+#define NG_SCROLL_PHASE_MOMENTUM_BEGAN 4u
+#define NG_SCROLL_PHASE_MOMENTUM 5u
+#define NG_SCROLL_PHASE_MOMENTUM_ENDED 6u
+
+#define NG_POINTER_MOUSE 0u
+#define NG_POINTER_TOUCH 1u
+#define NG_POINTER_PEN 2u
+
+#define NG_POINTER_DOWN 0u
+#define NG_POINTER_MOVE 1u
+#define NG_POINTER_UP 2u
+#define NG_POINTER_CANCEL 3u
+
+#define NG_TOUCHPAD_PHASE_BEGAN 0u
+#define NG_TOUCHPAD_PHASE_CHANGED 1u
+#define NG_TOUCHPAD_PHASE_ENDED 2u
+#define NG_TOUCHPAD_PHASE_CANCELLED 3u
+
+#define NG_POINTER_BUTTON_PRIMARY 0x1u
+#define NG_POINTER_BUTTON_SECONDARY 0x2u
+#define NG_POINTER_BUTTON_MIDDLE 0x4u
+#define NG_POINTER_BUTTON_ERASER 0x8u
+
+/* One touch or pen sample, mirrored by `NGPointerEvent` in src/ffi/callbacks.rs.
+ * Coordinates are window-relative in platform units; tilt and twist are in
+ * degrees. */
+typedef struct NGPointerEvent {
+    unsigned long long pointer_id;
+    unsigned int kind;
+    unsigned int phase;
+    double x;
+    double y;
+    float pressure;
+    float tilt_x;
+    float tilt_y;
+    float twist;
+    unsigned int buttons;
+    unsigned int modifiers;
+} NGPointerEvent;
+
+/* One key press or release, mirrored by `NGKeyEvent` in src/ffi/callbacks.rs.
+ * `text` is the UTF-8 the layout maps the key to with Shift applied but not
+ * Ctrl/Alt/Meta, or NULL. */
+typedef struct NGKeyEvent {
+    unsigned int keycode;
+    unsigned int physical;
+    const char* text;
+    int pressed;
+    int repeat;
+    unsigned int modifiers;
+} NGKeyEvent;
+
+#define NG_DRAG_ENTERED 0u
+#define NG_DRAG_OVER 1u
+#define NG_DRAG_LEFT 2u
+#define NG_DRAG_DROPPED 3u
+
+#define NG_DRAG_DATA_FILES 0u
+#define NG_DRAG_DATA_TEXT 1u
+#define NG_DRAG_DATA_URIS 2u
+
+#define NG_DROP_NONE 0u
+#define NG_DROP_COPY 1u
+#define NG_DROP_MOVE 2u
+#define NG_DROP_LINK 3u
+
+/* One step of a drag over a window, mirrored by `NGDragEvent` in
+ * src/ffi/callbacks.rs. `items` holds UTF-8 file paths, URIs or a single
+ * text and is only set for NG_DRAG_ENTERED and NG_DRAG_DROPPED; `effect` is
+ * only meaningful for NG_DRAG_DROPPED. */
+typedef struct NGDragEvent {
+    unsigned int phase;
+    double x;
+    double y;
+    unsigned int data_kind;
+    const char* const* items;
+    int item_count;
+    unsigned int effect;
+} NGDragEvent;
 
 #endif // NATIVE_GUI_INPUT_H
@@ -2,6 +2,7 @@
 #define AUREA_PLATFORM_API_H
 
 #include "types.h"
+#include <stddef.h>
 #include <stdint.h>
 
 #define AUREA_FFI_ABI_VERSION 2
@@ -20,11 +21,58 @@ void ng_platform_cleanup(void);
 int ng_platform_run(void);
 int ng_platform_poll_events(void);
 void ng_platform_request_frame(void);
+// Wakes the loop for a frame pass after delay_ms. Platforms without a timer
+// source fall back to an immediate ng_platform_request_frame, so callers
+// have to keep requesting passes until the deadline; check first with
+// ng_platform_can_request_frame_after.
+void ng_platform_request_frame_after(unsigned int delay_ms);
+// 1 when ng_platform_request_frame_after waits for its delay.
+int ng_platform_can_request_frame_after(void);
 void ng_platform_frame_idle(void);
 
 // Window
 NGHandle ng_platform_create_window(const char* title, int width, int height);
 NGHandle ng_platform_create_window_with_type(const char* title, int width, int height, int window_type);
+
+#define NG_WINDOW_OPTIONS_VERSION 1u
+
+#define NG_WINDOW_POSITION_DEFAULT 0u
+#define NG_WINDOW_POSITION_EXPLICIT 1u
+#define NG_WINDOW_POSITION_CENTERED 2u
+
+/* Mirrored by `NGWindowOptions` in aurea-ffi/src/declarations.rs. `version`
+ * is NG_WINDOW_OPTIONS_VERSION at the time the caller was built; fields are
+ * only ever appended, so a platform reads those its version knows about.
+ * Zero min/max dimensions leave the limit unset. A centred window is placed
+ * over `parent`, or over its monitor without one. `icon_rgba` and `app_id`
+ * may be NULL. */
+typedef struct NGWindowOptions {
+    unsigned int version;
+    const char* title;
+    int width;
+    int height;
+    unsigned int position;
+    int x;
+    int y;
+    int min_width;
+    int min_height;
+    int max_width;
+    int max_height;
+    int resizable;
+    int decorated;
+    int visible;
+    int transparent;
+    int window_type;
+    NGHandle parent;
+    int modal;
+    const unsigned char* icon_rgba;
+    unsigned int icon_width;
+    unsigned int icon_height;
+    const char* app_id;
+} NGWindowOptions;
+
+// Create a window with every attribute in place before it is first shown.
+NGHandle ng_platform_create_window_with_options(const NGWindowOptions* options);
 void ng_platform_destroy_window(NGHandle handle);
 void ng_platform_window_set_title(NGHandle window, const char* title);
 int ng_platform_window_set_icon_rgba(
@@ -39,6 +87,102 @@ void ng_platform_window_request_close(NGHandle window);
 int ng_platform_window_is_focused(NGHandle window);
 int ng_platform_window_set_cursor_visible(NGHandle window, int visible);
 int ng_platform_window_set_cursor_grab(NGHandle window, int mode);
+// `name` is a CSS cursor keyword such as "pointer" or "ew-resize".
+int ng_platform_window_set_cursor_icon(NGHandle window, const char* name);
+int ng_platform_window_set_custom_cursor(
+    NGHandle window,
+    const unsigned char* rgba,
+    unsigned int width,
+    unsigned int height,
+    int hotspot_x,
+    int hotspot_y
+);
+// Answer the drag currently over `window` with an NG_DROP_* effect.
+int ng_platform_window_set_drop_effect(NGHandle window, unsigned int effect);
+// Start dragging `items` (NG_DRAG_DATA_* `data_kind`) out of `window` with
+// an RGBA8 preview. Call while a mouse button is held.
+int ng_platform_window_start_drag(
+    NGHandle window,
+    unsigned int data_kind,
+    const char* const* items,
+    int item_count,
+    const unsigned char* preview_rgba,
+    unsigned int preview_width,
+    unsigned int preview_height
+);
+int ng_platform_window_set_ime_allowed(NGHandle window, int allowed);
+int ng_platform_window_set_ime_cursor_area(NGHandle window, float x, float y, float width, float height);
+
+// Window state
+#define NG_WINDOW_STATE_MINIMIZED 1u
+#define NG_WINDOW_STATE_MAXIMIZED 2u
+#define NG_WINDOW_STATE_FULLSCREEN 4u
+#define NG_WINDOW_STATE_ALWAYS_ON_TOP 8u
+#define NG_WINDOW_STATE_RESIZABLE 16u
+#define NG_WINDOW_STATE_DECORATED 32u
+
+// Bitmask of the NG_WINDOW_STATE_* flags currently set. Changes are
+// reported through ng_invoke_window_state_changed.
+unsigned int ng_platform_window_get_state(NGHandle window);
+// `monitor` is a monitor index, or -1 for the one the window is on. Ignored
+// when leaving fullscreen.
+int ng_platform_window_set_fullscreen(NGHandle window, int fullscreen, int monitor);
+int ng_platform_window_set_maximized(NGHandle window, int maximized);
+int ng_platform_window_set_minimized(NGHandle window, int minimized);
+// Limits in logical pixels; 0x0 removes the limit.
+int ng_platform_window_set_min_size(NGHandle window, int width, int height);
+int ng_platform_window_set_max_size(NGHandle window, int width, int height);
+int ng_platform_window_set_resizable(NGHandle window, int resizable);
+int ng_platform_window_set_always_on_top(NGHandle window, int always_on_top);
+int ng_platform_window_set_decorations(NGHandle window, int decorated);
+// Give the window an alpha channel so translucent canvas backgrounds show
+// what is behind it. Needs a compositing window manager.
+int ng_platform_window_set_transparent(NGHandle window, int transparent);
+// Keep `window` above `parent` (NULL detaches it), minimise it along with
+// the parent and destroy it when the parent is destroyed. A modal window
+// blocks input to its parent while shown.
+int ng_platform_window_set_parent(NGHandle window, NGHandle parent, int modal);
+
+// Monitors, by index; -1 when unknown.
+int ng_platform_primary_monitor(void);
+int ng_platform_window_current_monitor(NGHandle window);
+int ng_platform_monitor_count(void);
+
+/* Mirrored by `NGMonitorInfo` in aurea-ffi/src/declarations.rs. Geometry is
+ * in logical pixels in the desktop's coordinate space; the work area leaves
+ * out panels and docks. `refresh_millihertz` is 0 when unknown. */
+typedef struct NGMonitorInfo {
+    char name[128];
+    int x;
+    int y;
+    int width;
+    int height;
+    int work_x;
+    int work_y;
+    int work_width;
+    int work_height;
+    float scale_factor;
+    int refresh_millihertz;
+} NGMonitorInfo;
+
+int ng_platform_monitor_info(int monitor, NGMonitorInfo* info);
+
+/* Mirrored by `NGWindowFrame` in aurea-ffi/src/declarations.rs. The outer
+ * frame includes the title bar and borders; the content rect is the client
+ * area. Both are in logical pixels in the desktop's coordinate space. */
+typedef struct NGWindowFrame {
+    int x;
+    int y;
+    int width;
+    int height;
+    int content_x;
+    int content_y;
+    int content_width;
+    int content_height;
+} NGWindowFrame;
+
+// Fails until the window has been realized.
+int ng_platform_window_get_frame(NGHandle window, NGWindowFrame* frame);
 NGHandle ng_platform_window_get_content_view(NGHandle window);
 void ng_platform_window_show(NGHandle window);
 void ng_platform_window_hide(NGHandle window);
@@ -56,6 +200,48 @@ int ng_platform_add_menu_item(NGMenuHandle menu, const char* title, unsigned int
 int ng_platform_add_menu_separator(NGMenuHandle menu);
 NGMenuHandle ng_platform_create_submenu(NGMenuHandle parent_menu, const char* title);
 
+#define NG_MENU_ITEM_NORMAL 0u
+#define NG_MENU_ITEM_CHECK 1u
+#define NG_MENU_ITEM_RADIO 2u
+
+/* Mirrored by `NGMenuItemOptions` in aurea-ffi/src/declarations.rs. The
+ * accelerator is `accel_char` (a Unicode character) when nonzero, else
+ * `accel_key` (NG_KEY_*, NG_KEY_UNKNOWN for none), with NG_MOD_* modifiers.
+ * Radio items with the same `radio_group` in one menu are exclusive.
+ * `icon_rgba` may be NULL. */
+typedef struct NGMenuItemOptions {
+    unsigned int kind;
+    const char* title;
+    unsigned int id;
+    unsigned int radio_group;
+    int checked;
+    int enabled;
+    unsigned int accel_char;
+    unsigned int accel_key;
+    unsigned int accel_modifiers;
+    const unsigned char* icon_rgba;
+    unsigned int icon_width;
+    unsigned int icon_height;
+} NGMenuItemOptions;
+
+// Append an item to `menu` and return its handle in `item`. Activating it
+// calls ng_invoke_menu_callback with `options->id`.
+int ng_platform_menu_add_item(NGMenuHandle menu, const NGMenuItemOptions* options, NGHandle* item);
+int ng_platform_menu_item_set_enabled(NGHandle item, int enabled);
+// Changing the check state does not invoke the item's callback.
+int ng_platform_menu_item_set_checked(NGHandle item, int checked);
+int ng_platform_menu_item_is_checked(NGHandle item);
+int ng_platform_menu_item_set_label(NGHandle item, const char* label);
+// Destroy the item; `item` is invalid afterwards.
+int ng_platform_menu_item_remove(NGHandle item);
+
+// A popup menu root; fill it like a submenu and free it with ng_platform_destroy_menu.
+NGMenuHandle ng_platform_create_context_menu(void);
+// Pop `menu` up at (x, y) in logical points relative to `anchor`, which is a
+// window or an element inside one. Returns immediately; items report through
+// ng_invoke_menu_callback.
+int ng_platform_show_context_menu(NGHandle anchor, NGMenuHandle menu, float x, float y);
+
 // Elements
 NGHandle ng_platform_create_button(const char* title, unsigned int id);
 void ng_platform_button_invalidate(NGHandle button);
@@ -165,11 +351,181 @@ void ng_platform_sidebar_list_invalidate(NGHandle sidebar);
  * or when Swift implementation not linked. */
 NGHandle ng_platform_create_swiftui_host(int width, int height);
 
+// Dialogs
+#define NG_FILE_DIALOG_OPEN 0u
+#define NG_FILE_DIALOG_SAVE 1u
+#define NG_FILE_DIALOG_FOLDER 2u
+
+/* Mirrored by `NGFileDialogOptions` in aurea-ffi/src/declarations.rs. Any
+ * string may be NULL. `filter_extensions[i]` lists the extensions for
+ * `filter_names[i]` without dots, separated by ';' ("png;jpg"). */
+typedef struct NGFileDialogOptions {
+    unsigned int mode;
+    const char* title;
+    const char* directory;
+    const char* file_name;
+    const char* const* filter_names;
+    const char* const* filter_extensions;
+    int filter_count;
+    int multiple;
+    int blocking;
+} NGFileDialogOptions;
+
+// Show a file dialog, modal to `parent` when set. The chosen paths go to
+// ng_invoke_file_dialog_result with `request_id`, or none on cancel. A
+// blocking dialog reports before returning; otherwise the event loop keeps
+// running and the result arrives later.
+int ng_platform_file_dialog_show(NGHandle parent, const NGFileDialogOptions* options, unsigned int request_id);
+
+#define NG_MESSAGE_INFO 0u
+#define NG_MESSAGE_WARNING 1u
+#define NG_MESSAGE_ERROR 2u
+#define NG_MESSAGE_QUESTION 3u
+
+/* Mirrored by `NGMessageDialogOptions` in aurea-ffi/src/declarations.rs.
+ * `title` and `detail` may be NULL. With no buttons a single OK button is
+ * shown as index 0. `cancel_button` is reported when the dialog is closed
+ * with Escape or the title bar; -1 reports no choice. */
+typedef struct NGMessageDialogOptions {
+    unsigned int kind;
+    const char* title;
+    const char* message;
+    const char* detail;
+    const char* const* buttons;
+    int button_count;
+    int default_button;
+    int cancel_button;
+    int blocking;
+} NGMessageDialogOptions;
+
+/* Mirrored by `NGFontDescription` in aurea-ffi/src/declarations.rs. `size`
+ * is in points. */
+typedef struct NGFontDescription {
+    const char* family;
+    float size;
+    int bold;
+    int italic;
+} NGFontDescription;
+
+// Show a message box. The pressed button's index goes to
+// ng_invoke_message_dialog_result, or -1 when dismissed.
+int ng_platform_message_dialog_show(NGHandle parent, const NGMessageDialogOptions* options, unsigned int request_id);
+// Show a colour picker starting at `initial_rgba` (4 bytes, may be NULL).
+// The chosen colour goes to ng_invoke_color_picker_result, NULL on cancel.
+int ng_platform_color_picker_show(
+    NGHandle parent,
+    const char* title,
+    const unsigned char* initial_rgba,
+    int show_alpha,
+    int blocking,
+    unsigned int request_id);
+// Show a font picker starting at `initial` (may be NULL). The chosen font
+// goes to ng_invoke_font_picker_result, NULL on cancel.
+int ng_platform_font_picker_show(
+    NGHandle parent,
+    const char* title,
+    const NGFontDescription* initial,
+    int blocking,
+    unsigned int request_id);
+
 // Clipboard (global, not per-window)
 char* ng_platform_get_clipboard_text(void);
 void ng_platform_free_clipboard_text(char* text);
 int ng_platform_set_clipboard_text(const char* text);
 
+#define NG_CLIPBOARD_STANDARD 0u
+#define NG_CLIPBOARD_PRIMARY 1u
+
+/* One representation of a copy, mirrored by `NGClipboardItem` in
+ * aurea-ffi/src/declarations.rs. For "image/png" `data` holds `width` x
+ * `height` RGBA8 pixels that the platform encodes; for "text/uri-list" it
+ * holds newline-separated URIs. */
+typedef struct NGClipboardItem {
+    const char* mime_type;
+    const unsigned char* data;
+    size_t length;
+    unsigned int width;
+    unsigned int height;
+} NGClipboardItem;
+
+// Replace the contents of `selection` (NG_CLIPBOARD_*) with `items`.
+int ng_platform_clipboard_set(unsigned int selection, const NGClipboardItem* items, int count);
+// Newline-separated MIME types on offer; free with ng_platform_free_clipboard_text.
+char* ng_platform_clipboard_types(unsigned int selection);
+// The contents as `mime_type`, or NULL. "image/png" comes back as RGBA8 with
+// `width` and `height` set. Free with ng_platform_clipboard_free.
+unsigned char* ng_platform_clipboard_get(
+    unsigned int selection,
+    const char* mime_type,
+    size_t* length,
+    unsigned int* width,
+    unsigned int* height
+);
+void ng_platform_clipboard_free(unsigned char* data);
+// Report changes to `selection` through ng_invoke_clipboard_changed.
+int ng_platform_clipboard_watch(unsigned int selection);
+
+// Tray / status icons (global, not per-window)
+/* Show a status icon of `width` x `height` RGBA8 pixels. Clicks report
+ * through ng_invoke_tray_event with the returned handle. NULL when the
+ * platform or desktop has no tray. */
+NGHandle ng_platform_tray_create(const unsigned char* rgba, unsigned int width, unsigned int height, const char* tooltip);
+int ng_platform_tray_set_icon(NGHandle tray, const unsigned char* rgba, unsigned int width, unsigned int height);
+int ng_platform_tray_set_tooltip(NGHandle tray, const char* tooltip);
+// Offer `menu`, a context menu root, on secondary click; NULL removes it.
+int ng_platform_tray_set_menu(NGHandle tray, NGMenuHandle menu);
+void ng_platform_tray_destroy(NGHandle tray);
+
+// Accessibility
+/* Name a native element for assistive technologies, overriding the name
+ * the toolkit derives from its contents. `description` may be NULL. */
+int ng_platform_element_set_accessible(NGHandle element, const char* name, const char* description);
+
+// Keyboard focus
+// Give `element` (a widget or canvas) keyboard focus within its window.
+int ng_platform_element_focus(NGHandle element);
+/* With `managed` set, Tab and Shift-Tab still arrive as key events but no
+ * longer move focus through the toolkit's own chain, and every change of
+ * the focused widget is reported through ng_invoke_native_focus_changed. */
+int ng_platform_window_set_focus_traversal(NGHandle window, int managed);
+/* Mark `element` as one the application traverses to. An editable text
+ * view keeps Tab for itself while focused unless it is marked. */
+int ng_platform_element_set_focus_target(NGHandle element, int registered);
+// 1 when the window's focused widget takes Tab as input, not as traversal.
+int ng_platform_window_focus_keeps_tab(NGHandle window);
+
+// Desktop notifications (global, not per-window)
+#define NG_NOTIFICATION_URGENCY_LOW 0u
+#define NG_NOTIFICATION_URGENCY_NORMAL 1u
+#define NG_NOTIFICATION_URGENCY_CRITICAL 2u
+
+/* Mirrored by `NGNotificationOptions` in aurea-ffi/src/declarations.rs.
+ * `icon_rgba` may be NULL; `timeout_ms` < 0 leaves expiry to the server and
+ * 0 never expires. */
+typedef struct NGNotificationOptions {
+    const char* title;
+    const char* body;
+    const unsigned char* icon_rgba;
+    unsigned int icon_width;
+    unsigned int icon_height;
+    const char* const* action_ids;
+    const char* const* action_labels;
+    int action_count;
+    unsigned int urgency;
+    int timeout_ms;
+} NGNotificationOptions;
+
+/* Show a notification. Action clicks report through
+ * ng_invoke_notification_action and its end through
+ * ng_invoke_notification_closed, both with the returned handle. NULL when no
+ * notification server is available. */
+NGHandle ng_platform_notification_show(const NGNotificationOptions* options);
+// Replace the contents of a notification that is still showing.
+int ng_platform_notification_update(NGHandle notification, const NGNotificationOptions* options);
+int ng_platform_notification_close(NGHandle notification);
+// Free the handle and stop reporting its events; the notification stays up.
+void ng_platform_notification_release(NGHandle notification);
+
 #ifdef __cplusplus
 }
 #endif
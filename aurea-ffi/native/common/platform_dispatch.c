@@ -64,6 +64,20 @@ void ng_platform_frame_idle(void) {
     DISPATCH_VOID(frame_idle);
 }
 
+void ng_platform_request_frame_after(unsigned int delay_ms) {
+    ensure_ops();
+    if (g_ops->request_frame_after) {
+        g_ops->request_frame_after(delay_ms);
+    } else if (g_ops->request_frame) {
+        g_ops->request_frame();
+    }
+}
+
+int ng_platform_can_request_frame_after(void) {
+    ensure_ops();
+    return g_ops && g_ops->request_frame_after ? 1 : 0;
+}
+
 NGHandle ng_platform_create_window(const char* t, int w, int h) {
     DISPATCH_INIT(NGHandle, create_window, t, w, h);
 }
@@ -72,6 +86,34 @@ NGHandle ng_platform_create_window_with_type(const char* t, int w, int h, int wt
     DISPATCH_INIT(NGHandle, create_window_with_type, t, w, h, wt);
 }
 
+/* Platforms without their own implementation get a shown window of the
+ * right type with the remaining options applied afterwards. */
+static NGHandle create_window_then_configure(const NGWindowOptions* o) {
+    NGHandle w = ng_platform_create_window_with_type(o->title, o->width, o->height, o->window_type);
+    if (!w) return NULL;
+    if (o->position == NG_WINDOW_POSITION_EXPLICIT && g_ops->window_set_position) {
+        g_ops->window_set_position(w, o->x, o->y);
+    }
+    if (g_ops->window_set_min_size) g_ops->window_set_min_size(w, o->min_width, o->min_height);
+    if (g_ops->window_set_max_size) g_ops->window_set_max_size(w, o->max_width, o->max_height);
+    if (!o->resizable && g_ops->window_set_resizable) g_ops->window_set_resizable(w, 0);
+    if (!o->decorated && g_ops->window_set_decorations) g_ops->window_set_decorations(w, 0);
+    if (o->transparent && g_ops->window_set_transparent) g_ops->window_set_transparent(w, 1);
+    if ((o->parent || o->modal) && g_ops->window_set_parent) g_ops->window_set_parent(w, o->parent, o->modal);
+    if (o->icon_rgba && g_ops->window_set_icon_rgba) {
+        g_ops->window_set_icon_rgba(w, o->icon_rgba, o->icon_width, o->icon_height);
+    }
+    if (!o->visible && g_ops->window_hide) g_ops->window_hide(w);
+    return w;
+}
+
+NGHandle ng_platform_create_window_with_options(const NGWindowOptions* o) {
+    ensure_ops();
+    if (!o || o->version == 0) return NULL;
+    if (g_ops->create_window_with_options) return g_ops->create_window_with_options(o);
+    return create_window_then_configure(o);
+}
+
 void ng_platform_destroy_window(NGHandle h) {
     DISPATCH_VOID(destroy_window, h);
 }
@@ -113,6 +155,117 @@ int ng_platform_window_set_cursor_grab(NGHandle w, int m) {
     DISPATCH_INT(window_set_cursor_grab, w, m);
 }
 
+int ng_platform_window_set_cursor_icon(NGHandle w, const char* name) {
+    DISPATCH_INT(window_set_cursor_icon, w, name);
+}
+
+int ng_platform_window_set_custom_cursor(
+    NGHandle w,
+    const unsigned char* rgba,
+    unsigned int width,
+    unsigned int height,
+    int hotspot_x,
+    int hotspot_y
+) {
+    DISPATCH_INT(window_set_custom_cursor, w, rgba, width, height, hotspot_x, hotspot_y);
+}
+
+int ng_platform_window_set_drop_effect(NGHandle w, unsigned int effect) {
+    DISPATCH_INT(window_set_drop_effect, w, effect);
+}
+
+int ng_platform_window_start_drag(
+    NGHandle w,
+    unsigned int data_kind,
+    const char* const* items,
+    int item_count,
+    const unsigned char* preview_rgba,
+    unsigned int preview_width,
+    unsigned int preview_height
+) {
+    DISPATCH_INT(
+        window_start_drag,
+        w,
+        data_kind,
+        items,
+        item_count,
+        preview_rgba,
+        preview_width,
+        preview_height);
+}
+
+int ng_platform_window_set_ime_allowed(NGHandle w, int a) {
+    DISPATCH_INT(window_set_ime_allowed, w, a);
+}
+
+int ng_platform_window_set_ime_cursor_area(NGHandle w, float x, float y, float width, float height) {
+    DISPATCH_INT(window_set_ime_cursor_area, w, x, y, width, height);
+}
+
+unsigned int ng_platform_window_get_state(NGHandle w) {
+    DISPATCH_INIT(unsigned int, window_get_state, w);
+}
+
+int ng_platform_window_set_fullscreen(NGHandle w, int fullscreen, int monitor) {
+    DISPATCH_INT(window_set_fullscreen, w, fullscreen, monitor);
+}
+
+int ng_platform_window_set_maximized(NGHandle w, int maximized) {
+    DISPATCH_INT(window_set_maximized, w, maximized);
+}
+
+int ng_platform_window_set_minimized(NGHandle w, int minimized) {
+    DISPATCH_INT(window_set_minimized, w, minimized);
+}
+
+int ng_platform_window_set_min_size(NGHandle w, int width, int height) {
+    DISPATCH_INT(window_set_min_size, w, width, height);
+}
+
+int ng_platform_window_set_max_size(NGHandle w, int width, int height) {
+    DISPATCH_INT(window_set_max_size, w, width, height);
+}
+
+int ng_platform_window_set_resizable(NGHandle w, int resizable) {
+    DISPATCH_INT(window_set_resizable, w, resizable);
+}
+
+int ng_platform_window_set_always_on_top(NGHandle w, int always_on_top) {
+    DISPATCH_INT(window_set_always_on_top, w, always_on_top);
+}
+
+int ng_platform_window_set_decorations(NGHandle w, int decorated) {
+    DISPATCH_INT(window_set_decorations, w, decorated);
+}
+
+int ng_platform_window_set_transparent(NGHandle w, int transparent) {
+    DISPATCH_INT(window_set_transparent, w, transparent);
+}
+
+int ng_platform_window_set_parent(NGHandle w, NGHandle parent, int modal) {
+    DISPATCH_INT(window_set_parent, w, parent, modal);
+}
+
+int ng_platform_primary_monitor(void) {
+    DISPATCH_INIT_DEFAULT(int, -1, primary_monitor);
+}
+
+int ng_platform_window_current_monitor(NGHandle w) {
+    DISPATCH_INIT_DEFAULT(int, -1, window_current_monitor, w);
+}
+
+int ng_platform_monitor_count(void) {
+    DISPATCH_INIT(int, monitor_count);
+}
+
+int ng_platform_monitor_info(int monitor, NGMonitorInfo* info) {
+    DISPATCH_INT(monitor_info, monitor, info);
+}
+
+int ng_platform_window_get_frame(NGHandle w, NGWindowFrame* frame) {
+    DISPATCH_INT(window_get_frame, w, frame);
+}
+
 NGHandle ng_platform_window_get_content_view(NGHandle w) {
     DISPATCH_INIT(NGHandle, window_get_content_view, w);
 }
@@ -171,6 +324,38 @@ NGMenuHandle ng_platform_create_submenu(NGMenuHandle p, const char* t) {
     DISPATCH_INIT(NGMenuHandle, create_submenu, p, t);
 }
 
+int ng_platform_menu_add_item(NGMenuHandle m, const NGMenuItemOptions* options, NGHandle* item) {
+    DISPATCH_INT(menu_add_item, m, options, item);
+}
+
+int ng_platform_menu_item_set_enabled(NGHandle item, int enabled) {
+    DISPATCH_INT(menu_item_set_enabled, item, enabled);
+}
+
+int ng_platform_menu_item_set_checked(NGHandle item, int checked) {
+    DISPATCH_INT(menu_item_set_checked, item, checked);
+}
+
+int ng_platform_menu_item_is_checked(NGHandle item) {
+    DISPATCH_INIT(int, menu_item_is_checked, item);
+}
+
+int ng_platform_menu_item_set_label(NGHandle item, const char* label) {
+    DISPATCH_INT(menu_item_set_label, item, label);
+}
+
+int ng_platform_menu_item_remove(NGHandle item) {
+    DISPATCH_INT(menu_item_remove, item);
+}
+
+NGMenuHandle ng_platform_create_context_menu(void) {
+    DISPATCH_INIT(NGMenuHandle, create_context_menu);
+}
+
+int ng_platform_show_context_menu(NGHandle anchor, NGMenuHandle m, float x, float y) {
+    DISPATCH_INT(show_context_menu, anchor, m, x, y);
+}
+
 NGHandle ng_platform_create_button(const char* t, unsigned int id) {
     DISPATCH_INIT(NGHandle, create_button, t, id);
 }
@@ -475,6 +660,33 @@ NGHandle ng_platform_create_swiftui_host(int w, int h) {
     DISPATCH_INIT(NGHandle, create_swiftui_host, w, h);
 }
 
+int ng_platform_file_dialog_show(NGHandle parent, const NGFileDialogOptions* options, unsigned int request_id) {
+    DISPATCH_INT(file_dialog_show, parent, options, request_id);
+}
+
+int ng_platform_message_dialog_show(NGHandle parent, const NGMessageDialogOptions* options, unsigned int request_id) {
+    DISPATCH_INT(message_dialog_show, parent, options, request_id);
+}
+
+int ng_platform_color_picker_show(
+    NGHandle parent,
+    const char* title,
+    const unsigned char* initial_rgba,
+    int show_alpha,
+    int blocking,
+    unsigned int request_id) {
+    DISPATCH_INT(color_picker_show, parent, title, initial_rgba, show_alpha, blocking, request_id);
+}
+
+int ng_platform_font_picker_show(
+    NGHandle parent,
+    const char* title,
+    const NGFontDescription* initial,
+    int blocking,
+    unsigned int request_id) {
+    DISPATCH_INT(font_picker_show, parent, title, initial, blocking, request_id);
+}
+
 char* ng_platform_get_clipboard_text(void) {
     ensure_ops();
     return (g_ops && g_ops->get_clipboard_text) ? g_ops->get_clipboard_text() : NULL;
@@ -487,3 +699,89 @@ void ng_platform_free_clipboard_text(char* text) {
 int ng_platform_set_clipboard_text(const char* text) {
     DISPATCH_INT(set_clipboard_text, text);
 }
+
+int ng_platform_clipboard_set(unsigned int selection, const NGClipboardItem* items, int count) {
+    DISPATCH_INT(clipboard_set, selection, items, count);
+}
+
+char* ng_platform_clipboard_types(unsigned int selection) {
+    ensure_ops();
+    return (g_ops && g_ops->clipboard_types) ? g_ops->clipboard_types(selection) : NULL;
+}
+
+unsigned char* ng_platform_clipboard_get(
+    unsigned int selection,
+    const char* mime_type,
+    size_t* length,
+    unsigned int* width,
+    unsigned int* height
+) {
+    ensure_ops();
+    return (g_ops && g_ops->clipboard_get)
+        ? g_ops->clipboard_get(selection, mime_type, length, width, height)
+        : NULL;
+}
+
+void ng_platform_clipboard_free(unsigned char* data) {
+    if (data) { DISPATCH_VOID(clipboard_free, data); }
+}
+
+int ng_platform_clipboard_watch(unsigned int selection) {
+    DISPATCH_INT(clipboard_watch, selection);
+}
+
+NGHandle ng_platform_tray_create(const unsigned char* rgba, unsigned int width, unsigned int height, const char* tooltip) {
+    DISPATCH_INIT(NGHandle, tray_create, rgba, width, height, tooltip);
+}
+
+int ng_platform_tray_set_icon(NGHandle tray, const unsigned char* rgba, unsigned int width, unsigned int height) {
+    DISPATCH_INT(tray_set_icon, tray, rgba, width, height);
+}
+
+int ng_platform_tray_set_tooltip(NGHandle tray, const char* tooltip) {
+    DISPATCH_INT(tray_set_tooltip, tray, tooltip);
+}
+
+int ng_platform_tray_set_menu(NGHandle tray, NGMenuHandle m) {
+    DISPATCH_INT(tray_set_menu, tray, m);
+}
+
+void ng_platform_tray_destroy(NGHandle tray) {
+    DISPATCH_VOID(tray_destroy, tray);
+}
+
+int ng_platform_element_set_accessible(NGHandle element, const char* name, const char* description) {
+    DISPATCH_INT(element_set_accessible, element, name, description);
+}
+
+int ng_platform_element_focus(NGHandle element) {
+    DISPATCH_INT(element_focus, element);
+}
+
+int ng_platform_window_set_focus_traversal(NGHandle window, int managed) {
+    DISPATCH_INT(window_set_focus_traversal, window, managed);
+}
+
+int ng_platform_element_set_focus_target(NGHandle element, int registered) {
+    DISPATCH_INT(element_set_focus_target, element, registered);
+}
+
+int ng_platform_window_focus_keeps_tab(NGHandle window) {
+    DISPATCH_INIT(int, window_focus_keeps_tab, window);
+}
+
+NGHandle ng_platform_notification_show(const NGNotificationOptions* options) {
+    DISPATCH_INIT(NGHandle, notification_show, options);
+}
+
+int ng_platform_notification_update(NGHandle notification, const NGNotificationOptions* options) {
+    DISPATCH_INT(notification_update, notification, options);
+}
+
+int ng_platform_notification_close(NGHandle notification) {
+    DISPATCH_INT(notification_close, notification);
+}
+
+void ng_platform_notification_release(NGHandle notification) {
+    DISPATCH_VOID(notification_release, notification);
+}
@@ -14,9 +14,11 @@ typedef struct ng_platform_ops {
     int (*poll_events)(void);
     void (*request_frame)(void);
     void (*frame_idle)(void);
+    void (*request_frame_after)(unsigned int delay_ms);
 
     NGHandle (*create_window)(const char* title, int width, int height);
     NGHandle (*create_window_with_type)(const char* title, int width, int height, int window_type);
+    NGHandle (*create_window_with_options)(const NGWindowOptions* options);
     void (*destroy_window)(NGHandle handle);
     void (*window_set_title)(NGHandle window, const char* title);
     int (*window_set_icon_rgba)(
@@ -31,6 +33,43 @@ typedef struct ng_platform_ops {
     int (*window_is_focused)(NGHandle window);
     int (*window_set_cursor_visible)(NGHandle window, int visible);
     int (*window_set_cursor_grab)(NGHandle window, int mode);
+    int (*window_set_cursor_icon)(NGHandle window, const char* name);
+    int (*window_set_custom_cursor)(
+        NGHandle window,
+        const unsigned char* rgba,
+        unsigned int width,
+        unsigned int height,
+        int hotspot_x,
+        int hotspot_y
+    );
+    int (*window_set_drop_effect)(NGHandle window, unsigned int effect);
+    int (*window_start_drag)(
+        NGHandle window,
+        unsigned int data_kind,
+        const char* const* items,
+        int item_count,
+        const unsigned char* preview_rgba,
+        unsigned int preview_width,
+        unsigned int preview_height
+    );
+    int (*window_set_ime_allowed)(NGHandle window, int allowed);
+    int (*window_set_ime_cursor_area)(NGHandle window, float x, float y, float width, float height);
+    unsigned int (*window_get_state)(NGHandle window);
+    int (*window_set_fullscreen)(NGHandle window, int fullscreen, int monitor);
+    int (*window_set_maximized)(NGHandle window, int maximized);
+    int (*window_set_minimized)(NGHandle window, int minimized);
+    int (*window_set_min_size)(NGHandle window, int width, int height);
+    int (*window_set_max_size)(NGHandle window, int width, int height);
+    int (*window_set_resizable)(NGHandle window, int resizable);
+    int (*window_set_always_on_top)(NGHandle window, int always_on_top);
+    int (*window_set_decorations)(NGHandle window, int decorated);
+    int (*window_set_transparent)(NGHandle window, int transparent);
+    int (*window_set_parent)(NGHandle window, NGHandle parent, int modal);
+    int (*primary_monitor)(void);
+    int (*window_current_monitor)(NGHandle window);
+    int (*monitor_count)(void);
+    int (*monitor_info)(int monitor, NGMonitorInfo* info);
+    int (*window_get_frame)(NGHandle window, NGWindowFrame* frame);
     NGHandle (*window_get_content_view)(NGHandle window);
     void (*window_show)(NGHandle window);
     void (*window_hide)(NGHandle window);
@@ -46,6 +85,14 @@ typedef struct ng_platform_ops {
     int (*add_menu_item)(NGMenuHandle menu, const char* title, unsigned int id);
     int (*add_menu_separator)(NGMenuHandle menu);
     NGMenuHandle (*create_submenu)(NGMenuHandle parent, const char* title);
+    int (*menu_add_item)(NGMenuHandle menu, const NGMenuItemOptions* options, NGHandle* item);
+    int (*menu_item_set_enabled)(NGHandle item, int enabled);
+    int (*menu_item_set_checked)(NGHandle item, int checked);
+    int (*menu_item_is_checked)(NGHandle item);
+    int (*menu_item_set_label)(NGHandle item, const char* label);
+    int (*menu_item_remove)(NGHandle item);
+    NGMenuHandle (*create_context_menu)(void);
+    int (*show_context_menu)(NGHandle anchor, NGMenuHandle menu, float x, float y);
 
     NGHandle (*create_button)(const char* title, unsigned int id);
     void (*button_invalidate)(NGHandle button);
@@ -135,10 +182,51 @@ typedef struct ng_platform_ops {
 
     NGHandle (*create_swiftui_host)(int width, int height);
 
+    int (*file_dialog_show)(NGHandle parent, const NGFileDialogOptions* options, unsigned int request_id);
+    int (*message_dialog_show)(NGHandle parent, const NGMessageDialogOptions* options, unsigned int request_id);
+    int (*color_picker_show)(
+        NGHandle parent,
+        const char* title,
+        const unsigned char* initial_rgba,
+        int show_alpha,
+        int blocking,
+        unsigned int request_id);
+    int (*font_picker_show)(
+        NGHandle parent,
+        const char* title,
+        const NGFontDescription* initial,
+        int blocking,
+        unsigned int request_id);
+
     /* Clipboard (global, not per-window) */
     char* (*get_clipboard_text)(void);
     void (*free_clipboard_text)(char* text);
     int (*set_clipboard_text)(const char* text);
+    int (*clipboard_set)(unsigned int selection, const NGClipboardItem* items, int count);
+    char* (*clipboard_types)(unsigned int selection);
+    unsigned char* (*clipboard_get)(
+        unsigned int selection,
+        const char* mime_type,
+        size_t* length,
+        unsigned int* width,
+        unsigned int* height
+    );
+    void (*clipboard_free)(unsigned char* data);
+    int (*clipboard_watch)(unsigned int selection);
+    NGHandle (*tray_create)(const unsigned char* rgba, unsigned int width, unsigned int height, const char* tooltip);
+    int (*tray_set_icon)(NGHandle tray, const unsigned char* rgba, unsigned int width, unsigned int height);
+    int (*tray_set_tooltip)(NGHandle tray, const char* tooltip);
+    int (*tray_set_menu)(NGHandle tray, NGMenuHandle menu);
+    void (*tray_destroy)(NGHandle tray);
+    int (*element_set_accessible)(NGHandle element, const char* name, const char* description);
+    int (*element_focus)(NGHandle element);
+    int (*window_set_focus_traversal)(NGHandle window, int managed);
+    int (*element_set_focus_target)(NGHandle element, int registered);
+    int (*window_focus_keeps_tab)(NGHandle window);
+    NGHandle (*notification_show)(const NGNotificationOptions* options);
+    int (*notification_update)(NGHandle notification, const NGNotificationOptions* options);
+    int (*notification_close)(NGHandle notification);
+    void (*notification_release)(NGHandle notification);
 } ng_platform_ops_t;
 
 void ng_platform_register_ops(const ng_platform_ops_t* ops);
@@ -1,6 +1,9 @@
 #ifndef AUREA_RUST_CALLBACKS_H
 #define AUREA_RUST_CALLBACKS_H
 
+#include "input.h"
+#include "platform_api.h"
+
 #ifdef __cplusplus
 extern "C" {
 #endif
@@ -19,11 +22,33 @@ void ng_invoke_tab_bar_selected(unsigned int id, int index);
 void ng_invoke_tab_bar_detach(unsigned int id, int index);
 void ng_invoke_sidebar_list_selected(unsigned int id, int index);
 
+void ng_invoke_clipboard_changed(unsigned int selection);
+void ng_invoke_file_dialog_result(unsigned int request_id, const char* const* paths, int count);
+void ng_invoke_message_dialog_result(unsigned int request_id, int button);
+void ng_invoke_color_picker_result(unsigned int request_id, const unsigned char* rgba);
+void ng_invoke_font_picker_result(unsigned int request_id, const NGFontDescription* font);
+
 void ng_invoke_text_callback(unsigned int id, const char* content);
 void ng_invoke_textview_callback(unsigned int id, const char* content);
 
 void ng_invoke_lifecycle_callback(void* window, unsigned int event_id);
-void ng_invoke_key_event(void* window, unsigned int keycode, int pressed, unsigned int modifiers);
+// `flag` is one NG_WINDOW_STATE_* bit other than MINIMIZED, which goes
+// through the lifecycle callback.
+void ng_invoke_window_state_changed(void* window, unsigned int flag, int enabled);
+// Displays were connected, removed or reconfigured.
+void ng_invoke_monitors_changed(void);
+// `button` is 0 primary, 1 secondary, 2 middle; `click_count` is 2 for the
+// second click of a double-click.
+void ng_invoke_tray_event(void* tray, int button, int click_count, int x, int y);
+// `action` is "default" for a click on the notification body.
+void ng_invoke_notification_action(void* notification, const char* action);
+// `reason` is the freedesktop NotificationClosed code: 1 expired,
+// 2 dismissed, 3 closed by the application, 4 other.
+void ng_invoke_notification_closed(void* notification, unsigned int reason);
+// The focused widget of a window with managed focus traversal changed;
+// `element` is NULL when nothing inside the window has focus.
+void ng_invoke_native_focus_changed(void* window, void* element);
+void ng_invoke_key_event(void* window, const NGKeyEvent* event);
 void ng_invoke_mouse_button(
     void* window,
     int button,
@@ -32,9 +57,31 @@ void ng_invoke_mouse_button(
     double x,
     double y,
     int click_count);
-void ng_invoke_mouse_move(void* window, double x, double y);
-void ng_invoke_mouse_wheel(void* window, double delta_x, double delta_y, unsigned int modifiers);
+void ng_invoke_mouse_move(void* window, double x, double y, unsigned int modifiers);
+void ng_invoke_mouse_wheel(
+    void* window,
+    double delta_x,
+    double delta_y,
+    unsigned int modifiers,
+    unsigned int delta_kind,
+    unsigned int phase
+);
 void ng_invoke_text_input(void* window, const char* text);
+void ng_invoke_pointer_event(void* window, const NGPointerEvent* event);
+/* Touchpad pinch/rotate: `scale` is relative to the gesture start, `rotation`
+ * is cumulative radians, clockwise positive. */
+void ng_invoke_touchpad_pinch(
+    void* window,
+    unsigned int phase,
+    double x,
+    double y,
+    double scale,
+    double rotation
+);
+void ng_invoke_drag_event(void* window, const NGDragEvent* event);
+void ng_invoke_ime_state(void* window, int enabled);
+void ng_invoke_ime_preedit(void* window, const char* text, int cursor_start, int cursor_end);
+void ng_invoke_ime_commit(void* window, const char* text);
 void ng_invoke_focus_changed(void* window, int focused);
 void ng_invoke_cursor_entered(void* window, int entered);
 void ng_invoke_raw_mouse_motion(void* window, double delta_x, double delta_y);
@@ -8,6 +8,8 @@
 #include <gtk/gtk.h>
 
 static guint g_frame_source_id = 0;
+static guint g_frame_timer_id = 0;
+static gint64 g_frame_timer_deadline = 0;
 
 static gboolean process_frames_once(gpointer user_data) {
     (void)user_data;
@@ -16,6 +18,13 @@ static gboolean process_frames_once(gpointer user_data) {
     return G_SOURCE_REMOVE;
 }
 
+static gboolean process_frames_after_delay(gpointer user_data) {
+    (void)user_data;
+    g_frame_timer_id = 0;
+    ng_process_frames();
+    return G_SOURCE_REMOVE;
+}
+
 int ng_linux_run(void) {
     gtk_main();
     return NG_SUCCESS;
@@ -34,3 +43,14 @@ void ng_linux_request_frame(void) {
         g_frame_source_id = g_idle_add(process_frames_once, NULL);
     }
 }
+
+void ng_linux_request_frame_after(unsigned int delay_ms) {
+    gint64 deadline = g_get_monotonic_time() + (gint64)delay_ms * 1000;
+    if (g_frame_timer_id != 0) {
+        // Keep the earlier of the pending and the requested wake-up.
+        if (g_frame_timer_deadline <= deadline) return;
+        g_source_remove(g_frame_timer_id);
+    }
+    g_frame_timer_deadline = deadline;
+    g_frame_timer_id = g_timeout_add(delay_ms, process_frames_after_delay, NULL);
+}
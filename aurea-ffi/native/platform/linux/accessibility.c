@@ -0,0 +1,13 @@
+#include "accessibility.h"
+#include "common/errors.h"
+#include <gtk/gtk.h>
+
+int ng_linux_element_set_accessible(NGHandle element, const char* name, const char* description) {
+    if (!element || !name) return NG_ERROR_INVALID_PARAMETER;
+    /* GTK exposes its ATK objects to AT-SPI through the atk-bridge. */
+    AtkObject* accessible = gtk_widget_get_accessible(GTK_WIDGET(element));
+    if (!accessible) return NG_ERROR_PLATFORM_SPECIFIC;
+    atk_object_set_name(accessible, name);
+    atk_object_set_description(accessible, description ? description : "");
+    return NG_SUCCESS;
+}
@@ -0,0 +1,16 @@
+#ifndef NATIVE_GUI_LINUX_ACCESSIBILITY_H
+#define NATIVE_GUI_LINUX_ACCESSIBILITY_H
+
+#include "common/platform_api.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int ng_linux_element_set_accessible(NGHandle element, const char* name, const char* description);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_ACCESSIBILITY_H
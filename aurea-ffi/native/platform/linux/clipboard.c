@@ -0,0 +1,242 @@
+#include "clipboard.h"
+#include "utils.h"
+#include "common/errors.h"
+#include "common/rust_callbacks.h"
+#include <gtk/gtk.h>
+#include <string.h>
+
+#define MIME_TEXT "text/plain"
+#define MIME_PNG "image/png"
+#define MIME_URIS "text/uri-list"
+
+/* One representation, owned by the clipboard until the next copy replaces
+ * it. Exactly one of `pixbuf`, `uris` or `bytes` is served; `target` is set
+ * for MIME types GTK has no converters for. */
+typedef struct {
+    GdkAtom target;
+    GBytes* bytes;
+    GdkPixbuf* pixbuf;
+    gchar** uris;
+} OwnedItem;
+
+typedef struct {
+    OwnedItem* items;
+    int count;
+} OwnedContents;
+
+static GtkClipboard* clipboard_for(unsigned int selection) {
+    switch (selection) {
+        case NG_CLIPBOARD_STANDARD:
+            return gtk_clipboard_get(GDK_SELECTION_CLIPBOARD);
+        case NG_CLIPBOARD_PRIMARY:
+            return gtk_clipboard_get(GDK_SELECTION_PRIMARY);
+        default:
+            return NULL;
+    }
+}
+
+static void contents_free(OwnedContents* contents) {
+    for (int i = 0; i < contents->count; i++) {
+        OwnedItem* item = &contents->items[i];
+        if (item->bytes) g_bytes_unref(item->bytes);
+        if (item->pixbuf) g_object_unref(item->pixbuf);
+        g_strfreev(item->uris);
+    }
+    g_free(contents->items);
+    g_free(contents);
+}
+
+/* Copy `item` and register the targets it can be served as under `index`. */
+static gboolean own_item(OwnedItem* out, const NGClipboardItem* item, GtkTargetList* targets, guint index) {
+    if (!item->mime_type || (!item->data && item->length > 0)) return FALSE;
+
+    if (strcmp(item->mime_type, MIME_PNG) == 0) {
+        if (!item->data || item->width == 0 || item->height == 0) return FALSE;
+        out->pixbuf = ng_linux_pixbuf_from_rgba(item->data, item->width, item->height);
+        if (!out->pixbuf) return FALSE;
+        gtk_target_list_add_image_targets(targets, index, TRUE);
+        return TRUE;
+    }
+
+    if (strcmp(item->mime_type, MIME_URIS) == 0) {
+        gchar* text = g_strndup((const gchar*)item->data, item->length);
+        out->uris = g_strsplit(text, "\n", -1);
+        g_free(text);
+        gtk_target_list_add_uri_targets(targets, index);
+        return TRUE;
+    }
+
+    out->bytes = g_bytes_new(item->data, item->length);
+    if (strcmp(item->mime_type, MIME_TEXT) == 0) {
+        gtk_target_list_add_text_targets(targets, index);
+    } else {
+        out->target = gdk_atom_intern(item->mime_type, FALSE);
+        gtk_target_list_add(targets, out->target, 0, index);
+    }
+    return TRUE;
+}
+
+static void on_get(GtkClipboard* clipboard, GtkSelectionData* selection, guint info, gpointer data) {
+    (void)clipboard;
+    OwnedContents* contents = data;
+    if (info >= (guint)contents->count) return;
+    OwnedItem* item = &contents->items[info];
+
+    if (item->pixbuf) {
+        gtk_selection_data_set_pixbuf(selection, item->pixbuf);
+    } else if (item->uris) {
+        gtk_selection_data_set_uris(selection, item->uris);
+    } else {
+        gsize size = 0;
+        const guchar* bytes = g_bytes_get_data(item->bytes, &size);
+        if (item->target == GDK_NONE) {
+            gtk_selection_data_set_text(selection, (const gchar*)bytes, (gint)size);
+        } else {
+            gtk_selection_data_set(selection, item->target, 8, bytes, (gint)size);
+        }
+    }
+}
+
+static void on_clear(GtkClipboard* clipboard, gpointer data) {
+    (void)clipboard;
+    contents_free(data);
+}
+
+int ng_linux_clipboard_set(unsigned int selection, const NGClipboardItem* items, int count) {
+    GtkClipboard* clipboard = clipboard_for(selection);
+    if (!clipboard || (count > 0 && !items)) return NG_ERROR_INVALID_PARAMETER;
+    if (count <= 0) {
+        gtk_clipboard_clear(clipboard);
+        return NG_SUCCESS;
+    }
+
+    OwnedContents* contents = g_new0(OwnedContents, 1);
+    contents->items = g_new0(OwnedItem, count);
+    contents->count = count;
+    GtkTargetList* list = gtk_target_list_new(NULL, 0);
+    for (int i = 0; i < count; i++) {
+        if (!own_item(&contents->items[i], &items[i], list, (guint)i)) {
+            gtk_target_list_unref(list);
+            contents_free(contents);
+            return NG_ERROR_INVALID_PARAMETER;
+        }
+    }
+
+    gint n_targets = 0;
+    GtkTargetEntry* targets = gtk_target_table_new_from_list(list, &n_targets);
+    gtk_target_list_unref(list);
+    gboolean owned = gtk_clipboard_set_with_data(clipboard, targets, (guint)n_targets, on_get, on_clear, contents);
+    gtk_target_table_free(targets, n_targets);
+    if (!owned) {
+        contents_free(contents);
+        return NG_ERROR_PLATFORM_SPECIFIC;
+    }
+    /* Let a clipboard manager keep the copy after we exit. */
+    if (selection == NG_CLIPBOARD_STANDARD) gtk_clipboard_set_can_store(clipboard, NULL, 0);
+    return NG_SUCCESS;
+}
+
+static void add_type(GPtrArray* types, const char* mime_type) {
+    for (guint i = 0; i < types->len; i++) {
+        if (g_strcmp0(g_ptr_array_index(types, i), mime_type) == 0) return;
+    }
+    g_ptr_array_add(types, g_strdup(mime_type));
+}
+
+char* ng_linux_clipboard_types(unsigned int selection) {
+    GtkClipboard* clipboard = clipboard_for(selection);
+    GdkAtom* atoms = NULL;
+    gint n_atoms = 0;
+    if (!clipboard || !gtk_clipboard_wait_for_targets(clipboard, &atoms, &n_atoms)) return NULL;
+
+    /* The types we convert come first, then whatever MIME types the owner
+     * lists; X11 also advertises non-MIME targets like TARGETS, which are
+     * skipped. */
+    GPtrArray* types = g_ptr_array_new_with_free_func(g_free);
+    if (gtk_targets_include_text(atoms, n_atoms)) add_type(types, MIME_TEXT);
+    if (gtk_targets_include_image(atoms, n_atoms, FALSE)) add_type(types, MIME_PNG);
+    if (gtk_targets_include_uri(atoms, n_atoms)) add_type(types, MIME_URIS);
+    for (gint i = 0; i < n_atoms; i++) {
+        gchar* name = gdk_atom_name(atoms[i]);
+        if (name && strchr(name, '/')) add_type(types, name);
+        g_free(name);
+    }
+    g_free(atoms);
+
+    g_ptr_array_add(types, NULL);
+    gchar* joined = g_strjoinv("\n", (gchar**)types->pdata);
+    g_ptr_array_free(types, TRUE);
+    return joined;
+}
+
+static unsigned char* raw_contents(GtkClipboard* clipboard, const char* mime_type, size_t* length) {
+    GtkSelectionData* data = gtk_clipboard_wait_for_contents(clipboard, gdk_atom_intern(mime_type, FALSE));
+    if (!data) return NULL;
+    gint size = gtk_selection_data_get_length(data);
+    unsigned char* out = NULL;
+    if (size >= 0) {
+        /* NUL-terminated so an empty result is still non-NULL. */
+        out = g_malloc((gsize)size + 1);
+        memcpy(out, gtk_selection_data_get_data(data), (size_t)size);
+        out[size] = '\0';
+        *length = (size_t)size;
+    }
+    gtk_selection_data_free(data);
+    return out;
+}
+
+unsigned char* ng_linux_clipboard_get(
+    unsigned int selection,
+    const char* mime_type,
+    size_t* length,
+    unsigned int* width,
+    unsigned int* height) {
+    GtkClipboard* clipboard = clipboard_for(selection);
+    if (!clipboard || !mime_type || !length || !width || !height) return NULL;
+    *length = 0;
+    *width = 0;
+    *height = 0;
+
+    if (strcmp(mime_type, MIME_PNG) == 0) {
+        GdkPixbuf* image = gtk_clipboard_wait_for_image(clipboard);
+        unsigned char* rgba = ng_linux_rgba_from_pixbuf(image, width, height);
+        if (image) g_object_unref(image);
+        if (rgba) *length = (size_t)*width * (size_t)*height * 4;
+        return rgba;
+    }
+    if (strcmp(mime_type, MIME_TEXT) == 0) {
+        gchar* text = gtk_clipboard_wait_for_text(clipboard);
+        if (text) *length = strlen(text);
+        return (unsigned char*)text;
+    }
+    if (strcmp(mime_type, MIME_URIS) == 0) {
+        gchar** uris = gtk_clipboard_wait_for_uris(clipboard);
+        if (!uris) return NULL;
+        gchar* joined = g_strjoinv("\n", uris);
+        g_strfreev(uris);
+        *length = strlen(joined);
+        return (unsigned char*)joined;
+    }
+    return raw_contents(clipboard, mime_type, length);
+}
+
+void ng_linux_clipboard_free(unsigned char* data) {
+    g_free(data);
+}
+
+static void on_owner_change(GtkClipboard* clipboard, GdkEvent* event, gpointer data) {
+    (void)clipboard;
+    (void)event;
+    ng_invoke_clipboard_changed(GPOINTER_TO_UINT(data));
+}
+
+int ng_linux_clipboard_watch(unsigned int selection) {
+    static gboolean watching[2] = {FALSE, FALSE};
+    GtkClipboard* clipboard = clipboard_for(selection);
+    if (!clipboard) return NG_ERROR_INVALID_PARAMETER;
+    if (!watching[selection]) {
+        g_signal_connect(clipboard, "owner-change", G_CALLBACK(on_owner_change), GUINT_TO_POINTER(selection));
+        watching[selection] = TRUE;
+    }
+    return NG_SUCCESS;
+}
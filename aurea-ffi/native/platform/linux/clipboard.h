@@ -0,0 +1,25 @@
+#ifndef NATIVE_GUI_LINUX_CLIPBOARD_H
+#define NATIVE_GUI_LINUX_CLIPBOARD_H
+
+#include "common/platform_api.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int ng_linux_clipboard_set(unsigned int selection, const NGClipboardItem* items, int count);
+char* ng_linux_clipboard_types(unsigned int selection);
+unsigned char* ng_linux_clipboard_get(
+    unsigned int selection,
+    const char* mime_type,
+    size_t* length,
+    unsigned int* width,
+    unsigned int* height);
+void ng_linux_clipboard_free(unsigned char* data);
+int ng_linux_clipboard_watch(unsigned int selection);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_CLIPBOARD_H
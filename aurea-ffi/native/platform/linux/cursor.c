@@ -0,0 +1,88 @@
+#include "cursor.h"
+#include "utils.h"
+#include "common/errors.h"
+
+#define CURSOR_KEY "aurea-cursor"
+#define CURSOR_HIDDEN_KEY "aurea-cursor-hidden"
+
+static gboolean is_hidden(GtkWidget* window) {
+    return GPOINTER_TO_INT(g_object_get_data(G_OBJECT(window), CURSOR_HIDDEN_KEY)) != 0;
+}
+
+/* Show the stored cursor, or the blank one while hidden. A NULL cursor means
+ * the default arrow. */
+static int apply_cursor(GtkWidget* window) {
+    GdkWindow* gdk_window = gtk_widget_get_window(window);
+    if (!gdk_window) return NG_ERROR_INVALID_HANDLE;
+
+    if (!is_hidden(window)) {
+        gdk_window_set_cursor(gdk_window, g_object_get_data(G_OBJECT(window), CURSOR_KEY));
+        return NG_SUCCESS;
+    }
+
+    GdkCursor* blank = gdk_cursor_new_for_display(gdk_window_get_display(gdk_window), GDK_BLANK_CURSOR);
+    if (!blank) return NG_ERROR_PLATFORM_SPECIFIC;
+    gdk_window_set_cursor(gdk_window, blank);
+    g_object_unref(blank);
+    return NG_SUCCESS;
+}
+
+/* Takes ownership of `cursor`, which may be NULL. */
+static int store_cursor(GtkWidget* window, GdkCursor* cursor) {
+    g_object_set_data_full(G_OBJECT(window), CURSOR_KEY, cursor, cursor ? g_object_unref : NULL);
+    return apply_cursor(window);
+}
+
+int ng_linux_cursor_set_visible(GtkWidget* window, gboolean visible) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    g_object_set_data(G_OBJECT(window), CURSOR_HIDDEN_KEY, GINT_TO_POINTER(visible ? 0 : 1));
+    return apply_cursor(window);
+}
+
+int ng_linux_window_set_cursor_icon(NGHandle window, const char* name) {
+    if (!window || !name) return NG_ERROR_INVALID_PARAMETER;
+    GtkWidget* widget = (GtkWidget*)window;
+    if (g_strcmp0(name, "default") == 0) {
+        return store_cursor(widget, NULL);
+    }
+
+    GdkDisplay* display = gtk_widget_get_display(widget);
+    if (!display) return NG_ERROR_PLATFORM_SPECIFIC;
+    /* GDK resolves CSS names against the cursor theme; a theme without the
+     * shape gets the default arrow rather than an error. */
+    return store_cursor(widget, gdk_cursor_new_from_name(display, name));
+}
+
+int ng_linux_window_set_custom_cursor(
+    NGHandle window,
+    const unsigned char* rgba,
+    unsigned int width,
+    unsigned int height,
+    int hotspot_x,
+    int hotspot_y) {
+    if (!window || !rgba || width == 0 || height == 0) {
+        return NG_ERROR_INVALID_PARAMETER;
+    }
+    GtkWidget* widget = (GtkWidget*)window;
+    GdkDisplay* display = gtk_widget_get_display(widget);
+    if (!display) return NG_ERROR_PLATFORM_SPECIFIC;
+
+    GdkPixbuf* pixbuf = ng_linux_pixbuf_from_rgba(rgba, width, height);
+    if (!pixbuf) return NG_ERROR_PLATFORM_SPECIFIC;
+
+    /* The image is in device pixels; GDK takes the hotspot in logical ones. */
+    int scale = gtk_widget_get_scale_factor(widget);
+    if (scale < 1) scale = 1;
+    cairo_surface_t* surface = gdk_cairo_surface_create_from_pixbuf(pixbuf, scale, NULL);
+    g_object_unref(pixbuf);
+    if (!surface) return NG_ERROR_PLATFORM_SPECIFIC;
+
+    GdkCursor* cursor = gdk_cursor_new_from_surface(
+        display,
+        surface,
+        (gdouble)hotspot_x / scale,
+        (gdouble)hotspot_y / scale);
+    cairo_surface_destroy(surface);
+    if (!cursor) return NG_ERROR_PLATFORM_SPECIFIC;
+    return store_cursor(widget, cursor);
+}
@@ -0,0 +1,26 @@
+#ifndef NATIVE_GUI_LINUX_CURSOR_H
+#define NATIVE_GUI_LINUX_CURSOR_H
+
+#include "common/types.h"
+#include <gtk/gtk.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Hide or show the cursor; showing restores the icon last set for `window`.
+int ng_linux_cursor_set_visible(GtkWidget* window, gboolean visible);
+int ng_linux_window_set_cursor_icon(NGHandle window, const char* name);
+int ng_linux_window_set_custom_cursor(
+    NGHandle window,
+    const unsigned char* rgba,
+    unsigned int width,
+    unsigned int height,
+    int hotspot_x,
+    int hotspot_y);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_CURSOR_H
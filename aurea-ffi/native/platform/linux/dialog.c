@@ -0,0 +1,310 @@
+#include "dialog.h"
+#include "common/errors.h"
+#include "common/rust_callbacks.h"
+#include <gtk/gtk.h>
+
+static void report_files(GtkFileChooser* chooser, gint response, unsigned int request_id) {
+    if (response != GTK_RESPONSE_ACCEPT) {
+        ng_invoke_file_dialog_result(request_id, NULL, 0);
+        return;
+    }
+
+    GSList* files = gtk_file_chooser_get_filenames(chooser);
+    guint count = g_slist_length(files);
+    const char** paths = g_new0(const char*, count + 1);
+    guint i = 0;
+    for (GSList* it = files; it; it = it->next) {
+        paths[i++] = it->data;
+    }
+    ng_invoke_file_dialog_result(request_id, paths, (int)count);
+    g_free(paths);
+    g_slist_free_full(files, g_free);
+}
+
+static void on_file_dialog_response(GtkNativeDialog* dialog, gint response, gpointer user_data) {
+    report_files(GTK_FILE_CHOOSER(dialog), response, GPOINTER_TO_UINT(user_data));
+    g_object_unref(dialog);
+}
+
+/* GTK patterns are case-sensitive, so match both "*.png" and "*.PNG". */
+static void add_filter(GtkFileChooser* chooser, const char* name, const char* extensions) {
+    GtkFileFilter* filter = gtk_file_filter_new();
+    gtk_file_filter_set_name(filter, name ? name : "");
+
+    gchar** list = g_strsplit(extensions ? extensions : "", ";", -1);
+    for (gchar** ext = list; *ext; ext++) {
+        if (**ext == '\0') continue;
+        if (g_strcmp0(*ext, "*") == 0) {
+            gtk_file_filter_add_pattern(filter, "*");
+            continue;
+        }
+        gchar* lower = g_ascii_strdown(*ext, -1);
+        gchar* upper = g_ascii_strup(*ext, -1);
+        gchar* lower_pattern = g_strdup_printf("*.%s", lower);
+        gchar* upper_pattern = g_strdup_printf("*.%s", upper);
+        gtk_file_filter_add_pattern(filter, lower_pattern);
+        gtk_file_filter_add_pattern(filter, upper_pattern);
+        g_free(lower_pattern);
+        g_free(upper_pattern);
+        g_free(lower);
+        g_free(upper);
+    }
+    g_strfreev(list);
+    gtk_file_chooser_add_filter(chooser, filter);
+}
+
+static GtkFileChooserAction chooser_action(unsigned int mode) {
+    switch (mode) {
+        case NG_FILE_DIALOG_SAVE:
+            return GTK_FILE_CHOOSER_ACTION_SAVE;
+        case NG_FILE_DIALOG_FOLDER:
+            return GTK_FILE_CHOOSER_ACTION_SELECT_FOLDER;
+        default:
+            return GTK_FILE_CHOOSER_ACTION_OPEN;
+    }
+}
+
+int ng_linux_file_dialog_show(NGHandle parent, const NGFileDialogOptions* options, unsigned int request_id) {
+    if (!options || options->mode > NG_FILE_DIALOG_FOLDER || options->filter_count < 0) {
+        return NG_ERROR_INVALID_PARAMETER;
+    }
+    if (options->filter_count > 0 && (!options->filter_names || !options->filter_extensions)) {
+        return NG_ERROR_INVALID_PARAMETER;
+    }
+
+    /* The native variant goes through the desktop portal when sandboxed. */
+    GtkFileChooserNative* dialog = gtk_file_chooser_native_new(
+        options->title,
+        parent ? GTK_WINDOW(parent) : NULL,
+        chooser_action(options->mode),
+        NULL,
+        NULL);
+    if (!dialog) return NG_ERROR_PLATFORM_SPECIFIC;
+    GtkFileChooser* chooser = GTK_FILE_CHOOSER(dialog);
+    gtk_native_dialog_set_modal(GTK_NATIVE_DIALOG(dialog), TRUE);
+
+    if (options->directory) gtk_file_chooser_set_current_folder(chooser, options->directory);
+    if (options->mode == NG_FILE_DIALOG_SAVE) {
+        gtk_file_chooser_set_do_overwrite_confirmation(chooser, TRUE);
+        if (options->file_name) gtk_file_chooser_set_current_name(chooser, options->file_name);
+    } else {
+        gtk_file_chooser_set_select_multiple(chooser, options->multiple != 0);
+    }
+    for (int i = 0; i < options->filter_count; i++) {
+        add_filter(chooser, options->filter_names[i], options->filter_extensions[i]);
+    }
+
+    if (options->blocking) {
+        gint response = gtk_native_dialog_run(GTK_NATIVE_DIALOG(dialog));
+        report_files(chooser, response, request_id);
+        g_object_unref(dialog);
+        return NG_SUCCESS;
+    }
+    g_signal_connect(dialog, "response", G_CALLBACK(on_file_dialog_response), GUINT_TO_POINTER(request_id));
+    gtk_native_dialog_show(GTK_NATIVE_DIALOG(dialog));
+    return NG_SUCCESS;
+}
+
+typedef enum {
+    REQUEST_MESSAGE,
+    REQUEST_COLOR,
+    REQUEST_FONT,
+} RequestKind;
+
+/* Owned by the dialog; reports exactly once, on response or when the dialog
+ * is destroyed along with its parent. */
+typedef struct {
+    RequestKind kind;
+    unsigned int request_id;
+    int button_count;
+    int cancel_button;
+    gboolean reported;
+} DialogRequest;
+
+static unsigned char color_channel(gdouble value) {
+    return (unsigned char)(CLAMP(value, 0.0, 1.0) * 255.0 + 0.5);
+}
+
+static void report_color(GtkDialog* dialog, unsigned int request_id, gint response) {
+    if (response != GTK_RESPONSE_OK) {
+        ng_invoke_color_picker_result(request_id, NULL);
+        return;
+    }
+    GdkRGBA color;
+    gtk_color_chooser_get_rgba(GTK_COLOR_CHOOSER(dialog), &color);
+    unsigned char rgba[4] = {
+        color_channel(color.red),
+        color_channel(color.green),
+        color_channel(color.blue),
+        color_channel(color.alpha),
+    };
+    ng_invoke_color_picker_result(request_id, rgba);
+}
+
+static void report_font(GtkDialog* dialog, unsigned int request_id, gint response) {
+    PangoFontDescription* desc =
+        response == GTK_RESPONSE_OK ? gtk_font_chooser_get_font_desc(GTK_FONT_CHOOSER(dialog)) : NULL;
+    if (!desc) {
+        ng_invoke_font_picker_result(request_id, NULL);
+        return;
+    }
+    /* Absolute sizes are in device pixels; convert to points at 96 DPI. */
+    float size = (float)pango_font_description_get_size(desc) / PANGO_SCALE;
+    if (pango_font_description_get_size_is_absolute(desc)) size *= 72.0f / 96.0f;
+    NGFontDescription font = {
+        .family = pango_font_description_get_family(desc),
+        .size = size,
+        .bold = pango_font_description_get_weight(desc) >= PANGO_WEIGHT_SEMIBOLD,
+        .italic = pango_font_description_get_style(desc) != PANGO_STYLE_NORMAL,
+    };
+    ng_invoke_font_picker_result(request_id, &font);
+    pango_font_description_free(desc);
+}
+
+static void report_response(GtkDialog* dialog, DialogRequest* request, gint response) {
+    if (request->reported) return;
+    request->reported = TRUE;
+    switch (request->kind) {
+        case REQUEST_MESSAGE: {
+            int button = response >= 0 && response < request->button_count ? response : request->cancel_button;
+            ng_invoke_message_dialog_result(request->request_id, button);
+            break;
+        }
+        case REQUEST_COLOR:
+            report_color(dialog, request->request_id, response);
+            break;
+        case REQUEST_FONT:
+            report_font(dialog, request->request_id, response);
+            break;
+    }
+}
+
+static void on_dialog_response(GtkDialog* dialog, gint response, gpointer user_data) {
+    report_response(dialog, user_data, response);
+    gtk_widget_destroy(GTK_WIDGET(dialog));
+}
+
+static void on_dialog_destroy(GtkWidget* dialog, gpointer user_data) {
+    report_response(GTK_DIALOG(dialog), user_data, GTK_RESPONSE_DELETE_EVENT);
+}
+
+static int run_dialog(GtkWidget* dialog, DialogRequest* request, int blocking) {
+    gtk_window_set_modal(GTK_WINDOW(dialog), TRUE);
+    gtk_window_set_destroy_with_parent(GTK_WINDOW(dialog), TRUE);
+    g_object_set_data_full(G_OBJECT(dialog), "aurea-dialog-request", request, g_free);
+    g_signal_connect(dialog, "destroy", G_CALLBACK(on_dialog_destroy), request);
+
+    if (blocking) {
+        gint response = gtk_dialog_run(GTK_DIALOG(dialog));
+        report_response(GTK_DIALOG(dialog), request, response);
+        gtk_widget_destroy(dialog);
+        return NG_SUCCESS;
+    }
+    g_signal_connect(dialog, "response", G_CALLBACK(on_dialog_response), request);
+    gtk_widget_show(dialog);
+    return NG_SUCCESS;
+}
+
+static DialogRequest* new_request(RequestKind kind, unsigned int request_id) {
+    DialogRequest* request = g_new0(DialogRequest, 1);
+    request->kind = kind;
+    request->request_id = request_id;
+    request->cancel_button = -1;
+    return request;
+}
+
+static GtkMessageType message_type(unsigned int kind) {
+    switch (kind) {
+        case NG_MESSAGE_WARNING:
+            return GTK_MESSAGE_WARNING;
+        case NG_MESSAGE_ERROR:
+            return GTK_MESSAGE_ERROR;
+        case NG_MESSAGE_QUESTION:
+            return GTK_MESSAGE_QUESTION;
+        default:
+            return GTK_MESSAGE_INFO;
+    }
+}
+
+int ng_linux_message_dialog_show(NGHandle parent, const NGMessageDialogOptions* options, unsigned int request_id) {
+    if (!options || !options->message || options->kind > NG_MESSAGE_QUESTION || options->button_count < 0) {
+        return NG_ERROR_INVALID_PARAMETER;
+    }
+    if (options->button_count > 0 && !options->buttons) return NG_ERROR_INVALID_PARAMETER;
+
+    GtkWidget* dialog = gtk_message_dialog_new(
+        parent ? GTK_WINDOW(parent) : NULL,
+        GTK_DIALOG_MODAL | GTK_DIALOG_DESTROY_WITH_PARENT,
+        message_type(options->kind),
+        GTK_BUTTONS_NONE,
+        "%s",
+        options->message);
+    if (!dialog) return NG_ERROR_PLATFORM_SPECIFIC;
+    if (options->title) gtk_window_set_title(GTK_WINDOW(dialog), options->title);
+    if (options->detail) {
+        gtk_message_dialog_format_secondary_text(GTK_MESSAGE_DIALOG(dialog), "%s", options->detail);
+    }
+
+    /* Response ids are the button indices, so GTK's negative ids (Escape,
+     * closing the window) never collide with a button. */
+    int button_count = options->button_count;
+    if (button_count == 0) {
+        gtk_dialog_add_button(GTK_DIALOG(dialog), "_OK", 0);
+        button_count = 1;
+    }
+    for (int i = 0; i < options->button_count; i++) {
+        const char* label = options->buttons[i];
+        gtk_dialog_add_button(GTK_DIALOG(dialog), label ? label : "", i);
+    }
+    if (options->default_button >= 0 && options->default_button < button_count) {
+        gtk_dialog_set_default_response(GTK_DIALOG(dialog), options->default_button);
+    }
+
+    DialogRequest* request = new_request(REQUEST_MESSAGE, request_id);
+    request->button_count = button_count;
+    if (options->cancel_button < button_count) request->cancel_button = options->cancel_button;
+    return run_dialog(dialog, request, options->blocking);
+}
+
+int ng_linux_color_picker_show(
+    NGHandle parent,
+    const char* title,
+    const unsigned char* initial_rgba,
+    int show_alpha,
+    int blocking,
+    unsigned int request_id) {
+    GtkWidget* dialog = gtk_color_chooser_dialog_new(title, parent ? GTK_WINDOW(parent) : NULL);
+    if (!dialog) return NG_ERROR_PLATFORM_SPECIFIC;
+    GtkColorChooser* chooser = GTK_COLOR_CHOOSER(dialog);
+    gtk_color_chooser_set_use_alpha(chooser, show_alpha != 0);
+    if (initial_rgba) {
+        GdkRGBA color = {
+            .red = initial_rgba[0] / 255.0,
+            .green = initial_rgba[1] / 255.0,
+            .blue = initial_rgba[2] / 255.0,
+            .alpha = show_alpha ? initial_rgba[3] / 255.0 : 1.0,
+        };
+        gtk_color_chooser_set_rgba(chooser, &color);
+    }
+    return run_dialog(dialog, new_request(REQUEST_COLOR, request_id), blocking);
+}
+
+int ng_linux_font_picker_show(
+    NGHandle parent,
+    const char* title,
+    const NGFontDescription* initial,
+    int blocking,
+    unsigned int request_id) {
+    GtkWidget* dialog = gtk_font_chooser_dialog_new(title, parent ? GTK_WINDOW(parent) : NULL);
+    if (!dialog) return NG_ERROR_PLATFORM_SPECIFIC;
+    if (initial && initial->family) {
+        PangoFontDescription* desc = pango_font_description_new();
+        pango_font_description_set_family(desc, initial->family);
+        if (initial->size > 0.0f) pango_font_description_set_size(desc, (gint)(initial->size * PANGO_SCALE));
+        pango_font_description_set_weight(desc, initial->bold ? PANGO_WEIGHT_BOLD : PANGO_WEIGHT_NORMAL);
+        pango_font_description_set_style(desc, initial->italic ? PANGO_STYLE_ITALIC : PANGO_STYLE_NORMAL);
+        gtk_font_chooser_set_font_desc(GTK_FONT_CHOOSER(dialog), desc);
+        pango_font_description_free(desc);
+    }
+    return run_dialog(dialog, new_request(REQUEST_FONT, request_id), blocking);
+}
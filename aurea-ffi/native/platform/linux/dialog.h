@@ -0,0 +1,30 @@
+#ifndef NATIVE_GUI_LINUX_DIALOG_H
+#define NATIVE_GUI_LINUX_DIALOG_H
+
+#include "common/platform_api.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int ng_linux_file_dialog_show(NGHandle parent, const NGFileDialogOptions* options, unsigned int request_id);
+int ng_linux_message_dialog_show(NGHandle parent, const NGMessageDialogOptions* options, unsigned int request_id);
+int ng_linux_color_picker_show(
+    NGHandle parent,
+    const char* title,
+    const unsigned char* initial_rgba,
+    int show_alpha,
+    int blocking,
+    unsigned int request_id);
+int ng_linux_font_picker_show(
+    NGHandle parent,
+    const char* title,
+    const NGFontDescription* initial,
+    int blocking,
+    unsigned int request_id);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_DIALOG_H
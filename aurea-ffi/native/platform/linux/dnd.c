@@ -0,0 +1,378 @@
+#include "dnd.h"
+#include "utils.h"
+#include "common/errors.h"
+#include "common/input.h"
+#include "common/rust_callbacks.h"
+
+#define DND_STATE_KEY "aurea-dnd"
+#define DRAG_SOURCE_KEY "aurea-drag-source"
+
+enum { TARGET_URIS, TARGET_TEXT };
+
+/* The drag currently over a window. `effect` is the application's reply and
+ * starts as NG_DROP_NONE for every new drag. */
+typedef struct {
+    GdkDragContext* context;
+    gboolean entered;
+    gboolean dropping;
+    gboolean leave_pending;
+    unsigned int effect;
+    double x;
+    double y;
+} DndState;
+
+/* Content of a drag started from a window, served on request. `uris` is
+ * NULL for text drags. */
+typedef struct {
+    unsigned int kind;
+    gchar** items;
+    gchar** uris;
+} DragSource;
+
+static DndState* dnd_state(GtkWidget* window) {
+    return g_object_get_data(G_OBJECT(window), DND_STATE_KEY);
+}
+
+static void dnd_state_reset(DndState* state) {
+    if (state->context) g_object_unref(state->context);
+    state->context = NULL;
+    state->entered = FALSE;
+    state->dropping = FALSE;
+    state->leave_pending = FALSE;
+    state->effect = NG_DROP_NONE;
+}
+
+static void dnd_state_free(gpointer data) {
+    DndState* state = data;
+    dnd_state_reset(state);
+    g_free(state);
+}
+
+static void drag_source_free(gpointer data) {
+    DragSource* source = data;
+    g_strfreev(source->items);
+    g_strfreev(source->uris);
+    g_free(source);
+}
+
+static GdkDragAction action_for_effect(unsigned int effect) {
+    switch (effect) {
+        case NG_DROP_COPY:
+            return GDK_ACTION_COPY;
+        case NG_DROP_MOVE:
+            return GDK_ACTION_MOVE;
+        case NG_DROP_LINK:
+            return GDK_ACTION_LINK;
+        default:
+            return 0;
+    }
+}
+
+/* The reply as a GDK action, or 0 when the source doesn't offer it. */
+static GdkDragAction accepted_action(DndState* state) {
+    GdkDragAction action = action_for_effect(state->effect);
+    return gdk_drag_context_get_actions(state->context) & action ? action : 0;
+}
+
+static void emit(
+    GtkWidget* window,
+    unsigned int phase,
+    const DndState* state,
+    unsigned int kind,
+    gchar** items) {
+    NGDragEvent event = {0};
+    event.phase = phase;
+    event.x = state->x;
+    event.y = state->y;
+    event.data_kind = kind;
+    event.items = (const char* const*)items;
+    event.item_count = items ? (int)g_strv_length(items) : 0;
+    event.effect = state->effect;
+    ng_invoke_drag_event((void*)window, &event);
+}
+
+/* Local files become paths; any other URI keeps the whole list as URIs. */
+static gchar** uris_to_items(gchar** uris, unsigned int* kind) {
+    guint count = g_strv_length(uris);
+    gchar** paths = g_new0(gchar*, count + 1);
+    for (guint i = 0; i < count; i++) {
+        paths[i] = g_filename_from_uri(uris[i], NULL, NULL);
+        if (!paths[i]) {
+            g_strfreev(paths);
+            *kind = NG_DRAG_DATA_URIS;
+            return g_strdupv(uris);
+        }
+    }
+    *kind = NG_DRAG_DATA_FILES;
+    return paths;
+}
+
+static gchar** selection_items(GtkSelectionData* selection, unsigned int* kind) {
+    gchar** uris = gtk_selection_data_get_uris(selection);
+    if (uris && uris[0]) {
+        gchar** items = uris_to_items(uris, kind);
+        g_strfreev(uris);
+        return items;
+    }
+    g_strfreev(uris);
+
+    guchar* text = gtk_selection_data_get_text(selection);
+    if (!text) return NULL;
+    gchar** items = g_new0(gchar*, 2);
+    items[0] = (gchar*)text;
+    *kind = NG_DRAG_DATA_TEXT;
+    return items;
+}
+
+static gboolean request_data(GtkWidget* window, GdkDragContext* context, guint time) {
+    GdkAtom target = gtk_drag_dest_find_target(window, context, NULL);
+    if (target == GDK_NONE) return FALSE;
+    gtk_drag_get_data(window, context, target, time);
+    return TRUE;
+}
+
+static gboolean on_drag_motion(
+    GtkWidget* window,
+    GdkDragContext* context,
+    gint x,
+    gint y,
+    guint time,
+    gpointer user_data) {
+    (void)user_data;
+    DndState* state = dnd_state(window);
+    if (!state) return FALSE;
+
+    state->leave_pending = FALSE;
+    state->x = x;
+    state->y = y;
+    if (state->context != context) {
+        /* A new drag: fetch its data so DragEntered can carry it. */
+        dnd_state_reset(state);
+        state->context = g_object_ref(context);
+        if (!request_data(window, context, time)) {
+            gdk_drag_status(context, 0, time);
+            return TRUE;
+        }
+    } else if (state->entered) {
+        emit(window, NG_DRAG_OVER, state, NG_DRAG_DATA_TEXT, NULL);
+    }
+    gdk_drag_status(context, accepted_action(state), time);
+    return TRUE;
+}
+
+static gboolean flush_leave(gpointer data) {
+    GtkWidget* window = data;
+    DndState* state = dnd_state(window);
+    if (state && state->leave_pending) {
+        if (state->entered) emit(window, NG_DRAG_LEFT, state, NG_DRAG_DATA_TEXT, NULL);
+        dnd_state_reset(state);
+    }
+    g_object_unref(window);
+    return G_SOURCE_REMOVE;
+}
+
+/* GTK also sends drag-leave right before drag-drop, so a leave only counts
+ * if no drop follows in the same dispatch. */
+static void on_drag_leave(GtkWidget* window, GdkDragContext* context, guint time, gpointer user_data) {
+    (void)context;
+    (void)time;
+    (void)user_data;
+    DndState* state = dnd_state(window);
+    if (!state || state->dropping) return;
+    state->leave_pending = TRUE;
+    g_idle_add(flush_leave, g_object_ref(window));
+}
+
+static gboolean on_drag_drop(
+    GtkWidget* window,
+    GdkDragContext* context,
+    gint x,
+    gint y,
+    guint time,
+    gpointer user_data) {
+    (void)user_data;
+    DndState* state = dnd_state(window);
+    if (!state || state->context != context) return FALSE;
+
+    state->leave_pending = FALSE;
+    state->x = x;
+    state->y = y;
+    if (accepted_action(state) == 0 || !request_data(window, context, time)) {
+        gtk_drag_finish(context, FALSE, FALSE, time);
+        if (state->entered) emit(window, NG_DRAG_LEFT, state, NG_DRAG_DATA_TEXT, NULL);
+        dnd_state_reset(state);
+        return TRUE;
+    }
+    state->dropping = TRUE;
+    return TRUE;
+}
+
+static void on_drag_data_received(
+    GtkWidget* window,
+    GdkDragContext* context,
+    gint x,
+    gint y,
+    GtkSelectionData* selection,
+    guint info,
+    guint time,
+    gpointer user_data) {
+    (void)x;
+    (void)y;
+    (void)info;
+    (void)user_data;
+    DndState* state = dnd_state(window);
+    if (!state || state->context != context) return;
+
+    unsigned int kind = NG_DRAG_DATA_TEXT;
+    gchar** items = selection_items(selection, &kind);
+    if (state->dropping) {
+        gboolean ok = items != NULL;
+        if (ok || state->entered) {
+            emit(window, ok ? NG_DRAG_DROPPED : NG_DRAG_LEFT, state, kind, items);
+        }
+        gtk_drag_finish(context, ok, ok && state->effect == NG_DROP_MOVE, time);
+        dnd_state_reset(state);
+    } else if (items && !state->entered) {
+        /* Data we can't read is never offered, so it stays rejected. */
+        state->entered = TRUE;
+        emit(window, NG_DRAG_ENTERED, state, kind, items);
+    }
+    g_strfreev(items);
+}
+
+static void on_drag_data_get(
+    GtkWidget* window,
+    GdkDragContext* context,
+    GtkSelectionData* selection,
+    guint info,
+    guint time,
+    gpointer user_data) {
+    (void)context;
+    (void)time;
+    (void)user_data;
+    DragSource* source = g_object_get_data(G_OBJECT(window), DRAG_SOURCE_KEY);
+    if (!source) return;
+
+    if (source->uris && info == TARGET_URIS) {
+        gtk_selection_data_set_uris(selection, source->uris);
+        return;
+    }
+    gchar* text = g_strjoinv("\n", source->items);
+    gtk_selection_data_set_text(selection, text, -1);
+    g_free(text);
+}
+
+static void on_drag_end(GtkWidget* window, GdkDragContext* context, gpointer user_data) {
+    (void)context;
+    (void)user_data;
+    g_object_set_data(G_OBJECT(window), DRAG_SOURCE_KEY, NULL);
+}
+
+void ng_linux_dnd_attach(GtkWidget* window) {
+    gtk_drag_dest_set(window, 0, NULL, 0, GDK_ACTION_COPY | GDK_ACTION_MOVE | GDK_ACTION_LINK);
+    /* URI targets first so links and files aren't read as plain text. */
+    gtk_drag_dest_add_uri_targets(window);
+    gtk_drag_dest_add_text_targets(window);
+    g_object_set_data_full(G_OBJECT(window), DND_STATE_KEY, g_new0(DndState, 1), dnd_state_free);
+
+    g_signal_connect(G_OBJECT(window), "drag-motion", G_CALLBACK(on_drag_motion), NULL);
+    g_signal_connect(G_OBJECT(window), "drag-leave", G_CALLBACK(on_drag_leave), NULL);
+    g_signal_connect(G_OBJECT(window), "drag-drop", G_CALLBACK(on_drag_drop), NULL);
+    g_signal_connect(G_OBJECT(window), "drag-data-received", G_CALLBACK(on_drag_data_received), NULL);
+    g_signal_connect(G_OBJECT(window), "drag-data-get", G_CALLBACK(on_drag_data_get), NULL);
+    g_signal_connect(G_OBJECT(window), "drag-end", G_CALLBACK(on_drag_end), NULL);
+}
+
+int ng_linux_window_set_drop_effect(NGHandle window, unsigned int effect) {
+    if (!window || effect > NG_DROP_LINK) return NG_ERROR_INVALID_PARAMETER;
+    DndState* state = dnd_state((GtkWidget*)window);
+    if (!state) return NG_ERROR_INVALID_HANDLE;
+
+    state->effect = effect;
+    /* Answer now rather than on the next motion event. */
+    if (state->context && !state->dropping) {
+        gdk_drag_status(state->context, accepted_action(state), GDK_CURRENT_TIME);
+    }
+    return NG_SUCCESS;
+}
+
+static DragSource* drag_source_new(unsigned int kind, const char* const* items, int item_count) {
+    DragSource* source = g_new0(DragSource, 1);
+    source->kind = kind;
+    source->items = g_new0(gchar*, (gsize)item_count + 1);
+    for (int i = 0; i < item_count; i++) {
+        source->items[i] = g_strdup(items[i]);
+    }
+    if (kind == NG_DRAG_DATA_TEXT) return source;
+
+    source->uris = g_new0(gchar*, (gsize)item_count + 1);
+    for (int i = 0; i < item_count; i++) {
+        source->uris[i] = kind == NG_DRAG_DATA_FILES
+            ? g_filename_to_uri(items[i], NULL, NULL)
+            : g_strdup(items[i]);
+        if (!source->uris[i]) {
+            drag_source_free(source);
+            return NULL;
+        }
+    }
+    return source;
+}
+
+/* The preview is in device pixels, like cursors; its top-left corner
+ * follows the pointer. */
+static void set_preview(
+    GtkWidget* widget,
+    GdkDragContext* context,
+    const unsigned char* rgba,
+    unsigned int width,
+    unsigned int height) {
+    if (!rgba || width == 0 || height == 0) return;
+    GdkPixbuf* pixbuf = ng_linux_pixbuf_from_rgba(rgba, width, height);
+    if (!pixbuf) return;
+    int scale = gtk_widget_get_scale_factor(widget);
+    if (scale < 1) scale = 1;
+    cairo_surface_t* surface = gdk_cairo_surface_create_from_pixbuf(pixbuf, scale, NULL);
+    g_object_unref(pixbuf);
+    if (!surface) return;
+    gtk_drag_set_icon_surface(context, surface);
+    cairo_surface_destroy(surface);
+}
+
+int ng_linux_window_start_drag(
+    NGHandle window,
+    unsigned int data_kind,
+    const char* const* items,
+    int item_count,
+    const unsigned char* preview_rgba,
+    unsigned int preview_width,
+    unsigned int preview_height) {
+    if (!window || !items || item_count <= 0 || data_kind > NG_DRAG_DATA_URIS) {
+        return NG_ERROR_INVALID_PARAMETER;
+    }
+    for (int i = 0; i < item_count; i++) {
+        if (!items[i]) return NG_ERROR_INVALID_PARAMETER;
+    }
+    GtkWidget* widget = (GtkWidget*)window;
+    DragSource* source = drag_source_new(data_kind, items, item_count);
+    if (!source) return NG_ERROR_INVALID_PARAMETER;
+    g_object_set_data_full(G_OBJECT(widget), DRAG_SOURCE_KEY, source, drag_source_free);
+
+    GtkTargetList* targets = gtk_target_list_new(NULL, 0);
+    if (source->uris) gtk_target_list_add_uri_targets(targets, TARGET_URIS);
+    gtk_target_list_add_text_targets(targets, TARGET_TEXT);
+
+    /* Without a current event (handlers run after GTK's dispatch) GDK
+     * starts from the pointer's position; Wayland needs the event and
+     * refuses. */
+    GdkEvent* event = gtk_get_current_event();
+    GdkDragContext* context = gtk_drag_begin_with_coordinates(
+        widget, targets, GDK_ACTION_COPY, 1, event, -1, -1);
+    if (event) gdk_event_free(event);
+    gtk_target_list_unref(targets);
+    if (!context) {
+        g_object_set_data(G_OBJECT(widget), DRAG_SOURCE_KEY, NULL);
+        return NG_ERROR_PLATFORM_SPECIFIC;
+    }
+    set_preview(widget, context, preview_rgba, preview_width, preview_height);
+    return NG_SUCCESS;
+}
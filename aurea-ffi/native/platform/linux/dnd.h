@@ -0,0 +1,27 @@
+#ifndef NATIVE_GUI_LINUX_DND_H
+#define NATIVE_GUI_LINUX_DND_H
+
+#include "common/types.h"
+#include <gtk/gtk.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Make `window` a drop target and a drag source.
+void ng_linux_dnd_attach(GtkWidget* window);
+int ng_linux_window_set_drop_effect(NGHandle window, unsigned int effect);
+int ng_linux_window_start_drag(
+    NGHandle window,
+    unsigned int data_kind,
+    const char* const* items,
+    int item_count,
+    const unsigned char* preview_rgba,
+    unsigned int preview_width,
+    unsigned int preview_height);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_DND_H
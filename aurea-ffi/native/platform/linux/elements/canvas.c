@@ -1,6 +1,8 @@
 #include "../elements.h"
+#include "../window_state.h"
 #include "common/errors.h"
 #include <gtk/gtk.h>
+#include <stdint.h>
 #if defined(GDK_WINDOWING_X11) && defined(AUREA_HAVE_X11_XCB)
 #include <gdk/gdkx.h>
 #include <X11/Xlib-xcb.h>
@@ -16,6 +18,35 @@ typedef struct {
     int gpu_owned;
 } CanvasData;
 
+/* The rasterizer writes straight alpha while cairo expects premultiplied.
+ * The two agree for opaque pixels, so only transparent windows pay for the
+ * conversion. */
+static cairo_surface_t* premultiplied_surface(const unsigned char* buffer, unsigned int width, unsigned int height) {
+    cairo_surface_t* surface = cairo_image_surface_create(CAIRO_FORMAT_ARGB32, (int)width, (int)height);
+    if (cairo_surface_status(surface) != CAIRO_STATUS_SUCCESS) return surface;
+    cairo_surface_flush(surface);
+    unsigned char* data = cairo_image_surface_get_data(surface);
+    int stride = cairo_image_surface_get_stride(surface);
+    const uint32_t* src = (const uint32_t*)buffer;
+    for (unsigned int y = 0; y < height; y++) {
+        uint32_t* row = (uint32_t*)(data + (size_t)y * (size_t)stride);
+        for (unsigned int x = 0; x < width; x++) {
+            uint32_t pixel = src[(size_t)y * width + x];
+            uint32_t a = pixel >> 24;
+            if (a == 255) {
+                row[x] = pixel;
+                continue;
+            }
+            uint32_t r = ((pixel >> 16) & 0xFF) * a / 255;
+            uint32_t g = ((pixel >> 8) & 0xFF) * a / 255;
+            uint32_t b = (pixel & 0xFF) * a / 255;
+            row[x] = (a << 24) | (r << 16) | (g << 8) | b;
+        }
+    }
+    cairo_surface_mark_dirty(surface);
+    return surface;
+}
+
 static gboolean ng_linux_canvas_draw(GtkWidget* widget, cairo_t* cr, gpointer user_data) {
     CanvasData* data = (CanvasData*)user_data;
     if (data && data->gpu_owned) return TRUE;
@@ -23,13 +54,16 @@ static gboolean ng_linux_canvas_draw(GtkWidget* widget, cairo_t* cr, gpointer us
     GtkAllocation allocation;
     gtk_widget_get_allocation(widget, &allocation);
 
+    gboolean transparent = ng_linux_window_is_transparent(widget);
     if (data && data->buffer && data->width > 0 && data->height > 0) {
-        cairo_surface_t* surface = cairo_image_surface_create_for_data(
-            (unsigned char*)data->buffer,
-            CAIRO_FORMAT_ARGB32,
-            (int)data->width,
-            (int)data->height,
-            (int)data->width * 4);
+        cairo_surface_t* surface = transparent
+            ? premultiplied_surface(data->buffer, data->width, data->height)
+            : cairo_image_surface_create_for_data(
+                  (unsigned char*)data->buffer,
+                  CAIRO_FORMAT_ARGB32,
+                  (int)data->width,
+                  (int)data->height,
+                  (int)data->width * 4);
 
         if (cairo_surface_status(surface) == CAIRO_STATUS_SUCCESS) {
             cairo_save(cr);
@@ -57,6 +91,7 @@ static gboolean ng_linux_canvas_draw(GtkWidget* widget, cairo_t* cr, gpointer us
         return FALSE;
     }
 
+    if (transparent) return FALSE;
     cairo_set_source_rgb(cr, 1.0, 1.0, 1.0);
     cairo_rectangle(cr, 0, 0, allocation.width, allocation.height);
     cairo_fill(cr);
@@ -0,0 +1,71 @@
+#include "focus.h"
+#include "common/errors.h"
+#include "common/rust_callbacks.h"
+
+static const char* AUREA_FOCUS_MANAGED_KEY = "aurea-focus-managed";
+static const char* AUREA_FOCUS_HANDLER_KEY = "aurea-focus-handler";
+static const char* AUREA_FOCUS_TARGET_KEY = "aurea-focus-target";
+
+static void on_set_focus(GtkWindow* window, GtkWidget* widget, gpointer user_data) {
+    (void)user_data;
+    if (!g_object_get_data(G_OBJECT(window), AUREA_FOCUS_MANAGED_KEY)) return;
+    ng_invoke_native_focus_changed((void*)window, (void*)widget);
+}
+
+int ng_linux_element_focus(NGHandle element) {
+    if (!element) return NG_ERROR_INVALID_HANDLE;
+    GtkWidget* widget = GTK_WIDGET(element);
+    /* Drawing areas (canvases) do not take focus by default. */
+    if (!gtk_widget_get_can_focus(widget)) {
+        gtk_widget_set_can_focus(widget, TRUE);
+    }
+    gtk_widget_grab_focus(widget);
+    /* The window's focus widget, not has-focus, which also needs the window
+     * to be active. */
+    GtkWidget* toplevel = gtk_widget_get_toplevel(widget);
+    if (!GTK_IS_WINDOW(toplevel)) return NG_ERROR_PLATFORM_SPECIFIC;
+    return gtk_window_get_focus(GTK_WINDOW(toplevel)) == widget
+        ? NG_SUCCESS
+        : NG_ERROR_PLATFORM_SPECIFIC;
+}
+
+int ng_linux_window_set_focus_traversal(NGHandle window, int managed) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    GObject* object = G_OBJECT(window);
+    g_object_set_data(object, AUREA_FOCUS_MANAGED_KEY, GINT_TO_POINTER(managed ? 1 : 0));
+    if (managed && !g_object_get_data(object, AUREA_FOCUS_HANDLER_KEY)) {
+        gulong handler = g_signal_connect(object, "set-focus", G_CALLBACK(on_set_focus), NULL);
+        g_object_set_data(object, AUREA_FOCUS_HANDLER_KEY, (gpointer)(guintptr)handler);
+    }
+    return NG_SUCCESS;
+}
+
+int ng_linux_element_set_focus_target(NGHandle element, int registered) {
+    if (!element) return NG_ERROR_INVALID_HANDLE;
+    g_object_set_data(G_OBJECT(element), AUREA_FOCUS_TARGET_KEY, GINT_TO_POINTER(registered ? 1 : 0));
+    return NG_SUCCESS;
+}
+
+/* An editable text view inserts Tab itself, unless the application
+ * registered it for traversal. */
+static gboolean focus_keeps_tab(GtkWindow* window) {
+    GtkWidget* focus = gtk_window_get_focus(window);
+    if (!focus || !GTK_IS_TEXT_VIEW(focus)) return FALSE;
+    if (g_object_get_data(G_OBJECT(focus), AUREA_FOCUS_TARGET_KEY)) return FALSE;
+    GtkTextView* view = GTK_TEXT_VIEW(focus);
+    return gtk_text_view_get_editable(view) && gtk_text_view_get_accepts_tab(view);
+}
+
+int ng_linux_window_focus_keeps_tab(NGHandle window) {
+    if (!window) return 0;
+    return focus_keeps_tab(GTK_WINDOW(window)) ? 1 : 0;
+}
+
+gboolean ng_linux_focus_owns_key(GtkWidget* window, GdkEventKey* event) {
+    if (!g_object_get_data(G_OBJECT(window), AUREA_FOCUS_MANAGED_KEY)) return FALSE;
+    if (event->keyval != GDK_KEY_Tab && event->keyval != GDK_KEY_KP_Tab &&
+        event->keyval != GDK_KEY_ISO_Left_Tab) {
+        return FALSE;
+    }
+    return !focus_keeps_tab(GTK_WINDOW(window));
+}
@@ -0,0 +1,23 @@
+#ifndef NATIVE_GUI_LINUX_FOCUS_H
+#define NATIVE_GUI_LINUX_FOCUS_H
+
+#include "common/platform_api.h"
+#include <gtk/gtk.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int ng_linux_element_focus(NGHandle element);
+int ng_linux_window_set_focus_traversal(NGHandle window, int managed);
+int ng_linux_element_set_focus_target(NGHandle element, int registered);
+int ng_linux_window_focus_keeps_tab(NGHandle window);
+// TRUE when `event` is a Tab the application traverses itself; the window's
+// key handler then stops GTK from moving focus.
+gboolean ng_linux_focus_owns_key(GtkWidget* window, GdkEventKey* event);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_FOCUS_H
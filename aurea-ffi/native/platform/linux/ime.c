@@ -0,0 +1,163 @@
+#include "ime.h"
+#include "common/errors.h"
+#include "common/rust_callbacks.h"
+#include <math.h>
+
+// Per-window input method state, owned by the GtkWindow via object data.
+typedef struct {
+    GtkIMContext* context;
+    gboolean allowed;
+    gboolean focused;
+    gboolean enabled;
+} NGLinuxIme;
+
+static const char* AUREA_IME_KEY = "aurea-ime";
+
+static NGLinuxIme* ime_for(GtkWidget* window) {
+    if (!window) return NULL;
+    return (NGLinuxIme*)g_object_get_data(G_OBJECT(window), AUREA_IME_KEY);
+}
+
+static void ime_free(gpointer data) {
+    NGLinuxIme* ime = (NGLinuxIme*)data;
+    gtk_im_context_set_client_window(ime->context, NULL);
+    g_object_unref(ime->context);
+    g_free(ime);
+}
+
+// Emit ImeEnabled/ImeDisabled when "allowed and focused" changes.
+static void ime_sync(GtkWidget* window, NGLinuxIme* ime) {
+    gboolean enabled = ime->allowed && ime->focused;
+    if (enabled == ime->enabled) return;
+    ime->enabled = enabled;
+
+    if (enabled) {
+        gtk_im_context_focus_in(ime->context);
+    } else {
+        gtk_im_context_focus_out(ime->context);
+        gtk_im_context_reset(ime->context);
+    }
+    ng_invoke_ime_state((void*)window, enabled ? 1 : 0);
+}
+
+static void on_ime_commit(GtkIMContext* context, const gchar* text, gpointer user_data) {
+    GtkWidget* window = GTK_WIDGET(user_data);
+    NGLinuxIme* ime = ime_for(window);
+    if (!ime || !ime->enabled || !text) return;
+    ng_invoke_ime_commit((void*)window, text);
+}
+
+static void on_ime_preedit_changed(GtkIMContext* context, gpointer user_data) {
+    GtkWidget* window = GTK_WIDGET(user_data);
+    NGLinuxIme* ime = ime_for(window);
+    if (!ime || !ime->enabled) return;
+
+    gchar* text = NULL;
+    gint cursor_chars = 0;
+    gtk_im_context_get_preedit_string(context, &text, NULL, &cursor_chars);
+    if (!text) return;
+
+    // GTK reports the cursor in characters; the event carries byte offsets.
+    int cursor = -1;
+    if (text[0] != '\0' && cursor_chars >= 0) {
+        const gchar* at = g_utf8_offset_to_pointer(text, cursor_chars);
+        cursor = (int)(at - text);
+    }
+    ng_invoke_ime_preedit((void*)window, text, cursor, cursor);
+    g_free(text);
+}
+
+static void on_ime_preedit_end(GtkIMContext* context, gpointer user_data) {
+    GtkWidget* window = GTK_WIDGET(user_data);
+    NGLinuxIme* ime = ime_for(window);
+    if (!ime || !ime->enabled) return;
+    ng_invoke_ime_preedit((void*)window, "", -1, -1);
+}
+
+static void on_ime_realize(GtkWidget* widget, gpointer user_data) {
+    NGLinuxIme* ime = ime_for(widget);
+    if (!ime) return;
+    gtk_im_context_set_client_window(ime->context, gtk_widget_get_window(widget));
+}
+
+static void on_ime_unrealize(GtkWidget* widget, gpointer user_data) {
+    NGLinuxIme* ime = ime_for(widget);
+    if (!ime) return;
+    gtk_im_context_set_client_window(ime->context, NULL);
+}
+
+void ng_linux_ime_attach(GtkWidget* window) {
+    if (!window || ime_for(window)) return;
+
+    NGLinuxIme* ime = g_new0(NGLinuxIme, 1);
+    ime->context = gtk_im_multicontext_new();
+    g_object_set_data_full(G_OBJECT(window), AUREA_IME_KEY, ime, ime_free);
+
+    g_signal_connect(ime->context, "commit", G_CALLBACK(on_ime_commit), window);
+    g_signal_connect(ime->context, "preedit-changed", G_CALLBACK(on_ime_preedit_changed), window);
+    g_signal_connect(ime->context, "preedit-end", G_CALLBACK(on_ime_preedit_end), window);
+    g_signal_connect(G_OBJECT(window), "realize", G_CALLBACK(on_ime_realize), NULL);
+    g_signal_connect(G_OBJECT(window), "unrealize", G_CALLBACK(on_ime_unrealize), NULL);
+
+    if (gtk_widget_get_realized(window)) {
+        on_ime_realize(window, NULL);
+    }
+}
+
+/* A focused GtkEntry or GtkTextView runs its own input method; feeding the
+ * same keys to the window's context would compose them twice. */
+static gboolean native_editable_focused(GtkWidget* window) {
+    GtkWidget* focus = gtk_window_get_focus(GTK_WINDOW(window));
+    return focus && (GTK_IS_EDITABLE(focus) || GTK_IS_TEXT_VIEW(focus));
+}
+
+gboolean ng_linux_ime_filter_key(GtkWidget* window, GdkEventKey* event) {
+    NGLinuxIme* ime = ime_for(window);
+    if (!ime || !ime->enabled || !event) return FALSE;
+    if (native_editable_focused(window)) return FALSE;
+    return gtk_im_context_filter_keypress(ime->context, event);
+}
+
+gboolean ng_linux_ime_is_allowed(GtkWidget* window) {
+    NGLinuxIme* ime = ime_for(window);
+    return ime && ime->allowed;
+}
+
+void ng_linux_ime_focus(GtkWidget* window, gboolean focused) {
+    NGLinuxIme* ime = ime_for(window);
+    if (!ime) return;
+    ime->focused = focused;
+    ime_sync(window, ime);
+}
+
+int ng_linux_window_set_ime_allowed(NGHandle window, int allowed) {
+    GtkWidget* widget = (GtkWidget*)window;
+    NGLinuxIme* ime = ime_for(widget);
+    if (!ime) return NG_ERROR_INVALID_HANDLE;
+
+    ime->allowed = allowed ? TRUE : FALSE;
+    ime->focused = gtk_window_is_active(GTK_WINDOW(widget));
+    ime_sync(widget, ime);
+    return NG_SUCCESS;
+}
+
+static int round_to_int(float value) {
+    return (int)(value + (value >= 0.0f ? 0.5f : -0.5f));
+}
+
+int ng_linux_window_set_ime_cursor_area(NGHandle window, float x, float y, float width, float height) {
+    NGLinuxIme* ime = ime_for((GtkWidget*)window);
+    if (!ime) return NG_ERROR_INVALID_HANDLE;
+    if (!isfinite(x) || !isfinite(y) || !isfinite(width) || !isfinite(height)) {
+        return NG_ERROR_INVALID_PARAMETER;
+    }
+
+    GdkRectangle area = {
+        .x = round_to_int(x),
+        .y = round_to_int(y),
+        .width = round_to_int(width),
+        .height = round_to_int(height),
+    };
+    gtk_im_context_set_cursor_location(ime->context, &area);
+    return NG_SUCCESS;
+}
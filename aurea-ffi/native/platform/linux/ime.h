@@ -0,0 +1,22 @@
+#ifndef NATIVE_GUI_LINUX_IME_H
+#define NATIVE_GUI_LINUX_IME_H
+
+#include "common/types.h"
+#include <gtk/gtk.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void ng_linux_ime_attach(GtkWidget* window);
+gboolean ng_linux_ime_filter_key(GtkWidget* window, GdkEventKey* event);
+gboolean ng_linux_ime_is_allowed(GtkWidget* window);
+void ng_linux_ime_focus(GtkWidget* window, gboolean focused);
+int ng_linux_window_set_ime_allowed(NGHandle window, int allowed);
+int ng_linux_window_set_ime_cursor_area(NGHandle window, float x, float y, float width, float height);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_IME_H
@@ -0,0 +1,126 @@
+#include "keyboard.h"
+#include "common/input.h"
+#include "common/rust_callbacks.h"
+#include <string.h>
+
+#define HELD_KEYS_KEY "aurea-held-keys"
+
+/* X11 and Wayland keycodes are evdev codes offset by 8. */
+#define EVDEV_OFFSET 8
+#define CONSUMER(usage) (0x000C0000u | (usage))
+
+/* evdev KEY_* code to USB HID usage (linux/input-event-codes.h). */
+static const unsigned int EVDEV_TO_HID[] = {
+    [1] = 0x29,                                                 /* Esc */
+    [2] = 0x1E, [3] = 0x1F, [4] = 0x20, [5] = 0x21, [6] = 0x22, /* 1-5 */
+    [7] = 0x23, [8] = 0x24, [9] = 0x25, [10] = 0x26, [11] = 0x27, /* 6-0 */
+    [12] = 0x2D, [13] = 0x2E, [14] = 0x2A, [15] = 0x2B,
+    [16] = 0x14, [17] = 0x1A, [18] = 0x08, [19] = 0x15, [20] = 0x17, /* QWERT */
+    [21] = 0x1C, [22] = 0x18, [23] = 0x0C, [24] = 0x12, [25] = 0x13, /* YUIOP */
+    [26] = 0x2F, [27] = 0x30, [28] = 0x28, [29] = 0xE0,
+    [30] = 0x04, [31] = 0x16, [32] = 0x07, [33] = 0x09, [34] = 0x0A, /* ASDFG */
+    [35] = 0x0B, [36] = 0x0D, [37] = 0x0E, [38] = 0x0F,              /* HJKL */
+    [39] = 0x33, [40] = 0x34, [41] = 0x35, [42] = 0xE1, [43] = 0x31,
+    [44] = 0x1D, [45] = 0x1B, [46] = 0x06, [47] = 0x19, [48] = 0x05, /* ZXCVB */
+    [49] = 0x11, [50] = 0x10,                                        /* NM */
+    [51] = 0x36, [52] = 0x37, [53] = 0x38, [54] = 0xE5, [55] = 0x55,
+    [56] = 0xE2, [57] = 0x2C, [58] = 0x39,
+    [59] = 0x3A, [60] = 0x3B, [61] = 0x3C, [62] = 0x3D, [63] = 0x3E, /* F1-F5 */
+    [64] = 0x3F, [65] = 0x40, [66] = 0x41, [67] = 0x42, [68] = 0x43, /* F6-F10 */
+    [69] = 0x53, [70] = 0x47,
+    [71] = 0x5F, [72] = 0x60, [73] = 0x61, [74] = 0x56,              /* KP 7 8 9 - */
+    [75] = 0x5C, [76] = 0x5D, [77] = 0x5E, [78] = 0x57,              /* KP 4 5 6 + */
+    [79] = 0x59, [80] = 0x5A, [81] = 0x5B, [82] = 0x62, [83] = 0x63, /* KP 1 2 3 0 . */
+    [86] = 0x64, [87] = 0x44, [88] = 0x45,
+    [96] = 0x58, [97] = 0xE4, [98] = 0x54, [99] = 0x46, [100] = 0xE6,
+    [102] = 0x4A, [103] = 0x52, [104] = 0x4B, [105] = 0x50, [106] = 0x4F,
+    [107] = 0x4D, [108] = 0x51, [109] = 0x4E, [110] = 0x49, [111] = 0x4C,
+    [113] = CONSUMER(0xE2), [114] = CONSUMER(0xEA), [115] = CONSUMER(0xE9),
+    [117] = 0x67, [119] = 0x48, [125] = 0xE3, [126] = 0xE7, [127] = 0x65,
+    [163] = CONSUMER(0xB5), [164] = CONSUMER(0xCD), [165] = CONSUMER(0xB6),
+    [166] = CONSUMER(0xB7),
+    [183] = 0x68, [184] = 0x69, [185] = 0x6A, [186] = 0x6B,          /* F13-F16 */
+    [187] = 0x6C, [188] = 0x6D, [189] = 0x6E, [190] = 0x6F,          /* F17-F20 */
+    [191] = 0x70, [192] = 0x71, [193] = 0x72, [194] = 0x73,          /* F21-F24 */
+};
+
+#define EVDEV_TABLE_LEN (sizeof(EVDEV_TO_HID) / sizeof(EVDEV_TO_HID[0]))
+
+static unsigned int physical_from_hardware(guint16 hardware_keycode) {
+    if (hardware_keycode >= EVDEV_OFFSET) {
+        guint16 evdev = (guint16)(hardware_keycode - EVDEV_OFFSET);
+        if (evdev < EVDEV_TABLE_LEN && EVDEV_TO_HID[evdev] != 0) {
+            return EVDEV_TO_HID[evdev];
+        }
+    }
+    return NG_PHYSICAL_UNIDENTIFIED | hardware_keycode;
+}
+
+/* One bit per hardware keycode; GTK doesn't flag auto-repeat, so a press of
+ * a key that is already down is a repeat. */
+typedef struct {
+    guint8 bits[256 / 8];
+} NGHeldKeys;
+
+static NGHeldKeys* held_keys(GtkWidget* window) {
+    NGHeldKeys* held = g_object_get_data(G_OBJECT(window), HELD_KEYS_KEY);
+    if (!held) {
+        held = g_new0(NGHeldKeys, 1);
+        g_object_set_data_full(G_OBJECT(window), HELD_KEYS_KEY, held, g_free);
+    }
+    return held;
+}
+
+static gboolean update_held(GtkWidget* window, guint16 hardware_keycode, gboolean pressed) {
+    NGHeldKeys* held = held_keys(window);
+    guint8 index = (guint8)(hardware_keycode & 0xFF);
+    guint8 mask = (guint8)(1u << (index % 8));
+    gboolean was_down = (held->bits[index / 8] & mask) != 0;
+    if (pressed) {
+        held->bits[index / 8] |= mask;
+    } else {
+        held->bits[index / 8] &= (guint8)~mask;
+    }
+    return pressed && was_down;
+}
+
+void ng_linux_keyboard_emit(
+    GtkWidget* window,
+    GdkEventKey* event,
+    unsigned int keycode,
+    unsigned int modifiers) {
+    gboolean pressed = event->type == GDK_KEY_PRESS;
+    char text[8] = {0};
+    gunichar ch = gdk_keyval_to_unicode(event->keyval);
+    if (ch != 0) {
+        g_unichar_to_utf8(ch, text);
+    }
+
+    NGKeyEvent out = {0};
+    out.keycode = keycode;
+    out.physical = physical_from_hardware(event->hardware_keycode);
+    out.text = text[0] != '\0' ? text : NULL;
+    out.pressed = pressed ? 1 : 0;
+    out.repeat = update_held(window, event->hardware_keycode, pressed) ? 1 : 0;
+    out.modifiers = modifiers;
+    ng_invoke_key_event((void*)window, &out);
+}
+
+void ng_linux_keyboard_reset(GtkWidget* window) {
+    NGHeldKeys* held = g_object_get_data(G_OBJECT(window), HELD_KEYS_KEY);
+    if (held) {
+        memset(held->bits, 0, sizeof(held->bits));
+    }
+}
+
+unsigned int ng_linux_keyboard_lock_modifiers(GdkModifierType state) {
+    unsigned int mods = 0;
+    if (state & GDK_LOCK_MASK) {
+        mods |= NG_MOD_CAPS_LOCK;
+    }
+    GdkDisplay* display = gdk_display_get_default();
+    if (display && gdk_keymap_get_num_lock_state(gdk_keymap_get_for_display(display))) {
+        mods |= NG_MOD_NUM_LOCK;
+    }
+    return mods;
+}
@@ -0,0 +1,30 @@
+#ifndef NATIVE_GUI_LINUX_KEYBOARD_H
+#define NATIVE_GUI_LINUX_KEYBOARD_H
+
+#include <gtk/gtk.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Report a key press or release with its physical key, layout text and
+// repeat state. `keycode` is the NG_KEY_* code already derived from the
+// keyval.
+void ng_linux_keyboard_emit(
+    GtkWidget* window,
+    GdkEventKey* event,
+    unsigned int keycode,
+    unsigned int modifiers);
+
+// Forget held keys, e.g. when focus moves elsewhere and releases would be
+// missed.
+void ng_linux_keyboard_reset(GtkWidget* window);
+
+// Caps Lock / Num Lock state as NG_MOD_* bits.
+unsigned int ng_linux_keyboard_lock_modifiers(GdkModifierType state);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_KEYBOARD_H
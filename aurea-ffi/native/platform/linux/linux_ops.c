@@ -2,7 +2,17 @@
 #include "common/errors.h"
 #include "platform/linux/utils.h"
 #include "platform/linux/window.h"
+#include "platform/linux/clipboard.h"
+#include "platform/linux/cursor.h"
+#include "platform/linux/dialog.h"
+#include "platform/linux/dnd.h"
+#include "platform/linux/ime.h"
 #include "platform/linux/menu.h"
+#include "platform/linux/tray.h"
+#include "platform/linux/notification.h"
+#include "platform/linux/accessibility.h"
+#include "platform/linux/focus.h"
+#include "platform/linux/window_state.h"
 #include "platform/linux/elements.h"
 
 static int box_add_wrapper(NGHandle b, NGHandle e, float w) {
@@ -16,8 +26,10 @@ static const ng_platform_ops_t linux_ops = {
     .run = ng_linux_run,
     .poll_events = ng_linux_poll_events,
     .request_frame = ng_linux_request_frame,
+    .request_frame_after = ng_linux_request_frame_after,
     .create_window = ng_linux_create_window,
     .create_window_with_type = ng_linux_create_window_with_type,
+    .create_window_with_options = ng_linux_create_window_with_options,
     .destroy_window = ng_linux_destroy_window,
     .window_set_title = ng_linux_window_set_title,
     .window_set_icon_rgba = ng_linux_window_set_icon_rgba,
@@ -27,6 +39,28 @@ static const ng_platform_ops_t linux_ops = {
     .window_is_focused = ng_linux_window_is_focused,
     .window_set_cursor_visible = ng_linux_window_set_cursor_visible,
     .window_set_cursor_grab = ng_linux_window_set_cursor_grab,
+    .window_set_cursor_icon = ng_linux_window_set_cursor_icon,
+    .window_set_custom_cursor = ng_linux_window_set_custom_cursor,
+    .window_set_drop_effect = ng_linux_window_set_drop_effect,
+    .window_start_drag = ng_linux_window_start_drag,
+    .window_set_ime_allowed = ng_linux_window_set_ime_allowed,
+    .window_set_ime_cursor_area = ng_linux_window_set_ime_cursor_area,
+    .window_get_state = ng_linux_window_get_state,
+    .window_set_fullscreen = ng_linux_window_set_fullscreen,
+    .window_set_maximized = ng_linux_window_set_maximized,
+    .window_set_minimized = ng_linux_window_set_minimized,
+    .window_set_min_size = ng_linux_window_set_min_size,
+    .window_set_max_size = ng_linux_window_set_max_size,
+    .window_set_resizable = ng_linux_window_set_resizable,
+    .window_set_always_on_top = ng_linux_window_set_always_on_top,
+    .window_set_decorations = ng_linux_window_set_decorations,
+    .window_set_transparent = ng_linux_window_set_transparent,
+    .window_set_parent = ng_linux_window_set_parent,
+    .primary_monitor = ng_linux_primary_monitor,
+    .window_current_monitor = ng_linux_window_current_monitor,
+    .monitor_count = ng_linux_monitor_count,
+    .monitor_info = ng_linux_monitor_info,
+    .window_get_frame = ng_linux_window_get_frame,
     .window_get_content_view = ng_linux_window_get_content_view,
     .window_show = ng_linux_window_show,
     .window_hide = ng_linux_window_hide,
@@ -41,6 +75,14 @@ static const ng_platform_ops_t linux_ops = {
     .add_menu_item = ng_linux_add_menu_item,
     .add_menu_separator = ng_linux_add_menu_separator,
     .create_submenu = ng_linux_create_submenu,
+    .menu_add_item = ng_linux_menu_add_item,
+    .menu_item_set_enabled = ng_linux_menu_item_set_enabled,
+    .menu_item_set_checked = ng_linux_menu_item_set_checked,
+    .menu_item_is_checked = ng_linux_menu_item_is_checked,
+    .menu_item_set_label = ng_linux_menu_item_set_label,
+    .menu_item_remove = ng_linux_menu_item_remove,
+    .create_context_menu = ng_linux_create_context_menu,
+    .show_context_menu = ng_linux_show_context_menu,
     .create_button = ng_linux_create_button,
     .button_invalidate = ng_linux_button_invalidate,
     .create_label = ng_linux_create_label,
@@ -113,9 +155,32 @@ static const ng_platform_ops_t linux_ops = {
     .sidebar_list_get_selected = ng_linux_sidebar_list_get_selected,
     .sidebar_list_clear = ng_linux_sidebar_list_clear,
     .sidebar_list_invalidate = ng_linux_sidebar_list_invalidate,
+    .file_dialog_show = ng_linux_file_dialog_show,
+    .message_dialog_show = ng_linux_message_dialog_show,
+    .color_picker_show = ng_linux_color_picker_show,
+    .font_picker_show = ng_linux_font_picker_show,
     .get_clipboard_text = ng_linux_get_clipboard_text,
     .free_clipboard_text = ng_linux_free_clipboard_text,
     .set_clipboard_text = ng_linux_set_clipboard_text,
+    .clipboard_set = ng_linux_clipboard_set,
+    .clipboard_types = ng_linux_clipboard_types,
+    .clipboard_get = ng_linux_clipboard_get,
+    .clipboard_free = ng_linux_clipboard_free,
+    .clipboard_watch = ng_linux_clipboard_watch,
+    .tray_create = ng_linux_tray_create,
+    .tray_set_icon = ng_linux_tray_set_icon,
+    .tray_set_tooltip = ng_linux_tray_set_tooltip,
+    .tray_set_menu = ng_linux_tray_set_menu,
+    .tray_destroy = ng_linux_tray_destroy,
+    .element_set_accessible = ng_linux_element_set_accessible,
+    .element_focus = ng_linux_element_focus,
+    .window_set_focus_traversal = ng_linux_window_set_focus_traversal,
+    .element_set_focus_target = ng_linux_element_set_focus_target,
+    .window_focus_keeps_tab = ng_linux_window_focus_keeps_tab,
+    .notification_show = ng_linux_notification_show,
+    .notification_update = ng_linux_notification_update,
+    .notification_close = ng_linux_notification_close,
+    .notification_release = ng_linux_notification_release,
 };
 
 void linux_register_ops(void) {
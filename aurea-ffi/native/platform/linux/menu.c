@@ -1,5 +1,6 @@
 #include "menu.h"
 #include "window.h"
+#include "utils.h"
 #include "common/errors.h"
 #include "common/input.h"
 #include "common/rust_callbacks.h"
@@ -13,24 +14,8 @@ typedef struct {
     GtkWidget* root;
 } MenuRootEntry;
 
-typedef struct {
-    GtkWidget* root;
-    unsigned int keycode;
-    unsigned int modifiers;
-    unsigned int id;
-} MenuShortcutEntry;
-
-typedef struct {
-    GtkWidget* window;
-    GtkWidget* root;
-} WindowMenuBinding;
-
 static MenuRootEntry g_menu_roots[256] = {0};
 static int g_menu_root_count = 0;
-static MenuShortcutEntry g_shortcuts[512] = {0};
-static int g_shortcut_count = 0;
-static WindowMenuBinding g_window_bindings[256] = {0};
-static int g_window_binding_count = 0;
 
 static void register_menu_root(GtkWidget* menu, GtkWidget* root) {
     if (!menu) return;
@@ -56,29 +41,6 @@ static GtkWidget* find_menu_root(GtkWidget* menu) {
     return menu;
 }
 
-static void bind_window_root(GtkWidget* window, GtkWidget* root) {
-    for (int i = 0; i < g_window_binding_count; i++) {
-        if (g_window_bindings[i].window == window) {
-            g_window_bindings[i].root = root;
-            return;
-        }
-    }
-    if (g_window_binding_count < 256) {
-        g_window_bindings[g_window_binding_count].window = window;
-        g_window_bindings[g_window_binding_count].root = root;
-        g_window_binding_count++;
-    }
-}
-
-static GtkWidget* root_for_window(GtkWidget* window) {
-    for (int i = 0; i < g_window_binding_count; i++) {
-        if (g_window_bindings[i].window == window) {
-            return g_window_bindings[i].root;
-        }
-    }
-    return NULL;
-}
-
 static int token_eq(const char* a, const char* b) {
     while (*a && *b) {
         if (tolower((unsigned char)*a) != tolower((unsigned char)*b)) {
@@ -121,12 +83,13 @@ static int shortcut_keycode(const char* token, unsigned int* keycode) {
     return 0;
 }
 
-static void register_shortcut(GtkWidget* menu, const char* title, unsigned int id) {
+/* Parse the accelerator part of a legacy "Save\tCtrl+S" title. */
+static void parse_shortcut(const char* title, unsigned int* keycode, unsigned int* mods) {
+    *keycode = NG_KEY_UNKNOWN;
+    *mods = 0;
     const char* tab = strchr(title, '\t');
     if (!tab || tab[1] == '\0') return;
 
-    unsigned int mods = 0;
-    unsigned int keycode = NG_KEY_UNKNOWN;
     char buf[128];
     strncpy(buf, tab + 1, sizeof(buf) - 1);
     buf[sizeof(buf) - 1] = '\0';
@@ -135,26 +98,83 @@ static void register_shortcut(GtkWidget* menu, const char* title, unsigned int i
     while (part) {
         while (*part == ' ') part++;
         if (token_eq(part, "Ctrl") || token_eq(part, "Control")) {
-            mods |= NG_MOD_CTRL;
+            *mods |= NG_MOD_CTRL;
         } else if (token_eq(part, "Shift")) {
-            mods |= NG_MOD_SHIFT;
+            *mods |= NG_MOD_SHIFT;
         } else if (token_eq(part, "Alt") || token_eq(part, "Option")) {
-            mods |= NG_MOD_ALT;
+            *mods |= NG_MOD_ALT;
         } else if (token_eq(part, "Cmd") || token_eq(part, "Command") || token_eq(part, "Meta")) {
-            mods |= NG_MOD_META;
+            *mods |= NG_MOD_META;
         } else {
-            shortcut_keycode(part, &keycode);
+            shortcut_keycode(part, keycode);
         }
         part = strtok(NULL, "+");
     }
+}
+
+static guint keyval_from_ng(unsigned int key) {
+    if (key <= NG_KEY_Z) return GDK_KEY_a + (key - NG_KEY_A);
+    if (key >= NG_KEY_0 && key <= NG_KEY_9) return GDK_KEY_0 + (key - NG_KEY_0);
+    if (key >= NG_KEY_F1 && key <= NG_KEY_F12) return GDK_KEY_F1 + (key - NG_KEY_F1);
+    if (key >= NG_KEY_F13 && key <= NG_KEY_F24) return GDK_KEY_F13 + (key - NG_KEY_F13);
+    switch (key) {
+        case NG_KEY_SPACE: return GDK_KEY_space;
+        case NG_KEY_ENTER: return GDK_KEY_Return;
+        case NG_KEY_ESCAPE: return GDK_KEY_Escape;
+        case NG_KEY_TAB: return GDK_KEY_Tab;
+        case NG_KEY_BACKSPACE: return GDK_KEY_BackSpace;
+        case NG_KEY_DELETE: return GDK_KEY_Delete;
+        case NG_KEY_INSERT: return GDK_KEY_Insert;
+        case NG_KEY_HOME: return GDK_KEY_Home;
+        case NG_KEY_END: return GDK_KEY_End;
+        case NG_KEY_PAGE_UP: return GDK_KEY_Page_Up;
+        case NG_KEY_PAGE_DOWN: return GDK_KEY_Page_Down;
+        case NG_KEY_UP: return GDK_KEY_Up;
+        case NG_KEY_DOWN: return GDK_KEY_Down;
+        case NG_KEY_LEFT: return GDK_KEY_Left;
+        case NG_KEY_RIGHT: return GDK_KEY_Right;
+        default: return 0;
+    }
+}
+
+static GdkModifierType accel_mods_from_ng(unsigned int mods) {
+    GdkModifierType out = 0;
+    if (mods & NG_MOD_SHIFT) out |= GDK_SHIFT_MASK;
+    if (mods & NG_MOD_CTRL) out |= GDK_CONTROL_MASK;
+    if (mods & NG_MOD_ALT) out |= GDK_MOD1_MASK;
+    if (mods & NG_MOD_META) out |= GDK_SUPER_MASK;
+    return out;
+}
+
+static void add_accelerator(GtkWidget* menu, GtkWidget* item, const NGMenuItemOptions* options) {
+    guint keyval = 0;
+    if (options->accel_char) {
+        keyval = gdk_keyval_to_lower(gdk_unicode_to_keyval(options->accel_char));
+    } else if (options->accel_key != NG_KEY_UNKNOWN) {
+        keyval = keyval_from_ng(options->accel_key);
+    }
+    if (!keyval) return;
 
-    if (keycode == NG_KEY_UNKNOWN || g_shortcut_count >= 512) return;
+    GtkAccelGroup* accel = g_object_get_data(G_OBJECT(find_menu_root(menu)), "aurea-accel-group");
+    if (!accel) return;
+    gtk_widget_add_accelerator(item, "activate", accel, keyval,
+                               accel_mods_from_ng(options->accel_modifiers), GTK_ACCEL_VISIBLE);
+}
 
-    g_shortcuts[g_shortcut_count].root = find_menu_root(menu);
-    g_shortcuts[g_shortcut_count].keycode = keycode;
-    g_shortcuts[g_shortcut_count].modifiers = mods;
-    g_shortcuts[g_shortcut_count].id = id;
-    g_shortcut_count++;
+/* Radio items are grouped per menu; the stored group is offset by one so
+ * group 0 is distinguishable from "not a radio item". */
+static GtkWidget* radio_group_member(GtkWidget* menu, unsigned int group) {
+    GList* children = gtk_container_get_children(GTK_CONTAINER(menu));
+    GtkWidget* member = NULL;
+    for (GList* l = children; l; l = l->next) {
+        guint stored = GPOINTER_TO_UINT(g_object_get_data(G_OBJECT(l->data), "aurea-radio-group"));
+        if (stored == group + 1) {
+            member = GTK_WIDGET(l->data);
+            break;
+        }
+    }
+    g_list_free(children);
+    return member;
 }
 
 static void menu_item_clicked(GtkMenuItem *item, gpointer user_data) {
@@ -165,9 +185,52 @@ static void menu_item_clicked(GtkMenuItem *item, gpointer user_data) {
 NGMenuHandle ng_linux_create_menu(void) {
     GtkWidget *menubar = gtk_menu_bar_new();
     register_menu_root(menubar, menubar);
+    g_object_set_data_full(G_OBJECT(menubar), "aurea-accel-group",
+                           gtk_accel_group_new(), g_object_unref);
     return (NGMenuHandle)menubar;
 }
 
+NGMenuHandle ng_linux_create_context_menu(void) {
+    GtkWidget* menu = gtk_menu_new();
+    register_menu_root(menu, menu);
+    // Accelerators on popup items are shown in their labels but only fire
+    // through a menu bar attached to the window.
+    g_object_set_data_full(G_OBJECT(menu), "aurea-accel-group",
+                           gtk_accel_group_new(), g_object_unref);
+    return (NGMenuHandle)menu;
+}
+
+int ng_linux_show_context_menu(NGHandle anchor, NGMenuHandle menu, float x, float y) {
+    if (!anchor || !menu) return NG_ERROR_INVALID_HANDLE;
+
+    GtkWidget* widget = (GtkWidget*)anchor;
+    GtkWidget* toplevel = gtk_widget_get_toplevel(widget);
+    GdkWindow* gdk_window = gtk_widget_get_window(toplevel);
+    if (!gtk_widget_is_toplevel(toplevel) || !gdk_window) return NG_ERROR_PLATFORM_SPECIFIC;
+
+    int tx = (int)x;
+    int ty = (int)y;
+    if (widget != toplevel &&
+        !gtk_widget_translate_coordinates(widget, toplevel, (int)x, (int)y, &tx, &ty)) {
+        return NG_ERROR_PLATFORM_SPECIFIC;
+    }
+
+    GtkMenu* popup = GTK_MENU(menu);
+    if (gtk_menu_get_attach_widget(popup) != toplevel) {
+        if (gtk_menu_get_attach_widget(popup)) gtk_menu_detach(popup);
+        gtk_menu_attach_to_widget(popup, toplevel, NULL);
+    }
+
+    // Wayland needs the triggering event to grab the seat for the popup.
+    GdkRectangle rect = { tx, ty, 1, 1 };
+    GdkEvent* trigger = gtk_get_current_event();
+    gtk_widget_show_all((GtkWidget*)menu);
+    gtk_menu_popup_at_rect(popup, gdk_window, &rect,
+                           GDK_GRAVITY_NORTH_WEST, GDK_GRAVITY_NORTH_WEST, trigger);
+    if (trigger) gdk_event_free(trigger);
+    return NG_SUCCESS;
+}
+
 void ng_linux_destroy_menu(NGMenuHandle handle) {
     if (!handle) return;
     gtk_widget_destroy((GtkWidget*)handle);
@@ -190,7 +253,15 @@ int ng_linux_attach_menu(NGHandle window, NGMenuHandle menu) {
 
     // Keep menu at top, above content.
     gtk_box_reorder_child(GTK_BOX(vbox), menu_widget, 0);
-    bind_window_root((GtkWidget*)window, find_menu_root(menu_widget));
+
+    // Accelerators of every item under this root fire while the window has focus.
+    GtkAccelGroup* accel = g_object_get_data(G_OBJECT(find_menu_root(menu_widget)), "aurea-accel-group");
+    if (accel) {
+        GSList* groups = gtk_accel_groups_from_object(G_OBJECT(window));
+        if (!g_slist_find(groups, accel)) {
+            gtk_window_add_accel_group(GTK_WINDOW(window), accel);
+        }
+    }
     gtk_widget_show_all((GtkWidget*)window);
     return NG_SUCCESS;
 }
@@ -226,17 +297,104 @@ int ng_linux_add_menu_item(NGMenuHandle menu, const char* title, unsigned int id
     if (!menu || !title) return NG_ERROR_INVALID_PARAMETER;
 
     char display_buf[256];
-    const char* label = display_title(title, display_buf, sizeof(display_buf));
+    NGMenuItemOptions options = {0};
+    options.kind = NG_MENU_ITEM_NORMAL;
+    options.title = display_title(title, display_buf, sizeof(display_buf));
+    options.id = id;
+    options.enabled = 1;
+    parse_shortcut(title, &options.accel_key, &options.accel_modifiers);
+    return ng_linux_menu_add_item(menu, &options, NULL);
+}
 
-    GtkWidget *menu_item = gtk_menu_item_new_with_label(label);
-    g_object_set_data(G_OBJECT(menu_item), "menu-id", GUINT_TO_POINTER(id));
-    g_signal_connect(G_OBJECT(menu_item), "activate", 
+int ng_linux_menu_add_item(NGMenuHandle menu, const NGMenuItemOptions* options, NGHandle* item) {
+    if (!menu) return NG_ERROR_INVALID_HANDLE;
+    if (!options || !options->title) return NG_ERROR_INVALID_PARAMETER;
+
+    GtkWidget* menu_widget = (GtkWidget*)menu;
+    GtkWidget* menu_item;
+    if (options->kind == NG_MENU_ITEM_RADIO) {
+        GtkWidget* member = radio_group_member(menu_widget, options->radio_group);
+        menu_item = member ? gtk_radio_menu_item_new_from_widget(GTK_RADIO_MENU_ITEM(member))
+                           : gtk_radio_menu_item_new(NULL);
+        g_object_set_data(G_OBJECT(menu_item), "aurea-radio-group",
+                          GUINT_TO_POINTER(options->radio_group + 1));
+    } else if (options->kind == NG_MENU_ITEM_CHECK) {
+        menu_item = gtk_check_menu_item_new();
+    } else {
+        menu_item = gtk_menu_item_new();
+    }
+
+    GtkWidget* label = gtk_accel_label_new(options->title);
+    gtk_label_set_xalign(GTK_LABEL(label), 0.0f);
+    gtk_accel_label_set_accel_widget(GTK_ACCEL_LABEL(label), menu_item);
+    g_object_set_data(G_OBJECT(menu_item), "aurea-menu-label", label);
+
+    if (options->icon_rgba && options->icon_width > 0 && options->icon_height > 0) {
+        GdkPixbuf* pixbuf = ng_linux_pixbuf_from_rgba(options->icon_rgba,
+                                                      options->icon_width, options->icon_height);
+        GtkWidget* box = gtk_box_new(GTK_ORIENTATION_HORIZONTAL, 6);
+        if (pixbuf) {
+            gtk_box_pack_start(GTK_BOX(box), gtk_image_new_from_pixbuf(pixbuf), FALSE, FALSE, 0);
+            g_object_unref(pixbuf);
+        }
+        gtk_box_pack_start(GTK_BOX(box), label, TRUE, TRUE, 0);
+        gtk_container_add(GTK_CONTAINER(menu_item), box);
+    } else {
+        gtk_container_add(GTK_CONTAINER(menu_item), label);
+    }
+
+    // Initial state is applied before connecting so it does not report a click.
+    if (GTK_IS_CHECK_MENU_ITEM(menu_item)) {
+        gtk_check_menu_item_set_active(GTK_CHECK_MENU_ITEM(menu_item), options->checked != 0);
+    }
+    gtk_widget_set_sensitive(menu_item, options->enabled != 0);
+
+    g_object_set_data(G_OBJECT(menu_item), "menu-id", GUINT_TO_POINTER(options->id));
+    g_signal_connect(G_OBJECT(menu_item), "activate",
                      G_CALLBACK(menu_item_clicked), NULL);
-    
+    add_accelerator(menu_widget, menu_item, options);
+
     gtk_menu_shell_append(GTK_MENU_SHELL(menu), menu_item);
-    gtk_widget_show(menu_item);
-    register_shortcut((GtkWidget*)menu, title, id);
-    
+    gtk_widget_show_all(menu_item);
+
+    if (item) *item = (NGHandle)menu_item;
+    return NG_SUCCESS;
+}
+
+int ng_linux_menu_item_set_enabled(NGHandle item, int enabled) {
+    if (!item) return NG_ERROR_INVALID_HANDLE;
+    gtk_widget_set_sensitive((GtkWidget*)item, enabled != 0);
+    return NG_SUCCESS;
+}
+
+int ng_linux_menu_item_set_checked(NGHandle item, int checked) {
+    if (!item) return NG_ERROR_INVALID_HANDLE;
+    if (!GTK_IS_CHECK_MENU_ITEM(item)) return NG_ERROR_INVALID_PARAMETER;
+
+    // GtkCheckMenuItem emits "activate" when its state changes.
+    g_signal_handlers_block_by_func(item, G_CALLBACK(menu_item_clicked), NULL);
+    gtk_check_menu_item_set_active(GTK_CHECK_MENU_ITEM(item), checked != 0);
+    g_signal_handlers_unblock_by_func(item, G_CALLBACK(menu_item_clicked), NULL);
+    return NG_SUCCESS;
+}
+
+int ng_linux_menu_item_is_checked(NGHandle item) {
+    if (!item || !GTK_IS_CHECK_MENU_ITEM(item)) return 0;
+    return gtk_check_menu_item_get_active(GTK_CHECK_MENU_ITEM(item)) ? 1 : 0;
+}
+
+int ng_linux_menu_item_set_label(NGHandle item, const char* label) {
+    if (!item) return NG_ERROR_INVALID_HANDLE;
+    if (!label) return NG_ERROR_INVALID_PARAMETER;
+    GtkWidget* widget = g_object_get_data(G_OBJECT(item), "aurea-menu-label");
+    if (!widget) return NG_ERROR_PLATFORM_SPECIFIC;
+    gtk_label_set_text(GTK_LABEL(widget), label);
+    return NG_SUCCESS;
+}
+
+int ng_linux_menu_item_remove(NGHandle item) {
+    if (!item) return NG_ERROR_INVALID_HANDLE;
+    gtk_widget_destroy((GtkWidget*)item);
     return NG_SUCCESS;
 }
 
@@ -247,17 +405,3 @@ int ng_linux_add_menu_separator(NGMenuHandle menu) {
     gtk_widget_show(separator);
     return NG_SUCCESS;
 }
-
-int ng_linux_handle_menu_shortcut(NGHandle window, unsigned int keycode, unsigned int modifiers) {
-    GtkWidget* root = root_for_window((GtkWidget*)window);
-    if (!root) return 0;
-
-    for (int i = 0; i < g_shortcut_count; i++) {
-        if (g_shortcuts[i].root != root) continue;
-        if (g_shortcuts[i].keycode != keycode) continue;
-        if (g_shortcuts[i].modifiers != modifiers) continue;
-        ng_invoke_menu_callback(g_shortcuts[i].id);
-        return 1;
-    }
-    return 0;
-}
@@ -1,7 +1,7 @@
 #ifndef NATIVE_GUI_LINUX_MENU_H
 #define NATIVE_GUI_LINUX_MENU_H
 
-#include "common/types.h"
+#include "common/platform_api.h"
 
 #ifdef __cplusplus
 extern "C" {
@@ -13,7 +13,14 @@ int ng_linux_attach_menu(NGHandle window, NGMenuHandle menu);
 int ng_linux_add_menu_item(NGMenuHandle menu, const char* title, unsigned int id);
 int ng_linux_add_menu_separator(NGMenuHandle menu);
 NGMenuHandle ng_linux_create_submenu(NGMenuHandle parent_menu, const char* title);
-int ng_linux_handle_menu_shortcut(NGHandle window, unsigned int keycode, unsigned int modifiers);
+int ng_linux_menu_add_item(NGMenuHandle menu, const NGMenuItemOptions* options, NGHandle* item);
+int ng_linux_menu_item_set_enabled(NGHandle item, int enabled);
+int ng_linux_menu_item_set_checked(NGHandle item, int checked);
+int ng_linux_menu_item_is_checked(NGHandle item);
+int ng_linux_menu_item_set_label(NGHandle item, const char* label);
+int ng_linux_menu_item_remove(NGHandle item);
+NGMenuHandle ng_linux_create_context_menu(void);
+int ng_linux_show_context_menu(NGHandle anchor, NGMenuHandle menu, float x, float y);
 
 #ifdef __cplusplus
 }
@@ -0,0 +1,176 @@
+#include "notification.h"
+#include "common/errors.h"
+#include "common/rust_callbacks.h"
+#include <gio/gio.h>
+#include <string.h>
+
+#define NOTIFICATIONS_NAME "org.freedesktop.Notifications"
+#define NOTIFICATIONS_PATH "/org/freedesktop/Notifications"
+#define NOTIFICATIONS_INTERFACE "org.freedesktop.Notifications"
+
+/* A notification is known to the server by the id Notify returned; 0 once
+ * the server reported it closed, so a later update shows it anew. */
+typedef struct {
+    guint32 id;
+} NGNotification;
+
+static GDBusConnection* g_bus = NULL;
+// Server id -> NGNotification*, for routing the server's signals.
+static GHashTable* g_notifications = NULL;
+
+static NGNotification* lookup(guint32 id) {
+    return g_notifications ? g_hash_table_lookup(g_notifications, GUINT_TO_POINTER(id)) : NULL;
+}
+
+static void on_action_invoked(GDBusConnection* connection, const gchar* sender, const gchar* path,
+                              const gchar* interface, const gchar* signal, GVariant* parameters,
+                              gpointer user_data) {
+    (void)connection; (void)sender; (void)path; (void)interface; (void)signal; (void)user_data;
+    guint32 id = 0;
+    const gchar* action = NULL;
+    g_variant_get(parameters, "(u&s)", &id, &action);
+    NGNotification* notification = lookup(id);
+    if (notification) ng_invoke_notification_action(notification, action);
+}
+
+static void on_notification_closed(GDBusConnection* connection, const gchar* sender, const gchar* path,
+                                   const gchar* interface, const gchar* signal, GVariant* parameters,
+                                   gpointer user_data) {
+    (void)connection; (void)sender; (void)path; (void)interface; (void)signal; (void)user_data;
+    guint32 id = 0;
+    guint32 reason = 0;
+    g_variant_get(parameters, "(uu)", &id, &reason);
+    NGNotification* notification = lookup(id);
+    if (!notification) return;
+    g_hash_table_remove(g_notifications, GUINT_TO_POINTER(id));
+    notification->id = 0;
+    ng_invoke_notification_closed(notification, reason);
+}
+
+static void forget_id(gpointer key, gpointer value, gpointer user_data) {
+    (void)key; (void)user_data;
+    ((NGNotification*)value)->id = 0;
+}
+
+/* Connect privately, as the tray does: the shared session connection exits
+ * the process when the bus goes away. After that happens the old server ids
+ * mean nothing, so reconnect and let updates show their notifications anew. */
+static gboolean ensure_bus(void) {
+    if (g_bus && !g_dbus_connection_is_closed(g_bus)) return TRUE;
+    g_clear_object(&g_bus);
+    if (g_notifications) {
+        g_hash_table_foreach(g_notifications, forget_id, NULL);
+        g_hash_table_remove_all(g_notifications);
+    }
+    gchar* address = g_dbus_address_get_for_bus_sync(G_BUS_TYPE_SESSION, NULL, NULL);
+    if (!address) return FALSE;
+    g_bus = g_dbus_connection_new_for_address_sync(
+        address, G_DBUS_CONNECTION_FLAGS_AUTHENTICATION_CLIENT | G_DBUS_CONNECTION_FLAGS_MESSAGE_BUS_CONNECTION,
+        NULL, NULL, NULL);
+    g_free(address);
+    if (!g_bus) return FALSE;
+    if (!g_notifications) g_notifications = g_hash_table_new(g_direct_hash, g_direct_equal);
+    g_dbus_connection_signal_subscribe(g_bus, NOTIFICATIONS_NAME, NOTIFICATIONS_INTERFACE,
+                                       "ActionInvoked", NOTIFICATIONS_PATH, NULL,
+                                       G_DBUS_SIGNAL_FLAGS_NONE, on_action_invoked, NULL, NULL);
+    g_dbus_connection_signal_subscribe(g_bus, NOTIFICATIONS_NAME, NOTIFICATIONS_INTERFACE,
+                                       "NotificationClosed", NOTIFICATIONS_PATH, NULL,
+                                       G_DBUS_SIGNAL_FLAGS_NONE, on_notification_closed, NULL, NULL);
+    return TRUE;
+}
+
+static GVariant* image_hint(const NGNotificationOptions* options) {
+    gsize length = (gsize)options->icon_width * options->icon_height * 4;
+    GVariant* data = g_variant_new_fixed_array(G_VARIANT_TYPE_BYTE, options->icon_rgba, length, 1);
+    return g_variant_new("(iiibii@ay)", (gint32)options->icon_width, (gint32)options->icon_height,
+                         (gint32)(options->icon_width * 4), TRUE, 8, 4, data);
+}
+
+/* Send Notify for `options`, replacing `replaces_id` when non-zero. Returns
+ * the server's id, 0 on failure. */
+static guint32 notify(const NGNotificationOptions* options, guint32 replaces_id) {
+    GVariantBuilder actions;
+    g_variant_builder_init(&actions, G_VARIANT_TYPE("as"));
+    for (int i = 0; i < options->action_count; i++) {
+        g_variant_builder_add(&actions, "s", options->action_ids[i]);
+        g_variant_builder_add(&actions, "s", options->action_labels[i] ? options->action_labels[i] : "");
+    }
+
+    GVariantBuilder hints;
+    g_variant_builder_init(&hints, G_VARIANT_TYPE("a{sv}"));
+    guchar urgency = options->urgency > NG_NOTIFICATION_URGENCY_CRITICAL
+        ? NG_NOTIFICATION_URGENCY_NORMAL
+        : (guchar)options->urgency;
+    g_variant_builder_add(&hints, "{sv}", "urgency", g_variant_new_byte(urgency));
+    if (options->icon_rgba && options->icon_width > 0 && options->icon_height > 0) {
+        g_variant_builder_add(&hints, "{sv}", "image-data", image_hint(options));
+    }
+
+    const char* app_name = g_get_application_name();
+    GError* error = NULL;
+    GVariant* reply = g_dbus_connection_call_sync(
+        g_bus, NOTIFICATIONS_NAME, NOTIFICATIONS_PATH, NOTIFICATIONS_INTERFACE, "Notify",
+        g_variant_new("(susssasa{sv}i)", app_name ? app_name : "", replaces_id, "",
+                      options->title ? options->title : "", options->body ? options->body : "",
+                      &actions, &hints, options->timeout_ms),
+        G_VARIANT_TYPE("(u)"), G_DBUS_CALL_FLAGS_NONE, -1, NULL, &error);
+    if (!reply) {
+        g_clear_error(&error);
+        return 0;
+    }
+    guint32 id = 0;
+    g_variant_get(reply, "(u)", &id);
+    g_variant_unref(reply);
+    return id;
+}
+
+NGHandle ng_linux_notification_show(const NGNotificationOptions* options) {
+    if (!options || !ensure_bus()) return NULL;
+    guint32 id = notify(options, 0);
+    if (id == 0) return NULL;
+    NGNotification* notification = g_new0(NGNotification, 1);
+    notification->id = id;
+    g_hash_table_insert(g_notifications, GUINT_TO_POINTER(id), notification);
+    return notification;
+}
+
+int ng_linux_notification_update(NGHandle handle, const NGNotificationOptions* options) {
+    NGNotification* notification = handle;
+    if (!notification || !options) return NG_ERROR_INVALID_HANDLE;
+    if (!ensure_bus()) return NG_ERROR_PLATFORM_SPECIFIC;
+    guint32 id = notify(options, notification->id);
+    if (id == 0) return NG_ERROR_PLATFORM_SPECIFIC;
+    if (id != notification->id) {
+        if (notification->id != 0) {
+            g_hash_table_remove(g_notifications, GUINT_TO_POINTER(notification->id));
+        }
+        notification->id = id;
+        g_hash_table_insert(g_notifications, GUINT_TO_POINTER(id), notification);
+    }
+    return NG_SUCCESS;
+}
+
+int ng_linux_notification_close(NGHandle handle) {
+    NGNotification* notification = handle;
+    if (!notification || !g_bus) return NG_ERROR_INVALID_HANDLE;
+    if (notification->id == 0) return NG_SUCCESS;
+    GError* error = NULL;
+    GVariant* reply = g_dbus_connection_call_sync(
+        g_bus, NOTIFICATIONS_NAME, NOTIFICATIONS_PATH, NOTIFICATIONS_INTERFACE, "CloseNotification",
+        g_variant_new("(u)", notification->id), NULL, G_DBUS_CALL_FLAGS_NONE, -1, NULL, &error);
+    if (!reply) {
+        g_clear_error(&error);
+        return NG_ERROR_PLATFORM_SPECIFIC;
+    }
+    g_variant_unref(reply);
+    return NG_SUCCESS;
+}
+
+void ng_linux_notification_release(NGHandle handle) {
+    NGNotification* notification = handle;
+    if (!notification) return;
+    if (notification->id != 0 && g_notifications) {
+        g_hash_table_remove(g_notifications, GUINT_TO_POINTER(notification->id));
+    }
+    g_free(notification);
+}
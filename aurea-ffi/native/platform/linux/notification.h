@@ -0,0 +1,19 @@
+#ifndef NATIVE_GUI_LINUX_NOTIFICATION_H
+#define NATIVE_GUI_LINUX_NOTIFICATION_H
+
+#include "common/platform_api.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+NGHandle ng_linux_notification_show(const NGNotificationOptions* options);
+int ng_linux_notification_update(NGHandle notification, const NGNotificationOptions* options);
+int ng_linux_notification_close(NGHandle notification);
+void ng_linux_notification_release(NGHandle notification);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_NOTIFICATION_H
@@ -0,0 +1,176 @@
+#include "pointer.h"
+#include "common/input.h"
+#include "common/rust_callbacks.h"
+#include <stdint.h>
+
+static double event_axis(GdkEvent* event, GdkAxisUse axis, double fallback) {
+    double value = 0.0;
+    return gdk_event_get_axis(event, axis, &value) ? value : fallback;
+}
+
+static unsigned int button_bit(guint button, gboolean eraser) {
+    switch (button) {
+        case 1:
+            return eraser ? NG_POINTER_BUTTON_ERASER : NG_POINTER_BUTTON_PRIMARY;
+        case 2:
+            return NG_POINTER_BUTTON_MIDDLE;
+        case 3:
+            return NG_POINTER_BUTTON_SECONDARY;
+        default:
+            return 0;
+    }
+}
+
+static unsigned int buttons_from_state(GdkModifierType state, gboolean eraser) {
+    unsigned int buttons = 0;
+    if (state & GDK_BUTTON1_MASK) buttons |= button_bit(1, eraser);
+    if (state & GDK_BUTTON2_MASK) buttons |= button_bit(2, eraser);
+    if (state & GDK_BUTTON3_MASK) buttons |= button_bit(3, eraser);
+    return buttons;
+}
+
+static void fill_axes(NGPointerEvent* out, GdkEvent* event) {
+    // GDK normalises tilt to -1..1 and rotation to 0..1.
+    out->tilt_x = (float)(event_axis(event, GDK_AXIS_XTILT, 0.0) * 90.0);
+    out->tilt_y = (float)(event_axis(event, GDK_AXIS_YTILT, 0.0) * 90.0);
+    out->twist = (float)(event_axis(event, GDK_AXIS_ROTATION, 0.0) * 360.0);
+}
+
+static gboolean handle_touch(GtkWidget* window, GdkEventTouch* touch, unsigned int modifiers) {
+    NGPointerEvent out = {0};
+    out.pointer_id = (unsigned long long)(uintptr_t)touch->sequence;
+    out.kind = NG_POINTER_TOUCH;
+    out.x = touch->x;
+    out.y = touch->y;
+    out.modifiers = modifiers;
+    out.buttons = NG_POINTER_BUTTON_PRIMARY;
+
+    switch (touch->type) {
+        case GDK_TOUCH_BEGIN:
+            out.phase = NG_POINTER_DOWN;
+            break;
+        case GDK_TOUCH_UPDATE:
+            out.phase = NG_POINTER_MOVE;
+            break;
+        case GDK_TOUCH_END:
+            out.phase = NG_POINTER_UP;
+            out.buttons = 0;
+            break;
+        default:
+            out.phase = NG_POINTER_CANCEL;
+            out.buttons = 0;
+            break;
+    }
+    double fallback = out.buttons ? 0.5 : 0.0;
+    out.pressure = (float)event_axis((GdkEvent*)touch, GDK_AXIS_PRESSURE, fallback);
+    fill_axes(&out, (GdkEvent*)touch);
+
+    ng_invoke_pointer_event((void*)window, &out);
+    return TRUE;
+}
+
+static gboolean handle_pen(GtkWidget* window, GdkEvent* event, GdkDevice* device, unsigned int modifiers) {
+    GdkEventType type = gdk_event_get_event_type(event);
+    // Pointer events carry no click count; the plain press was reported.
+    if (type == GDK_2BUTTON_PRESS || type == GDK_3BUTTON_PRESS) return TRUE;
+
+    gboolean eraser = gdk_device_get_source(device) == GDK_SOURCE_ERASER;
+    GdkModifierType state = 0;
+    gdk_event_get_state(event, &state);
+
+    NGPointerEvent out = {0};
+    out.pointer_id = (unsigned long long)(uintptr_t)device;
+    out.kind = NG_POINTER_PEN;
+    out.modifiers = modifiers;
+    out.buttons = buttons_from_state(state, eraser);
+    gdk_event_get_coords(event, &out.x, &out.y);
+
+    // The state mask lags by one event: it excludes a button being pressed
+    // and still includes one being released.
+    guint button = 0;
+    gdk_event_get_button(event, &button);
+    switch (type) {
+        case GDK_BUTTON_PRESS:
+            out.phase = NG_POINTER_DOWN;
+            out.buttons |= button_bit(button, eraser);
+            break;
+        case GDK_BUTTON_RELEASE:
+            out.phase = NG_POINTER_UP;
+            out.buttons &= ~button_bit(button, eraser);
+            break;
+        default:
+            out.phase = NG_POINTER_MOVE;
+            break;
+    }
+    out.pressure = (float)event_axis(event, GDK_AXIS_PRESSURE, out.buttons ? 0.5 : 0.0);
+    fill_axes(&out, event);
+
+    ng_invoke_pointer_event((void*)window, &out);
+    return TRUE;
+}
+
+gboolean ng_linux_pointer_handle_event(GtkWidget* window, GdkEvent* event, unsigned int modifiers) {
+    if (!window || !event) return FALSE;
+
+    switch (gdk_event_get_event_type(event)) {
+        case GDK_TOUCH_BEGIN:
+        case GDK_TOUCH_UPDATE:
+        case GDK_TOUCH_END:
+        case GDK_TOUCH_CANCEL:
+            return handle_touch(window, (GdkEventTouch*)event, modifiers);
+        case GDK_BUTTON_PRESS:
+        case GDK_2BUTTON_PRESS:
+        case GDK_3BUTTON_PRESS:
+        case GDK_BUTTON_RELEASE:
+        case GDK_MOTION_NOTIFY:
+            break;
+        default:
+            return FALSE;
+    }
+
+    // Touch contacts already arrived as touch events.
+    if (gdk_event_get_pointer_emulated(event)) return TRUE;
+
+    GdkDevice* device = gdk_event_get_source_device(event);
+    if (!device) return FALSE;
+    switch (gdk_device_get_source(device)) {
+        case GDK_SOURCE_PEN:
+        case GDK_SOURCE_ERASER:
+            return handle_pen(window, event, device, modifiers);
+        default:
+            return FALSE;
+    }
+}
+
+/* GDK reports the pinch scale relative to the gesture start but the angle
+ * per event; only one touchpad gesture runs at a time. */
+static double g_touchpad_rotation = 0.0;
+
+gboolean ng_linux_pointer_handle_touchpad(GtkWidget* window, GdkEvent* event) {
+    if (!window || !event || gdk_event_get_event_type(event) != GDK_TOUCHPAD_PINCH) {
+        return FALSE;
+    }
+
+    GdkEventTouchpadPinch* pinch = (GdkEventTouchpadPinch*)event;
+    unsigned int phase = NG_TOUCHPAD_PHASE_CANCELLED;
+    switch (pinch->phase) {
+        case GDK_TOUCHPAD_GESTURE_PHASE_BEGIN:
+            g_touchpad_rotation = 0.0;
+            phase = NG_TOUCHPAD_PHASE_BEGAN;
+            break;
+        case GDK_TOUCHPAD_GESTURE_PHASE_UPDATE:
+            phase = NG_TOUCHPAD_PHASE_CHANGED;
+            break;
+        case GDK_TOUCHPAD_GESTURE_PHASE_END:
+            phase = NG_TOUCHPAD_PHASE_ENDED;
+            break;
+        default:
+            break;
+    }
+    // GDK's negative angles are counter-clockwise.
+    g_touchpad_rotation += pinch->angle_delta;
+
+    ng_invoke_touchpad_pinch(
+        (void*)window, phase, pinch->x, pinch->y, pinch->scale, g_touchpad_rotation);
+    return TRUE;
+}
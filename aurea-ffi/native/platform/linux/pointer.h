@@ -0,0 +1,23 @@
+#ifndef NATIVE_GUI_LINUX_POINTER_H
+#define NATIVE_GUI_LINUX_POINTER_H
+
+#include <gtk/gtk.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Report touch and pen input as pointer events. Returns TRUE when `event`
+// came from a touch or pen device (including GDK's emulated pointer events
+// for touch), in which case the caller must not treat it as mouse input.
+gboolean ng_linux_pointer_handle_event(GtkWidget* window, GdkEvent* event, unsigned int modifiers);
+
+// Report a GDK_TOUCHPAD_PINCH event as a touchpad pinch. Returns FALSE for
+// any other event type.
+gboolean ng_linux_pointer_handle_touchpad(GtkWidget* window, GdkEvent* event);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_POINTER_H
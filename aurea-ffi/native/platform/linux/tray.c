@@ -0,0 +1,505 @@
+#include "tray.h"
+#include "common/errors.h"
+#include "common/rust_callbacks.h"
+#include <gtk/gtk.h>
+#include <gio/gio.h>
+#include <string.h>
+#include <unistd.h>
+
+#define ITEM_PATH "/StatusNotifierItem"
+#define ITEM_INTERFACE "org.kde.StatusNotifierItem"
+#define MENU_PATH "/MenuBar"
+#define MENU_INTERFACE "com.canonical.dbusmenu"
+#define WATCHER_NAME "org.kde.StatusNotifierWatcher"
+#define WATCHER_PATH "/StatusNotifierWatcher"
+
+/* A StatusNotifierItem on its own session bus connection, so every icon can
+ * export the well-known object path. The menu is served over the dbusmenu
+ * protocol by walking the GtkMenu on each request; edits made through
+ * MenuItem handles show up the next time the host opens it. */
+typedef struct {
+    GDBusConnection* connection;
+    char* bus_name;
+    guint owner_id;
+    guint watch_id;
+    guint item_registration;
+    guint menu_registration;
+    GVariant* pixmaps;
+    char* tooltip;
+    GtkWidget* menu;
+    guint32 revision;
+    gint64 last_activate;
+} NGTray;
+
+static const char ITEM_XML[] =
+    "<node>"
+    " <interface name='" ITEM_INTERFACE "'>"
+    "  <property name='Category' type='s' access='read'/>"
+    "  <property name='Id' type='s' access='read'/>"
+    "  <property name='Title' type='s' access='read'/>"
+    "  <property name='Status' type='s' access='read'/>"
+    "  <property name='IconName' type='s' access='read'/>"
+    "  <property name='IconPixmap' type='a(iiay)' access='read'/>"
+    "  <property name='ToolTip' type='(sa(iiay)ss)' access='read'/>"
+    "  <property name='ItemIsMenu' type='b' access='read'/>"
+    "  <property name='Menu' type='o' access='read'/>"
+    "  <method name='ContextMenu'><arg name='x' type='i' direction='in'/><arg name='y' type='i' direction='in'/></method>"
+    "  <method name='Activate'><arg name='x' type='i' direction='in'/><arg name='y' type='i' direction='in'/></method>"
+    "  <method name='SecondaryActivate'><arg name='x' type='i' direction='in'/><arg name='y' type='i' direction='in'/></method>"
+    "  <method name='Scroll'><arg name='delta' type='i' direction='in'/><arg name='orientation' type='s' direction='in'/></method>"
+    "  <signal name='NewIcon'/>"
+    "  <signal name='NewToolTip'/>"
+    " </interface>"
+    "</node>";
+
+static const char MENU_XML[] =
+    "<node>"
+    " <interface name='" MENU_INTERFACE "'>"
+    "  <property name='Version' type='u' access='read'/>"
+    "  <property name='TextDirection' type='s' access='read'/>"
+    "  <property name='Status' type='s' access='read'/>"
+    "  <property name='IconThemePath' type='as' access='read'/>"
+    "  <method name='GetLayout'>"
+    "   <arg name='parentId' type='i' direction='in'/><arg name='recursionDepth' type='i' direction='in'/>"
+    "   <arg name='propertyNames' type='as' direction='in'/>"
+    "   <arg name='revision' type='u' direction='out'/><arg name='layout' type='(ia{sv}av)' direction='out'/>"
+    "  </method>"
+    "  <method name='GetGroupProperties'>"
+    "   <arg name='ids' type='ai' direction='in'/><arg name='propertyNames' type='as' direction='in'/>"
+    "   <arg name='properties' type='a(ia{sv})' direction='out'/>"
+    "  </method>"
+    "  <method name='GetProperty'>"
+    "   <arg name='id' type='i' direction='in'/><arg name='name' type='s' direction='in'/>"
+    "   <arg name='value' type='v' direction='out'/>"
+    "  </method>"
+    "  <method name='Event'>"
+    "   <arg name='id' type='i' direction='in'/><arg name='eventId' type='s' direction='in'/>"
+    "   <arg name='data' type='v' direction='in'/><arg name='timestamp' type='u' direction='in'/>"
+    "  </method>"
+    "  <method name='EventGroup'>"
+    "   <arg name='events' type='a(isvu)' direction='in'/><arg name='idErrors' type='ai' direction='out'/>"
+    "  </method>"
+    "  <method name='AboutToShow'>"
+    "   <arg name='id' type='i' direction='in'/><arg name='needUpdate' type='b' direction='out'/>"
+    "  </method>"
+    "  <method name='AboutToShowGroup'>"
+    "   <arg name='ids' type='ai' direction='in'/>"
+    "   <arg name='updatesNeeded' type='ai' direction='out'/><arg name='idErrors' type='ai' direction='out'/>"
+    "  </method>"
+    "  <signal name='ItemsPropertiesUpdated'>"
+    "   <arg name='updatedProps' type='a(ia{sv})'/><arg name='removedProps' type='a(ias)'/>"
+    "  </signal>"
+    "  <signal name='LayoutUpdated'><arg name='revision' type='u'/><arg name='parent' type='i'/></signal>"
+    " </interface>"
+    "</node>";
+
+static GDBusNodeInfo* g_item_info = NULL;
+static GDBusNodeInfo* g_menu_info = NULL;
+static guint g_tray_count = 0;
+static gint g_next_menu_id = 0;
+
+static gboolean ensure_node_info(void) {
+    if (!g_item_info) g_item_info = g_dbus_node_info_new_for_xml(ITEM_XML, NULL);
+    if (!g_menu_info) g_menu_info = g_dbus_node_info_new_for_xml(MENU_XML, NULL);
+    return g_item_info && g_menu_info;
+}
+
+/* IconPixmap wants ARGB32 in network byte order. */
+static GVariant* pixmaps_from_rgba(const unsigned char* rgba, unsigned int width, unsigned int height) {
+    GVariantBuilder builder;
+    g_variant_builder_init(&builder, G_VARIANT_TYPE("a(iiay)"));
+    if (rgba && width > 0 && height > 0) {
+        gsize size = (gsize)width * height * 4;
+        guchar* argb = g_malloc(size);
+        for (gsize i = 0; i < size; i += 4) {
+            argb[i] = rgba[i + 3];
+            argb[i + 1] = rgba[i];
+            argb[i + 2] = rgba[i + 1];
+            argb[i + 3] = rgba[i + 2];
+        }
+        GVariant* bytes = g_variant_new_from_data(G_VARIANT_TYPE("ay"), argb, size, TRUE, g_free, argb);
+        g_variant_builder_add(&builder, "(ii@ay)", (gint)width, (gint)height, bytes);
+    }
+    return g_variant_ref_sink(g_variant_builder_end(&builder));
+}
+
+static void emit_item_signal(NGTray* tray, const char* name) {
+    g_dbus_connection_emit_signal(tray->connection, NULL, ITEM_PATH, ITEM_INTERFACE, name, NULL, NULL);
+}
+
+static void emit_layout_updated(NGTray* tray) {
+    tray->revision++;
+    g_dbus_connection_emit_signal(tray->connection, NULL, MENU_PATH, MENU_INTERFACE, "LayoutUpdated",
+                                  g_variant_new("(ui)", tray->revision, 0), NULL);
+}
+
+/* ---- dbusmenu ---- */
+
+static gint menu_item_id(GtkWidget* item) {
+    gint id = GPOINTER_TO_INT(g_object_get_data(G_OBJECT(item), "aurea-dbusmenu-id"));
+    if (!id) {
+        id = ++g_next_menu_id;
+        g_object_set_data(G_OBJECT(item), "aurea-dbusmenu-id", GINT_TO_POINTER(id));
+    }
+    return id;
+}
+
+static GtkWidget* find_menu_item(GtkWidget* shell, gint id) {
+    GList* children = gtk_container_get_children(GTK_CONTAINER(shell));
+    GtkWidget* found = NULL;
+    for (GList* l = children; l && !found; l = l->next) {
+        GtkWidget* item = GTK_WIDGET(l->data);
+        if (GPOINTER_TO_INT(g_object_get_data(G_OBJECT(item), "aurea-dbusmenu-id")) == id) {
+            found = item;
+        } else if (GTK_IS_MENU_ITEM(item) && gtk_menu_item_get_submenu(GTK_MENU_ITEM(item))) {
+            found = find_menu_item(gtk_menu_item_get_submenu(GTK_MENU_ITEM(item)), id);
+        }
+    }
+    g_list_free(children);
+    return found;
+}
+
+/* dbusmenu labels use '_' as the mnemonic marker; double literal ones. */
+static GVariant* item_label(GtkWidget* item) {
+    GtkWidget* label = g_object_get_data(G_OBJECT(item), "aurea-menu-label");
+    const char* text = label ? gtk_label_get_text(GTK_LABEL(label))
+                             : gtk_menu_item_get_label(GTK_MENU_ITEM(item));
+    GString* escaped = g_string_new(NULL);
+    for (const char* p = text ? text : ""; *p; p++) {
+        if (*p == '_') g_string_append_c(escaped, '_');
+        g_string_append_c(escaped, *p);
+    }
+    return g_variant_new_take_string(g_string_free(escaped, FALSE));
+}
+
+static GVariant* item_icon(GtkWidget* item) {
+    GtkWidget* child = gtk_bin_get_child(GTK_BIN(item));
+    if (!GTK_IS_BOX(child)) return NULL;
+
+    GdkPixbuf* pixbuf = NULL;
+    GList* parts = gtk_container_get_children(GTK_CONTAINER(child));
+    for (GList* l = parts; l; l = l->next) {
+        if (GTK_IS_IMAGE(l->data) &&
+            gtk_image_get_storage_type(GTK_IMAGE(l->data)) == GTK_IMAGE_PIXBUF) {
+            pixbuf = gtk_image_get_pixbuf(GTK_IMAGE(l->data));
+            break;
+        }
+    }
+    g_list_free(parts);
+
+    gchar* png = NULL;
+    gsize length = 0;
+    if (!pixbuf || !gdk_pixbuf_save_to_buffer(pixbuf, &png, &length, "png", NULL, NULL)) return NULL;
+    return g_variant_new_from_data(G_VARIANT_TYPE("ay"), png, length, TRUE, g_free, png);
+}
+
+static GVariant* item_properties(GtkWidget* item) {
+    GVariantBuilder props;
+    g_variant_builder_init(&props, G_VARIANT_TYPE("a{sv}"));
+    if (!item) {
+        g_variant_builder_add(&props, "{sv}", "children-display", g_variant_new_string("submenu"));
+        return g_variant_builder_end(&props);
+    }
+
+    if (GTK_IS_SEPARATOR_MENU_ITEM(item)) {
+        g_variant_builder_add(&props, "{sv}", "type", g_variant_new_string("separator"));
+    } else {
+        g_variant_builder_add(&props, "{sv}", "label", item_label(item));
+        if (GTK_IS_CHECK_MENU_ITEM(item)) {
+            const char* toggle = GTK_IS_RADIO_MENU_ITEM(item) ? "radio" : "checkmark";
+            gboolean active = gtk_check_menu_item_get_active(GTK_CHECK_MENU_ITEM(item));
+            g_variant_builder_add(&props, "{sv}", "toggle-type", g_variant_new_string(toggle));
+            g_variant_builder_add(&props, "{sv}", "toggle-state", g_variant_new_int32(active ? 1 : 0));
+        }
+        if (gtk_menu_item_get_submenu(GTK_MENU_ITEM(item))) {
+            g_variant_builder_add(&props, "{sv}", "children-display", g_variant_new_string("submenu"));
+        }
+        GVariant* icon = item_icon(item);
+        if (icon) g_variant_builder_add(&props, "{sv}", "icon-data", icon);
+    }
+    g_variant_builder_add(&props, "{sv}", "enabled", g_variant_new_boolean(gtk_widget_get_sensitive(item)));
+    g_variant_builder_add(&props, "{sv}", "visible", g_variant_new_boolean(gtk_widget_get_visible(item)));
+    return g_variant_builder_end(&props);
+}
+
+/* `item` is NULL for the root, whose children are those of the tray menu. */
+static GVariant* menu_layout(GtkWidget* item, GtkWidget* shell, gint id, gint depth) {
+    GVariantBuilder children;
+    g_variant_builder_init(&children, G_VARIANT_TYPE("av"));
+    if (shell && depth != 0) {
+        GList* list = gtk_container_get_children(GTK_CONTAINER(shell));
+        for (GList* l = list; l; l = l->next) {
+            GtkWidget* child = GTK_WIDGET(l->data);
+            if (!GTK_IS_MENU_ITEM(child)) continue;
+            GtkWidget* submenu = gtk_menu_item_get_submenu(GTK_MENU_ITEM(child));
+            g_variant_builder_add(&children, "v",
+                                  menu_layout(child, submenu, menu_item_id(child), depth > 0 ? depth - 1 : depth));
+        }
+        g_list_free(list);
+    }
+    return g_variant_new("(i@a{sv}@av)", id, item_properties(item), g_variant_builder_end(&children));
+}
+
+/* Resolve a dbusmenu id; FALSE for ids that no longer exist. */
+static gboolean lookup_item(NGTray* tray, gint id, GtkWidget** item) {
+    *item = NULL;
+    if (id == 0) return TRUE;
+    if (tray->menu) *item = find_menu_item(tray->menu, id);
+    return *item != NULL;
+}
+
+static void activate_item(NGTray* tray, gint id, const char* event_id) {
+    GtkWidget* item = NULL;
+    if (strcmp(event_id, "clicked") != 0 || !lookup_item(tray, id, &item) || !item) return;
+    if (!gtk_widget_is_sensitive(item)) return;
+    gtk_menu_item_activate(GTK_MENU_ITEM(item));
+    // Check and radio items changed state.
+    if (GTK_IS_CHECK_MENU_ITEM(item)) emit_layout_updated(tray);
+}
+
+static void menu_method_call(GDBusConnection* connection, const gchar* sender, const gchar* path,
+                             const gchar* interface, const gchar* method, GVariant* params,
+                             GDBusMethodInvocation* invocation, gpointer user_data) {
+    NGTray* tray = user_data;
+    GtkWidget* item = NULL;
+
+    if (strcmp(method, "GetLayout") == 0) {
+        gint parent = 0;
+        gint depth = -1;
+        g_variant_get(params, "(ii^a&s)", &parent, &depth, NULL);
+        if (!lookup_item(tray, parent, &item)) {
+            g_dbus_method_invocation_return_error(invocation, G_DBUS_ERROR, G_DBUS_ERROR_INVALID_ARGS,
+                                                  "Unknown menu item %d", parent);
+            return;
+        }
+        GtkWidget* shell = item ? gtk_menu_item_get_submenu(GTK_MENU_ITEM(item)) : tray->menu;
+        g_dbus_method_invocation_return_value(
+            invocation, g_variant_new("(u@(ia{sv}av))", tray->revision, menu_layout(item, shell, parent, depth)));
+    } else if (strcmp(method, "GetGroupProperties") == 0) {
+        GVariantIter* ids = NULL;
+        gint id = 0;
+        GVariantBuilder result;
+        g_variant_builder_init(&result, G_VARIANT_TYPE("a(ia{sv})"));
+        g_variant_get(params, "(ai^a&s)", &ids, NULL);
+        while (g_variant_iter_loop(ids, "i", &id)) {
+            if (lookup_item(tray, id, &item)) {
+                g_variant_builder_add(&result, "(i@a{sv})", id, item_properties(item));
+            }
+        }
+        g_variant_iter_free(ids);
+        g_dbus_method_invocation_return_value(invocation, g_variant_new("(@a(ia{sv}))", g_variant_builder_end(&result)));
+    } else if (strcmp(method, "GetProperty") == 0) {
+        gint id = 0;
+        const gchar* name = NULL;
+        g_variant_get(params, "(i&s)", &id, &name);
+        GVariant* value = NULL;
+        if (lookup_item(tray, id, &item)) {
+            GVariant* props = g_variant_ref_sink(item_properties(item));
+            value = g_variant_lookup_value(props, name, NULL);
+            g_variant_unref(props);
+        }
+        if (!value) {
+            g_dbus_method_invocation_return_error(invocation, G_DBUS_ERROR, G_DBUS_ERROR_INVALID_ARGS,
+                                                  "Unknown property %s on item %d", name, id);
+            return;
+        }
+        g_dbus_method_invocation_return_value(invocation, g_variant_new("(v)", value));
+        g_variant_unref(value);
+    } else if (strcmp(method, "Event") == 0) {
+        gint id = 0;
+        const gchar* event_id = NULL;
+        g_variant_get(params, "(i&svu)", &id, &event_id, NULL, NULL);
+        activate_item(tray, id, event_id);
+        g_dbus_method_invocation_return_value(invocation, NULL);
+    } else if (strcmp(method, "EventGroup") == 0) {
+        GVariantIter* events = NULL;
+        gint id = 0;
+        const gchar* event_id = NULL;
+        g_variant_get(params, "(a(isvu))", &events);
+        while (g_variant_iter_loop(events, "(i&svu)", &id, &event_id, NULL, NULL)) {
+            activate_item(tray, id, event_id);
+        }
+        g_variant_iter_free(events);
+        g_dbus_method_invocation_return_value(invocation, g_variant_new("(@ai)", g_variant_new_array(G_VARIANT_TYPE_INT32, NULL, 0)));
+    } else if (strcmp(method, "AboutToShow") == 0) {
+        // The layout is read live, so hosts should always refetch it.
+        g_dbus_method_invocation_return_value(invocation, g_variant_new("(b)", TRUE));
+    } else if (strcmp(method, "AboutToShowGroup") == 0) {
+        GVariant* ids = g_variant_get_child_value(params, 0);
+        g_dbus_method_invocation_return_value(
+            invocation, g_variant_new("(@ai@ai)", ids, g_variant_new_array(G_VARIANT_TYPE_INT32, NULL, 0)));
+        g_variant_unref(ids);
+    } else {
+        g_dbus_method_invocation_return_error(invocation, G_DBUS_ERROR, G_DBUS_ERROR_UNKNOWN_METHOD,
+                                              "Unknown method %s", method);
+    }
+}
+
+static GVariant* menu_get_property(GDBusConnection* connection, const gchar* sender, const gchar* path,
+                                   const gchar* interface, const gchar* name, GError** error,
+                                   gpointer user_data) {
+    if (strcmp(name, "Version") == 0) return g_variant_new_uint32(3);
+    if (strcmp(name, "TextDirection") == 0) {
+        return g_variant_new_string(gtk_widget_get_default_direction() == GTK_TEXT_DIR_RTL ? "rtl" : "ltr");
+    }
+    if (strcmp(name, "Status") == 0) return g_variant_new_string("normal");
+    if (strcmp(name, "IconThemePath") == 0) return g_variant_new_strv(NULL, 0);
+    g_set_error(error, G_DBUS_ERROR, G_DBUS_ERROR_UNKNOWN_PROPERTY, "Unknown property %s", name);
+    return NULL;
+}
+
+static const GDBusInterfaceVTable menu_vtable = { menu_method_call, menu_get_property, NULL, { 0 } };
+
+/* ---- StatusNotifierItem ---- */
+
+static void item_method_call(GDBusConnection* connection, const gchar* sender, const gchar* path,
+                             const gchar* interface, const gchar* method, GVariant* params,
+                             GDBusMethodInvocation* invocation, gpointer user_data) {
+    NGTray* tray = user_data;
+    gint x = 0;
+    gint y = 0;
+    if (g_variant_is_of_type(params, G_VARIANT_TYPE("(ii)"))) {
+        g_variant_get(params, "(ii)", &x, &y);
+    }
+
+    if (strcmp(method, "Activate") == 0) {
+        // Hosts only report single activations; pair them up like GDK does.
+        gint interval = 400;
+        GtkSettings* settings = gtk_settings_get_default();
+        if (settings) g_object_get(settings, "gtk-double-click-time", &interval, NULL);
+        gint64 now = g_get_monotonic_time();
+        int clicks = tray->last_activate && now - tray->last_activate <= (gint64)interval * 1000 ? 2 : 1;
+        tray->last_activate = clicks == 2 ? 0 : now;
+        ng_invoke_tray_event(tray, 0, clicks, x, y);
+    } else if (strcmp(method, "SecondaryActivate") == 0) {
+        ng_invoke_tray_event(tray, 2, 1, x, y);
+    } else if (strcmp(method, "ContextMenu") == 0) {
+        // Hosts without dbusmenu support ask the item to show its own menu.
+        if (tray->menu) {
+            gtk_widget_show_all(tray->menu);
+            gtk_menu_popup_at_pointer(GTK_MENU(tray->menu), NULL);
+        }
+        ng_invoke_tray_event(tray, 1, 1, x, y);
+    }
+    g_dbus_method_invocation_return_value(invocation, NULL);
+}
+
+static GVariant* item_get_property(GDBusConnection* connection, const gchar* sender, const gchar* path,
+                                   const gchar* interface, const gchar* name, GError** error,
+                                   gpointer user_data) {
+    NGTray* tray = user_data;
+    const char* app = g_get_prgname() ? g_get_prgname() : "aurea";
+
+    if (strcmp(name, "Category") == 0) return g_variant_new_string("ApplicationStatus");
+    if (strcmp(name, "Id") == 0) return g_variant_new_string(app);
+    if (strcmp(name, "Title") == 0) {
+        return g_variant_new_string(g_get_application_name() ? g_get_application_name() : app);
+    }
+    if (strcmp(name, "Status") == 0) return g_variant_new_string("Active");
+    if (strcmp(name, "IconName") == 0) return g_variant_new_string("");
+    if (strcmp(name, "IconPixmap") == 0) return g_variant_ref(tray->pixmaps);
+    if (strcmp(name, "ToolTip") == 0) {
+        return g_variant_new("(s@a(iiay)ss)", "", g_variant_new_array(G_VARIANT_TYPE("(iiay)"), NULL, 0),
+                             tray->tooltip, "");
+    }
+    if (strcmp(name, "ItemIsMenu") == 0) return g_variant_new_boolean(FALSE);
+    if (strcmp(name, "Menu") == 0) return g_variant_new_object_path(MENU_PATH);
+    g_set_error(error, G_DBUS_ERROR, G_DBUS_ERROR_UNKNOWN_PROPERTY, "Unknown property %s", name);
+    return NULL;
+}
+
+static const GDBusInterfaceVTable item_vtable = { item_method_call, item_get_property, NULL, { 0 } };
+
+static void on_watcher_appeared(GDBusConnection* connection, const gchar* name, const gchar* owner,
+                                gpointer user_data) {
+    NGTray* tray = user_data;
+    g_dbus_connection_call(connection, WATCHER_NAME, WATCHER_PATH, WATCHER_NAME,
+                           "RegisterStatusNotifierItem", g_variant_new("(s)", tray->bus_name),
+                           NULL, G_DBUS_CALL_FLAGS_NONE, -1, NULL, NULL, NULL);
+}
+
+/* Register once the name is ours, and again whenever a new watcher (i.e. a
+ * restarted panel) appears. */
+static void on_name_acquired(GDBusConnection* connection, const gchar* name, gpointer user_data) {
+    NGTray* tray = user_data;
+    if (tray->watch_id) return;
+    tray->watch_id = g_bus_watch_name_on_connection(connection, WATCHER_NAME, G_BUS_NAME_WATCHER_FLAGS_NONE,
+                                                    on_watcher_appeared, NULL, tray, NULL);
+}
+
+NGHandle ng_linux_tray_create(const unsigned char* rgba, unsigned int width, unsigned int height, const char* tooltip) {
+    if (!ensure_node_info()) return NULL;
+
+    gchar* address = g_dbus_address_get_for_bus_sync(G_BUS_TYPE_SESSION, NULL, NULL);
+    if (!address) return NULL;
+    GDBusConnection* connection = g_dbus_connection_new_for_address_sync(
+        address, G_DBUS_CONNECTION_FLAGS_AUTHENTICATION_CLIENT | G_DBUS_CONNECTION_FLAGS_MESSAGE_BUS_CONNECTION,
+        NULL, NULL, NULL);
+    g_free(address);
+    if (!connection) return NULL;
+
+    NGTray* tray = g_new0(NGTray, 1);
+    tray->connection = connection;
+    tray->pixmaps = pixmaps_from_rgba(rgba, width, height);
+    tray->tooltip = g_strdup(tooltip ? tooltip : "");
+    tray->item_registration = g_dbus_connection_register_object(
+        connection, ITEM_PATH, g_item_info->interfaces[0], &item_vtable, tray, NULL, NULL);
+    tray->menu_registration = g_dbus_connection_register_object(
+        connection, MENU_PATH, g_menu_info->interfaces[0], &menu_vtable, tray, NULL, NULL);
+    if (!tray->item_registration || !tray->menu_registration) {
+        ng_linux_tray_destroy(tray);
+        return NULL;
+    }
+
+    tray->bus_name = g_strdup_printf("org.kde.StatusNotifierItem-%d-%u", (int)getpid(), ++g_tray_count);
+    tray->owner_id = g_bus_own_name_on_connection(connection, tray->bus_name, G_BUS_NAME_OWNER_FLAGS_NONE,
+                                                  on_name_acquired, NULL, tray, NULL);
+    return (NGHandle)tray;
+}
+
+int ng_linux_tray_set_icon(NGHandle handle, const unsigned char* rgba, unsigned int width, unsigned int height) {
+    NGTray* tray = handle;
+    if (!tray) return NG_ERROR_INVALID_HANDLE;
+    if (!rgba || width == 0 || height == 0) return NG_ERROR_INVALID_PARAMETER;
+    g_variant_unref(tray->pixmaps);
+    tray->pixmaps = pixmaps_from_rgba(rgba, width, height);
+    emit_item_signal(tray, "NewIcon");
+    return NG_SUCCESS;
+}
+
+int ng_linux_tray_set_tooltip(NGHandle handle, const char* tooltip) {
+    NGTray* tray = handle;
+    if (!tray) return NG_ERROR_INVALID_HANDLE;
+    g_free(tray->tooltip);
+    tray->tooltip = g_strdup(tooltip ? tooltip : "");
+    emit_item_signal(tray, "NewToolTip");
+    return NG_SUCCESS;
+}
+
+int ng_linux_tray_set_menu(NGHandle handle, NGMenuHandle menu) {
+    NGTray* tray = handle;
+    if (!tray) return NG_ERROR_INVALID_HANDLE;
+    if (menu && !GTK_IS_MENU(menu)) return NG_ERROR_INVALID_PARAMETER;
+    if (menu) g_object_ref(menu);
+    if (tray->menu) g_object_unref(tray->menu);
+    tray->menu = (GtkWidget*)menu;
+    emit_layout_updated(tray);
+    return NG_SUCCESS;
+}
+
+void ng_linux_tray_destroy(NGHandle handle) {
+    NGTray* tray = handle;
+    if (!tray) return;
+    if (tray->watch_id) g_bus_unwatch_name(tray->watch_id);
+    if (tray->owner_id) g_bus_unown_name(tray->owner_id);
+    if (tray->item_registration) g_dbus_connection_unregister_object(tray->connection, tray->item_registration);
+    if (tray->menu_registration) g_dbus_connection_unregister_object(tray->connection, tray->menu_registration);
+    // Closing drops the item from the watcher straight away.
+    g_dbus_connection_close_sync(tray->connection, NULL, NULL);
+    g_object_unref(tray->connection);
+    if (tray->menu) g_object_unref(tray->menu);
+    g_variant_unref(tray->pixmaps);
+    g_free(tray->tooltip);
+    g_free(tray->bus_name);
+    g_free(tray);
+}
@@ -0,0 +1,20 @@
+#ifndef NATIVE_GUI_LINUX_TRAY_H
+#define NATIVE_GUI_LINUX_TRAY_H
+
+#include "common/platform_api.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+NGHandle ng_linux_tray_create(const unsigned char* rgba, unsigned int width, unsigned int height, const char* tooltip);
+int ng_linux_tray_set_icon(NGHandle tray, const unsigned char* rgba, unsigned int width, unsigned int height);
+int ng_linux_tray_set_tooltip(NGHandle tray, const char* tooltip);
+int ng_linux_tray_set_menu(NGHandle tray, NGMenuHandle menu);
+void ng_linux_tray_destroy(NGHandle tray);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_TRAY_H
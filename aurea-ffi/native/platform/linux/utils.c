@@ -1,6 +1,8 @@
 #include "utils.h"
+#include "window_state.h"
 #include "common/errors.h"
 #include <gtk/gtk.h>
+#include <string.h>
 
 static gboolean gtk_initialized = FALSE;
 
@@ -9,6 +11,7 @@ int ng_linux_init(void) {
         int argc = 0;
         char **argv = NULL;
         gtk_init(&argc, &argv);
+        ng_linux_watch_monitors();
         gtk_initialized = TRUE;
     }
     return NG_SUCCESS;
@@ -24,3 +27,50 @@ gboolean ng_linux_is_initialized(void) {
     return gtk_initialized;
 }
 
+GdkPixbuf* ng_linux_pixbuf_from_rgba(const unsigned char* rgba, unsigned int width, unsigned int height) {
+    GdkPixbuf* pixbuf = gdk_pixbuf_new(
+        GDK_COLORSPACE_RGB,
+        TRUE,
+        8,
+        (int)width,
+        (int)height
+    );
+    if (!pixbuf) return NULL;
+
+    unsigned char* pixels = gdk_pixbuf_get_pixels(pixbuf);
+    int row_stride = gdk_pixbuf_get_rowstride(pixbuf);
+    size_t source_stride = (size_t)width * 4;
+    for (unsigned int row = 0; row < height; ++row) {
+        memcpy(
+            pixels + (size_t)row * (size_t)row_stride,
+            rgba + (size_t)row * source_stride,
+            source_stride
+        );
+    }
+    return pixbuf;
+}
+
+unsigned char* ng_linux_rgba_from_pixbuf(GdkPixbuf* pixbuf, unsigned int* width, unsigned int* height) {
+    if (!pixbuf) return NULL;
+    // add_alpha always returns a new pixbuf, so the copy below has 4 channels.
+    GdkPixbuf* rgba = gdk_pixbuf_add_alpha(pixbuf, FALSE, 0, 0, 0);
+    if (!rgba) return NULL;
+
+    int w = gdk_pixbuf_get_width(rgba);
+    int h = gdk_pixbuf_get_height(rgba);
+    int row_stride = gdk_pixbuf_get_rowstride(rgba);
+    const guchar* pixels = gdk_pixbuf_read_pixels(rgba);
+    size_t target_stride = (size_t)w * 4;
+    unsigned char* out = g_malloc(target_stride * (size_t)h);
+    for (int row = 0; row < h; ++row) {
+        memcpy(
+            out + (size_t)row * target_stride,
+            pixels + (size_t)row * (size_t)row_stride,
+            target_stride
+        );
+    }
+    g_object_unref(rgba);
+    *width = (unsigned int)w;
+    *height = (unsigned int)h;
+    return out;
+}
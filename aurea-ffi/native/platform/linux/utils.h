@@ -12,6 +12,13 @@ void ng_linux_cleanup(void);
 int ng_linux_run(void);
 int ng_linux_poll_events(void);
 void ng_linux_request_frame(void);
+void ng_linux_request_frame_after(unsigned int delay_ms);
+
+// Copy tightly packed RGBA8 pixels into a new pixbuf; NULL on failure.
+GdkPixbuf* ng_linux_pixbuf_from_rgba(const unsigned char* rgba, unsigned int width, unsigned int height);
+// Inverse of ng_linux_pixbuf_from_rgba for any 8-bit RGB(A) pixbuf; free the
+// result with g_free.
+unsigned char* ng_linux_rgba_from_pixbuf(GdkPixbuf* pixbuf, unsigned int* width, unsigned int* height);
 
 #ifdef __cplusplus
 }
@@ -1,6 +1,12 @@
 ﻿#include "window.h"
 #include "utils.h"
-#include "menu.h"
+#include "cursor.h"
+#include "dnd.h"
+#include "focus.h"
+#include "ime.h"
+#include "keyboard.h"
+#include "pointer.h"
+#include "window_state.h"
 #include "common/errors.h"
 #include "common/input.h"
 #include "common/rust_callbacks.h"
@@ -20,6 +26,7 @@ static GtkWidget* g_lifecycle_windows[256] = {0};
 static int g_lifecycle_callback_count = 0;
 
 static const char* AUREA_MAIN_VBOX_KEY = "aurea-main-vbox";
+static const char* AUREA_DESTROYED_KEY = "aurea-destroyed";
 
 static GtkWidget* ng_linux_window_main_vbox(GtkWidget* window) {
     if (!window) return NULL;
@@ -44,12 +51,15 @@ static gboolean on_button_press(GtkWidget* widget, GdkEventButton* event, gpoint
 static gboolean on_button_release(GtkWidget* widget, GdkEventButton* event, gpointer user_data);
 static gboolean on_motion_notify(GtkWidget* widget, GdkEventMotion* event, gpointer user_data);
 static gboolean on_scroll(GtkWidget* widget, GdkEventScroll* event, gpointer user_data);
+static gboolean on_touch(GtkWidget* widget, GdkEventTouch* event, gpointer user_data);
+static gboolean on_gesture(GtkWidget* widget, GdkEvent* event, gpointer user_data);
 static gboolean on_focus_in(GtkWidget* widget, GdkEventFocus* event, gpointer user_data);
 static gboolean on_focus_out(GtkWidget* widget, GdkEventFocus* event, gpointer user_data);
 static gboolean on_enter(GtkWidget* widget, GdkEventCrossing* event, gpointer user_data);
 static gboolean on_leave(GtkWidget* widget, GdkEventCrossing* event, gpointer user_data);
 
 static void on_window_destroy(GtkWidget* widget, gpointer data) {
+    g_object_set_data(G_OBJECT(widget), AUREA_DESTROYED_KEY, GINT_TO_POINTER(1));
     // Invoke lifecycle callback if enabled
     for (int i = 0; i < g_lifecycle_callback_count; i++) {
         if (g_lifecycle_windows[i] == widget && g_lifecycle_callbacks[i]) {
@@ -86,10 +96,14 @@ static gboolean on_window_state_event(GtkWidget* widget, GdkEventWindowState* ev
     return FALSE;
 }
 
-NGHandle ng_linux_create_window(const char* title, int width, int height) {
-    if (!title) return NULL;
-    
+/* Builds the window and its signal wiring without showing it, so callers
+ * can finish configuring it before it is first mapped. */
+static GtkWidget* new_window(const char* title, int width, int height) {
     GtkWidget *window = gtk_window_new(GTK_WINDOW_TOPLEVEL);
+    /* The handle's own reference, dropped in ng_linux_destroy_window: GTK
+     * destroys the window itself when the user closes it or its parent goes
+     * (destroy-with-parent), and the handle has to stay valid until then. */
+    g_object_ref(window);
     gtk_window_set_title(GTK_WINDOW(window), title);
     gtk_window_set_default_size(GTK_WINDOW(window), width, height);
 
@@ -97,7 +111,8 @@ NGHandle ng_linux_create_window(const char* title, int width, int height) {
         window,
         GDK_KEY_PRESS_MASK | GDK_KEY_RELEASE_MASK | GDK_BUTTON_PRESS_MASK |
             GDK_BUTTON_RELEASE_MASK | GDK_POINTER_MOTION_MASK | GDK_SCROLL_MASK |
-            GDK_ENTER_NOTIFY_MASK | GDK_LEAVE_NOTIFY_MASK | GDK_FOCUS_CHANGE_MASK);
+            GDK_ENTER_NOTIFY_MASK | GDK_LEAVE_NOTIFY_MASK | GDK_FOCUS_CHANGE_MASK |
+            GDK_TOUCH_MASK | GDK_TOUCHPAD_GESTURE_MASK);
 
     g_signal_connect(G_OBJECT(window), "key-press-event", G_CALLBACK(on_key_press), NULL);
     g_signal_connect(G_OBJECT(window), "key-release-event", G_CALLBACK(on_key_release), NULL);
@@ -105,6 +120,9 @@ NGHandle ng_linux_create_window(const char* title, int width, int height) {
     g_signal_connect(G_OBJECT(window), "button-release-event", G_CALLBACK(on_button_release), NULL);
     g_signal_connect(G_OBJECT(window), "motion-notify-event", G_CALLBACK(on_motion_notify), NULL);
     g_signal_connect(G_OBJECT(window), "scroll-event", G_CALLBACK(on_scroll), NULL);
+    g_signal_connect(G_OBJECT(window), "touch-event", G_CALLBACK(on_touch), NULL);
+    // Touchpad gestures have no dedicated signal in GTK 3.
+    g_signal_connect(G_OBJECT(window), "event", G_CALLBACK(on_gesture), NULL);
     g_signal_connect(G_OBJECT(window), "focus-in-event", G_CALLBACK(on_focus_in), NULL);
     g_signal_connect(G_OBJECT(window), "focus-out-event", G_CALLBACK(on_focus_out), NULL);
     g_signal_connect(G_OBJECT(window), "enter-notify-event", G_CALLBACK(on_enter), NULL);
@@ -119,17 +137,22 @@ NGHandle ng_linux_create_window(const char* title, int width, int height) {
     GtkWidget* main_vbox = gtk_box_new(GTK_ORIENTATION_VERTICAL, 0);
     gtk_container_add(GTK_CONTAINER(window), main_vbox);
     g_object_set_data(G_OBJECT(window), AUREA_MAIN_VBOX_KEY, main_vbox);
-    
+
+    ng_linux_ime_attach(window);
+    ng_linux_dnd_attach(window);
+    ng_linux_window_state_attach(window);
+
+    return window;
+}
+
+NGHandle ng_linux_create_window(const char* title, int width, int height) {
+    if (!title) return NULL;
+    GtkWidget* window = new_window(title, width, height);
     gtk_widget_show_all(window);
-    
     return (NGHandle)window;
 }
 
-NGHandle ng_linux_create_window_with_type(const char* title, int width, int height, int window_type) {
-    NGHandle handle = ng_linux_create_window(title, width, height);
-    if (!handle) return NULL;
-
-    GtkWidget* widget = (GtkWidget*)handle;
+static GdkWindowTypeHint type_hint(int window_type) {
     GdkWindowTypeHint hint = GDK_WINDOW_TYPE_HINT_NORMAL;
     switch (window_type) {
         case 1: // Popup
@@ -149,7 +172,64 @@ NGHandle ng_linux_create_window_with_type(const char* title, int width, int heig
             hint = GDK_WINDOW_TYPE_HINT_NORMAL;
             break;
     }
-    gtk_window_set_type_hint(GTK_WINDOW(widget), hint);
+    return hint;
+}
+
+NGHandle ng_linux_create_window_with_type(const char* title, int width, int height, int window_type) {
+    if (!title) return NULL;
+    GtkWidget* window = new_window(title, width, height);
+    gtk_window_set_type_hint(GTK_WINDOW(window), type_hint(window_type));
+    gtk_widget_show_all(window);
+    return (NGHandle)window;
+}
+
+NGHandle ng_linux_create_window_with_options(const NGWindowOptions* options) {
+    if (!options || options->version == 0 || !options->title) return NULL;
+    /* The Wayland app ID and WM_CLASS's instance come from the program name
+     * and WM_CLASS's class from the program class, which GTK otherwise
+     * derives by capitalizing the name; both are read when the window is
+     * realized, so they have to be set first. */
+    if (options->app_id && *options->app_id) {
+        g_set_prgname(options->app_id);
+        gdk_set_program_class(options->app_id);
+    }
+
+    GtkWidget* window = new_window(options->title, options->width, options->height);
+    GtkWindow* gtk_window = GTK_WINDOW(window);
+    NGHandle handle = (NGHandle)window;
+    gtk_window_set_type_hint(gtk_window, type_hint(options->window_type));
+    ng_linux_window_set_min_size(handle, options->min_width, options->min_height);
+    ng_linux_window_set_max_size(handle, options->max_width, options->max_height);
+    gtk_window_set_resizable(gtk_window, options->resizable != 0);
+    gtk_window_set_decorated(gtk_window, options->decorated != 0);
+    if (options->parent || options->modal) {
+        ng_linux_window_set_parent(handle, options->parent, options->modal);
+    }
+    if (options->icon_rgba) {
+        ng_linux_window_set_icon_rgba(handle, options->icon_rgba, options->icon_width, options->icon_height);
+    }
+    /* Before realizing, this only picks the visual; nothing is recreated. */
+    if (options->transparent && ng_linux_window_set_transparent(handle, 1) != NG_SUCCESS) {
+        /* Nobody has seen this window, so closing it must not quit the
+         * main loop. */
+        g_signal_handlers_disconnect_by_func(window, G_CALLBACK(on_window_destroy), NULL);
+        ng_linux_destroy_window(handle);
+        return NULL;
+    }
+    if (options->position == NG_WINDOW_POSITION_EXPLICIT) {
+        gtk_window_move(gtk_window, options->x, options->y);
+    } else if (options->position == NG_WINDOW_POSITION_CENTERED) {
+        gtk_window_set_position(
+            gtk_window, options->parent ? GTK_WIN_POS_CENTER_ON_PARENT : GTK_WIN_POS_CENTER);
+    }
+
+    /* A hidden window still gets its children shown, so a later
+     * ng_platform_window_show maps the contents with it. */
+    if (options->visible) {
+        gtk_widget_show_all(window);
+    } else {
+        gtk_widget_show_all(gtk_bin_get_child(GTK_BIN(window)));
+    }
     return handle;
 }
 
@@ -202,10 +282,62 @@ static unsigned int ng_linux_modifiers(GdkModifierType state) {
     if (state & GDK_SUPER_MASK || state & GDK_META_MASK) {
         mods |= NG_MOD_META;
     }
-    return mods;
+    return mods | ng_linux_keyboard_lock_modifiers(state);
+}
+
+/* Keys matched by keyval before the character-based mapping below, so the
+ * keypad and function row keep their own codes. */
+static unsigned int ng_linux_keycode_from_special_keyval(guint keyval) {
+    if (keyval >= GDK_KEY_KP_0 && keyval <= GDK_KEY_KP_9) {
+        return NG_KEY_NUMPAD_0 + (unsigned int)(keyval - GDK_KEY_KP_0);
+    }
+    if (keyval >= GDK_KEY_F13 && keyval <= GDK_KEY_F24) {
+        return NG_KEY_F13 + (unsigned int)(keyval - GDK_KEY_F13);
+    }
+    switch (keyval) {
+        case GDK_KEY_KP_Add: return NG_KEY_NUMPAD_ADD;
+        case GDK_KEY_KP_Subtract: return NG_KEY_NUMPAD_SUBTRACT;
+        case GDK_KEY_KP_Multiply: return NG_KEY_NUMPAD_MULTIPLY;
+        case GDK_KEY_KP_Divide: return NG_KEY_NUMPAD_DIVIDE;
+        case GDK_KEY_KP_Decimal:
+        case GDK_KEY_KP_Separator: return NG_KEY_NUMPAD_DECIMAL;
+        case GDK_KEY_KP_Enter: return NG_KEY_NUMPAD_ENTER;
+        case GDK_KEY_KP_Equal: return NG_KEY_NUMPAD_EQUALS;
+        /* Num Lock off: the keypad acts as navigation keys. */
+        case GDK_KEY_KP_Home: return NG_KEY_HOME;
+        case GDK_KEY_KP_End: return NG_KEY_END;
+        case GDK_KEY_KP_Page_Up: return NG_KEY_PAGE_UP;
+        case GDK_KEY_KP_Page_Down: return NG_KEY_PAGE_DOWN;
+        case GDK_KEY_KP_Up: return NG_KEY_UP;
+        case GDK_KEY_KP_Down: return NG_KEY_DOWN;
+        case GDK_KEY_KP_Left: return NG_KEY_LEFT;
+        case GDK_KEY_KP_Right: return NG_KEY_RIGHT;
+        case GDK_KEY_KP_Insert: return NG_KEY_INSERT;
+        case GDK_KEY_KP_Delete: return NG_KEY_DELETE;
+        case GDK_KEY_Caps_Lock: return NG_KEY_CAPS_LOCK;
+        case GDK_KEY_Num_Lock: return NG_KEY_NUM_LOCK;
+        case GDK_KEY_Scroll_Lock: return NG_KEY_SCROLL_LOCK;
+        case GDK_KEY_Print: return NG_KEY_PRINT_SCREEN;
+        case GDK_KEY_Pause: return NG_KEY_PAUSE;
+        case GDK_KEY_Menu: return NG_KEY_CONTEXT_MENU;
+        case GDK_KEY_AudioPlay:
+        case GDK_KEY_AudioPause: return NG_KEY_MEDIA_PLAY_PAUSE;
+        case GDK_KEY_AudioStop: return NG_KEY_MEDIA_STOP;
+        case GDK_KEY_AudioNext: return NG_KEY_MEDIA_NEXT_TRACK;
+        case GDK_KEY_AudioPrev: return NG_KEY_MEDIA_PREVIOUS_TRACK;
+        case GDK_KEY_AudioRaiseVolume: return NG_KEY_VOLUME_UP;
+        case GDK_KEY_AudioLowerVolume: return NG_KEY_VOLUME_DOWN;
+        case GDK_KEY_AudioMute: return NG_KEY_VOLUME_MUTE;
+        default: return NG_KEY_UNKNOWN;
+    }
 }
 
 static unsigned int ng_linux_keycode_from_keyval(guint keyval) {
+    unsigned int special = ng_linux_keycode_from_special_keyval(keyval);
+    if (special != NG_KEY_UNKNOWN) {
+        return special;
+    }
+
     gunichar ch = gdk_keyval_to_unicode(gdk_keyval_to_upper(keyval));
     if (ch >= 'A' && ch <= 'Z') {
         return NG_KEY_A + (unsigned int)(ch - 'A');
@@ -235,11 +367,11 @@ static unsigned int ng_linux_keycode_from_keyval(guint keyval) {
         case GDK_KEY_space:
             return NG_KEY_SPACE;
         case GDK_KEY_Return:
-        case GDK_KEY_KP_Enter:
             return NG_KEY_ENTER;
         case GDK_KEY_Escape:
             return NG_KEY_ESCAPE;
         case GDK_KEY_Tab:
+        case GDK_KEY_ISO_Left_Tab: // Shift+Tab
             return NG_KEY_TAB;
         case GDK_KEY_BackSpace:
             return NG_KEY_BACKSPACE;
@@ -320,29 +452,47 @@ static int ng_linux_mouse_button_from_event(guint button) {
 }
 
 static gboolean on_key_press(GtkWidget* widget, GdkEventKey* event, gpointer user_data) {
+    // An active input method owns the key (composition or direct commit);
+    // its result arrives as preedit and commit events instead.
+    if (ng_linux_ime_filter_key(widget, event)) {
+        return TRUE;
+    }
+
     unsigned int mods = ng_linux_modifiers(event->state);
     unsigned int keycode = ng_linux_keycode_from_keyval(event->keyval);
-    ng_invoke_key_event((void*)widget, keycode, 1, mods);
+    ng_linux_keyboard_emit(widget, event, keycode, mods);
 
-    if (ng_linux_handle_menu_shortcut((void*)widget, keycode, mods)) {
+    // The application's focus manager moves focus on Tab; keep GTK out.
+    if (ng_linux_focus_owns_key(widget, event)) {
         return TRUE;
     }
 
-    if (event->string && event->string[0] != '\0') {
+    // Menu accelerators (and mnemonics) take the key before it becomes text.
+    if (gtk_window_activate_key(GTK_WINDOW(widget), event)) {
+        return TRUE;
+    }
+
+    if (!ng_linux_ime_is_allowed(widget) && event->string && event->string[0] != '\0') {
         ng_invoke_text_input((void*)widget, event->string);
     }
     return FALSE;
 }
 
 static gboolean on_key_release(GtkWidget* widget, GdkEventKey* event, gpointer user_data) {
+    if (ng_linux_ime_filter_key(widget, event)) {
+        return TRUE;
+    }
     unsigned int mods = ng_linux_modifiers(event->state);
     unsigned int keycode = ng_linux_keycode_from_keyval(event->keyval);
-    ng_invoke_key_event((void*)widget, keycode, 0, mods);
+    ng_linux_keyboard_emit(widget, event, keycode, mods);
     return FALSE;
 }
 
 static gboolean on_button_press(GtkWidget* widget, GdkEventButton* event, gpointer user_data) {
     unsigned int mods = ng_linux_modifiers(event->state);
+    if (ng_linux_pointer_handle_event(widget, (GdkEvent*)event, mods)) {
+        return FALSE;
+    }
     int button = ng_linux_mouse_button_from_event(event->button);
     int click_count = event->type == GDK_3BUTTON_PRESS ? 3 :
         event->type == GDK_2BUTTON_PRESS ? 2 : 1;
@@ -352,13 +502,20 @@ static gboolean on_button_press(GtkWidget* widget, GdkEventButton* event, gpoint
 
 static gboolean on_button_release(GtkWidget* widget, GdkEventButton* event, gpointer user_data) {
     unsigned int mods = ng_linux_modifiers(event->state);
+    if (ng_linux_pointer_handle_event(widget, (GdkEvent*)event, mods)) {
+        return FALSE;
+    }
     int button = ng_linux_mouse_button_from_event(event->button);
     ng_invoke_mouse_button((void*)widget, button, 0, mods, event->x, event->y, 1);
     return FALSE;
 }
 
 static gboolean on_motion_notify(GtkWidget* widget, GdkEventMotion* event, gpointer user_data) {
-    ng_invoke_mouse_move((void*)widget, event->x, event->y);
+    unsigned int mods = ng_linux_modifiers(event->state);
+    if (ng_linux_pointer_handle_event(widget, (GdkEvent*)event, mods)) {
+        return FALSE;
+    }
+    ng_invoke_mouse_move((void*)widget, event->x, event->y, mods);
 
     int index = ng_linux_find_window_index(widget);
     if (index >= 0 && g_cursor_grab_mode[index] == 2) {
@@ -377,6 +534,34 @@ static gboolean on_motion_notify(GtkWidget* widget, GdkEventMotion* event, gpoin
     return FALSE;
 }
 
+static const char* AUREA_SCROLLING_KEY = "aurea-scrolling";
+
+// GDK reports touchpad travel in wheel-click units; convert to pixels.
+#define NG_LINUX_TOUCHPAD_PIXELS_PER_UNIT 20.0
+
+static gboolean ng_linux_scroll_is_precise(GdkEventScroll* event) {
+    if (event->direction != GDK_SCROLL_SMOOTH) return FALSE;
+    GdkDevice* device = gdk_event_get_source_device((GdkEvent*)event);
+    if (!device) return FALSE;
+    GdkInputSource source = gdk_device_get_source(device);
+    return source == GDK_SOURCE_TOUCHPAD || source == GDK_SOURCE_TRACKPOINT;
+}
+
+// Touchpads have no explicit "began"; the first event after a stop event
+// starts a new gesture.
+static unsigned int ng_linux_scroll_phase(GtkWidget* widget, GdkEventScroll* event) {
+    gboolean scrolling = GPOINTER_TO_INT(g_object_get_data(G_OBJECT(widget), AUREA_SCROLLING_KEY));
+    if (gdk_event_is_scroll_stop_event((GdkEvent*)event)) {
+        g_object_set_data(G_OBJECT(widget), AUREA_SCROLLING_KEY, GINT_TO_POINTER(FALSE));
+        return NG_SCROLL_PHASE_ENDED;
+    }
+    if (!scrolling) {
+        g_object_set_data(G_OBJECT(widget), AUREA_SCROLLING_KEY, GINT_TO_POINTER(TRUE));
+        return NG_SCROLL_PHASE_BEGAN;
+    }
+    return NG_SCROLL_PHASE_CHANGED;
+}
+
 static gboolean on_scroll(GtkWidget* widget, GdkEventScroll* event, gpointer user_data) {
     unsigned int mods = ng_linux_modifiers(event->state);
     double dx = 0.0;
@@ -401,17 +586,42 @@ static gboolean on_scroll(GtkWidget* widget, GdkEventScroll* event, gpointer use
         }
     }
 
-    ng_invoke_mouse_wheel((void*)widget, dx, dy, mods);
+    if (ng_linux_scroll_is_precise(event)) {
+        unsigned int phase = ng_linux_scroll_phase(widget, event);
+        ng_invoke_mouse_wheel(
+            (void*)widget,
+            dx * NG_LINUX_TOUCHPAD_PIXELS_PER_UNIT,
+            dy * NG_LINUX_TOUCHPAD_PIXELS_PER_UNIT,
+            mods,
+            NG_SCROLL_DELTA_PIXEL,
+            phase);
+        return FALSE;
+    }
+
+    ng_invoke_mouse_wheel((void*)widget, dx, dy, mods, NG_SCROLL_DELTA_LINE, NG_SCROLL_PHASE_DISCRETE);
     return FALSE;
 }
 
+static gboolean on_touch(GtkWidget* widget, GdkEventTouch* event, gpointer user_data) {
+    unsigned int mods = ng_linux_modifiers(event->state);
+    return ng_linux_pointer_handle_event(widget, (GdkEvent*)event, mods);
+}
+
+static gboolean on_gesture(GtkWidget* widget, GdkEvent* event, gpointer user_data) {
+    // Everything else continues to its own signal.
+    return ng_linux_pointer_handle_touchpad(widget, event);
+}
+
 static gboolean on_focus_in(GtkWidget* widget, GdkEventFocus* event, gpointer user_data) {
     ng_invoke_focus_changed((void*)widget, 1);
+    ng_linux_ime_focus(widget, TRUE);
     return FALSE;
 }
 
 static gboolean on_focus_out(GtkWidget* widget, GdkEventFocus* event, gpointer user_data) {
     ng_invoke_focus_changed((void*)widget, 0);
+    ng_linux_ime_focus(widget, FALSE);
+    ng_linux_keyboard_reset(widget);
     return FALSE;
 }
 
@@ -482,7 +692,12 @@ void ng_linux_window_set_scale_factor_callback(NGHandle window, ScaleFactorCallb
 
 void ng_linux_destroy_window(NGHandle handle) {
     if (!handle) return;
-    gtk_widget_destroy((GtkWidget*)handle);
+    GtkWidget* window = (GtkWidget*)handle;
+    /* Destroying twice would emit "destroy" again. */
+    if (!g_object_get_data(G_OBJECT(window), AUREA_DESTROYED_KEY) && !gtk_widget_in_destruction(window)) {
+        gtk_widget_destroy(window);
+    }
+    g_object_unref(window);
 }
 
 void ng_linux_window_show(NGHandle window) {
@@ -573,25 +788,8 @@ int ng_linux_window_set_icon_rgba(
     if (!window || !rgba || width == 0 || height == 0) {
         return NG_ERROR_INVALID_PARAMETER;
     }
-    GdkPixbuf* pixbuf = gdk_pixbuf_new(
-        GDK_COLORSPACE_RGB,
-        TRUE,
-        8,
-        (int)width,
-        (int)height
-    );
+    GdkPixbuf* pixbuf = ng_linux_pixbuf_from_rgba(rgba, width, height);
     if (!pixbuf) return NG_ERROR_PLATFORM_SPECIFIC;
-
-    unsigned char* pixels = gdk_pixbuf_get_pixels(pixbuf);
-    int row_stride = gdk_pixbuf_get_rowstride(pixbuf);
-    size_t source_stride = (size_t)width * 4;
-    for (unsigned int row = 0; row < height; ++row) {
-        memcpy(
-            pixels + (size_t)row * (size_t)row_stride,
-            rgba + (size_t)row * source_stride,
-            source_stride
-        );
-    }
     gtk_window_set_icon(GTK_WINDOW((GtkWidget*)window), pixbuf);
     g_object_unref(pixbuf);
     return NG_SUCCESS;
@@ -676,25 +874,7 @@ int ng_linux_window_get_wayland_handle(NGHandle window, void** surface, void** d
 
 int ng_linux_window_set_cursor_visible(NGHandle window, int visible) {
     if (!window) return NG_ERROR_INVALID_HANDLE;
-    GtkWidget* widget = (GtkWidget*)window;
-    GdkWindow* gdkWindow = gtk_widget_get_window(widget);
-    if (!gdkWindow) return NG_ERROR_INVALID_HANDLE;
-
-    if (visible) {
-        gdk_window_set_cursor(gdkWindow, NULL);
-        return NG_SUCCESS;
-    }
-
-    GdkDisplay* display = gdk_window_get_display(gdkWindow);
-    if (!display) return NG_ERROR_PLATFORM_SPECIFIC;
-
-    GdkCursor* cursor = gdk_cursor_new_for_display(display, GDK_BLANK_CURSOR);
-    if (!cursor) return NG_ERROR_PLATFORM_SPECIFIC;
-
-    gdk_window_set_cursor(gdkWindow, cursor);
-    g_object_unref(cursor);
-
-    return NG_SUCCESS;
+    return ng_linux_cursor_set_visible((GtkWidget*)window, visible != 0);
 }
 
 int ng_linux_window_set_cursor_grab(NGHandle window, int mode) {
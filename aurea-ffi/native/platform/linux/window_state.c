@@ -0,0 +1,335 @@
+#include "window_state.h"
+#include "common/errors.h"
+#include "common/platform_api.h"
+#include "common/rust_callbacks.h"
+#include <string.h>
+
+#define SIZE_LIMITS_KEY "aurea-size-limits"
+#define TRANSPARENT_KEY "aurea-transparent"
+
+/* Both limits go through one geometry-hints call, so each setter keeps the
+ * other limit alive. Zero means unset. */
+typedef struct {
+    int min_width;
+    int min_height;
+    int max_width;
+    int max_height;
+} SizeLimits;
+
+static const struct {
+    GdkWindowState gdk;
+    unsigned int flag;
+} reported_states[] = {
+    {GDK_WINDOW_STATE_MAXIMIZED, NG_WINDOW_STATE_MAXIMIZED},
+    {GDK_WINDOW_STATE_FULLSCREEN, NG_WINDOW_STATE_FULLSCREEN},
+    {GDK_WINDOW_STATE_ABOVE, NG_WINDOW_STATE_ALWAYS_ON_TOP},
+};
+
+static gboolean on_window_state(GtkWidget* widget, GdkEventWindowState* event, gpointer user_data) {
+    (void)user_data;
+    for (gsize i = 0; i < G_N_ELEMENTS(reported_states); i++) {
+        GdkWindowState gdk = reported_states[i].gdk;
+        if (event->changed_mask & gdk) {
+            ng_invoke_window_state_changed(widget, reported_states[i].flag, (event->new_window_state & gdk) != 0);
+        }
+    }
+    return FALSE;
+}
+
+static void on_resizable_changed(GObject* object, GParamSpec* pspec, gpointer user_data) {
+    (void)pspec;
+    (void)user_data;
+    ng_invoke_window_state_changed(object, NG_WINDOW_STATE_RESIZABLE, gtk_window_get_resizable(GTK_WINDOW(object)));
+}
+
+static void on_decorated_changed(GObject* object, GParamSpec* pspec, gpointer user_data) {
+    (void)pspec;
+    (void)user_data;
+    ng_invoke_window_state_changed(object, NG_WINDOW_STATE_DECORATED, gtk_window_get_decorated(GTK_WINDOW(object)));
+}
+
+/* Runs before GtkWindow's own handler, which skips its themed background
+ * once the window is app-paintable, so the children draw onto a clear
+ * surface. */
+static gboolean on_draw(GtkWidget* widget, cairo_t* cr, gpointer user_data) {
+    (void)user_data;
+    if (!g_object_get_data(G_OBJECT(widget), TRANSPARENT_KEY)) return FALSE;
+    cairo_save(cr);
+    cairo_set_operator(cr, CAIRO_OPERATOR_SOURCE);
+    cairo_set_source_rgba(cr, 0.0, 0.0, 0.0, 0.0);
+    cairo_paint(cr);
+    cairo_restore(cr);
+    return FALSE;
+}
+
+void ng_linux_window_state_attach(GtkWidget* window) {
+    g_object_set_data_full(G_OBJECT(window), SIZE_LIMITS_KEY, g_new0(SizeLimits, 1), g_free);
+    g_signal_connect(window, "window-state-event", G_CALLBACK(on_window_state), NULL);
+    g_signal_connect(window, "notify::resizable", G_CALLBACK(on_resizable_changed), NULL);
+    g_signal_connect(window, "notify::decorated", G_CALLBACK(on_decorated_changed), NULL);
+    g_signal_connect(window, "draw", G_CALLBACK(on_draw), NULL);
+}
+
+gboolean ng_linux_window_is_transparent(GtkWidget* widget) {
+    GtkWidget* toplevel = widget ? gtk_widget_get_toplevel(widget) : NULL;
+    return toplevel && g_object_get_data(G_OBJECT(toplevel), TRANSPARENT_KEY) != NULL;
+}
+
+unsigned int ng_linux_window_get_state(NGHandle window) {
+    if (!window) return 0;
+    GtkWindow* gtk_window = GTK_WINDOW(window);
+    unsigned int state = 0;
+    GdkWindow* gdk_window = gtk_widget_get_window(GTK_WIDGET(gtk_window));
+    if (gdk_window) {
+        GdkWindowState gdk = gdk_window_get_state(gdk_window);
+        if (gdk & GDK_WINDOW_STATE_ICONIFIED) state |= NG_WINDOW_STATE_MINIMIZED;
+        for (gsize i = 0; i < G_N_ELEMENTS(reported_states); i++) {
+            if (gdk & reported_states[i].gdk) state |= reported_states[i].flag;
+        }
+    }
+    if (gtk_window_get_resizable(gtk_window)) state |= NG_WINDOW_STATE_RESIZABLE;
+    if (gtk_window_get_decorated(gtk_window)) state |= NG_WINDOW_STATE_DECORATED;
+    return state;
+}
+
+int ng_linux_window_set_fullscreen(NGHandle window, int fullscreen, int monitor) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    GtkWindow* gtk_window = GTK_WINDOW(window);
+    if (!fullscreen) {
+        gtk_window_unfullscreen(gtk_window);
+        return NG_SUCCESS;
+    }
+    if (monitor < 0) {
+        gtk_window_fullscreen(gtk_window);
+        return NG_SUCCESS;
+    }
+    GdkScreen* screen = gtk_window_get_screen(gtk_window);
+    if (monitor >= gdk_display_get_n_monitors(gdk_screen_get_display(screen))) {
+        return NG_ERROR_INVALID_PARAMETER;
+    }
+    gtk_window_fullscreen_on_monitor(gtk_window, screen, monitor);
+    return NG_SUCCESS;
+}
+
+int ng_linux_window_set_maximized(NGHandle window, int maximized) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    if (maximized) {
+        gtk_window_maximize(GTK_WINDOW(window));
+    } else {
+        gtk_window_unmaximize(GTK_WINDOW(window));
+    }
+    return NG_SUCCESS;
+}
+
+int ng_linux_window_set_minimized(NGHandle window, int minimized) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    if (minimized) {
+        gtk_window_iconify(GTK_WINDOW(window));
+    } else {
+        gtk_window_deiconify(GTK_WINDOW(window));
+    }
+    return NG_SUCCESS;
+}
+
+static int apply_size_limits(GtkWindow* window, const SizeLimits* limits) {
+    GdkGeometry geometry = {0};
+    GdkWindowHints hints = 0;
+    if (limits->min_width > 0 || limits->min_height > 0) {
+        geometry.min_width = limits->min_width;
+        geometry.min_height = limits->min_height;
+        hints |= GDK_HINT_MIN_SIZE;
+    }
+    if (limits->max_width > 0 || limits->max_height > 0) {
+        geometry.max_width = limits->max_width > 0 ? limits->max_width : G_MAXSHORT;
+        geometry.max_height = limits->max_height > 0 ? limits->max_height : G_MAXSHORT;
+        hints |= GDK_HINT_MAX_SIZE;
+    }
+    gtk_window_set_geometry_hints(window, NULL, &geometry, hints);
+    return NG_SUCCESS;
+}
+
+static SizeLimits* size_limits(NGHandle window) {
+    return window ? g_object_get_data(G_OBJECT(window), SIZE_LIMITS_KEY) : NULL;
+}
+
+int ng_linux_window_set_min_size(NGHandle window, int width, int height) {
+    SizeLimits* limits = size_limits(window);
+    if (!limits) return NG_ERROR_INVALID_HANDLE;
+    if (width < 0 || height < 0) return NG_ERROR_INVALID_PARAMETER;
+    limits->min_width = width;
+    limits->min_height = height;
+    return apply_size_limits(GTK_WINDOW(window), limits);
+}
+
+int ng_linux_window_set_max_size(NGHandle window, int width, int height) {
+    SizeLimits* limits = size_limits(window);
+    if (!limits) return NG_ERROR_INVALID_HANDLE;
+    if (width < 0 || height < 0) return NG_ERROR_INVALID_PARAMETER;
+    limits->max_width = width;
+    limits->max_height = height;
+    return apply_size_limits(GTK_WINDOW(window), limits);
+}
+
+int ng_linux_window_set_resizable(NGHandle window, int resizable) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    gtk_window_set_resizable(GTK_WINDOW(window), resizable != 0);
+    return NG_SUCCESS;
+}
+
+int ng_linux_window_set_always_on_top(NGHandle window, int always_on_top) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    gtk_window_set_keep_above(GTK_WINDOW(window), always_on_top != 0);
+    return NG_SUCCESS;
+}
+
+int ng_linux_window_set_decorations(NGHandle window, int decorated) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    gtk_window_set_decorated(GTK_WINDOW(window), decorated != 0);
+    return NG_SUCCESS;
+}
+
+int ng_linux_window_set_transparent(NGHandle window, int transparent) {
+    if (!window) return NG_ERROR_INVALID_HANDLE;
+    GtkWidget* widget = GTK_WIDGET(window);
+    GdkScreen* screen = gtk_widget_get_screen(widget);
+    GdkVisual* visual = transparent ? gdk_screen_get_rgba_visual(screen) : gdk_screen_get_system_visual(screen);
+    if (!visual) return NG_ERROR_PLATFORM_SPECIFIC;
+
+    g_object_set_data(G_OBJECT(widget), TRANSPARENT_KEY, transparent ? GINT_TO_POINTER(1) : NULL);
+    gtk_widget_set_app_paintable(widget, transparent != 0);
+
+    /* The visual is fixed once the GdkWindow exists, so recreate it. */
+    if (gtk_widget_get_visual(widget) != visual) {
+        gboolean realized = gtk_widget_get_realized(widget);
+        gboolean visible = gtk_widget_get_visible(widget);
+        if (visible) gtk_widget_hide(widget);
+        if (realized) gtk_widget_unrealize(widget);
+        gtk_widget_set_visual(widget, visual);
+        if (visible) {
+            gtk_widget_show(widget);
+        } else if (realized) {
+            gtk_widget_realize(widget);
+        }
+    }
+    gtk_widget_queue_draw(widget);
+    return NG_SUCCESS;
+}
+
+/* GTK's modality blocks input to every window in the modal window's group,
+ * and ungrouped windows share the application-wide default group. Putting a
+ * child in its parent's own group keeps a modal child from blocking
+ * unrelated windows. */
+static void join_parent_group(GtkWindow* window, GtkWindow* parent) {
+    if (gtk_window_has_group(window)) {
+        gtk_window_group_remove_window(gtk_window_get_group(window), window);
+    }
+    if (!parent) return;
+    if (!gtk_window_has_group(parent)) {
+        GtkWindowGroup* group = gtk_window_group_new();
+        gtk_window_group_add_window(group, parent);
+        g_object_unref(group);
+    }
+    gtk_window_group_add_window(gtk_window_get_group(parent), window);
+}
+
+int ng_linux_window_set_parent(NGHandle window, NGHandle parent, int modal) {
+    if (!window || window == parent) return NG_ERROR_INVALID_HANDLE;
+    GtkWindow* gtk_window = GTK_WINDOW(window);
+    /* Transient-for gives stacking above the parent and minimising with it. */
+    join_parent_group(gtk_window, parent ? GTK_WINDOW(parent) : NULL);
+    gtk_window_set_transient_for(gtk_window, parent ? GTK_WINDOW(parent) : NULL);
+    /* GTK owns closing the child with its parent; the child's handle keeps
+     * its own reference, so dropping it afterwards is safe. */
+    gtk_window_set_destroy_with_parent(gtk_window, parent != NULL);
+    gtk_window_set_modal(gtk_window, modal != 0);
+    return NG_SUCCESS;
+}
+
+int ng_linux_primary_monitor(void) {
+    GdkDisplay* display = gdk_display_get_default();
+    if (!display) return -1;
+    GdkMonitor* primary = gdk_display_get_primary_monitor(display);
+    int count = gdk_display_get_n_monitors(display);
+    for (int i = 0; i < count; i++) {
+        if (gdk_display_get_monitor(display, i) == primary) return i;
+    }
+    /* Wayland has no primary monitor; the first one stands in. */
+    return count > 0 ? 0 : -1;
+}
+
+int ng_linux_window_current_monitor(NGHandle window) {
+    if (!window) return -1;
+    GdkWindow* gdk_window = gtk_widget_get_window(GTK_WIDGET(window));
+    if (!gdk_window) return -1;
+    GdkDisplay* display = gdk_window_get_display(gdk_window);
+    GdkMonitor* monitor = gdk_display_get_monitor_at_window(display, gdk_window);
+    int count = gdk_display_get_n_monitors(display);
+    for (int i = 0; i < count; i++) {
+        if (gdk_display_get_monitor(display, i) == monitor) return i;
+    }
+    return -1;
+}
+
+int ng_linux_monitor_count(void) {
+    GdkDisplay* display = gdk_display_get_default();
+    return display ? gdk_display_get_n_monitors(display) : 0;
+}
+
+int ng_linux_monitor_info(int index, NGMonitorInfo* info) {
+    if (!info) return NG_ERROR_INVALID_PARAMETER;
+    GdkDisplay* display = gdk_display_get_default();
+    GdkMonitor* monitor = display ? gdk_display_get_monitor(display, index) : NULL;
+    if (!monitor) return NG_ERROR_INVALID_PARAMETER;
+
+    memset(info, 0, sizeof(*info));
+    /* Connector names are not exposed by GDK 3; the model is the closest. */
+    const char* name = gdk_monitor_get_model(monitor);
+    if (!name) name = gdk_monitor_get_manufacturer(monitor);
+    if (name) g_strlcpy(info->name, name, sizeof(info->name));
+
+    GdkRectangle bounds;
+    gdk_monitor_get_geometry(monitor, &bounds);
+    info->x = bounds.x;
+    info->y = bounds.y;
+    info->width = bounds.width;
+    info->height = bounds.height;
+    GdkRectangle work;
+    gdk_monitor_get_workarea(monitor, &work);
+    info->work_x = work.x;
+    info->work_y = work.y;
+    info->work_width = work.width;
+    info->work_height = work.height;
+    info->scale_factor = (float)gdk_monitor_get_scale_factor(monitor);
+    info->refresh_millihertz = gdk_monitor_get_refresh_rate(monitor);
+    return NG_SUCCESS;
+}
+
+int ng_linux_window_get_frame(NGHandle window, NGWindowFrame* frame) {
+    if (!window || !frame) return NG_ERROR_INVALID_PARAMETER;
+    GdkWindow* gdk_window = gtk_widget_get_window(GTK_WIDGET(window));
+    if (!gdk_window) return NG_ERROR_PLATFORM_SPECIFIC;
+
+    GdkRectangle outer;
+    gdk_window_get_frame_extents(gdk_window, &outer);
+    frame->x = outer.x;
+    frame->y = outer.y;
+    frame->width = outer.width;
+    frame->height = outer.height;
+    gdk_window_get_origin(gdk_window, &frame->content_x, &frame->content_y);
+    gtk_window_get_size(GTK_WINDOW(window), &frame->content_width, &frame->content_height);
+    return NG_SUCCESS;
+}
+
+static void on_monitors_changed(GdkScreen* screen, gpointer user_data) {
+    (void)screen;
+    (void)user_data;
+    ng_invoke_monitors_changed();
+}
+
+void ng_linux_watch_monitors(void) {
+    GdkScreen* screen = gdk_screen_get_default();
+    /* "monitors-changed" covers hotplug as well as geometry and scale. */
+    if (screen) {
+        g_signal_connect(screen, "monitors-changed", G_CALLBACK(on_monitors_changed), NULL);
+    }
+}
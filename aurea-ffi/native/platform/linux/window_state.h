@@ -0,0 +1,40 @@
+#ifndef NATIVE_GUI_LINUX_WINDOW_STATE_H
+#define NATIVE_GUI_LINUX_WINDOW_STATE_H
+
+#include "common/types.h"
+#include "common/platform_api.h"
+#include <gtk/gtk.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Report maximize, fullscreen, keep-above, resizable and decoration changes.
+void ng_linux_window_state_attach(GtkWidget* window);
+// Whether the toplevel holding `widget` was made transparent.
+gboolean ng_linux_window_is_transparent(GtkWidget* widget);
+
+unsigned int ng_linux_window_get_state(NGHandle window);
+int ng_linux_window_set_fullscreen(NGHandle window, int fullscreen, int monitor);
+int ng_linux_window_set_maximized(NGHandle window, int maximized);
+int ng_linux_window_set_minimized(NGHandle window, int minimized);
+int ng_linux_window_set_min_size(NGHandle window, int width, int height);
+int ng_linux_window_set_max_size(NGHandle window, int width, int height);
+int ng_linux_window_set_resizable(NGHandle window, int resizable);
+int ng_linux_window_set_always_on_top(NGHandle window, int always_on_top);
+int ng_linux_window_set_decorations(NGHandle window, int decorated);
+int ng_linux_window_set_transparent(NGHandle window, int transparent);
+int ng_linux_window_set_parent(NGHandle window, NGHandle parent, int modal);
+int ng_linux_primary_monitor(void);
+int ng_linux_window_current_monitor(NGHandle window);
+int ng_linux_monitor_count(void);
+int ng_linux_monitor_info(int monitor, NGMonitorInfo* info);
+int ng_linux_window_get_frame(NGHandle window, NGWindowFrame* frame);
+// Report display changes through ng_invoke_monitors_changed.
+void ng_linux_watch_monitors(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_LINUX_WINDOW_STATE_H
@@ -8,6 +8,7 @@ void ng_macos_cleanup(void);
 int ng_macos_run(void);
 int ng_macos_poll_events(void);
 void ng_macos_request_frame(void);
+void ng_macos_request_frame_after(unsigned int delay_ms);
 void ng_macos_frame_idle(void);
 
 #ifdef __OBJC__
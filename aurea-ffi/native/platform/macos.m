@@ -175,6 +175,14 @@ void ng_macos_request_frame(void) {
     }
 }
 
+// The display link does the pass: this only unpauses it at the deadline.
+void ng_macos_request_frame_after(unsigned int delay_ms) {
+    dispatch_after(dispatch_time(DISPATCH_TIME_NOW, (int64_t)delay_ms * (int64_t)NSEC_PER_MSEC),
+                   dispatch_get_main_queue(), ^{
+                       ng_macos_request_frame();
+                   });
+}
+
 void ng_macos_frame_idle(void) {
     if (s_using_legacy) {
         legacy_display_link_frame_idle();
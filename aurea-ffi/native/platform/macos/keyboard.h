@@ -0,0 +1,28 @@
+#ifndef NATIVE_GUI_MACOS_KEYBOARD_H
+#define NATIVE_GUI_MACOS_KEYBOARD_H
+
+#ifdef __OBJC__
+@class NSEvent;
+#endif
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#ifdef __OBJC__
+// Report a key press or release with its physical key, layout text and
+// repeat state. `keycode` is the NG_KEY_* code already derived from the
+// event's virtual key code.
+void ng_macos_keyboard_emit(
+    void* windowHandle,
+    NSEvent* event,
+    unsigned int keycode,
+    int pressed,
+    unsigned int modifiers);
+#endif
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_MACOS_KEYBOARD_H
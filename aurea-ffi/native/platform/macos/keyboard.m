@@ -0,0 +1,87 @@
+#import "keyboard.h"
+#import "common/input.h"
+#import "common/rust_callbacks.h"
+#import <Cocoa/Cocoa.h>
+
+#define CONSUMER(usage) (0x000C0000u | (usage))
+
+/* Carbon kVK_* virtual key code to USB HID usage. */
+static const unsigned int VIRTUAL_KEY_TO_HID[] = {
+    [0x00] = 0x04, [0x01] = 0x16, [0x02] = 0x07, [0x03] = 0x09, /* A S D F */
+    [0x04] = 0x0B, [0x05] = 0x0A, [0x06] = 0x1D, [0x07] = 0x1B, /* H G Z X */
+    [0x08] = 0x06, [0x09] = 0x19, [0x0A] = 0x64, [0x0B] = 0x05, /* C V § B */
+    [0x0C] = 0x14, [0x0D] = 0x1A, [0x0E] = 0x08, [0x0F] = 0x15, /* Q W E R */
+    [0x10] = 0x1C, [0x11] = 0x17, [0x12] = 0x1E, [0x13] = 0x1F, /* Y T 1 2 */
+    [0x14] = 0x20, [0x15] = 0x21, [0x16] = 0x23, [0x17] = 0x22, /* 3 4 6 5 */
+    [0x18] = 0x2E, [0x19] = 0x26, [0x1A] = 0x24, [0x1B] = 0x2D, /* = 9 7 - */
+    [0x1C] = 0x25, [0x1D] = 0x27, [0x1E] = 0x30, [0x1F] = 0x12, /* 8 0 ] O */
+    [0x20] = 0x18, [0x21] = 0x2F, [0x22] = 0x0C, [0x23] = 0x13, /* U [ I P */
+    [0x24] = 0x28, [0x25] = 0x0F, [0x26] = 0x0D, [0x27] = 0x34, /* Return L J ' */
+    [0x28] = 0x0E, [0x29] = 0x33, [0x2A] = 0x31, [0x2B] = 0x36, /* K ; \ , */
+    [0x2C] = 0x38, [0x2D] = 0x11, [0x2E] = 0x10, [0x2F] = 0x37, /* / N M . */
+    [0x30] = 0x2B, [0x31] = 0x2C, [0x32] = 0x35, [0x33] = 0x2A, /* Tab Space ` Backspace */
+    [0x35] = 0x29, [0x36] = 0xE7, [0x37] = 0xE3, [0x38] = 0xE1,
+    [0x39] = 0x39, [0x3A] = 0xE2, [0x3B] = 0xE0, [0x3C] = 0xE5,
+    [0x3D] = 0xE6, [0x3E] = 0xE4, [0x40] = 0x6C, [0x41] = 0x63,
+    [0x43] = 0x55, [0x45] = 0x57, [0x47] = 0x53,
+    [0x48] = CONSUMER(0xE9), [0x49] = CONSUMER(0xEA), [0x4A] = CONSUMER(0xE2),
+    [0x4B] = 0x54, [0x4C] = 0x58, [0x4E] = 0x56, [0x4F] = 0x6D,
+    [0x50] = 0x6E, [0x51] = 0x67,
+    [0x52] = 0x62, [0x53] = 0x59, [0x54] = 0x5A, [0x55] = 0x5B, /* KP 0-3 */
+    [0x56] = 0x5C, [0x57] = 0x5D, [0x58] = 0x5E, [0x59] = 0x5F, /* KP 4-7 */
+    [0x5A] = 0x6F, [0x5B] = 0x60, [0x5C] = 0x61,                /* F20 KP 8 9 */
+    [0x60] = 0x3E, [0x61] = 0x3F, [0x62] = 0x40, [0x63] = 0x3C, /* F5 F6 F7 F3 */
+    [0x64] = 0x41, [0x65] = 0x42, [0x67] = 0x44, [0x69] = 0x68, /* F8 F9 F11 F13 */
+    [0x6A] = 0x6B, [0x6B] = 0x69, [0x6D] = 0x43, [0x6E] = 0x65, /* F16 F14 F10 Menu */
+    [0x6F] = 0x45, [0x71] = 0x6A, [0x72] = 0x49, [0x73] = 0x4A, /* F12 F15 Help Home */
+    [0x74] = 0x4B, [0x75] = 0x4C, [0x76] = 0x3D, [0x77] = 0x4D, /* PgUp Del F4 End */
+    [0x78] = 0x3B, [0x79] = 0x4E, [0x7A] = 0x3A, [0x7B] = 0x50, /* F2 PgDn F1 Left */
+    [0x7C] = 0x4F, [0x7D] = 0x51, [0x7E] = 0x52,                /* Right Down Up */
+};
+
+#define VIRTUAL_KEY_TABLE_LEN (sizeof(VIRTUAL_KEY_TO_HID) / sizeof(VIRTUAL_KEY_TO_HID[0]))
+
+static unsigned int physical_from_virtual_key(unsigned short virtualKey) {
+    if (virtualKey < VIRTUAL_KEY_TABLE_LEN && VIRTUAL_KEY_TO_HID[virtualKey] != 0) {
+        return VIRTUAL_KEY_TO_HID[virtualKey];
+    }
+    return NG_PHYSICAL_UNIDENTIFIED | virtualKey;
+}
+
+/* The layout's text with Shift applied but not Control/Option/Command, or
+ * nil for keys that only carry AppKit's private-use function key chars. */
+static NSString* layout_text(NSEvent* event) {
+    NSString* chars = nil;
+    if (@available(macOS 10.15, *)) {
+        chars = [event charactersByApplyingModifiers:([event modifierFlags] & NSEventModifierFlagShift)];
+    } else {
+        chars = [event charactersIgnoringModifiers];
+    }
+    if (!chars || [chars length] == 0) {
+        return nil;
+    }
+    unichar first = [chars characterAtIndex:0];
+    if (first >= 0xF700 && first <= 0xF8FF) {
+        return nil;
+    }
+    return chars;
+}
+
+void ng_macos_keyboard_emit(
+    void* windowHandle,
+    NSEvent* event,
+    unsigned int keycode,
+    int pressed,
+    unsigned int modifiers) {
+    BOOL isKeyEvent = [event type] == NSEventTypeKeyDown || [event type] == NSEventTypeKeyUp;
+    NSString* text = isKeyEvent ? layout_text(event) : nil;
+
+    NGKeyEvent out = {0};
+    out.keycode = keycode;
+    out.physical = physical_from_virtual_key([event keyCode]);
+    out.text = text ? [text UTF8String] : NULL;
+    out.pressed = pressed;
+    out.repeat = isKeyEvent && [event isARepeat] ? 1 : 0;
+    out.modifiers = modifiers;
+    ng_invoke_key_event(windowHandle, &out);
+}
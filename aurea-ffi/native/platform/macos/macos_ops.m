@@ -12,6 +12,7 @@ static const ng_platform_ops_t macos_ops = {
     .run = ng_macos_run,
     .poll_events = ng_macos_poll_events,
     .request_frame = ng_macos_request_frame,
+    .request_frame_after = ng_macos_request_frame_after,
     .frame_idle = ng_macos_frame_idle,
     .create_window = ng_macos_create_window,
     .create_window_with_type = ng_macos_create_window_with_type,
@@ -2,6 +2,7 @@
 #import "common/errors.h"
 #import "common/input.h"
 #import "common/rust_callbacks.h"
+#import "keyboard.h"
 #import "utils.h"
 #import <Cocoa/Cocoa.h>
 #import <CoreGraphics/CoreGraphics.h>
@@ -9,6 +10,19 @@
 
 static int ng_macos_get_cursor_grab_mode(void* windowHandle);
 
+static unsigned int ng_macos_scroll_phase(NSEvent* event) {
+    NSEventPhase momentum = [event momentumPhase];
+    if (momentum & NSEventPhaseBegan) return NG_SCROLL_PHASE_MOMENTUM_BEGAN;
+    if (momentum & (NSEventPhaseEnded | NSEventPhaseCancelled)) return NG_SCROLL_PHASE_MOMENTUM_ENDED;
+    if (momentum != NSEventPhaseNone) return NG_SCROLL_PHASE_MOMENTUM;
+
+    NSEventPhase phase = [event phase];
+    if (phase & (NSEventPhaseBegan | NSEventPhaseMayBegin)) return NG_SCROLL_PHASE_BEGAN;
+    if (phase & (NSEventPhaseEnded | NSEventPhaseCancelled)) return NG_SCROLL_PHASE_ENDED;
+    if (phase != NSEventPhaseNone) return NG_SCROLL_PHASE_CHANGED;
+    return NG_SCROLL_PHASE_DISCRETE;
+}
+
 @interface WindowDelegate : NSObject <NSWindowDelegate>
 @property (nonatomic, assign) void* windowHandle;
 @property (nonatomic, assign) ScaleFactorCallback scaleFactorCallback;
@@ -30,6 +44,9 @@ static unsigned int ng_macos_modifiers(NSEvent* event) {
     if (flags & NSEventModifierFlagCommand) {
         mods |= NG_MOD_META;
     }
+    if (flags & NSEventModifierFlagCapsLock) {
+        mods |= NG_MOD_CAPS_LOCK;
+    }
     return mods;
 }
 
@@ -124,6 +141,37 @@ static unsigned int ng_macos_keycode_from_event(unsigned short keycode) {
         case 43: return NG_KEY_COMMA;
         case 47: return NG_KEY_PERIOD;
         case 44: return NG_KEY_SLASH;
+        case 57: return NG_KEY_CAPS_LOCK;
+        case 71: return NG_KEY_NUM_LOCK; /* Keypad Clear */
+        case 110: return NG_KEY_CONTEXT_MENU;
+        case 82: return NG_KEY_NUMPAD_0;
+        case 83: return NG_KEY_NUMPAD_1;
+        case 84: return NG_KEY_NUMPAD_2;
+        case 85: return NG_KEY_NUMPAD_3;
+        case 86: return NG_KEY_NUMPAD_4;
+        case 87: return NG_KEY_NUMPAD_5;
+        case 88: return NG_KEY_NUMPAD_6;
+        case 89: return NG_KEY_NUMPAD_7;
+        case 91: return NG_KEY_NUMPAD_8;
+        case 92: return NG_KEY_NUMPAD_9;
+        case 69: return NG_KEY_NUMPAD_ADD;
+        case 78: return NG_KEY_NUMPAD_SUBTRACT;
+        case 67: return NG_KEY_NUMPAD_MULTIPLY;
+        case 75: return NG_KEY_NUMPAD_DIVIDE;
+        case 65: return NG_KEY_NUMPAD_DECIMAL;
+        case 76: return NG_KEY_NUMPAD_ENTER;
+        case 81: return NG_KEY_NUMPAD_EQUALS;
+        case 105: return NG_KEY_F13;
+        case 107: return NG_KEY_F14;
+        case 113: return NG_KEY_F15;
+        case 106: return NG_KEY_F16;
+        case 64: return NG_KEY_F17;
+        case 79: return NG_KEY_F18;
+        case 80: return NG_KEY_F19;
+        case 90: return NG_KEY_F20;
+        case 72: return NG_KEY_VOLUME_UP;
+        case 73: return NG_KEY_VOLUME_DOWN;
+        case 74: return NG_KEY_VOLUME_MUTE;
         default:
             return NG_KEY_UNKNOWN;
     }
@@ -178,7 +226,7 @@ static unsigned int ng_macos_keycode_from_event(unsigned short keycode) {
     if (!self.windowHandle) return;
     NSPoint location = [self convertPoint:[event locationInWindow] fromView:nil];
     CGFloat h = [self bounds].size.height;
-    ng_invoke_mouse_move(self.windowHandle, location.x, h - location.y);
+    ng_invoke_mouse_move(self.windowHandle, location.x, h - location.y, ng_macos_modifiers(event));
 
     int mode = ng_macos_get_cursor_grab_mode(self.windowHandle);
     if (mode == 2) {
@@ -252,13 +300,15 @@ static unsigned int ng_macos_keycode_from_event(unsigned short keycode) {
         self.windowHandle,
         (double)[event scrollingDeltaX],
         (double)[event scrollingDeltaY],
-        ng_macos_modifiers(event));
+        ng_macos_modifiers(event),
+        [event hasPreciseScrollingDeltas] ? NG_SCROLL_DELTA_PIXEL : NG_SCROLL_DELTA_LINE,
+        ng_macos_scroll_phase(event));
 }
 
 - (void)keyDown:(NSEvent*)event {
     if (!self.windowHandle) return;
     unsigned int keycode = ng_macos_keycode_from_event([event keyCode]);
-    ng_invoke_key_event(self.windowHandle, keycode, 1, ng_macos_modifiers(event));
+    ng_macos_keyboard_emit(self.windowHandle, event, keycode, 1, ng_macos_modifiers(event));
 
     // Only fire TextInput for printable characters.
     // Exclude: C0/C1 control chars, DEL (0x7F), and the macOS private-use
@@ -281,7 +331,7 @@ static unsigned int ng_macos_keycode_from_event(unsigned short keycode) {
 - (void)keyUp:(NSEvent*)event {
     if (!self.windowHandle) return;
     unsigned int keycode = ng_macos_keycode_from_event([event keyCode]);
-    ng_invoke_key_event(self.windowHandle, keycode, 0, ng_macos_modifiers(event));
+    ng_macos_keyboard_emit(self.windowHandle, event, keycode, 0, ng_macos_modifiers(event));
 }
 
 // macOS sends modifier-only presses/releases via flagsChanged: rather than
@@ -299,10 +349,12 @@ static unsigned int ng_macos_keycode_from_event(unsigned short keycode) {
         case 59: case 62: pressed = (flags & NSEventModifierFlagControl) ? 1 : 0; break;
         case 58: case 61: pressed = (flags & NSEventModifierFlagOption)  ? 1 : 0; break;
         case 55: case 54: pressed = (flags & NSEventModifierFlagCommand) ? 1 : 0; break;
+        // Reports the toggled lock state rather than the key going down and up.
+        case 57:          pressed = (flags & NSEventModifierFlagCapsLock) ? 1 : 0; break;
         default: return;
     }
 
-    ng_invoke_key_event(self.windowHandle, keycode, pressed, ng_macos_modifiers(event));
+    ng_macos_keyboard_emit(self.windowHandle, event, keycode, pressed, ng_macos_modifiers(event));
 }
 @end
 
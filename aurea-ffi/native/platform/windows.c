@@ -9,11 +9,33 @@
 #include <stdio.h>
 
 static HANDLE g_frame_event = NULL;
+/* Lives for the process: it is created on first use, so a wake-up requested
+ * before ng_windows_run is still pending once the loop waits on it. */
+static HANDLE g_wake_timer = NULL;
 
 void ng_windows_request_frame(void) {
     if (g_frame_event) SetEvent(g_frame_event);
 }
 
+static HANDLE wake_timer(void) {
+    if (!g_wake_timer) {
+        HANDLE timer = CreateWaitableTimerA(NULL, FALSE, NULL); // auto-reset
+        if (timer && InterlockedCompareExchangePointer(&g_wake_timer, timer, NULL) != NULL) {
+            CloseHandle(timer);
+        }
+    }
+    return g_wake_timer;
+}
+
+void ng_windows_request_frame_after(unsigned int delay_ms) {
+    HANDLE timer = wake_timer();
+    LARGE_INTEGER due;
+    due.QuadPart = -(LONGLONG)delay_ms * 10000; // relative, in 100 ns units
+    if (!timer || !SetWaitableTimer(timer, &due, 0, NULL, NULL, FALSE)) {
+        ng_windows_request_frame();
+    }
+}
+
 int ng_windows_run(void) {
     g_frame_event = CreateEventA(NULL, FALSE, FALSE, NULL); // auto-reset
     /* Flush any frames scheduled before the loop started (e.g. set_draw_callback
@@ -21,16 +43,19 @@ int ng_windows_run(void) {
        g_frame_event was NULL; signal it now so the first iteration fires. */
     SetEvent(g_frame_event);
 
+    HANDLE handles[2] = { g_frame_event, wake_timer() };
+    DWORD handle_count = handles[1] ? 2 : 1;
+
     for (;;) {
         DWORD result = MsgWaitForMultipleObjectsEx(
-            1, &g_frame_event,
+            handle_count, handles,
             INFINITE,
             QS_ALLINPUT,
             MWMO_ALERTABLE | MWMO_INPUTAVAILABLE);
 
-        if (result == WAIT_OBJECT_0) {
+        if (result < WAIT_OBJECT_0 + handle_count) {
             ng_process_frames();
-        } else if (result == WAIT_OBJECT_0 + 1) {
+        } else if (result == WAIT_OBJECT_0 + handle_count) {
             MSG msg;
             while (PeekMessageA(&msg, NULL, 0, 0, PM_REMOVE)) {
                 if (msg.message == WM_QUIT) goto done;
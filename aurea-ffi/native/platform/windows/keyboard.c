@@ -0,0 +1,130 @@
+#include "keyboard.h"
+#include "common/input.h"
+#include "common/rust_callbacks.h"
+
+#define CONSUMER(usage) (0x000C0000u | (usage))
+
+/* Set 1 scancode (without the E0 prefix) to USB HID usage. */
+static const unsigned int SCANCODE_TO_HID[] = {
+    [0x01] = 0x29,
+    [0x02] = 0x1E, [0x03] = 0x1F, [0x04] = 0x20, [0x05] = 0x21, [0x06] = 0x22, /* 1-5 */
+    [0x07] = 0x23, [0x08] = 0x24, [0x09] = 0x25, [0x0A] = 0x26, [0x0B] = 0x27, /* 6-0 */
+    [0x0C] = 0x2D, [0x0D] = 0x2E, [0x0E] = 0x2A, [0x0F] = 0x2B,
+    [0x10] = 0x14, [0x11] = 0x1A, [0x12] = 0x08, [0x13] = 0x15, [0x14] = 0x17, /* QWERT */
+    [0x15] = 0x1C, [0x16] = 0x18, [0x17] = 0x0C, [0x18] = 0x12, [0x19] = 0x13, /* YUIOP */
+    [0x1A] = 0x2F, [0x1B] = 0x30, [0x1C] = 0x28, [0x1D] = 0xE0,
+    [0x1E] = 0x04, [0x1F] = 0x16, [0x20] = 0x07, [0x21] = 0x09, [0x22] = 0x0A, /* ASDFG */
+    [0x23] = 0x0B, [0x24] = 0x0D, [0x25] = 0x0E, [0x26] = 0x0F,                /* HJKL */
+    [0x27] = 0x33, [0x28] = 0x34, [0x29] = 0x35, [0x2A] = 0xE1, [0x2B] = 0x31,
+    [0x2C] = 0x1D, [0x2D] = 0x1B, [0x2E] = 0x06, [0x2F] = 0x19, [0x30] = 0x05, /* ZXCVB */
+    [0x31] = 0x11, [0x32] = 0x10,                                              /* NM */
+    [0x33] = 0x36, [0x34] = 0x37, [0x35] = 0x38, [0x36] = 0xE5, [0x37] = 0x55,
+    [0x38] = 0xE2, [0x39] = 0x2C, [0x3A] = 0x39,
+    [0x3B] = 0x3A, [0x3C] = 0x3B, [0x3D] = 0x3C, [0x3E] = 0x3D, [0x3F] = 0x3E, /* F1-F5 */
+    [0x40] = 0x3F, [0x41] = 0x40, [0x42] = 0x41, [0x43] = 0x42, [0x44] = 0x43, /* F6-F10 */
+    /* Pause arrives as a bare 0x45; Num Lock is the extended 0xE045. */
+    [0x45] = 0x48, [0x46] = 0x47,
+    [0x47] = 0x5F, [0x48] = 0x60, [0x49] = 0x61, [0x4A] = 0x56,                /* KP 7 8 9 - */
+    [0x4B] = 0x5C, [0x4C] = 0x5D, [0x4D] = 0x5E, [0x4E] = 0x57,                /* KP 4 5 6 + */
+    [0x4F] = 0x59, [0x50] = 0x5A, [0x51] = 0x5B, [0x52] = 0x62, [0x53] = 0x63, /* KP 1 2 3 0 . */
+    [0x56] = 0x64, [0x57] = 0x44, [0x58] = 0x45, [0x59] = 0x67,
+    [0x64] = 0x68, [0x65] = 0x69, [0x66] = 0x6A, [0x67] = 0x6B, [0x68] = 0x6C, /* F13-F17 */
+    [0x69] = 0x6D, [0x6A] = 0x6E, [0x6B] = 0x6F, [0x6C] = 0x70, [0x6D] = 0x71, /* F18-F22 */
+    [0x6E] = 0x72, [0x76] = 0x73,                                              /* F23 F24 */
+};
+
+#define SCANCODE_TABLE_LEN (sizeof(SCANCODE_TO_HID) / sizeof(SCANCODE_TO_HID[0]))
+
+static unsigned int physical_from_extended(unsigned int scancode) {
+    switch (scancode) {
+        case 0x10: return CONSUMER(0xB6);
+        case 0x19: return CONSUMER(0xB5);
+        case 0x1C: return 0x58;
+        case 0x1D: return 0xE4;
+        case 0x20: return CONSUMER(0xE2);
+        case 0x22: return CONSUMER(0xCD);
+        case 0x24: return CONSUMER(0xB7);
+        case 0x2E: return CONSUMER(0xEA);
+        case 0x30: return CONSUMER(0xE9);
+        case 0x35: return 0x54;
+        case 0x37: return 0x46;
+        case 0x38: return 0xE6;
+        case 0x45: return 0x53;
+        case 0x47: return 0x4A;
+        case 0x48: return 0x52;
+        case 0x49: return 0x4B;
+        case 0x4B: return 0x50;
+        case 0x4D: return 0x4F;
+        case 0x4F: return 0x4D;
+        case 0x50: return 0x51;
+        case 0x51: return 0x4E;
+        case 0x52: return 0x49;
+        case 0x53: return 0x4C;
+        case 0x5B: return 0xE3;
+        case 0x5C: return 0xE7;
+        case 0x5D: return 0x65;
+        default: return 0;
+    }
+}
+
+static unsigned int physical_from_lparam(LPARAM lParam) {
+    unsigned int scancode = (unsigned int)((lParam >> 16) & 0xFF);
+    BOOL extended = (lParam & (1 << 24)) != 0;
+    unsigned int usage = 0;
+    if (extended) {
+        usage = physical_from_extended(scancode);
+    } else if (scancode < SCANCODE_TABLE_LEN) {
+        usage = SCANCODE_TO_HID[scancode];
+    }
+    if (usage != 0) {
+        return usage;
+    }
+    return NG_PHYSICAL_UNIDENTIFIED | (extended ? 0xE000u : 0u) | scancode;
+}
+
+/* The layout's text for the key with Shift (and AltGr) applied but not plain
+ * Ctrl/Alt. Returns FALSE for dead keys and keys without text. */
+static BOOL layout_text(WPARAM vk, LPARAM lParam, char* out, int out_size) {
+    BYTE state[256];
+    if (!GetKeyboardState(state)) {
+        return FALSE;
+    }
+    BOOL altgr = (state[VK_RMENU] & 0x80) && (state[VK_LCONTROL] & 0x80);
+    if (!altgr) {
+        state[VK_CONTROL] = state[VK_LCONTROL] = state[VK_RCONTROL] = 0;
+        state[VK_MENU] = state[VK_LMENU] = state[VK_RMENU] = 0;
+    }
+
+    wchar_t units[4];
+    UINT scancode = (UINT)((lParam >> 16) & 0xFF);
+    /* Flag 0x4 leaves the dead-key state alone so WM_CHAR still composes. */
+    int count = ToUnicode((UINT)vk, scancode, state, units, 4, 0x4);
+    if (count <= 0) {
+        return FALSE;
+    }
+    int len = WideCharToMultiByte(CP_UTF8, 0, units, count, out, out_size - 1, NULL, NULL);
+    if (len <= 0) {
+        return FALSE;
+    }
+    out[len] = '\0';
+    return TRUE;
+}
+
+void ng_windows_keyboard_emit(
+    HWND hwnd,
+    WPARAM vk,
+    LPARAM lParam,
+    unsigned int keycode,
+    unsigned int modifiers,
+    int pressed) {
+    char text[16];
+    NGKeyEvent out = {0};
+    out.keycode = keycode;
+    out.physical = physical_from_lparam(lParam);
+    out.text = layout_text(vk, lParam, text, (int)sizeof(text)) ? text : NULL;
+    out.pressed = pressed;
+    /* Bit 30 is the previous key state: set on auto-repeat. */
+    out.repeat = pressed && (lParam & (1 << 30)) != 0;
+    out.modifiers = modifiers;
+    ng_invoke_key_event((void*)hwnd, &out);
+}
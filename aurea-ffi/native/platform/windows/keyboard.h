@@ -0,0 +1,24 @@
+#ifndef NATIVE_GUI_WINDOWS_KEYBOARD_H
+#define NATIVE_GUI_WINDOWS_KEYBOARD_H
+
+#include <windows.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Report a WM_(SYS)KEYDOWN/UP with its physical key, layout text and repeat
+// state. `keycode` is the NG_KEY_* code already derived from the virtual key.
+void ng_windows_keyboard_emit(
+    HWND hwnd,
+    WPARAM vk,
+    LPARAM lParam,
+    unsigned int keycode,
+    unsigned int modifiers,
+    int pressed);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // NATIVE_GUI_WINDOWS_KEYBOARD_H
@@ -209,7 +209,7 @@ int ng_windows_handle_menu_shortcut(NGHandle window, unsigned int keycode, unsig
     for (int i = 0; i < g_shortcut_count; i++) {
         if (g_shortcuts[i].root != root) continue;
         if (g_shortcuts[i].keycode != keycode) continue;
-        if (g_shortcuts[i].modifiers != modifiers) continue;
+        if (g_shortcuts[i].modifiers != (modifiers & NG_MOD_HELD_MASK)) continue;
         ng_invoke_menu_callback(g_shortcuts[i].id);
         return 1;
     }
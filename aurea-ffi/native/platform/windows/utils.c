@@ -1,5 +1,6 @@
 #include "utils.h"
 #include "menu.h"
+#include "keyboard.h"
 #include "elements/common.h"
 #include "common/errors.h"
 #include "common/input.h"
@@ -96,10 +97,16 @@ static unsigned int ng_windows_modifiers(void) {
     if (GetKeyState(VK_LWIN) & 0x8000 || GetKeyState(VK_RWIN) & 0x8000) {
         mods |= NG_MOD_META;
     }
+    if (GetKeyState(VK_CAPITAL) & 0x1) {
+        mods |= NG_MOD_CAPS_LOCK;
+    }
+    if (GetKeyState(VK_NUMLOCK) & 0x1) {
+        mods |= NG_MOD_NUM_LOCK;
+    }
     return mods;
 }
 
-static unsigned int ng_windows_keycode_from_vk(WPARAM vk) {
+static unsigned int ng_windows_keycode_from_vk(WPARAM vk, LPARAM lParam) {
     if (vk >= 'A' && vk <= 'Z') {
         return NG_KEY_A + (unsigned int)(vk - 'A');
     }
@@ -107,11 +114,19 @@ static unsigned int ng_windows_keycode_from_vk(WPARAM vk) {
         return NG_KEY_0 + (unsigned int)(vk - '0');
     }
 
+    if (vk >= VK_NUMPAD0 && vk <= VK_NUMPAD9) {
+        return NG_KEY_NUMPAD_0 + (unsigned int)(vk - VK_NUMPAD0);
+    }
+    if (vk >= VK_F13 && vk <= VK_F24) {
+        return NG_KEY_F13 + (unsigned int)(vk - VK_F13);
+    }
+
     switch (vk) {
         case VK_SPACE:
             return NG_KEY_SPACE;
         case VK_RETURN:
-            return NG_KEY_ENTER;
+            /* The keypad Enter shares VK_RETURN but is an extended key. */
+            return (lParam & (1 << 24)) ? NG_KEY_NUMPAD_ENTER : NG_KEY_ENTER;
         case VK_ESCAPE:
             return NG_KEY_ESCAPE;
         case VK_TAB:
@@ -199,6 +214,43 @@ static unsigned int ng_windows_keycode_from_vk(WPARAM vk) {
             return NG_KEY_PERIOD;
         case VK_OEM_2:
             return NG_KEY_SLASH;
+        case VK_CAPITAL:
+            return NG_KEY_CAPS_LOCK;
+        case VK_NUMLOCK:
+            return NG_KEY_NUM_LOCK;
+        case VK_SCROLL:
+            return NG_KEY_SCROLL_LOCK;
+        case VK_SNAPSHOT:
+            return NG_KEY_PRINT_SCREEN;
+        case VK_PAUSE:
+            return NG_KEY_PAUSE;
+        case VK_APPS:
+            return NG_KEY_CONTEXT_MENU;
+        case VK_ADD:
+            return NG_KEY_NUMPAD_ADD;
+        case VK_SUBTRACT:
+            return NG_KEY_NUMPAD_SUBTRACT;
+        case VK_MULTIPLY:
+            return NG_KEY_NUMPAD_MULTIPLY;
+        case VK_DIVIDE:
+            return NG_KEY_NUMPAD_DIVIDE;
+        case VK_DECIMAL:
+        case VK_SEPARATOR:
+            return NG_KEY_NUMPAD_DECIMAL;
+        case VK_MEDIA_PLAY_PAUSE:
+            return NG_KEY_MEDIA_PLAY_PAUSE;
+        case VK_MEDIA_STOP:
+            return NG_KEY_MEDIA_STOP;
+        case VK_MEDIA_NEXT_TRACK:
+            return NG_KEY_MEDIA_NEXT_TRACK;
+        case VK_MEDIA_PREV_TRACK:
+            return NG_KEY_MEDIA_PREVIOUS_TRACK;
+        case VK_VOLUME_UP:
+            return NG_KEY_VOLUME_UP;
+        case VK_VOLUME_DOWN:
+            return NG_KEY_VOLUME_DOWN;
+        case VK_VOLUME_MUTE:
+            return NG_KEY_VOLUME_MUTE;
         default:
             return NG_KEY_UNKNOWN;
     }
@@ -283,7 +335,7 @@ LRESULT CALLBACK WindowProc(HWND hwnd, UINT uMsg, WPARAM wParam, LPARAM lParam)
 
             double x = (double)GET_X_LPARAM(lParam);
             double y = (double)GET_Y_LPARAM(lParam);
-            ng_invoke_mouse_move((void*)hwnd, x, y);
+            ng_invoke_mouse_move((void*)hwnd, x, y, ng_windows_modifiers());
             break;
         }
         case WM_MOUSELEAVE: {
@@ -323,9 +375,9 @@ LRESULT CALLBACK WindowProc(HWND hwnd, UINT uMsg, WPARAM wParam, LPARAM lParam)
         }
         case WM_KEYDOWN:
         case WM_SYSKEYDOWN: {
-            unsigned int keycode = ng_windows_keycode_from_vk(wParam);
+            unsigned int keycode = ng_windows_keycode_from_vk(wParam, lParam);
             unsigned int modifiers = ng_windows_modifiers();
-            ng_invoke_key_event((void*)hwnd, keycode, 1, modifiers);
+            ng_windows_keyboard_emit(hwnd, wParam, lParam, keycode, modifiers, 1);
             if (ng_windows_handle_menu_shortcut((void*)hwnd, keycode, modifiers)) {
                 return 0;
             }
@@ -340,8 +392,8 @@ LRESULT CALLBACK WindowProc(HWND hwnd, UINT uMsg, WPARAM wParam, LPARAM lParam)
         }
         case WM_KEYUP:
         case WM_SYSKEYUP: {
-            unsigned int keycode = ng_windows_keycode_from_vk(wParam);
-            ng_invoke_key_event((void*)hwnd, keycode, 0, ng_windows_modifiers());
+            unsigned int keycode = ng_windows_keycode_from_vk(wParam, lParam);
+            ng_windows_keyboard_emit(hwnd, wParam, lParam, keycode, ng_windows_modifiers(), 0);
             if (wParam == VK_MENU || wParam == VK_F10) {
                 return 0;
             }
@@ -397,12 +449,16 @@ LRESULT CALLBACK WindowProc(HWND hwnd, UINT uMsg, WPARAM wParam, LPARAM lParam)
         }
         case WM_MOUSEWHEEL: {
             double delta = (double)GET_WHEEL_DELTA_WPARAM(wParam) / (double)WHEEL_DELTA;
-            ng_invoke_mouse_wheel((void*)hwnd, 0.0, delta, ng_windows_modifiers());
+            ng_invoke_mouse_wheel(
+                (void*)hwnd, 0.0, delta, ng_windows_modifiers(),
+                NG_SCROLL_DELTA_LINE, NG_SCROLL_PHASE_DISCRETE);
             break;
         }
         case WM_MOUSEHWHEEL: {
             double delta = (double)GET_WHEEL_DELTA_WPARAM(wParam) / (double)WHEEL_DELTA;
-            ng_invoke_mouse_wheel((void*)hwnd, delta, 0.0, ng_windows_modifiers());
+            ng_invoke_mouse_wheel(
+                (void*)hwnd, delta, 0.0, ng_windows_modifiers(),
+                NG_SCROLL_DELTA_LINE, NG_SCROLL_PHASE_DISCRETE);
             break;
         }
         case WM_COMMAND:
@@ -16,6 +16,7 @@ void ng_windows_cleanup(void);
 int ng_windows_run(void);
 int ng_windows_poll_events(void);
 void ng_windows_request_frame(void);
+void ng_windows_request_frame_after(unsigned int delay_ms);
 BOOL ng_windows_is_initialized(void);
 const char* ng_windows_get_class_name(void);
 void ng_windows_register_scale_callback(HWND hwnd, ScaleFactorCallback callback);
@@ -16,6 +16,7 @@ static const ng_platform_ops_t windows_ops = {
     .run = ng_windows_run,
     .poll_events = ng_windows_poll_events,
     .request_frame = ng_windows_request_frame,
+    .request_frame_after = ng_windows_request_frame_after,
     .create_window = ng_windows_create_window,
     .create_window_with_type = ng_windows_create_window_with_type,
     .destroy_window = ng_windows_destroy_window,
@@ -1,4 +1,11 @@
-use std::os::raw::{c_char, c_int, c_void};
+use std::os::raw::{c_char, c_float, c_int, c_void};
+
+// Status codes from `common/errors.h`.
+pub const NG_SUCCESS: c_int = 0;
+pub const NG_ERROR_INVALID_HANDLE: c_int = -1;
+pub const NG_ERROR_CREATION_FAILED: c_int = -2;
+pub const NG_ERROR_INVALID_PARAMETER: c_int = -3;
+pub const NG_ERROR_PLATFORM_SPECIFIC: c_int = -4;
 
 #[cfg(target_os = "android")]
 #[allow(clippy::missing_safety_doc, dead_code)]
@@ -11,6 +18,143 @@ unsafe extern "C" {
     pub fn ng_android_on_surface_recreated();
 }
 
+/// One representation of a clipboard copy; mirror of `NGClipboardItem` in
+/// `common/platform_api.h`.
+#[repr(C)]
+pub struct NGClipboardItem {
+    pub mime_type: *const c_char,
+    pub data: *const u8,
+    pub length: usize,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Mirror of `NGFileDialogOptions` in `common/platform_api.h`.
+#[repr(C)]
+pub struct NGFileDialogOptions {
+    pub mode: u32,
+    pub title: *const c_char,
+    pub directory: *const c_char,
+    pub file_name: *const c_char,
+    pub filter_names: *const *const c_char,
+    pub filter_extensions: *const *const c_char,
+    pub filter_count: c_int,
+    pub multiple: c_int,
+    pub blocking: c_int,
+}
+
+/// Mirror of `NGMessageDialogOptions` in `common/platform_api.h`.
+#[repr(C)]
+pub struct NGMessageDialogOptions {
+    pub kind: u32,
+    pub title: *const c_char,
+    pub message: *const c_char,
+    pub detail: *const c_char,
+    pub buttons: *const *const c_char,
+    pub button_count: c_int,
+    pub default_button: c_int,
+    pub cancel_button: c_int,
+    pub blocking: c_int,
+}
+
+/// Mirror of `NGFontDescription` in `common/platform_api.h`.
+#[repr(C)]
+pub struct NGFontDescription {
+    pub family: *const c_char,
+    pub size: f32,
+    pub bold: c_int,
+    pub italic: c_int,
+}
+
+/// Mirror of `NGMenuItemOptions` in `common/platform_api.h`.
+#[repr(C)]
+pub struct NGMenuItemOptions {
+    pub kind: u32,
+    pub title: *const c_char,
+    pub id: u32,
+    pub radio_group: u32,
+    pub checked: c_int,
+    pub enabled: c_int,
+    pub accel_char: u32,
+    pub accel_key: u32,
+    pub accel_modifiers: u32,
+    pub icon_rgba: *const u8,
+    pub icon_width: u32,
+    pub icon_height: u32,
+}
+
+/// Mirror of `NGMonitorInfo` in `common/platform_api.h`.
+#[repr(C)]
+pub struct NGMonitorInfo {
+    pub name: [c_char; 128],
+    pub x: c_int,
+    pub y: c_int,
+    pub width: c_int,
+    pub height: c_int,
+    pub work_x: c_int,
+    pub work_y: c_int,
+    pub work_width: c_int,
+    pub work_height: c_int,
+    pub scale_factor: f32,
+    pub refresh_millihertz: c_int,
+}
+
+/// Mirror of `NGWindowFrame` in `common/platform_api.h`.
+#[repr(C)]
+pub struct NGWindowFrame {
+    pub x: c_int,
+    pub y: c_int,
+    pub width: c_int,
+    pub height: c_int,
+    pub content_x: c_int,
+    pub content_y: c_int,
+    pub content_width: c_int,
+    pub content_height: c_int,
+}
+
+/// Mirror of `NGNotificationOptions` in `common/platform_api.h`.
+#[repr(C)]
+pub struct NGNotificationOptions {
+    pub title: *const c_char,
+    pub body: *const c_char,
+    pub icon_rgba: *const u8,
+    pub icon_width: u32,
+    pub icon_height: u32,
+    pub action_ids: *const *const c_char,
+    pub action_labels: *const *const c_char,
+    pub action_count: c_int,
+    pub urgency: u32,
+    pub timeout_ms: c_int,
+}
+
+/// Mirror of `NGWindowOptions` in `common/platform_api.h`. Fields are only
+/// appended; `version` tells the platform how many of them the caller set.
+#[repr(C)]
+pub struct NGWindowOptions {
+    pub version: u32,
+    pub title: *const c_char,
+    pub width: c_int,
+    pub height: c_int,
+    pub position: u32,
+    pub x: c_int,
+    pub y: c_int,
+    pub min_width: c_int,
+    pub min_height: c_int,
+    pub max_width: c_int,
+    pub max_height: c_int,
+    pub resizable: c_int,
+    pub decorated: c_int,
+    pub visible: c_int,
+    pub transparent: c_int,
+    pub window_type: c_int,
+    pub parent: *mut c_void,
+    pub modal: c_int,
+    pub icon_rgba: *const u8,
+    pub icon_width: u32,
+    pub icon_height: u32,
+    pub app_id: *const c_char,
+}
+
 #[allow(clippy::missing_safety_doc, dead_code)]
 unsafe extern "C" {
     pub fn ng_platform_get_abi_version() -> c_int;
@@ -21,6 +165,8 @@ unsafe extern "C" {
     pub fn ng_platform_run() -> c_int;
     pub fn ng_platform_poll_events() -> c_int;
     pub fn ng_platform_request_frame();
+    pub fn ng_platform_request_frame_after(delay_ms: u32);
+    pub fn ng_platform_can_request_frame_after() -> c_int;
     pub fn ng_platform_frame_idle();
 
     pub fn ng_platform_create_window(
@@ -35,6 +181,7 @@ unsafe extern "C" {
         height: c_int,
         window_type: c_int,
     ) -> *mut c_void;
+    pub fn ng_platform_create_window_with_options(options: *const NGWindowOptions) -> *mut c_void;
     pub fn ng_platform_destroy_window(handle: *mut c_void);
     pub fn ng_platform_window_set_title(window: *mut c_void, title: *const c_char);
     pub fn ng_platform_window_set_icon_rgba(
@@ -49,6 +196,66 @@ unsafe extern "C" {
     pub fn ng_platform_window_is_focused(window: *mut c_void) -> c_int;
     pub fn ng_platform_window_set_cursor_visible(window: *mut c_void, visible: c_int) -> c_int;
     pub fn ng_platform_window_set_cursor_grab(window: *mut c_void, mode: c_int) -> c_int;
+    pub fn ng_platform_window_set_cursor_icon(window: *mut c_void, name: *const c_char) -> c_int;
+    pub fn ng_platform_window_set_custom_cursor(
+        window: *mut c_void,
+        rgba: *const u8,
+        width: u32,
+        height: u32,
+        hotspot_x: c_int,
+        hotspot_y: c_int,
+    ) -> c_int;
+    pub fn ng_platform_window_set_drop_effect(window: *mut c_void, effect: u32) -> c_int;
+    pub fn ng_platform_window_start_drag(
+        window: *mut c_void,
+        data_kind: u32,
+        items: *const *const c_char,
+        item_count: c_int,
+        preview_rgba: *const u8,
+        preview_width: u32,
+        preview_height: u32,
+    ) -> c_int;
+    pub fn ng_platform_window_set_ime_allowed(window: *mut c_void, allowed: c_int) -> c_int;
+    pub fn ng_platform_window_set_ime_cursor_area(
+        window: *mut c_void,
+        x: c_float,
+        y: c_float,
+        width: c_float,
+        height: c_float,
+    ) -> c_int;
+    pub fn ng_platform_window_get_state(window: *mut c_void) -> u32;
+    pub fn ng_platform_window_set_fullscreen(
+        window: *mut c_void,
+        fullscreen: c_int,
+        monitor: c_int,
+    ) -> c_int;
+    pub fn ng_platform_window_set_maximized(window: *mut c_void, maximized: c_int) -> c_int;
+    pub fn ng_platform_window_set_minimized(window: *mut c_void, minimized: c_int) -> c_int;
+    pub fn ng_platform_window_set_min_size(
+        window: *mut c_void,
+        width: c_int,
+        height: c_int,
+    ) -> c_int;
+    pub fn ng_platform_window_set_max_size(
+        window: *mut c_void,
+        width: c_int,
+        height: c_int,
+    ) -> c_int;
+    pub fn ng_platform_window_set_resizable(window: *mut c_void, resizable: c_int) -> c_int;
+    pub fn ng_platform_window_set_always_on_top(window: *mut c_void, always_on_top: c_int)
+    -> c_int;
+    pub fn ng_platform_window_set_decorations(window: *mut c_void, decorated: c_int) -> c_int;
+    pub fn ng_platform_window_set_transparent(window: *mut c_void, transparent: c_int) -> c_int;
+    pub fn ng_platform_window_set_parent(
+        window: *mut c_void,
+        parent: *mut c_void,
+        modal: c_int,
+    ) -> c_int;
+    pub fn ng_platform_primary_monitor() -> c_int;
+    pub fn ng_platform_window_current_monitor(window: *mut c_void) -> c_int;
+    pub fn ng_platform_monitor_count() -> c_int;
+    pub fn ng_platform_monitor_info(monitor: c_int, info: *mut NGMonitorInfo) -> c_int;
+    pub fn ng_platform_window_get_frame(window: *mut c_void, frame: *mut NGWindowFrame) -> c_int;
     pub fn ng_platform_window_get_content_view(window: *mut c_void) -> *mut c_void;
     pub fn ng_platform_window_show(window: *mut c_void);
     pub fn ng_platform_window_hide(window: *mut c_void);
@@ -91,11 +298,104 @@ unsafe extern "C" {
     ) -> c_int;
     pub fn ng_platform_add_menu_separator(menu_handle: *mut c_void) -> c_int;
     pub fn ng_platform_create_submenu(parent: *mut c_void, title: *const c_char) -> *mut c_void;
+    pub fn ng_platform_menu_add_item(
+        menu: *mut c_void,
+        options: *const NGMenuItemOptions,
+        item: *mut *mut c_void,
+    ) -> c_int;
+    pub fn ng_platform_menu_item_set_enabled(item: *mut c_void, enabled: c_int) -> c_int;
+    pub fn ng_platform_menu_item_set_checked(item: *mut c_void, checked: c_int) -> c_int;
+    pub fn ng_platform_menu_item_is_checked(item: *mut c_void) -> c_int;
+    pub fn ng_platform_menu_item_set_label(item: *mut c_void, label: *const c_char) -> c_int;
+    pub fn ng_platform_menu_item_remove(item: *mut c_void) -> c_int;
+    pub fn ng_platform_create_context_menu() -> *mut c_void;
+    pub fn ng_platform_show_context_menu(
+        anchor: *mut c_void,
+        menu: *mut c_void,
+        x: f32,
+        y: f32,
+    ) -> c_int;
 
     // Clipboard
+    pub fn ng_platform_file_dialog_show(
+        parent: *mut c_void,
+        options: *const NGFileDialogOptions,
+        request_id: u32,
+    ) -> c_int;
+    pub fn ng_platform_message_dialog_show(
+        parent: *mut c_void,
+        options: *const NGMessageDialogOptions,
+        request_id: u32,
+    ) -> c_int;
+    pub fn ng_platform_color_picker_show(
+        parent: *mut c_void,
+        title: *const c_char,
+        initial_rgba: *const u8,
+        show_alpha: c_int,
+        blocking: c_int,
+        request_id: u32,
+    ) -> c_int;
+    pub fn ng_platform_font_picker_show(
+        parent: *mut c_void,
+        title: *const c_char,
+        initial: *const NGFontDescription,
+        blocking: c_int,
+        request_id: u32,
+    ) -> c_int;
+
     pub fn ng_platform_get_clipboard_text() -> *mut c_char;
     pub fn ng_platform_free_clipboard_text(text: *mut c_char);
     pub fn ng_platform_set_clipboard_text(text: *const c_char) -> c_int;
+    pub fn ng_platform_clipboard_set(
+        selection: u32,
+        items: *const NGClipboardItem,
+        count: c_int,
+    ) -> c_int;
+    pub fn ng_platform_clipboard_types(selection: u32) -> *mut c_char;
+    pub fn ng_platform_clipboard_get(
+        selection: u32,
+        mime_type: *const c_char,
+        length: *mut usize,
+        width: *mut u32,
+        height: *mut u32,
+    ) -> *mut u8;
+    pub fn ng_platform_clipboard_free(data: *mut u8);
+    pub fn ng_platform_clipboard_watch(selection: u32) -> c_int;
+
+    pub fn ng_platform_tray_create(
+        rgba: *const u8,
+        width: u32,
+        height: u32,
+        tooltip: *const c_char,
+    ) -> *mut c_void;
+    pub fn ng_platform_tray_set_icon(
+        tray: *mut c_void,
+        rgba: *const u8,
+        width: u32,
+        height: u32,
+    ) -> c_int;
+    pub fn ng_platform_tray_set_tooltip(tray: *mut c_void, tooltip: *const c_char) -> c_int;
+    pub fn ng_platform_tray_set_menu(tray: *mut c_void, menu: *mut c_void) -> c_int;
+    pub fn ng_platform_tray_destroy(tray: *mut c_void);
+
+    pub fn ng_platform_element_set_accessible(
+        element: *mut c_void,
+        name: *const c_char,
+        description: *const c_char,
+    ) -> c_int;
+
+    pub fn ng_platform_element_focus(element: *mut c_void) -> c_int;
+    pub fn ng_platform_window_set_focus_traversal(window: *mut c_void, managed: c_int) -> c_int;
+    pub fn ng_platform_element_set_focus_target(element: *mut c_void, registered: c_int) -> c_int;
+    pub fn ng_platform_window_focus_keeps_tab(window: *mut c_void) -> c_int;
+
+    pub fn ng_platform_notification_show(options: *const NGNotificationOptions) -> *mut c_void;
+    pub fn ng_platform_notification_update(
+        notification: *mut c_void,
+        options: *const NGNotificationOptions,
+    ) -> c_int;
+    pub fn ng_platform_notification_close(notification: *mut c_void) -> c_int;
+    pub fn ng_platform_notification_release(notification: *mut c_void);
 
     pub fn ng_platform_create_button(title: *const c_char, id: u32) -> *mut c_void;
     pub fn ng_platform_button_invalidate(button: *mut c_void);
@@ -64,11 +64,9 @@ impl Capability {
             Capability::FontPicker => true,
             Capability::SystemNotifications => true,
             Capability::MouseInput => true,
-            Capability::TouchInput => false,
+            Capability::TouchInput => matches!(desktop, DesktopPlatform::Linux),
             Capability::KeyboardInput => true,
-            Capability::StylusInput => {
-                matches!(desktop, DesktopPlatform::Windows | DesktopPlatform::MacOS)
-            }
+            Capability::StylusInput => true,
             Capability::HardwareAcceleration => true,
             Capability::OpenGL => true,
             Capability::Metal => matches!(desktop, DesktopPlatform::MacOS),
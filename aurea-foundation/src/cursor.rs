@@ -0,0 +1,90 @@
+//! Mouse cursor shapes.
+
+/// Standard cursor shapes, following the CSS `cursor` keywords.
+///
+/// Cursors are requested from the platform by their CSS name; themes that
+/// lack one fall back to [`CursorIcon::Default`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    ContextMenu,
+    Help,
+    /// A hand, as over links.
+    Pointer,
+    /// Busy, but still interactive.
+    Progress,
+    Wait,
+    Cell,
+    Crosshair,
+    /// I-beam over editable or selectable text.
+    Text,
+    VerticalText,
+    Alias,
+    Copy,
+    Move,
+    NoDrop,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    AllScroll,
+    ColResize,
+    RowResize,
+    NResize,
+    EResize,
+    SResize,
+    WResize,
+    NeResize,
+    NwResize,
+    SeResize,
+    SwResize,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ZoomIn,
+    ZoomOut,
+}
+
+impl CursorIcon {
+    /// The CSS keyword for this cursor, e.g. `"ew-resize"`. This is also
+    /// the name passed to the native layer.
+    pub fn css_name(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::ContextMenu => "context-menu",
+            Self::Help => "help",
+            Self::Pointer => "pointer",
+            Self::Progress => "progress",
+            Self::Wait => "wait",
+            Self::Cell => "cell",
+            Self::Crosshair => "crosshair",
+            Self::Text => "text",
+            Self::VerticalText => "vertical-text",
+            Self::Alias => "alias",
+            Self::Copy => "copy",
+            Self::Move => "move",
+            Self::NoDrop => "no-drop",
+            Self::NotAllowed => "not-allowed",
+            Self::Grab => "grab",
+            Self::Grabbing => "grabbing",
+            Self::AllScroll => "all-scroll",
+            Self::ColResize => "col-resize",
+            Self::RowResize => "row-resize",
+            Self::NResize => "n-resize",
+            Self::EResize => "e-resize",
+            Self::SResize => "s-resize",
+            Self::WResize => "w-resize",
+            Self::NeResize => "ne-resize",
+            Self::NwResize => "nw-resize",
+            Self::SeResize => "se-resize",
+            Self::SwResize => "sw-resize",
+            Self::EwResize => "ew-resize",
+            Self::NsResize => "ns-resize",
+            Self::NeswResize => "nesw-resize",
+            Self::NwseResize => "nwse-resize",
+            Self::ZoomIn => "zoom-in",
+            Self::ZoomOut => "zoom-out",
+        }
+    }
+}
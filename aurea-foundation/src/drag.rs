@@ -0,0 +1,99 @@
+//! Drag-and-drop payloads and drop effects.
+
+use std::path::PathBuf;
+
+/// Content carried by a drag, into or out of a window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DragData {
+    /// Local files, e.g. from a file manager.
+    Files(Vec<PathBuf>),
+    Text(String),
+    /// Non-file URIs, e.g. links from a browser.
+    Uris(Vec<String>),
+}
+
+impl DragData {
+    /// Rebuild from a kind tag and its items. `Text` joins multiple items
+    /// with newlines.
+    pub fn from_raw(kind: u32, items: Vec<String>) -> Self {
+        match kind {
+            0 => Self::Files(items.into_iter().map(PathBuf::from).collect()),
+            2 => Self::Uris(items),
+            _ => Self::Text(items.join("\n")),
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`]: the kind tag and the items as strings.
+    /// Paths that are not valid UTF-8 are converted lossily.
+    pub fn to_raw(&self) -> (u32, Vec<String>) {
+        match self {
+            Self::Files(paths) => (
+                0,
+                paths
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect(),
+            ),
+            Self::Text(text) => (1, vec![text.clone()]),
+            Self::Uris(uris) => (2, uris.clone()),
+        }
+    }
+}
+
+/// What a drop would do, shown to the user by the pointer. `None` rejects
+/// the drop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DropEffect {
+    #[default]
+    None,
+    Copy,
+    Move,
+    Link,
+}
+
+impl DropEffect {
+    pub fn from_raw(effect: u32) -> Self {
+        match effect {
+            1 => Self::Copy,
+            2 => Self::Move,
+            3 => Self::Link,
+            _ => Self::None,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Copy => 1,
+            Self::Move => 2,
+            Self::Link => 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drag_data_raw_round_trip() {
+        let samples = [
+            DragData::Files(vec![PathBuf::from("/tmp/a.png"), PathBuf::from("b c.txt")]),
+            DragData::Text("hello\nworld".to_owned()),
+            DragData::Uris(vec!["https://example.com/".to_owned()]),
+        ];
+        for data in samples {
+            let (kind, items) = data.to_raw();
+            assert_eq!(DragData::from_raw(kind, items), data);
+        }
+        for effect in [
+            DropEffect::None,
+            DropEffect::Copy,
+            DropEffect::Move,
+            DropEffect::Link,
+        ] {
+            assert_eq!(DropEffect::from_raw(effect.to_raw()), effect);
+        }
+    }
+}
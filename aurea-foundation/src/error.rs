@@ -37,6 +37,12 @@ pub enum AureaError {
     AnimationNotFound,
     /// Internal render frame setup failed
     RenderFrameSetupFailed(String),
+    /// An event recording could not be parsed
+    InvalidRecording(String),
+    /// A saved window state could not be parsed
+    InvalidWindowState(String),
+    /// Reading or writing a file failed
+    Io(String),
 }
 
 /// Result type for GUI operations.
@@ -103,6 +109,13 @@ impl Display for AureaError {
             AureaError::RenderFrameSetupFailed(msg) => {
                 write!(f, "Render frame setup failed: {}", msg)
             }
+            AureaError::InvalidRecording(msg) => {
+                write!(f, "Invalid event recording: {}", msg)
+            }
+            AureaError::InvalidWindowState(msg) => {
+                write!(f, "Invalid window state: {}", msg)
+            }
+            AureaError::Io(msg) => write!(f, "I/O error: {}", msg),
         }
     }
 }
@@ -1,5 +1,8 @@
 //! Window event types for external event loop integration.
 
+use crate::drag::{DragData, DropEffect};
+use crate::keyboard::{KeyLocation, LogicalKey, PhysicalKey};
+use std::ops::BitOr;
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
@@ -17,9 +20,28 @@ pub enum WindowEvent {
     Unfocused,
     Minimized,
     Restored,
+    Maximized,
+    /// The window left the maximized state, by the user or
+    /// `Window::set_maximized(false)`.
+    Unmaximized,
+    EnteredFullscreen,
+    ExitedFullscreen,
+    /// The window manager accepted or dropped the keep-above hint.
+    AlwaysOnTopChanged {
+        always_on_top: bool,
+    },
+    ResizableChanged {
+        resizable: bool,
+    },
+    DecorationsChanged {
+        decorated: bool,
+    },
     ScaleFactorChanged {
         scale_factor: f32,
     },
+    /// Displays were connected, removed or reconfigured; query
+    /// `Monitor::all` again. Delivered to every event queue.
+    MonitorsChanged,
     SurfaceLost,
     SurfaceRecreated,
     MouseEntered,
@@ -27,6 +49,7 @@ pub enum WindowEvent {
     MouseMove {
         x: f64,
         y: f64,
+        modifiers: Modifiers,
     },
     RawMouseMotion {
         delta_x: f64,
@@ -44,15 +67,464 @@ pub enum WindowEvent {
         delta_x: f64,
         delta_y: f64,
         modifiers: Modifiers,
+        /// Whether the deltas count wheel lines or logical pixels.
+        delta_kind: ScrollDeltaKind,
+        phase: ScrollPhase,
     },
     KeyInput {
+        /// Layout-mapped key code, kept for shortcut matching; prefer
+        /// `physical_key` or `logical_key` for new code.
         key: KeyCode,
+        physical_key: PhysicalKey,
+        logical_key: LogicalKey,
+        location: KeyLocation,
         pressed: bool,
+        /// Auto-repeat of a key that is still held.
+        repeat: bool,
         modifiers: Modifiers,
     },
     TextInput {
         text: String,
     },
+    /// An input method started composing text for this window.
+    ImeEnabled,
+    /// The in-progress composition changed. `cursor_range` is a byte range
+    /// into `text`; `None` hides the cursor. An empty `text` clears the
+    /// preedit.
+    ImePreedit {
+        text: String,
+        cursor_range: Option<(usize, usize)>,
+    },
+    /// The input method finished composing and inserts `text`.
+    ImeCommit {
+        text: String,
+    },
+    ImeDisabled,
+    /// Unified mouse/touch/pen input. Touch and pen contacts also produce
+    /// synthesised `MouseMove`/`MouseButton` events for the primary pointer,
+    /// so handle either these or the mouse events, not both.
+    Pointer(PointerEvent),
+    /// A two-finger pinch or rotation on a touchpad. `scale` is the finger
+    /// spread relative to when the gesture began and `rotation` the radians
+    /// turned since then, clockwise positive.
+    TouchpadPinch {
+        phase: TouchpadPhase,
+        position: (f64, f64),
+        scale: f64,
+        rotation: f64,
+    },
+    /// A drag carrying `data` entered the window. Reply with
+    /// `Window::set_drop_effect`; drops are rejected until then.
+    DragEntered {
+        data: DragData,
+        x: f64,
+        y: f64,
+    },
+    DragOver {
+        x: f64,
+        y: f64,
+    },
+    /// The drag left the window or was cancelled without dropping.
+    DragLeft,
+    /// `data` was dropped with the `effect` the window last accepted.
+    Dropped {
+        data: DragData,
+        x: f64,
+        y: f64,
+        effect: DropEffect,
+    },
+    /// A `TrayIcon` was clicked. `x`/`y` are screen coordinates, or 0 when
+    /// the host does not report them. Only delivered to tray icon queues.
+    TrayClicked {
+        button: MouseButton,
+        x: i32,
+        y: i32,
+    },
+    /// Follows the `TrayClicked` of the second click in a double-click.
+    TrayDoubleClicked {
+        x: i32,
+        y: i32,
+    },
+    /// The user picked one of a notification's actions by its id; `"default"`
+    /// is a click on the notification itself. Only delivered to notification
+    /// handle queues.
+    NotificationAction {
+        action: String,
+    },
+    NotificationClosed {
+        reason: NotificationCloseReason,
+    },
+    /// An assistive technology asked for `action` on the accessibility node
+    /// of the shape whose `InteractiveId` is `target` on the canvas at
+    /// address `canvas`, as in [`FocusTarget::CanvasItem`].
+    AccessibilityAction {
+        canvas: usize,
+        target: u64,
+        action: AccessibilityAction,
+    },
+    /// Keyboard focus moved onto `target`, one of the targets registered
+    /// with the window's focus manager.
+    FocusGained {
+        target: FocusTarget,
+    },
+    /// Keyboard focus left `target`; followed by a `FocusGained` unless it
+    /// moved to something the focus manager does not know about.
+    FocusLost {
+        target: FocusTarget,
+    },
+}
+
+/// Something inside a window that can hold keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FocusTarget {
+    /// A native element, by the address of its handle.
+    Element(usize),
+    /// An interactive shape on a canvas: the address of the canvas handle
+    /// and the shape's `InteractiveId`.
+    CanvasItem { canvas: usize, id: u64 },
+}
+
+impl FocusTarget {
+    /// Rebuild a target from the fields of [`Self::to_raw`].
+    pub fn from_raw(kind: u32, handle: usize, id: u64) -> Option<Self> {
+        match kind {
+            0 => Some(Self::Element(handle)),
+            1 => Some(Self::CanvasItem { canvas: handle, id }),
+            _ => None,
+        }
+    }
+
+    /// `(kind, handle, id)`, with `id` 0 for elements.
+    pub fn to_raw(self) -> (u32, usize, u64) {
+        match self {
+            Self::Element(handle) => (0, handle, 0),
+            Self::CanvasItem { canvas, id } => (1, canvas, id),
+        }
+    }
+
+    /// The native handle address that receives platform focus: the element
+    /// itself, or the canvas holding the shape.
+    pub fn native_handle(self) -> usize {
+        match self {
+            Self::Element(handle) | Self::CanvasItem { canvas: handle, .. } => handle,
+        }
+    }
+}
+
+/// What an assistive technology can ask an accessibility node to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessibilityAction {
+    /// Activate the node, like a click or tap.
+    Click,
+    /// Move keyboard focus to the node.
+    Focus,
+    Increment,
+    Decrement,
+    Expand,
+    Collapse,
+    ScrollIntoView,
+    ShowContextMenu,
+}
+
+impl AccessibilityAction {
+    pub fn from_raw(action: u32) -> Option<Self> {
+        Some(match action {
+            0 => Self::Click,
+            1 => Self::Focus,
+            2 => Self::Increment,
+            3 => Self::Decrement,
+            4 => Self::Expand,
+            5 => Self::Collapse,
+            6 => Self::ScrollIntoView,
+            7 => Self::ShowContextMenu,
+            _ => return None,
+        })
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Click => 0,
+            Self::Focus => 1,
+            Self::Increment => 2,
+            Self::Decrement => 3,
+            Self::Expand => 4,
+            Self::Collapse => 5,
+            Self::ScrollIntoView => 6,
+            Self::ShowContextMenu => 7,
+        }
+    }
+}
+
+/// Why a notification went away, as reported by the notification server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NotificationCloseReason {
+    Expired,
+    /// The user dismissed it.
+    Dismissed,
+    /// Closed through the application's handle.
+    Closed,
+    #[default]
+    Unknown,
+}
+
+impl NotificationCloseReason {
+    /// Reasons use the freedesktop `NotificationClosed` codes.
+    pub fn from_raw(reason: u32) -> Self {
+        match reason {
+            1 => Self::Expired,
+            2 => Self::Dismissed,
+            3 => Self::Closed,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Expired => 1,
+            Self::Dismissed => 2,
+            Self::Closed => 3,
+            Self::Unknown => 4,
+        }
+    }
+}
+
+/// Unit of `MouseWheel` deltas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScrollDeltaKind {
+    /// Wheel notches; the application picks the line height.
+    #[default]
+    Line,
+    /// Logical pixels from a trackpad or other precise device.
+    Pixel,
+}
+
+impl ScrollDeltaKind {
+    pub fn from_raw(kind: u32) -> Self {
+        match kind {
+            1 => Self::Pixel,
+            _ => Self::Line,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Line => 0,
+            Self::Pixel => 1,
+        }
+    }
+}
+
+/// Where a `MouseWheel` event sits in a scroll gesture.
+///
+/// Trackpads report `Began`/`Changed`/`Ended` while fingers are down, then
+/// optionally `Momentum*` while the platform coasts. Wheels report `Discrete`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScrollPhase {
+    #[default]
+    Discrete,
+    Began,
+    Changed,
+    Ended,
+    MomentumBegan,
+    Momentum,
+    MomentumEnded,
+}
+
+impl ScrollPhase {
+    pub fn from_raw(phase: u32) -> Self {
+        match phase {
+            1 => Self::Began,
+            2 => Self::Changed,
+            3 => Self::Ended,
+            4 => Self::MomentumBegan,
+            5 => Self::Momentum,
+            6 => Self::MomentumEnded,
+            _ => Self::Discrete,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Discrete => 0,
+            Self::Began => 1,
+            Self::Changed => 2,
+            Self::Ended => 3,
+            Self::MomentumBegan => 4,
+            Self::Momentum => 5,
+            Self::MomentumEnded => 6,
+        }
+    }
+
+    /// True for events the platform generates after the fingers lifted.
+    pub fn is_momentum(self) -> bool {
+        matches!(
+            self,
+            Self::MomentumBegan | Self::Momentum | Self::MomentumEnded
+        )
+    }
+}
+
+/// Stage of a touchpad gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchpadPhase {
+    Began,
+    Changed,
+    Ended,
+    Cancelled,
+}
+
+impl TouchpadPhase {
+    pub fn from_raw(phase: u32) -> Self {
+        match phase {
+            0 => Self::Began,
+            1 => Self::Changed,
+            2 => Self::Ended,
+            _ => Self::Cancelled,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Began => 0,
+            Self::Changed => 1,
+            Self::Ended => 2,
+            Self::Cancelled => 3,
+        }
+    }
+}
+
+/// One sample from a mouse, touch contact or pen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+    /// Stable for the lifetime of a contact; the mouse is always `0`.
+    pub pointer_id: u64,
+    pub kind: PointerKind,
+    pub phase: PointerPhase,
+    /// Logical window coordinates.
+    pub position: (f64, f64),
+    /// Normalised `0.0..=1.0`. Devices without pressure report `0.5` while a
+    /// button or contact is down and `0.0` otherwise.
+    pub pressure: f32,
+    /// Pen tilt along x and y in degrees, `-90.0..=90.0`.
+    pub tilt: (f32, f32),
+    /// Pen barrel rotation in degrees, `0.0..360.0`.
+    pub twist: f32,
+    pub buttons: PointerButtons,
+    pub modifiers: Modifiers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerKind {
+    Mouse,
+    Touch,
+    Pen,
+}
+
+impl PointerKind {
+    pub fn from_raw(kind: u32) -> Self {
+        match kind {
+            1 => Self::Touch,
+            2 => Self::Pen,
+            _ => Self::Mouse,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Mouse => 0,
+            Self::Touch => 1,
+            Self::Pen => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerPhase {
+    /// A contact started or a button was pressed.
+    Down,
+    /// Motion, including hover with nothing pressed.
+    Move,
+    /// A contact ended or a button was released.
+    Up,
+    /// The platform took the contact away (e.g. a system gesture).
+    Cancel,
+}
+
+impl PointerPhase {
+    pub fn from_raw(phase: u32) -> Self {
+        match phase {
+            0 => Self::Down,
+            2 => Self::Up,
+            3 => Self::Cancel,
+            _ => Self::Move,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Down => 0,
+            Self::Move => 1,
+            Self::Up => 2,
+            Self::Cancel => 3,
+        }
+    }
+}
+
+/// Buttons held during a pointer event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PointerButtons(u32);
+
+impl PointerButtons {
+    /// Left mouse button, touch contact or pen tip.
+    pub const PRIMARY: Self = Self(0b0001);
+    /// Right mouse button or pen barrel button.
+    pub const SECONDARY: Self = Self(0b0010);
+    pub const MIDDLE: Self = Self(0b0100);
+    /// Pen eraser end touching the surface.
+    pub const ERASER: Self = Self(0b1000);
+
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits & 0b1111)
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for PointerButtons {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -72,6 +544,16 @@ impl MouseButton {
             other => Self::Other(other),
         }
     }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u8 {
+        match self {
+            Self::Left => 0,
+            Self::Right => 1,
+            Self::Middle => 2,
+            Self::Other(other) => other,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -80,6 +562,9 @@ pub struct Modifiers {
     pub ctrl: bool,
     pub alt: bool,
     pub meta: bool,
+    /// Lock states, not held keys; they don't count towards [`Self::is_any`].
+    pub caps_lock: bool,
+    pub num_lock: bool,
 }
 
 impl Modifiers {
@@ -97,8 +582,20 @@ impl Modifiers {
             ctrl: bits & 0b0010 != 0,
             alt: bits & 0b0100 != 0,
             meta: bits & 0b1000 != 0,
+            caps_lock: bits & 0b1_0000 != 0,
+            num_lock: bits & 0b10_0000 != 0,
         }
     }
+
+    /// Inverse of [`Self::from_bits`].
+    pub fn to_bits(&self) -> u32 {
+        u32::from(self.shift)
+            | u32::from(self.ctrl) << 1
+            | u32::from(self.alt) << 2
+            | u32::from(self.meta) << 3
+            | u32::from(self.caps_lock) << 4
+            | u32::from(self.num_lock) << 5
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -182,9 +679,56 @@ pub enum KeyCode {
     Comma,
     Period,
     Slash,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    PrintScreen,
+    Pause,
+    ContextMenu,
+    /// Numeric keypad keys, reported while Num Lock is on; with it off the
+    /// keypad produces the navigation codes (`Home`, `Up`, ...).
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    NumpadEquals,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    MediaPlayPause,
+    MediaStop,
+    MediaNextTrack,
+    MediaPreviousTrack,
+    VolumeUp,
+    VolumeDown,
+    VolumeMute,
     Unknown(u32),
 }
 
+/// Highest assigned `NG_KEY_*` code.
+const LAST_KEY_CODE: u32 = 119;
+
 impl KeyCode {
     pub fn from_raw(code: u32) -> Self {
         match code {
@@ -272,11 +816,74 @@ impl KeyCode {
             75 => Self::Comma,
             76 => Self::Period,
             77 => Self::Slash,
+            78..=83 => [
+                Self::CapsLock,
+                Self::NumLock,
+                Self::ScrollLock,
+                Self::PrintScreen,
+                Self::Pause,
+                Self::ContextMenu,
+            ][(code - 78) as usize],
+            84..=100 => [
+                Self::Numpad0,
+                Self::Numpad1,
+                Self::Numpad2,
+                Self::Numpad3,
+                Self::Numpad4,
+                Self::Numpad5,
+                Self::Numpad6,
+                Self::Numpad7,
+                Self::Numpad8,
+                Self::Numpad9,
+                Self::NumpadAdd,
+                Self::NumpadSubtract,
+                Self::NumpadMultiply,
+                Self::NumpadDivide,
+                Self::NumpadDecimal,
+                Self::NumpadEnter,
+                Self::NumpadEquals,
+            ][(code - 84) as usize],
+            101..=112 => [
+                Self::F13,
+                Self::F14,
+                Self::F15,
+                Self::F16,
+                Self::F17,
+                Self::F18,
+                Self::F19,
+                Self::F20,
+                Self::F21,
+                Self::F22,
+                Self::F23,
+                Self::F24,
+            ][(code - 101) as usize],
+            113..=119 => [
+                Self::MediaPlayPause,
+                Self::MediaStop,
+                Self::MediaNextTrack,
+                Self::MediaPreviousTrack,
+                Self::VolumeUp,
+                Self::VolumeDown,
+                Self::VolumeMute,
+            ][(code - 113) as usize],
             _ => Self::Unknown(code),
         }
     }
+
+    /// Inverse of [`Self::from_raw`]: the `NG_KEY_*` code for this key.
+    pub fn to_raw(self) -> u32 {
+        if let Self::Unknown(code) = self {
+            return code;
+        }
+        // Codes are dense, so a linear probe over the known range is exact.
+        (0..=LAST_KEY_CODE)
+            .find(|code| Self::from_raw(*code) == self)
+            .unwrap_or(u32::MAX)
+    }
 }
 
+pub type EventCallback = Arc<dyn Fn(WindowEvent) + Send + Sync>;
+
 #[cfg(test)]
 mod keycode_tests {
     use super::KeyCode;
@@ -295,11 +902,19 @@ mod keycode_tests {
         assert_eq!(KeyCode::from_raw(67), KeyCode::Minus);
         assert_eq!(KeyCode::from_raw(68), KeyCode::Equals);
         assert_eq!(KeyCode::from_raw(77), KeyCode::Slash);
+        assert_eq!(KeyCode::from_raw(84), KeyCode::Numpad0);
+        assert_eq!(KeyCode::from_raw(112), KeyCode::F24);
+        assert_eq!(KeyCode::from_raw(119), KeyCode::VolumeMute);
         assert!(matches!(
             KeyCode::from_raw(0xFFFF_FFFF),
             KeyCode::Unknown(_)
         ));
     }
-}
 
-pub type EventCallback = Arc<dyn Fn(WindowEvent) + Send + Sync>;
+    #[test]
+    fn to_raw_round_trips() {
+        for code in [0, 25, 36, 62, 77, 83, 100, 119, 500] {
+            assert_eq!(KeyCode::from_raw(code).to_raw(), code);
+        }
+    }
+}
@@ -0,0 +1,453 @@
+//! Layout-independent and layout-mapped key identities.
+//!
+//! A key press is described twice: [`PhysicalKey`] names the key by its
+//! position on a US keyboard, so `KeyW` is the key left of `KeyE` whatever it
+//! types, and [`LogicalKey`] is what the active layout maps it to. Bind game
+//! controls such as WASD to physical keys and shortcuts to logical ones.
+
+use crate::events::KeyCode;
+
+/// A key by position, named after its US-layout legend.
+///
+/// Codes follow the USB HID keyboard usage page (0x07); media keys use the
+/// consumer page and are stored as `0x000C_0000 | usage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhysicalKey {
+    KeyA,
+    KeyB,
+    KeyC,
+    KeyD,
+    KeyE,
+    KeyF,
+    KeyG,
+    KeyH,
+    KeyI,
+    KeyJ,
+    KeyK,
+    KeyL,
+    KeyM,
+    KeyN,
+    KeyO,
+    KeyP,
+    KeyQ,
+    KeyR,
+    KeyS,
+    KeyT,
+    KeyU,
+    KeyV,
+    KeyW,
+    KeyX,
+    KeyY,
+    KeyZ,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Digit0,
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+    Space,
+    Minus,
+    Equal,
+    BracketLeft,
+    BracketRight,
+    Backslash,
+    Semicolon,
+    Quote,
+    Backquote,
+    Comma,
+    Period,
+    Slash,
+    CapsLock,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    Insert,
+    Home,
+    PageUp,
+    Delete,
+    End,
+    PageDown,
+    ArrowRight,
+    ArrowLeft,
+    ArrowDown,
+    ArrowUp,
+    NumLock,
+    NumpadDivide,
+    NumpadMultiply,
+    NumpadSubtract,
+    NumpadAdd,
+    NumpadEnter,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    Numpad0,
+    NumpadDecimal,
+    IntlBackslash,
+    ContextMenu,
+    NumpadEqual,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    ControlLeft,
+    ShiftLeft,
+    AltLeft,
+    MetaLeft,
+    ControlRight,
+    ShiftRight,
+    AltRight,
+    MetaRight,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    MediaStop,
+    MediaPlayPause,
+    AudioVolumeMute,
+    AudioVolumeUp,
+    AudioVolumeDown,
+    /// A key without a HID mapping, carrying the platform scancode (0 when
+    /// the platform reported none).
+    Unidentified(u32),
+}
+
+/// `Unidentified` raw codes are marked with this bit so they never collide
+/// with a HID usage.
+const UNIDENTIFIED_BIT: u32 = 0x8000_0000;
+
+const HID_USAGES: &[(PhysicalKey, u32)] = &[
+    (PhysicalKey::KeyA, 0x0004),
+    (PhysicalKey::KeyB, 0x0005),
+    (PhysicalKey::KeyC, 0x0006),
+    (PhysicalKey::KeyD, 0x0007),
+    (PhysicalKey::KeyE, 0x0008),
+    (PhysicalKey::KeyF, 0x0009),
+    (PhysicalKey::KeyG, 0x000A),
+    (PhysicalKey::KeyH, 0x000B),
+    (PhysicalKey::KeyI, 0x000C),
+    (PhysicalKey::KeyJ, 0x000D),
+    (PhysicalKey::KeyK, 0x000E),
+    (PhysicalKey::KeyL, 0x000F),
+    (PhysicalKey::KeyM, 0x0010),
+    (PhysicalKey::KeyN, 0x0011),
+    (PhysicalKey::KeyO, 0x0012),
+    (PhysicalKey::KeyP, 0x0013),
+    (PhysicalKey::KeyQ, 0x0014),
+    (PhysicalKey::KeyR, 0x0015),
+    (PhysicalKey::KeyS, 0x0016),
+    (PhysicalKey::KeyT, 0x0017),
+    (PhysicalKey::KeyU, 0x0018),
+    (PhysicalKey::KeyV, 0x0019),
+    (PhysicalKey::KeyW, 0x001A),
+    (PhysicalKey::KeyX, 0x001B),
+    (PhysicalKey::KeyY, 0x001C),
+    (PhysicalKey::KeyZ, 0x001D),
+    (PhysicalKey::Digit1, 0x001E),
+    (PhysicalKey::Digit2, 0x001F),
+    (PhysicalKey::Digit3, 0x0020),
+    (PhysicalKey::Digit4, 0x0021),
+    (PhysicalKey::Digit5, 0x0022),
+    (PhysicalKey::Digit6, 0x0023),
+    (PhysicalKey::Digit7, 0x0024),
+    (PhysicalKey::Digit8, 0x0025),
+    (PhysicalKey::Digit9, 0x0026),
+    (PhysicalKey::Digit0, 0x0027),
+    (PhysicalKey::Enter, 0x0028),
+    (PhysicalKey::Escape, 0x0029),
+    (PhysicalKey::Backspace, 0x002A),
+    (PhysicalKey::Tab, 0x002B),
+    (PhysicalKey::Space, 0x002C),
+    (PhysicalKey::Minus, 0x002D),
+    (PhysicalKey::Equal, 0x002E),
+    (PhysicalKey::BracketLeft, 0x002F),
+    (PhysicalKey::BracketRight, 0x0030),
+    (PhysicalKey::Backslash, 0x0031),
+    (PhysicalKey::Semicolon, 0x0033),
+    (PhysicalKey::Quote, 0x0034),
+    (PhysicalKey::Backquote, 0x0035),
+    (PhysicalKey::Comma, 0x0036),
+    (PhysicalKey::Period, 0x0037),
+    (PhysicalKey::Slash, 0x0038),
+    (PhysicalKey::CapsLock, 0x0039),
+    (PhysicalKey::F1, 0x003A),
+    (PhysicalKey::F2, 0x003B),
+    (PhysicalKey::F3, 0x003C),
+    (PhysicalKey::F4, 0x003D),
+    (PhysicalKey::F5, 0x003E),
+    (PhysicalKey::F6, 0x003F),
+    (PhysicalKey::F7, 0x0040),
+    (PhysicalKey::F8, 0x0041),
+    (PhysicalKey::F9, 0x0042),
+    (PhysicalKey::F10, 0x0043),
+    (PhysicalKey::F11, 0x0044),
+    (PhysicalKey::F12, 0x0045),
+    (PhysicalKey::PrintScreen, 0x0046),
+    (PhysicalKey::ScrollLock, 0x0047),
+    (PhysicalKey::Pause, 0x0048),
+    (PhysicalKey::Insert, 0x0049),
+    (PhysicalKey::Home, 0x004A),
+    (PhysicalKey::PageUp, 0x004B),
+    (PhysicalKey::Delete, 0x004C),
+    (PhysicalKey::End, 0x004D),
+    (PhysicalKey::PageDown, 0x004E),
+    (PhysicalKey::ArrowRight, 0x004F),
+    (PhysicalKey::ArrowLeft, 0x0050),
+    (PhysicalKey::ArrowDown, 0x0051),
+    (PhysicalKey::ArrowUp, 0x0052),
+    (PhysicalKey::NumLock, 0x0053),
+    (PhysicalKey::NumpadDivide, 0x0054),
+    (PhysicalKey::NumpadMultiply, 0x0055),
+    (PhysicalKey::NumpadSubtract, 0x0056),
+    (PhysicalKey::NumpadAdd, 0x0057),
+    (PhysicalKey::NumpadEnter, 0x0058),
+    (PhysicalKey::Numpad1, 0x0059),
+    (PhysicalKey::Numpad2, 0x005A),
+    (PhysicalKey::Numpad3, 0x005B),
+    (PhysicalKey::Numpad4, 0x005C),
+    (PhysicalKey::Numpad5, 0x005D),
+    (PhysicalKey::Numpad6, 0x005E),
+    (PhysicalKey::Numpad7, 0x005F),
+    (PhysicalKey::Numpad8, 0x0060),
+    (PhysicalKey::Numpad9, 0x0061),
+    (PhysicalKey::Numpad0, 0x0062),
+    (PhysicalKey::NumpadDecimal, 0x0063),
+    (PhysicalKey::IntlBackslash, 0x0064),
+    (PhysicalKey::ContextMenu, 0x0065),
+    (PhysicalKey::NumpadEqual, 0x0067),
+    (PhysicalKey::F13, 0x0068),
+    (PhysicalKey::F14, 0x0069),
+    (PhysicalKey::F15, 0x006A),
+    (PhysicalKey::F16, 0x006B),
+    (PhysicalKey::F17, 0x006C),
+    (PhysicalKey::F18, 0x006D),
+    (PhysicalKey::F19, 0x006E),
+    (PhysicalKey::F20, 0x006F),
+    (PhysicalKey::F21, 0x0070),
+    (PhysicalKey::F22, 0x0071),
+    (PhysicalKey::F23, 0x0072),
+    (PhysicalKey::F24, 0x0073),
+    (PhysicalKey::ControlLeft, 0x00E0),
+    (PhysicalKey::ShiftLeft, 0x00E1),
+    (PhysicalKey::AltLeft, 0x00E2),
+    (PhysicalKey::MetaLeft, 0x00E3),
+    (PhysicalKey::ControlRight, 0x00E4),
+    (PhysicalKey::ShiftRight, 0x00E5),
+    (PhysicalKey::AltRight, 0x00E6),
+    (PhysicalKey::MetaRight, 0x00E7),
+    (PhysicalKey::MediaTrackNext, 0x000C_00B5),
+    (PhysicalKey::MediaTrackPrevious, 0x000C_00B6),
+    (PhysicalKey::MediaStop, 0x000C_00B7),
+    (PhysicalKey::MediaPlayPause, 0x000C_00CD),
+    (PhysicalKey::AudioVolumeMute, 0x000C_00E2),
+    (PhysicalKey::AudioVolumeUp, 0x000C_00E9),
+    (PhysicalKey::AudioVolumeDown, 0x000C_00EA),
+];
+
+impl PhysicalKey {
+    /// Map a HID usage as reported by the native layer; 0 means the
+    /// platform had no mapping.
+    pub fn from_hid_usage(usage: u32) -> Option<Self> {
+        HID_USAGES
+            .iter()
+            .find(|(_, code)| *code == usage)
+            .map(|(key, _)| *key)
+    }
+
+    pub fn hid_usage(self) -> Option<u32> {
+        HID_USAGES
+            .iter()
+            .find(|(key, _)| *key == self)
+            .map(|(_, code)| *code)
+    }
+
+    /// Decode [`Self::to_raw`]: a HID usage, or a platform scancode tagged
+    /// as unidentified.
+    pub fn from_raw(raw: u32) -> Self {
+        if raw & UNIDENTIFIED_BIT != 0 {
+            return Self::Unidentified(raw & !UNIDENTIFIED_BIT);
+        }
+        Self::from_hid_usage(raw).unwrap_or(Self::Unidentified(raw))
+    }
+
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Unidentified(scancode) => scancode | UNIDENTIFIED_BIT,
+            key => key.hid_usage().unwrap_or(UNIDENTIFIED_BIT),
+        }
+    }
+
+    /// Which copy of a duplicated key this is.
+    pub fn location(self) -> KeyLocation {
+        match self {
+            Self::ShiftLeft | Self::ControlLeft | Self::AltLeft | Self::MetaLeft => {
+                KeyLocation::Left
+            }
+            Self::ShiftRight | Self::ControlRight | Self::AltRight | Self::MetaRight => {
+                KeyLocation::Right
+            }
+            key => match key.hid_usage() {
+                Some(0x54..=0x63 | 0x67) => KeyLocation::Numpad,
+                _ => KeyLocation::Standard,
+            },
+        }
+    }
+}
+
+/// Where on the keyboard a key sits when it exists more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KeyLocation {
+    #[default]
+    Standard,
+    Left,
+    Right,
+    Numpad,
+}
+
+impl KeyLocation {
+    pub fn from_raw(raw: u32) -> Self {
+        match raw {
+            1 => Self::Left,
+            2 => Self::Right,
+            3 => Self::Numpad,
+            _ => Self::Standard,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Standard => 0,
+            Self::Left => 1,
+            Self::Right => 2,
+            Self::Numpad => 3,
+        }
+    }
+}
+
+/// A key as the active layout interprets it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LogicalKey {
+    /// The text the key produces, e.g. `"z"` for the physical `KeyY` on a
+    /// German layout. Shift is applied; Ctrl, Alt and Meta are not.
+    Character(String),
+    /// A key that produces no text.
+    Named(KeyCode),
+    Unidentified,
+}
+
+impl LogicalKey {
+    /// Build from the key code and the text the layout reports for it.
+    /// Control characters (Enter, Tab, ...) are named keys, not text.
+    pub fn new(key: KeyCode, text: Option<&str>) -> Self {
+        match text {
+            Some(text) if !text.is_empty() && !text.chars().any(char::is_control) => {
+                Self::Character(text.to_owned())
+            }
+            _ => match key {
+                KeyCode::Unknown(_) => Self::Unidentified,
+                named => Self::Named(named),
+            },
+        }
+    }
+
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Self::Character(text) => {
+                let mut chars = text.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::Character(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hid_usages_round_trip() {
+        assert_eq!(PhysicalKey::from_raw(0x1A), PhysicalKey::KeyW);
+        assert_eq!(PhysicalKey::from_raw(0x73), PhysicalKey::F24);
+        assert_eq!(
+            PhysicalKey::from_raw(0x000C_00CD),
+            PhysicalKey::MediaPlayPause
+        );
+        for (key, usage) in HID_USAGES {
+            assert_eq!(PhysicalKey::from_raw(key.to_raw()), *key);
+            assert_eq!(key.to_raw(), *usage);
+        }
+    }
+
+    #[test]
+    fn unidentified_keeps_the_scancode() {
+        let key = PhysicalKey::Unidentified(0x1F0);
+        assert_eq!(PhysicalKey::from_raw(key.to_raw()), key);
+        assert_eq!(PhysicalKey::from_raw(0x01), PhysicalKey::Unidentified(0x01));
+    }
+
+    #[test]
+    fn locations() {
+        assert_eq!(PhysicalKey::ShiftRight.location(), KeyLocation::Right);
+        assert_eq!(PhysicalKey::MetaLeft.location(), KeyLocation::Left);
+        assert_eq!(PhysicalKey::Numpad7.location(), KeyLocation::Numpad);
+        assert_eq!(PhysicalKey::NumpadEnter.location(), KeyLocation::Numpad);
+        assert_eq!(PhysicalKey::Enter.location(), KeyLocation::Standard);
+    }
+
+    #[test]
+    fn logical_key_treats_control_text_as_named() {
+        assert_eq!(
+            LogicalKey::new(KeyCode::Y, Some("z")),
+            LogicalKey::Character("z".into())
+        );
+        assert_eq!(
+            LogicalKey::new(KeyCode::Enter, Some("\r")),
+            LogicalKey::Named(KeyCode::Enter)
+        );
+        assert_eq!(
+            LogicalKey::new(KeyCode::Unknown(7), None),
+            LogicalKey::Unidentified
+        );
+        assert_eq!(LogicalKey::new(KeyCode::A, Some("ä")).as_char(), Some('ä'));
+    }
+}
@@ -1,13 +1,23 @@
 //! Core shared types, errors, platform detection, and events for Aurea.
 
 pub mod capability;
+pub mod cursor;
+pub mod drag;
 pub mod error;
 pub mod events;
+pub mod keyboard;
 pub mod platform;
 pub mod sync;
 
 pub use capability::{Capability, CapabilityChecker};
+pub use cursor::CursorIcon;
+pub use drag::{DragData, DropEffect};
 pub use error::{AureaError, AureaResult};
-pub use events::{EventCallback, KeyCode, Modifiers, MouseButton, WindowEvent};
+pub use events::{
+    AccessibilityAction, EventCallback, FocusTarget, KeyCode, Modifiers, MouseButton,
+    NotificationCloseReason, PointerButtons, PointerEvent, PointerKind, PointerPhase,
+    ScrollDeltaKind, ScrollPhase, TouchpadPhase, WindowEvent,
+};
+pub use keyboard::{KeyLocation, LogicalKey, PhysicalKey};
 pub use platform::{DesktopPlatform, MobilePlatform, Platform};
 pub use sync::lock;
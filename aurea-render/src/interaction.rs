@@ -5,8 +5,8 @@
 use crate::command::DrawCommand;
 use crate::cpu::hit_test;
 use crate::display_list::{DisplayItem, DisplayList};
-use crate::types::{InteractiveId, Point};
-use aurea_foundation::{AureaResult, lock};
+use crate::types::{InteractiveId, Point, Rect};
+use aurea_foundation::{AureaResult, CursorIcon, lock};
 use std::collections::HashMap;
 use std::sync::Mutex;
 
@@ -21,6 +21,7 @@ pub struct InteractionRegistry {
     click_callbacks: Mutex<HashMap<InteractiveId, ClickCallback>>,
     hover_callbacks: Mutex<HashMap<InteractiveId, HoverCallback>>,
     hover_state: Mutex<HashMap<InteractiveId, bool>>, // Track current hover state
+    cursors: Mutex<HashMap<InteractiveId, CursorIcon>>,
 }
 
 impl InteractionRegistry {
@@ -29,6 +30,7 @@ impl InteractionRegistry {
             click_callbacks: Mutex::new(HashMap::new()),
             hover_callbacks: Mutex::new(HashMap::new()),
             hover_state: Mutex::new(HashMap::new()),
+            cursors: Mutex::new(HashMap::new()),
         }
     }
 
@@ -44,6 +46,52 @@ impl InteractionRegistry {
         callbacks.insert(id, callback);
     }
 
+    /// Declare the cursor shown while the pointer is over a shape
+    pub fn register_cursor(&self, id: InteractiveId, cursor: CursorIcon) {
+        let mut cursors = lock(&self.cursors);
+        cursors.insert(id, cursor);
+    }
+
+    /// Whether any shape declared a cursor
+    pub fn has_cursors(&self) -> bool {
+        !lock(&self.cursors).is_empty()
+    }
+
+    /// Cursor for a point: the one declared by the topmost interactive shape
+    /// under it, or the default arrow.
+    pub fn cursor_at(&self, display_list: &DisplayList, point: Point) -> CursorIcon {
+        let topmost = Self::topmost_at(display_list, point);
+        let cursors = lock(&self.cursors);
+        topmost
+            .and_then(|id| cursors.get(&id).copied())
+            .unwrap_or_default()
+    }
+
+    /// The topmost interactive shape under a point
+    pub fn topmost_at(display_list: &DisplayList, point: Point) -> Option<InteractiveId> {
+        display_list
+            .items()
+            .iter()
+            .rev()
+            .find_map(|item| item.interactive_id.filter(|_| item_hit(item, point)))
+    }
+
+    /// Bounds of everything drawn for a shape, in the display list's pixels
+    pub fn bounds_of(display_list: &DisplayList, id: InteractiveId) -> Option<Rect> {
+        display_list
+            .items()
+            .iter()
+            .filter(|item| item.interactive_id == Some(id))
+            .map(|item| item.bounds)
+            .reduce(|a, b| {
+                let x = a.x.min(b.x);
+                let y = a.y.min(b.y);
+                let right = (a.x + a.width).max(b.x + b.width);
+                let bottom = (a.y + a.height).max(b.y + b.height);
+                Rect::new(x, y, right - x, bottom - y)
+            })
+    }
+
     /// Unregister callbacks for an ID
     pub fn unregister(&self, id: InteractiveId) {
         let mut click_callbacks = lock(&self.click_callbacks);
@@ -54,6 +102,9 @@ impl InteractionRegistry {
 
         let mut hover_state = lock(&self.hover_state);
         hover_state.remove(&id);
+
+        let mut cursors = lock(&self.cursors);
+        cursors.remove(&id);
     }
 
     /// Handle a click event at a point
@@ -153,3 +204,70 @@ impl Default for InteractionRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_list::{CacheKey, DisplayItem, NodeId};
+    use crate::types::{BlendMode, Color, Paint, Rect};
+
+    fn rect_item(id: u64, rect: Rect) -> DisplayItem {
+        DisplayItem::new_interactive(
+            NodeId::new(),
+            CacheKey::from_hash(id),
+            rect,
+            false,
+            InteractiveId(id),
+            BlendMode::Normal,
+            DrawCommand::DrawRect(rect, Paint::new().color(Color::rgb(0, 0, 0))),
+        )
+    }
+
+    #[test]
+    fn cursor_comes_from_the_topmost_shape() {
+        let mut list = DisplayList::new();
+        list.push(rect_item(1, Rect::new(0.0, 0.0, 100.0, 100.0)));
+        list.push(rect_item(2, Rect::new(50.0, 0.0, 10.0, 100.0)));
+
+        let registry = InteractionRegistry::new();
+        registry.register_cursor(InteractiveId(1), CursorIcon::Pointer);
+        registry.register_cursor(InteractiveId(2), CursorIcon::EwResize);
+
+        assert_eq!(
+            registry.cursor_at(&list, Point::new(55.0, 50.0)),
+            CursorIcon::EwResize
+        );
+        assert_eq!(
+            registry.cursor_at(&list, Point::new(10.0, 50.0)),
+            CursorIcon::Pointer
+        );
+        assert_eq!(
+            registry.cursor_at(&list, Point::new(150.0, 50.0)),
+            CursorIcon::Default
+        );
+        assert_eq!(
+            InteractionRegistry::topmost_at(&list, Point::new(55.0, 50.0)),
+            Some(InteractiveId(2))
+        );
+        assert_eq!(
+            InteractionRegistry::topmost_at(&list, Point::new(150.0, 50.0)),
+            None
+        );
+        list.push(rect_item(2, Rect::new(70.0, 120.0, 10.0, 10.0)));
+        assert_eq!(
+            InteractionRegistry::bounds_of(&list, InteractiveId(2)),
+            Some(Rect::new(50.0, 0.0, 30.0, 130.0))
+        );
+        assert_eq!(
+            InteractionRegistry::bounds_of(&list, InteractiveId(3)),
+            None
+        );
+
+        // A shape without a declared cursor hides the one beneath it.
+        registry.unregister(InteractiveId(2));
+        assert_eq!(
+            registry.cursor_at(&list, Point::new(55.0, 50.0)),
+            CursorIcon::Default
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Channel for handing values from worker threads to the UI thread.
+//!
+//! Sending wakes the platform loop, so a receiver drained from `on_update`
+//! or awaited inside an [`Executor`](crate::Executor) task sees the value on
+//! the next loop pass without polling on a timer.
+
+use crate::frame_queue::FrameScheduler;
+use aurea_foundation::lock;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+struct Shared<T> {
+    queue: VecDeque<T>,
+    waker: Option<Waker>,
+    senders: usize,
+    receiver_alive: bool,
+}
+
+/// Create a channel whose receiver lives on the UI thread.
+pub fn main_thread_channel<T: Send + 'static>() -> (MainThreadSender<T>, MainThreadReceiver<T>) {
+    let shared = Arc::new(Mutex::new(Shared {
+        queue: VecDeque::new(),
+        waker: None,
+        senders: 1,
+        receiver_alive: true,
+    }));
+    (
+        MainThreadSender {
+            shared: shared.clone(),
+        },
+        MainThreadReceiver { shared },
+    )
+}
+
+/// Sending half of [`main_thread_channel`]. Cheap to clone and `Send`.
+pub struct MainThreadSender<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> MainThreadSender<T> {
+    /// Queue `value` for the UI thread and wake the loop. Returns the value
+    /// back if the receiver has been dropped.
+    pub fn send(&self, value: T) -> Result<(), T> {
+        let waker = {
+            let mut shared = lock(&self.shared);
+            if !shared.receiver_alive {
+                return Err(value);
+            }
+            shared.queue.push_back(value);
+            shared.waker.take()
+        };
+        Self::notify(waker);
+        Ok(())
+    }
+
+    fn notify(waker: Option<Waker>) {
+        // A parked `recv()` future wakes through its task; a receiver drained
+        // from `on_update` only needs the loop to run once more.
+        match waker {
+            Some(waker) => waker.wake(),
+            None => FrameScheduler::wake(),
+        }
+    }
+}
+
+impl<T> Clone for MainThreadSender<T> {
+    fn clone(&self) -> Self {
+        lock(&self.shared).senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for MainThreadSender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut shared = lock(&self.shared);
+            shared.senders -= 1;
+            if shared.senders > 0 {
+                return;
+            }
+            shared.waker.take()
+        };
+        // Last sender gone: let a pending `recv()` observe the disconnect.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Receiving half of [`main_thread_channel`].
+pub struct MainThreadReceiver<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> MainThreadReceiver<T> {
+    /// Take the next queued value without blocking.
+    pub fn try_recv(&self) -> Option<T> {
+        lock(&self.shared).queue.pop_front()
+    }
+
+    /// Take every queued value without blocking.
+    pub fn drain(&self) -> Vec<T> {
+        lock(&self.shared).queue.drain(..).collect()
+    }
+
+    /// Whether every sender has been dropped.
+    pub fn is_disconnected(&self) -> bool {
+        lock(&self.shared).senders == 0
+    }
+
+    /// Wait for the next value. Resolves to `None` once the queue is empty
+    /// and every sender has been dropped.
+    pub fn recv(&self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+}
+
+impl<T> Drop for MainThreadReceiver<T> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.receiver_alive = false;
+        shared.queue.clear();
+    }
+}
+
+/// Future returned by [`MainThreadReceiver::recv`].
+pub struct Recv<'a, T> {
+    receiver: &'a MainThreadReceiver<T>,
+}
+
+impl<T> Future for Recv<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut shared = lock(&self.receiver.shared);
+        if let Some(value) = shared.queue.pop_front() {
+            return Poll::Ready(Some(value));
+        }
+        if shared.senders == 0 {
+            return Poll::Ready(None);
+        }
+        shared.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+    use std::thread;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn values_sent_from_other_threads_arrive_in_order() {
+        let (tx, rx) = main_thread_channel();
+        let worker = thread::spawn(move || {
+            for n in 0..4 {
+                tx.send(n).unwrap();
+            }
+        });
+        worker.join().unwrap();
+
+        assert_eq!(rx.drain(), vec![0, 1, 2, 3]);
+        assert!(rx.is_disconnected());
+    }
+
+    #[test]
+    fn recv_wakes_on_send_and_ends_on_disconnect() {
+        let (tx, rx) = main_thread_channel();
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut recv = rx.recv();
+        assert_eq!(Pin::new(&mut recv).poll(&mut cx), Poll::Pending);
+        tx.send("hello").unwrap();
+        assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            Pin::new(&mut recv).poll(&mut cx),
+            Poll::Ready(Some("hello"))
+        );
+
+        assert_eq!(Pin::new(&mut recv).poll(&mut cx), Poll::Pending);
+        drop(tx);
+        assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+        assert_eq!(Pin::new(&mut recv).poll(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn send_fails_after_receiver_dropped() {
+        let (tx, rx) = main_thread_channel();
+        drop(rx);
+        assert_eq!(tx.send(7), Err(7));
+    }
+}
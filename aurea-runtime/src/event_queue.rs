@@ -1,12 +1,19 @@
 //! Event queue for window-level events.
 
-use aurea_foundation::{EventCallback, WindowEvent, lock};
+use crate::event_router::{EventRouter, Propagation};
+use crate::pointer::PointerTranslator;
+use crate::recording::{EventRecording, Recorder};
+use aurea_foundation::{EventCallback, PointerPhase, WindowEvent, lock};
+use std::iter;
 use std::mem::{discriminant, take};
 use std::sync::{Arc, Mutex};
 
 pub struct EventQueue {
     events: Mutex<Vec<WindowEvent>>,
     callbacks: Mutex<Arc<Vec<EventCallback>>>,
+    router: EventRouter,
+    recorder: Mutex<Option<Recorder>>,
+    pointers: Mutex<PointerTranslator>,
 }
 
 impl EventQueue {
@@ -14,18 +21,20 @@ impl EventQueue {
         Self {
             events: Mutex::new(Vec::new()),
             callbacks: Mutex::new(Arc::new(Vec::new())),
+            router: EventRouter::new(),
+            recorder: Mutex::new(None),
+            pointers: Mutex::new(PointerTranslator::default()),
         }
     }
 
     pub fn push(&self, event: WindowEvent) {
+        self.record(&event);
         let mut events = lock(&self.events);
         // Coalesce high-frequency motion events: replace the tail if it is the
         // same variant, so a fast mouse or trackpad never queues more than one
         // entry per process_events() call.
         match &event {
-            WindowEvent::MouseMove { .. }
-            | WindowEvent::RawMouseMotion { .. }
-            | WindowEvent::MouseWheel { .. } => {
+            WindowEvent::MouseMove { .. } | WindowEvent::RawMouseMotion { .. } => {
                 if let Some(last) = events.last_mut()
                     && discriminant(last) == discriminant(&event)
                 {
@@ -33,11 +42,68 @@ impl EventQueue {
                     return;
                 }
             }
+            WindowEvent::MouseWheel { .. } => {
+                if let Some(last) = events.last_mut()
+                    && merge_wheel(last, &event)
+                {
+                    return;
+                }
+            }
             _ => {}
         }
         events.push(event);
     }
 
+    /// Push an event reported by the platform, followed by the events derived
+    /// from it: `Pointer` events for mouse input and emulated mouse events
+    /// for the primary touch or pen contact.
+    pub fn push_platform(&self, event: WindowEvent) {
+        let derived = lock(&self.pointers).derive(&event);
+        if derived.is_empty() {
+            self.push(event);
+            return;
+        }
+        let unit: Vec<_> = iter::once(event).chain(derived).collect();
+        for event in &unit {
+            self.record(event);
+        }
+        let mut events = lock(&self.events);
+        // A move and its derived counterpart replace the previous pair as
+        // one unit; coalescing them one by one would never find two moves
+        // next to each other.
+        let tail = events.len().saturating_sub(unit.len());
+        if events.len() >= unit.len()
+            && events
+                .get(tail..)
+                .is_some_and(|queued| queued.iter().zip(&unit).all(|(a, b)| same_motion(a, b)))
+        {
+            events.truncate(tail);
+        }
+        events.extend(unit);
+    }
+
+    /// Record before coalescing so a replay reproduces the raw stream.
+    fn record(&self, event: &WindowEvent) {
+        if let Some(recorder) = lock(&self.recorder).as_mut() {
+            recorder.record(event);
+        }
+    }
+
+    /// Start capturing every pushed event, discarding any recording in
+    /// progress.
+    pub fn start_recording(&self) {
+        *lock(&self.recorder) = Some(Recorder::new());
+    }
+
+    /// Stop capturing and return what was recorded, if recording.
+    pub fn stop_recording(&self) -> Option<EventRecording> {
+        lock(&self.recorder).take().map(Recorder::finish)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        lock(&self.recorder).is_some()
+    }
+
     pub fn pop_all(&self) -> Vec<WindowEvent> {
         let mut events = lock(&self.events);
         take(&mut *events)
@@ -50,6 +116,12 @@ impl EventQueue {
         *callbacks = Arc::new(updated);
     }
 
+    /// Routed handlers run before the flat callbacks registered with
+    /// [`Self::register_callback`], which only see events nobody stopped.
+    pub fn router(&self) -> &EventRouter {
+        &self.router
+    }
+
     pub fn process_events(&self) -> Vec<WindowEvent> {
         let events = self.pop_all();
         if events.is_empty() {
@@ -61,6 +133,9 @@ impl EventQueue {
         let callbacks = lock(&self.callbacks).clone();
 
         for event in &events {
+            if self.router.dispatch(event) == Propagation::Stop {
+                continue;
+            }
             for callback in callbacks.iter() {
                 callback(event.clone());
             }
@@ -70,8 +145,107 @@ impl EventQueue {
     }
 }
 
+/// Whether `next` supersedes `queued` as the latest position of the same
+/// pointer.
+fn same_motion(queued: &WindowEvent, next: &WindowEvent) -> bool {
+    match (queued, next) {
+        (WindowEvent::MouseMove { .. }, WindowEvent::MouseMove { .. }) => true,
+        (WindowEvent::Pointer(queued), WindowEvent::Pointer(next)) => {
+            queued.phase == PointerPhase::Move
+                && next.phase == PointerPhase::Move
+                && queued.pointer_id == next.pointer_id
+                && queued.kind == next.kind
+                && queued.buttons == next.buttons
+        }
+        _ => false,
+    }
+}
+
+/// Fold `next` into a queued wheel event of the same unit and phase by summing
+/// the deltas. Phase changes are never merged so gesture boundaries survive.
+fn merge_wheel(last: &mut WindowEvent, next: &WindowEvent) -> bool {
+    let (
+        WindowEvent::MouseWheel {
+            delta_x,
+            delta_y,
+            modifiers,
+            delta_kind,
+            phase,
+        },
+        WindowEvent::MouseWheel {
+            delta_x: next_x,
+            delta_y: next_y,
+            modifiers: next_modifiers,
+            delta_kind: next_kind,
+            phase: next_phase,
+        },
+    ) = (last, next)
+    else {
+        return false;
+    };
+    if delta_kind != next_kind || phase != next_phase {
+        return false;
+    }
+    *delta_x += next_x;
+    *delta_y += next_y;
+    *modifiers = *next_modifiers;
+    true
+}
+
 impl Default for EventQueue {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurea_foundation::{Modifiers, MouseButton, PointerKind};
+
+    fn mouse_move(x: f64, modifiers: Modifiers) -> WindowEvent {
+        WindowEvent::MouseMove {
+            x,
+            y: 0.0,
+            modifiers,
+        }
+    }
+
+    #[test]
+    fn platform_moves_collapse_with_their_pointer_events() {
+        let queue = EventQueue::new();
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Modifiers::new()
+        };
+        for i in 0..100 {
+            queue.push_platform(mouse_move(f64::from(i), ctrl));
+        }
+        let events = queue.pop_all();
+        assert_eq!(events.len(), 2, "{events:?}");
+        assert!(matches!(events[0], WindowEvent::MouseMove { x, .. } if x == 99.0));
+        let WindowEvent::Pointer(pointer) = &events[1] else {
+            unreachable!("expected a pointer event, got {:?}", events[1]);
+        };
+        assert_eq!(pointer.kind, PointerKind::Mouse);
+        assert_eq!(pointer.position.0, 99.0);
+        assert!(pointer.modifiers.ctrl);
+    }
+
+    #[test]
+    fn moves_separated_by_a_press_are_kept() {
+        let queue = EventQueue::new();
+        queue.push_platform(mouse_move(1.0, Modifiers::new()));
+        queue.push_platform(WindowEvent::MouseButton {
+            button: MouseButton::Left,
+            pressed: true,
+            modifiers: Modifiers::new(),
+            x: 1.0,
+            y: 0.0,
+            click_count: 1,
+        });
+        queue.push_platform(mouse_move(2.0, Modifiers::new()));
+        queue.push_platform(mouse_move(3.0, Modifiers::new()));
+        assert_eq!(queue.pop_all().len(), 6);
+    }
+}
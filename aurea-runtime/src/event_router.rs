@@ -0,0 +1,449 @@
+//! Event routing through a hierarchy of handler scopes.
+//!
+//! Each window's [`EventQueue`](crate::EventQueue) owns an [`EventRouter`].
+//! Scopes form a tree under [`EventRouter::root`]; an event is routed to a
+//! target scope, visiting capture handlers from the root down to the target
+//! and then bubble handlers from the target back up. Any handler can return
+//! [`Propagation::Stop`] to consume the event.
+
+use aurea_foundation::{WindowEvent, lock};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Returned by routed handlers to continue or end propagation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Propagation {
+    #[default]
+    Continue,
+    Stop,
+}
+
+/// Phase a handler is registered for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Runs on the way down, before any scope closer to the target.
+    Capture,
+    /// Runs on the way back up, after every scope closer to the target.
+    Bubble,
+}
+
+/// Where in the route a handler is being invoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPhase {
+    Capturing,
+    AtTarget,
+    Bubbling,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScopeId(u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+/// Event as seen by a routed handler.
+#[derive(Debug, Clone, Copy)]
+pub struct RoutedEvent<'a> {
+    pub event: &'a WindowEvent,
+    /// Scope the event was routed to.
+    pub target: ScopeId,
+    /// Scope whose handler is running.
+    pub current: ScopeId,
+    pub phase: EventPhase,
+}
+
+pub type RoutedHandler = Arc<dyn Fn(&RoutedEvent<'_>) -> Propagation + Send + Sync>;
+type TargetResolver = Arc<dyn Fn(&WindowEvent) -> Option<ScopeId> + Send + Sync>;
+
+#[derive(Clone)]
+struct HandlerEntry {
+    id: HandlerId,
+    phase: Phase,
+    priority: i32,
+    handler: RoutedHandler,
+}
+
+#[derive(Clone, Default)]
+struct RouterState {
+    /// Parent of every live scope; the root maps to `None`.
+    parents: HashMap<ScopeId, Option<ScopeId>>,
+    /// Handlers per scope, highest priority first, then registration order.
+    handlers: HashMap<ScopeId, Vec<HandlerEntry>>,
+    focus: Option<ScopeId>,
+    resolver: Option<TargetResolver>,
+}
+
+struct RouterShared {
+    state: Mutex<Arc<RouterState>>,
+    next_id: AtomicU64,
+}
+
+impl RouterShared {
+    fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Copy-on-write update; readers keep the snapshot they already took.
+    fn update<R>(&self, f: impl FnOnce(&mut RouterState) -> R) -> R {
+        let mut state = lock(&self.state);
+        let mut updated = (**state).clone();
+        let result = f(&mut updated);
+        *state = Arc::new(updated);
+        result
+    }
+
+    fn remove_handler(&self, id: HandlerId) -> bool {
+        self.update(|state| {
+            let mut removed = false;
+            for list in state.handlers.values_mut() {
+                let before = list.len();
+                list.retain(|entry| entry.id != id);
+                removed |= list.len() != before;
+            }
+            removed
+        })
+    }
+}
+
+/// Unregisters its handler when [`EventHandlerHandle::unregister`] is called.
+/// Dropping the handle leaves the handler registered.
+#[derive(Clone)]
+pub struct EventHandlerHandle {
+    id: HandlerId,
+    router: Weak<RouterShared>,
+}
+
+impl EventHandlerHandle {
+    pub fn id(&self) -> HandlerId {
+        self.id
+    }
+
+    /// Remove the handler. Returns `false` if it was already gone.
+    pub fn unregister(&self) -> bool {
+        self.router
+            .upgrade()
+            .is_some_and(|router| router.remove_handler(self.id))
+    }
+}
+
+pub struct EventRouter {
+    shared: Arc<RouterShared>,
+    root: ScopeId,
+}
+
+impl EventRouter {
+    pub fn new() -> Self {
+        let root = ScopeId(0);
+        let mut state = RouterState::default();
+        state.parents.insert(root, None);
+        Self {
+            shared: Arc::new(RouterShared {
+                state: Mutex::new(Arc::new(state)),
+                next_id: AtomicU64::new(1),
+            }),
+            root,
+        }
+    }
+
+    /// The window-level scope every other scope descends from.
+    pub fn root(&self) -> ScopeId {
+        self.root
+    }
+
+    /// Create a child scope. Returns `None` if `parent` no longer exists.
+    pub fn create_scope(&self, parent: ScopeId) -> Option<ScopeId> {
+        let id = ScopeId(self.shared.next_id());
+        self.shared.update(|state| {
+            state.parents.contains_key(&parent).then(|| {
+                state.parents.insert(id, Some(parent));
+                id
+            })
+        })
+    }
+
+    /// Remove a scope, its descendants and all their handlers. The root
+    /// cannot be removed.
+    pub fn remove_scope(&self, scope: ScopeId) {
+        if scope == self.root {
+            return;
+        }
+        self.shared.update(|state| {
+            let doomed: Vec<ScopeId> = state
+                .parents
+                .keys()
+                .copied()
+                .filter(|candidate| is_descendant(state, *candidate, scope))
+                .collect();
+            for id in doomed {
+                state.parents.remove(&id);
+                state.handlers.remove(&id);
+                if state.focus == Some(id) {
+                    state.focus = None;
+                }
+            }
+        });
+    }
+
+    /// Register `handler` on `scope` for `phase`. Higher `priority` runs
+    /// first within the same scope and phase.
+    pub fn add_handler<F>(
+        &self,
+        scope: ScopeId,
+        phase: Phase,
+        priority: i32,
+        handler: F,
+    ) -> EventHandlerHandle
+    where
+        F: Fn(&RoutedEvent<'_>) -> Propagation + Send + Sync + 'static,
+    {
+        let id = HandlerId(self.shared.next_id());
+        let entry = HandlerEntry {
+            id,
+            phase,
+            priority,
+            handler: Arc::new(handler),
+        };
+        self.shared.update(|state| {
+            if !state.parents.contains_key(&scope) {
+                return;
+            }
+            let list = state.handlers.entry(scope).or_default();
+            // Insert after every entry of equal or higher priority so ties
+            // keep registration order.
+            let at = list.partition_point(|other| other.priority >= priority);
+            list.insert(at, entry);
+        });
+        EventHandlerHandle {
+            id,
+            router: Arc::downgrade(&self.shared),
+        }
+    }
+
+    /// Bubble-phase handler with default priority.
+    pub fn on_bubble<F>(&self, scope: ScopeId, handler: F) -> EventHandlerHandle
+    where
+        F: Fn(&RoutedEvent<'_>) -> Propagation + Send + Sync + 'static,
+    {
+        self.add_handler(scope, Phase::Bubble, 0, handler)
+    }
+
+    /// Capture-phase handler with default priority.
+    pub fn on_capture<F>(&self, scope: ScopeId, handler: F) -> EventHandlerHandle
+    where
+        F: Fn(&RoutedEvent<'_>) -> Propagation + Send + Sync + 'static,
+    {
+        self.add_handler(scope, Phase::Capture, 0, handler)
+    }
+
+    pub fn unregister(&self, id: HandlerId) -> bool {
+        self.shared.remove_handler(id)
+    }
+
+    /// Scope that keyboard and text events are routed to; `None` routes
+    /// them to the root.
+    pub fn set_focus(&self, scope: Option<ScopeId>) {
+        self.shared.update(|state| {
+            state.focus = scope.filter(|id| state.parents.contains_key(id));
+        });
+    }
+
+    pub fn focus(&self) -> Option<ScopeId> {
+        lock(&self.shared.state).focus
+    }
+
+    /// Pick the target scope for events that are not keyboard or text input,
+    /// typically by hit-testing pointer coordinates. Returning `None` or an
+    /// unknown scope routes to the root.
+    pub fn set_target_resolver<F>(&self, resolver: F)
+    where
+        F: Fn(&WindowEvent) -> Option<ScopeId> + Send + Sync + 'static,
+    {
+        self.shared
+            .update(|state| state.resolver = Some(Arc::new(resolver)));
+    }
+
+    /// Route `event` to the focused scope (keyboard/text/IME), the resolver's
+    /// choice (everything else), or the root.
+    pub fn dispatch(&self, event: &WindowEvent) -> Propagation {
+        let state = lock(&self.shared.state).clone();
+        let target = match event {
+            WindowEvent::KeyInput { .. }
+            | WindowEvent::TextInput { .. }
+            | WindowEvent::ImeEnabled
+            | WindowEvent::ImePreedit { .. }
+            | WindowEvent::ImeCommit { .. }
+            | WindowEvent::ImeDisabled => state.focus,
+            _ => state.resolver.as_ref().and_then(|resolve| resolve(event)),
+        };
+        self.route(&state, target.unwrap_or(self.root), event)
+    }
+
+    /// Route `event` to an explicit target scope.
+    pub fn dispatch_to(&self, target: ScopeId, event: &WindowEvent) -> Propagation {
+        let state = lock(&self.shared.state).clone();
+        self.route(&state, target, event)
+    }
+
+    fn route(&self, state: &RouterState, target: ScopeId, event: &WindowEvent) -> Propagation {
+        let target = if state.parents.contains_key(&target) {
+            target
+        } else {
+            self.root
+        };
+        let path = path_to(state, target);
+        let capture = path.iter().map(|scope| (*scope, Phase::Capture));
+        let bubble = path.iter().rev().map(|scope| (*scope, Phase::Bubble));
+
+        for (scope, phase) in capture.chain(bubble) {
+            let routed = RoutedEvent {
+                event,
+                target,
+                current: scope,
+                phase: event_phase(scope, target, phase),
+            };
+            if run_handlers(state, &routed, phase) == Propagation::Stop {
+                return Propagation::Stop;
+            }
+        }
+        Propagation::Continue
+    }
+}
+
+impl Default for EventRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn event_phase(scope: ScopeId, target: ScopeId, phase: Phase) -> EventPhase {
+    match phase {
+        _ if scope == target => EventPhase::AtTarget,
+        Phase::Capture => EventPhase::Capturing,
+        Phase::Bubble => EventPhase::Bubbling,
+    }
+}
+
+fn run_handlers(state: &RouterState, routed: &RoutedEvent<'_>, phase: Phase) -> Propagation {
+    let Some(list) = state.handlers.get(&routed.current) else {
+        return Propagation::Continue;
+    };
+    for entry in list.iter().filter(|entry| entry.phase == phase) {
+        if (entry.handler)(routed) == Propagation::Stop {
+            return Propagation::Stop;
+        }
+    }
+    Propagation::Continue
+}
+
+/// Scopes from the root down to `target`, inclusive.
+fn path_to(state: &RouterState, target: ScopeId) -> Vec<ScopeId> {
+    let mut path = vec![target];
+    let mut current = target;
+    while let Some(Some(parent)) = state.parents.get(&current) {
+        path.push(*parent);
+        current = *parent;
+    }
+    path.reverse();
+    path
+}
+
+fn is_descendant(state: &RouterState, candidate: ScopeId, ancestor: ScopeId) -> bool {
+    path_to(state, candidate).contains(&ancestor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    fn record(
+        log: &Log,
+        label: &'static str,
+        result: Propagation,
+    ) -> impl Fn(&RoutedEvent<'_>) -> Propagation + Send + Sync + 'static {
+        let log = log.clone();
+        move |_| {
+            lock(&log).push(label.to_owned());
+            result
+        }
+    }
+
+    #[test]
+    fn capture_runs_top_down_then_bubble_bottom_up() {
+        let router = EventRouter::new();
+        let log: Log = Arc::default();
+        let root = router.root();
+        let child = router.create_scope(root).unwrap();
+        let leaf = router.create_scope(child).unwrap();
+        for (scope, name) in [(root, "root"), (child, "child"), (leaf, "leaf")] {
+            router.on_capture(scope, record(&log, name, Propagation::Continue));
+            router.on_bubble(scope, record(&log, name, Propagation::Continue));
+        }
+
+        let result = router.dispatch_to(leaf, &WindowEvent::Focused);
+        assert_eq!(result, Propagation::Continue);
+        assert_eq!(
+            *lock(&log),
+            ["root", "child", "leaf", "leaf", "child", "root"]
+        );
+    }
+
+    #[test]
+    fn higher_priority_runs_first_and_stop_consumes() {
+        let router = EventRouter::new();
+        let log: Log = Arc::default();
+        let root = router.root();
+        router.add_handler(
+            root,
+            Phase::Bubble,
+            0,
+            record(&log, "menu", Propagation::Continue),
+        );
+        router.add_handler(
+            root,
+            Phase::Bubble,
+            10,
+            record(&log, "canvas", Propagation::Stop),
+        );
+
+        let result = router.dispatch(&WindowEvent::CloseRequested);
+        assert_eq!(result, Propagation::Stop);
+        assert_eq!(*lock(&log), ["canvas"]);
+    }
+
+    #[test]
+    fn key_events_go_to_focus_and_handles_unregister() {
+        let router = EventRouter::new();
+        let log: Log = Arc::default();
+        let field = router.create_scope(router.root()).unwrap();
+        let handle = router.on_bubble(field, record(&log, "field", Propagation::Continue));
+        router.set_focus(Some(field));
+
+        let key = WindowEvent::TextInput {
+            text: "a".to_owned(),
+        };
+        router.dispatch(&key);
+        assert!(handle.unregister());
+        assert!(!handle.unregister());
+        router.dispatch(&key);
+        assert_eq!(*lock(&log), ["field"]);
+    }
+
+    #[test]
+    fn removing_a_scope_drops_descendants_and_focus() {
+        let router = EventRouter::new();
+        let log: Log = Arc::default();
+        let panel = router.create_scope(router.root()).unwrap();
+        let button = router.create_scope(panel).unwrap();
+        router.on_bubble(button, record(&log, "button", Propagation::Continue));
+        router.set_focus(Some(button));
+
+        router.remove_scope(panel);
+        assert_eq!(router.focus(), None);
+        assert!(router.create_scope(button).is_none());
+        router.dispatch_to(button, &WindowEvent::Focused);
+        assert!(lock(&log).is_empty());
+    }
+}
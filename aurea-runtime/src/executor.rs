@@ -0,0 +1,225 @@
+//! Executor that polls futures on the UI thread.
+//!
+//! There is no executor thread: wake-ups go through [`FrameScheduler::wake`],
+//! and the platform pump polls ready tasks from `ng_process_frames` via
+//! [`Executor::run_ready`]. Futures and posted closures may be spawned from
+//! any thread but always run on the thread that drives the event loop.
+
+use crate::frame_queue::FrameScheduler;
+use aurea_foundation::lock;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+type PostedFn = Box<dyn FnOnce() + Send + 'static>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+/// A spawned future. The slot is emptied while the task is being polled so a
+/// wake-up from inside `poll` never deadlocks on the task's own lock.
+type TaskSlot = Arc<Mutex<Option<TaskFuture>>>;
+
+static TASK_COUNTER: AtomicU64 = AtomicU64::new(0);
+static TASKS: LazyLock<Mutex<HashMap<TaskId, TaskSlot>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static READY_TASKS: LazyLock<Mutex<VecDeque<TaskId>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+static POSTED: LazyLock<Mutex<VecDeque<PostedFn>>> = LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+struct TaskWaker(TaskId);
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        Executor::mark_ready(self.0);
+    }
+}
+
+pub struct Executor;
+
+impl Executor {
+    /// Spawn a future onto the UI thread. It is first polled on the next
+    /// `ng_process_frames` pass, then again whenever its waker fires.
+    pub fn spawn<F>(future: F) -> TaskId
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let id = TaskId(TASK_COUNTER.fetch_add(1, Ordering::Relaxed));
+        lock(&TASKS).insert(id, Arc::new(Mutex::new(Some(Box::pin(future)))));
+        Self::mark_ready(id);
+        id
+    }
+
+    /// Run `f` once on the UI thread during the next loop pass.
+    pub fn post<F>(f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        lock(&POSTED).push_back(Box::new(f));
+        FrameScheduler::wake();
+    }
+
+    /// Drop a spawned task without polling it again. Returns `false` if the
+    /// task already finished or was cancelled.
+    pub fn cancel(id: TaskId) -> bool {
+        lock(&TASKS).remove(&id).is_some()
+    }
+
+    /// Number of spawned tasks that have not completed yet.
+    pub fn pending_tasks() -> usize {
+        lock(&TASKS).len()
+    }
+
+    /// Whether posted closures or woken tasks are waiting for a loop pass.
+    pub fn has_ready() -> bool {
+        !lock(&READY_TASKS).is_empty() || !lock(&POSTED).is_empty()
+    }
+
+    /// Run posted closures, then poll every task that was woken before this
+    /// call. Tasks woken while polling run on the following pass so one busy
+    /// future cannot starve the frame.
+    pub fn run_ready() {
+        let posted: Vec<PostedFn> = lock(&POSTED).drain(..).collect();
+        for f in posted {
+            f();
+        }
+
+        let ready: Vec<TaskId> = lock(&READY_TASKS).drain(..).collect();
+        for id in ready {
+            Self::poll_task(id);
+        }
+    }
+
+    fn poll_task(id: TaskId) {
+        let Some(slot) = lock(&TASKS).get(&id).cloned() else {
+            return; // finished or cancelled
+        };
+        let Some(mut future) = lock(&slot).take() else {
+            return; // already being polled further up the stack
+        };
+
+        let waker = Waker::from(Arc::new(TaskWaker(id)));
+        let mut cx = Context::from_waker(&waker);
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(()) => {
+                lock(&TASKS).remove(&id);
+            }
+            Poll::Pending => {
+                *lock(&slot) = Some(future);
+            }
+        }
+    }
+
+    fn mark_ready(id: TaskId) {
+        {
+            let mut ready = lock(&READY_TASKS);
+            if ready.contains(&id) {
+                return;
+            }
+            ready.push_back(id);
+        }
+        FrameScheduler::wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::MutexGuard;
+    use std::sync::atomic::AtomicUsize;
+
+    static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+    fn reset() -> MutexGuard<'static, ()> {
+        let guard = lock(&TEST_LOCK);
+        lock(&TASKS).clear();
+        lock(&READY_TASKS).clear();
+        lock(&POSTED).clear();
+        guard
+    }
+
+    /// Completes on its second poll, waking itself after the first.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn spawned_task_runs_on_run_ready() {
+        let _guard = reset();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let h = hits.clone();
+        Executor::spawn(async move {
+            h.fetch_add(1, Ordering::Relaxed);
+        });
+
+        assert!(Executor::has_ready());
+        assert_eq!(hits.load(Ordering::Relaxed), 0);
+        Executor::run_ready();
+        assert_eq!(hits.load(Ordering::Relaxed), 1);
+        assert_eq!(Executor::pending_tasks(), 0);
+    }
+
+    #[test]
+    fn self_woken_task_resumes_on_next_pass() {
+        let _guard = reset();
+        let done = Arc::new(AtomicUsize::new(0));
+        let d = done.clone();
+        Executor::spawn(async move {
+            YieldOnce(false).await;
+            d.fetch_add(1, Ordering::Relaxed);
+        });
+
+        Executor::run_ready();
+        assert_eq!(done.load(Ordering::Relaxed), 0);
+        assert!(Executor::has_ready());
+        Executor::run_ready();
+        assert_eq!(done.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn cancelled_task_is_not_polled() {
+        let _guard = reset();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let h = hits.clone();
+        let id = Executor::spawn(async move {
+            h.fetch_add(1, Ordering::Relaxed);
+        });
+
+        assert!(Executor::cancel(id));
+        Executor::run_ready();
+        assert_eq!(hits.load(Ordering::Relaxed), 0);
+        assert!(!Executor::cancel(id));
+    }
+
+    #[test]
+    fn posted_closures_run_in_order() {
+        let _guard = reset();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        for n in 0..3 {
+            let log = log.clone();
+            Executor::post(move || lock(&log).push(n));
+        }
+        Executor::run_ready();
+        assert_eq!(*lock(&log), vec![0, 1, 2]);
+        assert!(!Executor::has_ready());
+    }
+}
@@ -1,5 +1,6 @@
 //! Frame queue for scheduling and processing redraws.
 
+use crate::frame_stats::{DEFAULT_FRAME_INTERVAL, FrameStats, FrameStatsRecorder};
 use aurea_foundation::{AureaError, lock};
 use std::collections::{HashMap, HashSet};
 use std::os::raw::c_void;
@@ -9,8 +10,13 @@ use std::time::{Duration, Instant};
 
 type CanvasRedrawCallback = Arc<dyn Fn() -> Result<(), AureaError> + Send + Sync>;
 type FrameCallback = Arc<dyn Fn() + Send + Sync + 'static>;
-type TickerFn = Arc<Mutex<dyn FnMut(FrameInfo) -> bool + Send>>;
+type TickerFn = Arc<Mutex<Ticker>>;
 type RequestFrameHook = Option<Box<dyn Fn() + Send + Sync>>;
+type RequestFrameAfterHook = Option<Box<dyn Fn(Duration) + Send + Sync>>;
+
+/// Paced tickers run this much early rather than waiting a whole extra frame
+/// for a platform timer that fired slightly ahead of the deadline.
+const PACING_SLACK: Duration = Duration::from_millis(1);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FrameCallbackId(u64);
@@ -23,10 +29,31 @@ pub struct TickerId(u64);
 #[derive(Debug, Clone, Copy)]
 pub struct FrameInfo {
     pub time: Instant,
+    /// Time since this ticker last ran (the frame delta for unpaced tickers).
     pub delta: Duration,
     pub frame: u64,
 }
 
+struct Ticker {
+    callback: Box<dyn FnMut(FrameInfo) -> bool + Send>,
+    /// Per-ticker pacing; `None` falls back to the global target interval.
+    interval: Option<Duration>,
+    last_run: Option<Instant>,
+}
+
+impl Ticker {
+    /// When this ticker next wants to run; `None` means every frame.
+    fn due_at(&self, global: Option<Duration>) -> Option<Instant> {
+        let interval = self.interval.or(global)?;
+        self.last_run.map(|last| last + interval)
+    }
+
+    fn is_due(&self, global: Option<Duration>, now: Instant) -> bool {
+        self.due_at(global)
+            .is_none_or(|due| due <= now + PACING_SLACK)
+    }
+}
+
 static FRAME_SCHEDULED: AtomicBool = AtomicBool::new(false);
 static ALL_CANVASES_SCHEDULED: AtomicBool = AtomicBool::new(false);
 static CANVAS_REGISTRY: LazyLock<Mutex<Arc<HashMap<usize, CanvasRedrawCallback>>>> =
@@ -42,6 +69,15 @@ static TICKERS: LazyLock<Mutex<Arc<HashMap<TickerId, TickerFn>>>> =
 static FRAME_COUNTER: AtomicU64 = AtomicU64::new(0);
 static LAST_FRAME_TIME: LazyLock<Mutex<Instant>> = LazyLock::new(|| Mutex::new(Instant::now()));
 static REQUEST_FRAME_HOOK: LazyLock<Mutex<RequestFrameHook>> = LazyLock::new(|| Mutex::new(None));
+static REQUEST_FRAME_AFTER_HOOK: LazyLock<Mutex<RequestFrameAfterHook>> =
+    LazyLock::new(|| Mutex::new(None));
+/// Earliest pending delayed wake-up. A frame pass runs once this passes even
+/// if nothing called `schedule*` in between.
+static NEXT_WAKE: LazyLock<Mutex<Option<Instant>>> = LazyLock::new(|| Mutex::new(None));
+static TARGET_FRAME_INTERVAL: LazyLock<Mutex<Option<Duration>>> =
+    LazyLock::new(|| Mutex::new(None));
+static FRAME_STATS: LazyLock<Mutex<FrameStatsRecorder>> =
+    LazyLock::new(|| Mutex::new(FrameStatsRecorder::default()));
 
 pub struct FrameScheduler;
 
@@ -50,12 +86,66 @@ impl FrameScheduler {
         *lock(&REQUEST_FRAME_HOOK) = Some(Box::new(f));
     }
 
+    /// Install the hook used to wake the platform loop after a delay. Without
+    /// one, delayed wake-ups fall back to the immediate request-frame hook,
+    /// asked again on every pass until the wake-up is due.
+    pub fn set_request_frame_after_hook<F: Fn(Duration) + Send + Sync + 'static>(f: F) {
+        *lock(&REQUEST_FRAME_AFTER_HOOK) = Some(Box::new(f));
+    }
+
     fn notify_platform() {
         if let Some(hook) = lock(&REQUEST_FRAME_HOOK).as_ref() {
             hook();
         }
     }
 
+    /// Ask the platform for a frame pass at `at`, keeping the earliest of all
+    /// pending wake-ups.
+    pub(crate) fn schedule_wake(at: Instant) {
+        let hook = lock(&REQUEST_FRAME_AFTER_HOOK);
+        {
+            let mut next = lock(&NEXT_WAKE);
+            let earlier = next.is_none_or(|pending| at < pending);
+            if earlier {
+                *next = Some(at);
+            } else if hook.is_some() {
+                return;
+            }
+        }
+        let delay = at.saturating_duration_since(Instant::now());
+        match hook.as_ref() {
+            Some(hook) => hook(delay),
+            None => {
+                drop(hook);
+                Self::notify_platform();
+            }
+        }
+    }
+
+    /// Clears the pending wake-up if it has passed and reports whether it had.
+    fn take_due_wake(now: Instant) -> bool {
+        let mut next = lock(&NEXT_WAKE);
+        if next.is_some_and(|at| at <= now + PACING_SLACK) {
+            *next = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether a delayed wake-up is pending with no hook to time it, so the
+    /// platform loop has to keep running passes until it is due.
+    pub fn polls_for_wake() -> bool {
+        let hook = lock(&REQUEST_FRAME_AFTER_HOOK);
+        hook.is_none() && lock(&NEXT_WAKE).is_some()
+    }
+
+    /// Wake the platform loop for one `ng_process_frames` pass without
+    /// scheduling a redraw. Used by the executor and main-thread channels.
+    pub fn wake() {
+        Self::notify_platform();
+    }
+
     pub fn schedule() {
         ALL_CANVASES_SCHEDULED.store(true, Ordering::Relaxed);
         FRAME_SCHEDULED.store(true, Ordering::Relaxed);
@@ -91,6 +181,7 @@ impl FrameScheduler {
         updated.remove(&(handle as usize));
         *registry = Arc::new(updated);
         lock(&PENDING_CANVASES).remove(&(handle as usize));
+        lock(&FRAME_STATS).remove_canvas(handle as usize);
     }
 
     pub fn register_frame_callback<F>(callback: F) -> FrameCallbackId
@@ -118,13 +209,37 @@ impl FrameScheduler {
     /// same frame. Canvas-specific invalidation should call [`Self::schedule_canvas`]
     /// from inside the ticker.
     pub fn register_ticker<F>(ticker: F) -> TickerId
+    where
+        F: FnMut(FrameInfo) -> bool + Send + 'static,
+    {
+        Self::insert_ticker(ticker, None)
+    }
+
+    /// Register a ticker that runs at most once per `interval`, e.g.
+    /// `Duration::from_secs(1) / 30` to cap an animation at 30 fps.
+    /// Overrides the global [`Self::set_target_frame_interval`] for this ticker.
+    pub fn register_ticker_with_interval<F>(interval: Duration, ticker: F) -> TickerId
+    where
+        F: FnMut(FrameInfo) -> bool + Send + 'static,
+    {
+        Self::insert_ticker(ticker, Some(interval))
+    }
+
+    fn insert_ticker<F>(ticker: F, interval: Option<Duration>) -> TickerId
     where
         F: FnMut(FrameInfo) -> bool + Send + 'static,
     {
         let id = TickerId(TICKER_COUNTER.fetch_add(1, Ordering::Relaxed));
         let mut tickers = lock(&TICKERS);
         let mut updated = (**tickers).clone();
-        updated.insert(id, Arc::new(Mutex::new(ticker)));
+        updated.insert(
+            id,
+            Arc::new(Mutex::new(Ticker {
+                callback: Box::new(ticker),
+                interval,
+                last_run: None,
+            })),
+        );
         *tickers = Arc::new(updated);
         // Pump-only arm: don't set ALL_CANVASES_SCHEDULED — one active ticker
         // must not force a full repaint of every canvas every frame.
@@ -140,16 +255,73 @@ impl FrameScheduler {
         *tickers = Arc::new(updated);
     }
 
-    /// Runs every registered ticker once, unregistering any that return `false`.
+    /// Change (or clear, with `None`) the pacing interval of a registered ticker.
+    /// Must not be called from inside that ticker's own callback.
+    pub fn set_ticker_interval(id: TickerId, interval: Option<Duration>) {
+        let ticker = lock(&TICKERS).get(&id).cloned();
+        if let Some(ticker) = ticker {
+            lock(&ticker).interval = interval;
+            FRAME_SCHEDULED.store(true, Ordering::Relaxed);
+            Self::notify_platform();
+        }
+    }
+
+    /// Cap every ticker without its own interval to one run per `interval`.
+    ///
+    /// Only tickers (and therefore `Canvas::animate` animations) are paced;
+    /// redraws requested by input or `schedule*` still run on the next frame.
+    /// The interval is also the budget used for missed-deadline accounting in
+    /// [`Self::stats`]. `None` restores running on every platform frame.
+    pub fn set_target_frame_interval(interval: Option<Duration>) {
+        *lock(&TARGET_FRAME_INTERVAL) = interval;
+        if !lock(&TICKERS).is_empty() {
+            FRAME_SCHEDULED.store(true, Ordering::Relaxed);
+            Self::notify_platform();
+        }
+    }
+
+    pub fn target_frame_interval() -> Option<Duration> {
+        *lock(&TARGET_FRAME_INTERVAL)
+    }
+
+    /// Rolling frame-timing statistics: frame time percentiles, missed
+    /// deadlines and per-canvas render/present times.
+    pub fn stats() -> FrameStats {
+        let deadline = Self::target_frame_interval().unwrap_or(DEFAULT_FRAME_INTERVAL);
+        lock(&FRAME_STATS).snapshot(deadline)
+    }
+
+    pub fn reset_stats() {
+        *lock(&FRAME_STATS) = FrameStatsRecorder::default();
+    }
+
+    /// Record how long a canvas spent rendering its frame and handing it to
+    /// the platform. Called by the canvas redraw path; shows up in [`Self::stats`].
+    pub fn record_canvas_timing(handle: *mut c_void, render: Duration, present: Duration) {
+        lock(&FRAME_STATS).record_canvas(handle as usize, render, present);
+    }
+
+    /// Runs every registered ticker that is due, unregistering any that return `false`.
     /// Locks are released before invoking user code: ticker callbacks may
     /// re-register canvases or other tickers.
     fn run_tickers(frame_info: FrameInfo) {
         let tickers = lock(&TICKERS).clone();
+        let global = Self::target_frame_interval();
         let mut to_remove = Vec::new();
         for (id, ticker_fn) in tickers.iter() {
             let keep = {
-                let mut f = ticker_fn.lock().expect("ticker mutex not poisoned");
-                f(frame_info)
+                let mut ticker = ticker_fn.lock().expect("ticker mutex not poisoned");
+                if !ticker.is_due(global, frame_info.time) {
+                    continue;
+                }
+                let delta = ticker.last_run.map_or(frame_info.delta, |last| {
+                    frame_info.time.duration_since(last)
+                });
+                ticker.last_run = Some(frame_info.time);
+                (ticker.callback)(FrameInfo {
+                    delta,
+                    ..frame_info
+                })
             };
             if !keep {
                 to_remove.push(*id);
@@ -160,14 +332,43 @@ impl FrameScheduler {
         }
     }
 
-    pub fn process_frames() -> Result<(), AureaError> {
-        if !Self::take() {
-            return Ok(());
+    /// Re-arms the pump for the remaining tickers: immediately if any is due
+    /// every frame, otherwise as a delayed wake-up at the earliest deadline.
+    fn rearm_tickers(now: Instant) {
+        // Check the live map — not the snapshot — so finished tickers don't waste a frame.
+        let tickers = lock(&TICKERS).clone();
+        let global = Self::target_frame_interval();
+        let mut next_due: Option<Instant> = None;
+        for ticker_fn in tickers.values() {
+            match lock(ticker_fn).due_at(global) {
+                Some(due) if due > now + PACING_SLACK => {
+                    next_due = Some(next_due.map_or(due, |next| next.min(due)));
+                }
+                _ => {
+                    FRAME_SCHEDULED.store(true, Ordering::Relaxed);
+                    Self::notify_platform();
+                    return;
+                }
+            }
         }
+        if let Some(due) = next_due {
+            Self::schedule_wake(due);
+        }
+    }
 
+    pub fn process_frames() -> Result<(), AureaError> {
         // Sample frame time once — tickers receive it so draw callbacks never
         // read the wall clock themselves (required by the determinism contract).
         let now = Instant::now();
+        let wake_due = Self::take_due_wake(now);
+        let scheduled = Self::take();
+        if !scheduled && !wake_due {
+            if Self::polls_for_wake() {
+                Self::notify_platform();
+            }
+            return Ok(());
+        }
+
         let delta = {
             let mut last = lock(&LAST_FRAME_TIME);
             let d = now.duration_since(*last);
@@ -181,19 +382,25 @@ impl FrameScheduler {
             frame,
         };
 
-        // === Tickers run before canvas redraws so mutations are visible this frame ===
+        // === Timers and tickers run before canvas redraws so mutations are visible this frame ===
+        Self::run_timers(now);
         Self::run_tickers(frame_info);
 
         // === Canvas redraws ===
-        Self::redraw_canvases();
+        // A pass woken only for timers or paced tickers repaints nothing
+        // unless one of them scheduled a redraw.
+        if scheduled | Self::take() {
+            Self::redraw_canvases();
+        }
 
         // Re-arm pump if tickers remain after removal (pump-only, not all-canvas).
-        // Check the live map — not the snapshot — so finished tickers don't waste a frame.
-        // scheduler.rs calls ng_platform_frame_idle() when !is_scheduled().
-        if !lock(&TICKERS).is_empty() {
-            FRAME_SCHEDULED.store(true, Ordering::Relaxed);
-            Self::notify_platform();
-        }
+        // scheduler.rs calls ng_platform_frame_idle() when !is_scheduled(), so
+        // tickers that are only due later leave the platform idle until the wake.
+        Self::rearm_tickers(now);
+        Self::schedule_timer_wake();
+
+        let deadline = Self::target_frame_interval().unwrap_or(DEFAULT_FRAME_INTERVAL);
+        lock(&FRAME_STATS).record_frame(now.elapsed(), deadline);
 
         Ok(())
     }
@@ -245,13 +452,15 @@ impl FrameScheduler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::LazyLock;
+    use crate::timer::{Debouncer, reset_timers};
     use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{LazyLock, MutexGuard};
+    use std::thread;
 
     static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
     struct TestGuard {
-        _guard: std::sync::MutexGuard<'static, ()>,
+        _guard: MutexGuard<'static, ()>,
     }
 
     impl TestGuard {
@@ -280,6 +489,11 @@ mod tests {
         *lock(&TICKERS) = Arc::new(HashMap::new());
         *lock(&LAST_FRAME_TIME) = Instant::now();
         *lock(&REQUEST_FRAME_HOOK) = None;
+        *lock(&REQUEST_FRAME_AFTER_HOOK) = None;
+        *lock(&NEXT_WAKE) = None;
+        *lock(&TARGET_FRAME_INTERVAL) = None;
+        *lock(&FRAME_STATS) = FrameStatsRecorder::default();
+        reset_timers();
     }
 
     fn handle(id: usize) -> *mut c_void {
@@ -422,4 +636,224 @@ mod tests {
             "ticker must not fire after unregister"
         );
     }
+
+    #[test]
+    fn paced_ticker_waits_for_its_interval() {
+        let _guard = TestGuard::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = count.clone();
+        let wakes = Arc::new(Mutex::new(Vec::new()));
+        let w = wakes.clone();
+        FrameScheduler::set_request_frame_after_hook(move |delay| lock(&w).push(delay));
+
+        FrameScheduler::register_ticker_with_interval(Duration::from_secs(60), move |_| {
+            c.fetch_add(1, Ordering::Relaxed);
+            true
+        });
+
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(
+            !FrameScheduler::is_scheduled(),
+            "a paced ticker must leave the pump idle until its deadline"
+        );
+        assert_eq!(lock(&wakes).len(), 1, "a delayed wake-up must be requested");
+
+        FrameScheduler::schedule();
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(
+            count.load(Ordering::Relaxed),
+            1,
+            "ticker must not run again before its interval elapses"
+        );
+    }
+
+    #[test]
+    fn paced_ticker_without_timed_wake_polls_until_due() {
+        let _guard = TestGuard::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = count.clone();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let r = requests.clone();
+        FrameScheduler::set_request_frame_hook(move || {
+            r.fetch_add(1, Ordering::Relaxed);
+        });
+
+        FrameScheduler::register_ticker_with_interval(Duration::from_millis(5), move |_| {
+            c.fetch_add(1, Ordering::Relaxed);
+            true
+        });
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(FrameScheduler::polls_for_wake());
+
+        // Each early pass asks for another instead of leaving the loop idle.
+        let before = requests.load(Ordering::Relaxed);
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(requests.load(Ordering::Relaxed) > before);
+
+        thread::sleep(Duration::from_millis(10));
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn global_target_interval_paces_unpaced_tickers() {
+        let _guard = TestGuard::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = count.clone();
+        FrameScheduler::set_target_frame_interval(Some(Duration::from_secs(60)));
+        FrameScheduler::register_ticker(move |_| {
+            c.fetch_add(1, Ordering::Relaxed);
+            true
+        });
+
+        for _ in 0..3 {
+            FrameScheduler::schedule();
+            FrameScheduler::process_frames().unwrap();
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        FrameScheduler::set_target_frame_interval(None);
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn stats_count_frames_and_canvas_timings() {
+        let _guard = TestGuard::new();
+        FrameScheduler::register_canvas(
+            handle(5),
+            Arc::new(|| {
+                FrameScheduler::record_canvas_timing(
+                    handle(5),
+                    Duration::from_millis(3),
+                    Duration::from_millis(1),
+                );
+                Ok(())
+            }),
+        );
+
+        for _ in 0..2 {
+            FrameScheduler::schedule_canvas(handle(5));
+            FrameScheduler::process_frames().unwrap();
+        }
+
+        let stats = FrameScheduler::stats();
+        assert_eq!(stats.frames, 2);
+        assert_eq!(stats.deadline, DEFAULT_FRAME_INTERVAL);
+        assert_eq!(stats.canvases.len(), 1);
+        assert_eq!(stats.canvases[0].frames, 2);
+        assert_eq!(stats.canvases[0].render_p50, Duration::from_millis(3));
+
+        FrameScheduler::unregister_canvas(handle(5));
+        assert!(FrameScheduler::stats().canvases.is_empty());
+    }
+
+    #[test]
+    fn after_fires_once_without_repainting_canvases() {
+        let _guard = TestGuard::new();
+        let fired = Arc::new(AtomicUsize::new(0));
+        let redraws = Arc::new(AtomicUsize::new(0));
+        let f = fired.clone();
+        let r = redraws.clone();
+        let wakes = Arc::new(Mutex::new(Vec::new()));
+        let w = wakes.clone();
+        FrameScheduler::set_request_frame_after_hook(move |delay| lock(&w).push(delay));
+        FrameScheduler::register_canvas(
+            handle(1),
+            Arc::new(move || {
+                r.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }),
+        );
+
+        FrameScheduler::after(Duration::from_millis(5), move || {
+            f.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(lock(&wakes).len(), 1, "a delayed wake-up must be requested");
+
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 0);
+
+        thread::sleep(Duration::from_millis(10));
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 1);
+        assert_eq!(redraws.load(Ordering::Relaxed), 0);
+
+        FrameScheduler::schedule();
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(
+            fired.load(Ordering::Relaxed),
+            1,
+            "one-shot timers fire once"
+        );
+    }
+
+    #[test]
+    fn after_fires_without_a_timed_wake() {
+        let _guard = TestGuard::new();
+        let fired = Arc::new(AtomicBool::new(false));
+        let f = fired.clone();
+        let requested = Arc::new(AtomicBool::new(false));
+        let r = requested.clone();
+        FrameScheduler::set_request_frame_hook(move || r.store(true, Ordering::Relaxed));
+
+        FrameScheduler::after(Duration::from_millis(5), move || {
+            f.store(true, Ordering::Relaxed);
+        });
+        // A platform loop that only runs passes it was asked for.
+        let started = Instant::now();
+        while requested.swap(false, Ordering::Relaxed) && !fired.load(Ordering::Relaxed) {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "timer never fired"
+            );
+            FrameScheduler::process_frames().unwrap();
+        }
+        assert!(fired.load(Ordering::Relaxed), "the loop went idle first");
+        assert!(!FrameScheduler::polls_for_wake());
+    }
+
+    #[test]
+    fn cancelled_interval_timer_stops_firing() {
+        let _guard = TestGuard::new();
+        let fired = Arc::new(AtomicUsize::new(0));
+        let f = fired.clone();
+        let timer = FrameScheduler::every(Duration::from_millis(2), move || {
+            f.fetch_add(1, Ordering::Relaxed);
+        });
+
+        thread::sleep(Duration::from_millis(5));
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+        thread::sleep(Duration::from_millis(5));
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 2);
+
+        timer.cancel();
+        thread::sleep(Duration::from_millis(5));
+        FrameScheduler::schedule();
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn debouncer_runs_only_the_last_call() {
+        let _guard = TestGuard::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let debouncer = Debouncer::new(Duration::from_millis(3));
+        for n in 0..3 {
+            let seen = seen.clone();
+            debouncer.call(move || lock(&seen).push(n));
+        }
+        assert!(debouncer.is_pending());
+
+        thread::sleep(Duration::from_millis(6));
+        FrameScheduler::process_frames().unwrap();
+        assert_eq!(*lock(&seen), vec![2]);
+        assert!(!debouncer.is_pending());
+    }
 }
@@ -0,0 +1,201 @@
+//! Rolling frame-timing statistics collected by the frame scheduler.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Number of most recent samples kept per series.
+pub const STATS_WINDOW: usize = 120;
+
+/// Frame budget used for deadline accounting when no target interval is set (60 Hz).
+pub const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_nanos(16_666_667);
+
+/// Snapshot of scheduler frame timings, returned by `FrameScheduler::stats()`.
+///
+/// Percentiles are computed over the last [`STATS_WINDOW`] frames; counters
+/// accumulate since the last `FrameScheduler::reset_stats()`.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    /// Frames processed since the last reset.
+    pub frames: u64,
+    /// Median time spent inside one `process_frames` pass.
+    pub frame_time_p50: Duration,
+    /// 95th percentile time spent inside one `process_frames` pass.
+    pub frame_time_p95: Duration,
+    /// Slowest frame within the rolling window.
+    pub frame_time_max: Duration,
+    /// Frame budget the deadline counter was measured against.
+    pub deadline: Duration,
+    /// Frames whose processing time exceeded `deadline`.
+    pub missed_deadlines: u64,
+    /// Per-canvas render/present timings, sorted by handle.
+    pub canvases: Vec<CanvasFrameStats>,
+}
+
+/// Render and present timings of a single canvas.
+#[derive(Debug, Clone, Default)]
+pub struct CanvasFrameStats {
+    /// Raw canvas handle, as accepted by `request_canvas_redraw`.
+    pub handle: usize,
+    /// Redraws recorded since the last reset.
+    pub frames: u64,
+    pub render_p50: Duration,
+    pub render_p95: Duration,
+    pub present_p50: Duration,
+    pub present_p95: Duration,
+    pub last_render: Duration,
+    pub last_present: Duration,
+}
+
+/// Fixed-capacity ring of duration samples.
+#[derive(Debug, Default)]
+struct Samples {
+    values: VecDeque<Duration>,
+}
+
+impl Samples {
+    fn push(&mut self, value: Duration) {
+        if self.values.len() == STATS_WINDOW {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    fn last(&self) -> Duration {
+        self.values.back().copied().unwrap_or_default()
+    }
+
+    fn max(&self) -> Duration {
+        self.values.iter().copied().max().unwrap_or_default()
+    }
+
+    /// Nearest-rank percentile; `pct` is in `0..=100`.
+    fn percentile(&self, pct: usize) -> Duration {
+        if self.values.is_empty() {
+            return Duration::ZERO;
+        }
+        let mut sorted: Vec<Duration> = self.values.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (pct.min(100) * sorted.len()).div_ceil(100).max(1);
+        sorted[rank - 1]
+    }
+}
+
+#[derive(Debug, Default)]
+struct CanvasSamples {
+    frames: u64,
+    render: Samples,
+    present: Samples,
+}
+
+/// Accumulator behind `FrameScheduler::stats()`.
+#[derive(Debug, Default)]
+pub(crate) struct FrameStatsRecorder {
+    frames: u64,
+    missed_deadlines: u64,
+    frame_times: Samples,
+    canvases: HashMap<usize, CanvasSamples>,
+}
+
+impl FrameStatsRecorder {
+    pub(crate) fn record_frame(&mut self, elapsed: Duration, deadline: Duration) {
+        self.frames += 1;
+        if elapsed > deadline {
+            self.missed_deadlines += 1;
+        }
+        self.frame_times.push(elapsed);
+    }
+
+    pub(crate) fn record_canvas(&mut self, handle: usize, render: Duration, present: Duration) {
+        let canvas = self.canvases.entry(handle).or_default();
+        canvas.frames += 1;
+        canvas.render.push(render);
+        canvas.present.push(present);
+    }
+
+    pub(crate) fn remove_canvas(&mut self, handle: usize) {
+        self.canvases.remove(&handle);
+    }
+
+    pub(crate) fn snapshot(&self, deadline: Duration) -> FrameStats {
+        let mut canvases: Vec<CanvasFrameStats> = self
+            .canvases
+            .iter()
+            .map(|(&handle, c)| CanvasFrameStats {
+                handle,
+                frames: c.frames,
+                render_p50: c.render.percentile(50),
+                render_p95: c.render.percentile(95),
+                present_p50: c.present.percentile(50),
+                present_p95: c.present.percentile(95),
+                last_render: c.render.last(),
+                last_present: c.present.last(),
+            })
+            .collect();
+        canvases.sort_by_key(|c| c.handle);
+
+        FrameStats {
+            frames: self.frames,
+            frame_time_p50: self.frame_times.percentile(50),
+            frame_time_p95: self.frame_times.percentile(95),
+            frame_time_max: self.frame_times.max(),
+            deadline,
+            missed_deadlines: self.missed_deadlines,
+            canvases,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let mut recorder = FrameStatsRecorder::default();
+        for n in 1..=100 {
+            recorder.record_frame(ms(n), ms(1000));
+        }
+        let stats = recorder.snapshot(ms(1000));
+        assert_eq!(stats.frame_time_p50, ms(50));
+        assert_eq!(stats.frame_time_p95, ms(95));
+        assert_eq!(stats.frame_time_max, ms(100));
+        assert_eq!(stats.missed_deadlines, 0);
+    }
+
+    #[test]
+    fn window_keeps_only_recent_frames_but_counters_accumulate() {
+        let mut recorder = FrameStatsRecorder::default();
+        for _ in 0..STATS_WINDOW {
+            recorder.record_frame(ms(40), ms(16));
+        }
+        for _ in 0..STATS_WINDOW {
+            recorder.record_frame(ms(2), ms(16));
+        }
+        let stats = recorder.snapshot(ms(16));
+        assert_eq!(stats.frames, 2 * STATS_WINDOW as u64);
+        assert_eq!(stats.missed_deadlines, STATS_WINDOW as u64);
+        assert_eq!(stats.frame_time_max, ms(2));
+    }
+
+    #[test]
+    fn canvas_timings_are_tracked_per_handle() {
+        let mut recorder = FrameStatsRecorder::default();
+        recorder.record_canvas(2, ms(5), ms(1));
+        recorder.record_canvas(1, ms(3), ms(2));
+        recorder.record_canvas(1, ms(7), ms(4));
+
+        let stats = recorder.snapshot(DEFAULT_FRAME_INTERVAL);
+        assert_eq!(stats.canvases.len(), 2);
+        assert_eq!(stats.canvases[0].handle, 1);
+        assert_eq!(stats.canvases[0].frames, 2);
+        assert_eq!(stats.canvases[0].last_render, ms(7));
+        assert_eq!(stats.canvases[0].present_p95, ms(4));
+
+        recorder.remove_canvas(1);
+        assert_eq!(recorder.snapshot(DEFAULT_FRAME_INTERVAL).canvases.len(), 1);
+    }
+}
@@ -0,0 +1,938 @@
+//! Gesture recognition on top of pointer events.
+//!
+//! A [`GestureDetector`] consumes [`WindowEvent::Pointer`] events and
+//! recognizes taps, double-taps, long-presses, pans, pinches and rotations.
+//! Touchpad pinches ([`WindowEvent::TouchpadPinch`]) feed the pinch and
+//! rotate recognizers as well.
+//! Recognizers compete for each touch sequence: the first one whose
+//! conditions are met wins and every other candidate fails until all
+//! contacts have lifted. A tap that could still become a double-tap is held
+//! back for [`GestureConfig::double_tap_interval`].
+//!
+//! Feed events by hand with [`GestureDetector::handle`] and
+//! [`GestureDetector::tick`], or let [`GestureDetector::attach`] do both from
+//! an [`EventRouter`] scope and the UI-loop timers.
+
+mod velocity;
+
+pub use velocity::{Fling, VelocityTracker};
+
+use crate::event_router::{EventHandlerHandle, EventRouter, Propagation, ScopeId};
+use crate::frame_queue::FrameScheduler;
+use crate::timer::TimerHandle;
+use aurea_foundation::{
+    PointerButtons, PointerEvent, PointerPhase, TouchpadPhase, WindowEvent, lock,
+};
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GestureKind {
+    Tap,
+    DoubleTap,
+    LongPress,
+    Pan,
+    Pinch,
+    Rotate,
+}
+
+impl GestureKind {
+    pub const ALL: [GestureKind; 6] = [
+        Self::Tap,
+        Self::DoubleTap,
+        Self::LongPress,
+        Self::Pan,
+        Self::Pinch,
+        Self::Rotate,
+    ];
+}
+
+/// Lifecycle of a continuous gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GestureState {
+    Began,
+    Changed,
+    Ended,
+    /// The contact was cancelled by the platform; do not apply momentum.
+    Cancelled,
+}
+
+/// A recognized gesture. Positions are logical window coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureEvent {
+    Tap {
+        position: (f64, f64),
+    },
+    DoubleTap {
+        position: (f64, f64),
+    },
+    LongPress {
+        state: GestureState,
+        position: (f64, f64),
+    },
+    Pan {
+        state: GestureState,
+        position: (f64, f64),
+        /// Offset from where the contact went down.
+        translation: (f64, f64),
+        /// Offset since the previous pan event.
+        delta: (f64, f64),
+        /// Pixels per second; on `Ended`, the fling velocity.
+        velocity: (f64, f64),
+    },
+    Pinch {
+        state: GestureState,
+        center: (f64, f64),
+        /// Contact distance relative to when the pinch started.
+        scale: f64,
+        /// Change of `scale` per second.
+        velocity: f64,
+    },
+    Rotate {
+        state: GestureState,
+        center: (f64, f64),
+        /// Radians since the rotation started, clockwise positive.
+        rotation: f64,
+        /// Radians per second.
+        velocity: f64,
+    },
+}
+
+impl GestureEvent {
+    pub fn kind(&self) -> GestureKind {
+        match self {
+            Self::Tap { .. } => GestureKind::Tap,
+            Self::DoubleTap { .. } => GestureKind::DoubleTap,
+            Self::LongPress { .. } => GestureKind::LongPress,
+            Self::Pan { .. } => GestureKind::Pan,
+            Self::Pinch { .. } => GestureKind::Pinch,
+            Self::Rotate { .. } => GestureKind::Rotate,
+        }
+    }
+}
+
+/// Thresholds used by the recognizers. Distances are logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// Movement beyond this turns a press into a pan (or fails it).
+    pub touch_slop: f64,
+    pub long_press: Duration,
+    /// Longest gap between the taps of a double-tap.
+    pub double_tap_interval: Duration,
+    /// Largest distance between the taps of a double-tap.
+    pub double_tap_slop: f64,
+    /// Relative change in contact distance that starts a pinch.
+    pub pinch_threshold: f64,
+    /// Radians of twist that start a rotation.
+    pub rotate_threshold: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            touch_slop: 8.0,
+            long_press: Duration::from_millis(500),
+            double_tap_interval: Duration::from_millis(300),
+            double_tap_slop: 24.0,
+            pinch_threshold: 0.08,
+            rotate_threshold: 0.2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Contact {
+    start: (f64, f64),
+    position: (f64, f64),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TwoFinger {
+    distance: f64,
+    angle: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Stage {
+    Idle,
+    /// One contact down, no winner yet.
+    Single {
+        since: Instant,
+    },
+    /// Two contacts down, waiting for a pinch or rotation.
+    Multi(TwoFinger),
+    Active(GestureKind, Option<TwoFinger>),
+    /// A touchpad pinch is in progress, recognized once it passes a
+    /// threshold.
+    Touchpad(Option<GestureKind>),
+    /// Every candidate failed; wait until all contacts lift.
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingTap {
+    position: (f64, f64),
+    at: Instant,
+}
+
+/// Recognizes gestures from a window's pointer events.
+pub struct GestureDetector {
+    config: GestureConfig,
+    enabled: Vec<GestureKind>,
+    contacts: BTreeMap<u64, Contact>,
+    primary: Option<u64>,
+    stage: Stage,
+    pending_tap: Option<PendingTap>,
+    last_pan: (f64, f64),
+    tracker: VelocityTracker,
+}
+
+impl GestureDetector {
+    /// A detector with every recognizer enabled.
+    pub fn new() -> Self {
+        Self::with_config(GestureConfig::default())
+    }
+
+    pub fn with_config(config: GestureConfig) -> Self {
+        Self {
+            config,
+            enabled: GestureKind::ALL.to_vec(),
+            contacts: BTreeMap::new(),
+            primary: None,
+            stage: Stage::Idle,
+            pending_tap: None,
+            last_pan: (0.0, 0.0),
+            tracker: VelocityTracker::new(),
+        }
+    }
+
+    /// Restrict recognition to `kinds`. Disabling `DoubleTap` makes taps
+    /// report immediately.
+    pub fn set_enabled(&mut self, kinds: &[GestureKind]) {
+        self.enabled = kinds.to_vec();
+    }
+
+    pub fn is_enabled(&self, kind: GestureKind) -> bool {
+        self.enabled.contains(&kind)
+    }
+
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Feed one event; events other than pointer and touchpad input are
+    /// ignored.
+    pub fn handle(&mut self, event: &WindowEvent, now: Instant) -> Vec<GestureEvent> {
+        let pointer = match event {
+            WindowEvent::Pointer(pointer) => pointer,
+            WindowEvent::TouchpadPinch {
+                phase,
+                position,
+                scale,
+                rotation,
+            } => return self.touchpad(*phase, *position, (*scale, *rotation), now),
+            _ => return Vec::new(),
+        };
+        let pressed = pointer.buttons.contains(PointerButtons::PRIMARY)
+            && pointer.phase != PointerPhase::Cancel;
+        let tracked = self.contacts.contains_key(&pointer.pointer_id);
+        match (pressed, tracked) {
+            (true, false) => self.down(pointer, now),
+            (true, true) => self.moved(pointer, now),
+            (false, true) => self.up(pointer, now),
+            (false, false) => Vec::new(),
+        }
+    }
+
+    /// Fire time-based recognizers (long-press, a tap that can no longer
+    /// become a double-tap). Call at [`Self::next_deadline`].
+    pub fn tick(&mut self, now: Instant) -> Vec<GestureEvent> {
+        let mut out = Vec::new();
+        if let Some(tap) = self.pending_tap
+            && now >= tap.at + self.config.double_tap_interval
+        {
+            out.extend(self.flush_pending_tap());
+        }
+        if let Stage::Single { since } = self.stage
+            && self.is_enabled(GestureKind::LongPress)
+            && now >= since + self.config.long_press
+        {
+            out.extend(self.flush_pending_tap());
+            self.stage = Stage::Active(GestureKind::LongPress, None);
+            out.push(GestureEvent::LongPress {
+                state: GestureState::Began,
+                position: self.primary_position(),
+            });
+        }
+        out
+    }
+
+    /// When [`Self::tick`] next has something to do.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let tap = self
+            .pending_tap
+            .map(|tap| tap.at + self.config.double_tap_interval);
+        let long_press = match self.stage {
+            Stage::Single { since } if self.is_enabled(GestureKind::LongPress) => {
+                Some(since + self.config.long_press)
+            }
+            _ => None,
+        };
+        tap.into_iter().chain(long_press).min()
+    }
+
+    /// Feed pointer events reaching `scope` and report gestures to
+    /// `on_gesture`, using UI-loop timers for time-based recognizers.
+    pub fn attach<F>(self, router: &EventRouter, scope: ScopeId, on_gesture: F) -> GestureHandle
+    where
+        F: Fn(GestureEvent) + Send + Sync + 'static,
+    {
+        let shared = Arc::new(Attached {
+            detector: Mutex::new(self),
+            timer: Mutex::new(None),
+            on_gesture: Box::new(on_gesture),
+        });
+        let handler = {
+            let shared = shared.clone();
+            router.on_bubble(scope, move |routed| {
+                if matches!(
+                    routed.event,
+                    WindowEvent::Pointer(_) | WindowEvent::TouchpadPinch { .. }
+                ) {
+                    let events = lock(&shared.detector).handle(routed.event, Instant::now());
+                    Attached::deliver(&shared, events);
+                }
+                Propagation::Continue
+            })
+        };
+        GestureHandle { handler, shared }
+    }
+
+    fn down(&mut self, pointer: &PointerEvent, now: Instant) -> Vec<GestureEvent> {
+        let position = pointer.position;
+        self.contacts.insert(
+            pointer.pointer_id,
+            Contact {
+                start: position,
+                position,
+            },
+        );
+        match (self.stage, self.contacts.len()) {
+            (Stage::Idle, 1) => {
+                self.primary = Some(pointer.pointer_id);
+                self.stage = Stage::Single { since: now };
+                self.tracker.reset();
+                self.tracker.add(position, now);
+                Vec::new()
+            }
+            (Stage::Single { .. }, 2) => self.second_contact(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn second_contact(&mut self) -> Vec<GestureEvent> {
+        let wants_two = self.is_enabled(GestureKind::Pinch) || self.is_enabled(GestureKind::Rotate);
+        self.stage = match self.two_finger() {
+            Some(start) if wants_two => Stage::Multi(start),
+            _ => Stage::Failed,
+        };
+        self.flush_pending_tap().into_iter().collect()
+    }
+
+    fn moved(&mut self, pointer: &PointerEvent, now: Instant) -> Vec<GestureEvent> {
+        if let Some(contact) = self.contacts.get_mut(&pointer.pointer_id) {
+            contact.position = pointer.position;
+        }
+        let is_primary = self.primary == Some(pointer.pointer_id);
+        match self.stage {
+            Stage::Single { .. } if is_primary => {
+                self.tracker.add(pointer.position, now);
+                self.single_moved()
+            }
+            Stage::Multi(start) => self.multi_moved(start, now),
+            Stage::Active(GestureKind::Pan, _) if is_primary => {
+                self.tracker.add(pointer.position, now);
+                vec![self.pan_event(GestureState::Changed)]
+            }
+            Stage::Active(GestureKind::LongPress, _) if is_primary => {
+                vec![GestureEvent::LongPress {
+                    state: GestureState::Changed,
+                    position: pointer.position,
+                }]
+            }
+            Stage::Active(kind, Some(start)) => {
+                self.two_finger_event(kind, start, now, GestureState::Changed)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn single_moved(&mut self) -> Vec<GestureEvent> {
+        let Some(contact) = self.primary_contact() else {
+            return Vec::new();
+        };
+        if distance(contact.start, contact.position) <= self.config.touch_slop {
+            return Vec::new();
+        }
+        if !self.is_enabled(GestureKind::Pan) {
+            self.stage = Stage::Failed;
+            return Vec::new();
+        }
+        let mut out: Vec<_> = self.flush_pending_tap().into_iter().collect();
+        self.stage = Stage::Active(GestureKind::Pan, None);
+        self.last_pan = contact.start;
+        out.push(self.pan_event(GestureState::Began));
+        out
+    }
+
+    fn multi_moved(&mut self, start: TwoFinger, now: Instant) -> Vec<GestureEvent> {
+        let Some(current) = self.two_finger() else {
+            return Vec::new();
+        };
+        let scale = current.distance / start.distance;
+        let rotation = normalize_angle(current.angle - start.angle);
+        let Some(kind) = self.two_finger_kind(scale, rotation) else {
+            return Vec::new();
+        };
+        self.stage = Stage::Active(kind, Some(start));
+        self.tracker.reset();
+        self.two_finger_event(kind, start, now, GestureState::Began)
+    }
+
+    /// Which two-finger recognizer `scale` and `rotation` satisfy; pinch wins
+    /// when both do.
+    fn two_finger_kind(&self, scale: f64, rotation: f64) -> Option<GestureKind> {
+        if self.is_enabled(GestureKind::Pinch) && (scale - 1.0).abs() > self.config.pinch_threshold
+        {
+            Some(GestureKind::Pinch)
+        } else if self.is_enabled(GestureKind::Rotate)
+            && rotation.abs() > self.config.rotate_threshold
+        {
+            Some(GestureKind::Rotate)
+        } else {
+            None
+        }
+    }
+
+    /// A touchpad pinch reports scale and rotation directly, so it skips the
+    /// contact tracking but competes like a two-finger touch. It is ignored
+    /// while a touch sequence is in progress.
+    fn touchpad(
+        &mut self,
+        phase: TouchpadPhase,
+        center: (f64, f64),
+        (scale, rotation): (f64, f64),
+        now: Instant,
+    ) -> Vec<GestureEvent> {
+        let rotation = normalize_angle(rotation);
+        match (self.stage, phase) {
+            (Stage::Idle, TouchpadPhase::Began) => {
+                let wants_two =
+                    self.is_enabled(GestureKind::Pinch) || self.is_enabled(GestureKind::Rotate);
+                if wants_two {
+                    self.stage = Stage::Touchpad(None);
+                }
+                Vec::new()
+            }
+            (Stage::Touchpad(None), TouchpadPhase::Changed) => {
+                let Some(kind) = self.two_finger_kind(scale, rotation) else {
+                    return Vec::new();
+                };
+                self.stage = Stage::Touchpad(Some(kind));
+                self.tracker.reset();
+                vec![self.two_finger_gesture(
+                    kind,
+                    center,
+                    (scale, rotation),
+                    now,
+                    GestureState::Began,
+                )]
+            }
+            (Stage::Touchpad(Some(kind)), TouchpadPhase::Changed) => {
+                vec![self.two_finger_gesture(
+                    kind,
+                    center,
+                    (scale, rotation),
+                    now,
+                    GestureState::Changed,
+                )]
+            }
+            (Stage::Touchpad(kind), TouchpadPhase::Ended | TouchpadPhase::Cancelled) => {
+                self.stage = Stage::Idle;
+                let end = if phase == TouchpadPhase::Cancelled {
+                    GestureState::Cancelled
+                } else {
+                    GestureState::Ended
+                };
+                kind.map(|kind| self.two_finger_gesture(kind, center, (scale, rotation), now, end))
+                    .into_iter()
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn up(&mut self, pointer: &PointerEvent, now: Instant) -> Vec<GestureEvent> {
+        let cancelled = pointer.phase == PointerPhase::Cancel;
+        let is_primary = self.primary == Some(pointer.pointer_id);
+        if let Some(contact) = self.contacts.get_mut(&pointer.pointer_id) {
+            contact.position = pointer.position;
+        }
+        let end = if cancelled {
+            GestureState::Cancelled
+        } else {
+            GestureState::Ended
+        };
+        let out = match self.stage {
+            Stage::Single { since } if !cancelled => self.tap(pointer.position, since, now),
+            Stage::Active(GestureKind::Pan, _) if is_primary => {
+                self.tracker.add(pointer.position, now);
+                vec![self.pan_event(end)]
+            }
+            Stage::Active(GestureKind::LongPress, _) if is_primary => {
+                vec![GestureEvent::LongPress {
+                    state: end,
+                    position: pointer.position,
+                }]
+            }
+            Stage::Active(kind, Some(start)) => self.two_finger_event(kind, start, now, end),
+            _ => Vec::new(),
+        };
+        self.contacts.remove(&pointer.pointer_id);
+        self.after_lift(is_primary);
+        out
+    }
+
+    /// Any lift ends the current recognizer; the rest of the sequence is
+    /// ignored until every contact is up.
+    fn after_lift(&mut self, was_primary: bool) {
+        if self.contacts.is_empty() {
+            if !matches!(self.stage, Stage::Touchpad(_)) {
+                self.stage = Stage::Idle;
+            }
+            self.primary = None;
+        } else if was_primary
+            || !matches!(
+                self.stage,
+                Stage::Active(GestureKind::Pan | GestureKind::LongPress, _)
+            )
+        {
+            self.stage = Stage::Failed;
+        }
+    }
+
+    fn tap(&mut self, position: (f64, f64), since: Instant, now: Instant) -> Vec<GestureEvent> {
+        if now.duration_since(since) >= self.config.long_press
+            && self.is_enabled(GestureKind::LongPress)
+        {
+            return Vec::new();
+        }
+        if !self.is_enabled(GestureKind::DoubleTap) {
+            return self.tap_event(position).into_iter().collect();
+        }
+        if let Some(first) = self.pending_tap
+            && now.duration_since(first.at) <= self.config.double_tap_interval
+            && distance(first.position, position) <= self.config.double_tap_slop
+        {
+            self.pending_tap = None;
+            return vec![GestureEvent::DoubleTap { position }];
+        }
+        let out = self.flush_pending_tap().into_iter().collect();
+        self.pending_tap = Some(PendingTap { position, at: now });
+        out
+    }
+
+    fn tap_event(&self, position: (f64, f64)) -> Option<GestureEvent> {
+        self.is_enabled(GestureKind::Tap)
+            .then_some(GestureEvent::Tap { position })
+    }
+
+    /// Report a held-back tap as a single tap now that no double-tap can
+    /// follow.
+    fn flush_pending_tap(&mut self) -> Option<GestureEvent> {
+        let tap = self.pending_tap.take()?;
+        self.tap_event(tap.position)
+    }
+
+    fn pan_event(&mut self, state: GestureState) -> GestureEvent {
+        let contact = self.primary_contact().unwrap_or(Contact {
+            start: self.last_pan,
+            position: self.last_pan,
+        });
+        let position = contact.position;
+        let delta = (position.0 - self.last_pan.0, position.1 - self.last_pan.1);
+        self.last_pan = position;
+        let velocity = match state {
+            GestureState::Cancelled => (0.0, 0.0),
+            _ => self.tracker.velocity(),
+        };
+        GestureEvent::Pan {
+            state,
+            position,
+            translation: (position.0 - contact.start.0, position.1 - contact.start.1),
+            delta,
+            velocity,
+        }
+    }
+
+    fn two_finger_event(
+        &mut self,
+        kind: GestureKind,
+        start: TwoFinger,
+        now: Instant,
+        state: GestureState,
+    ) -> Vec<GestureEvent> {
+        let (Some(current), Some(center)) = (self.two_finger(), self.center()) else {
+            return Vec::new();
+        };
+        let scale = current.distance / start.distance;
+        let rotation = normalize_angle(current.angle - start.angle);
+        vec![self.two_finger_gesture(kind, center, (scale, rotation), now, state)]
+    }
+
+    fn two_finger_gesture(
+        &mut self,
+        kind: GestureKind,
+        center: (f64, f64),
+        (scale, rotation): (f64, f64),
+        now: Instant,
+        state: GestureState,
+    ) -> GestureEvent {
+        self.tracker.add((scale, rotation), now);
+        let (scale_velocity, rotation_velocity) = match state {
+            GestureState::Cancelled => (0.0, 0.0),
+            _ => self.tracker.velocity(),
+        };
+        match kind {
+            GestureKind::Pinch => GestureEvent::Pinch {
+                state,
+                center,
+                scale,
+                velocity: scale_velocity,
+            },
+            _ => GestureEvent::Rotate {
+                state,
+                center,
+                rotation,
+                velocity: rotation_velocity,
+            },
+        }
+    }
+
+    fn primary_contact(&self) -> Option<Contact> {
+        self.primary.and_then(|id| self.contacts.get(&id)).copied()
+    }
+
+    fn primary_position(&self) -> (f64, f64) {
+        self.primary_contact()
+            .map_or((0.0, 0.0), |contact| contact.position)
+    }
+
+    /// Distance and angle between the two oldest contacts.
+    fn two_finger(&self) -> Option<TwoFinger> {
+        let mut contacts = self.contacts.values();
+        let (a, b) = (contacts.next()?.position, contacts.next()?.position);
+        let distance = distance(a, b);
+        (distance > 0.0).then(|| TwoFinger {
+            distance,
+            angle: (b.1 - a.1).atan2(b.0 - a.0),
+        })
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        let mut contacts = self.contacts.values();
+        let (a, b) = (contacts.next()?.position, contacts.next()?.position);
+        Some(((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0))
+    }
+}
+
+impl Default for GestureDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// Wrap to `(-PI, PI]` so crossing the atan2 seam is not a full turn.
+fn normalize_angle(angle: f64) -> f64 {
+    let wrapped = (angle + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped == -PI { PI } else { wrapped }
+}
+
+struct Attached {
+    detector: Mutex<GestureDetector>,
+    timer: Mutex<Option<TimerHandle>>,
+    on_gesture: Box<dyn Fn(GestureEvent) + Send + Sync>,
+}
+
+impl Attached {
+    /// Report `events` outside the detector lock, then re-arm the timer for
+    /// the detector's next deadline.
+    fn deliver(shared: &Arc<Self>, events: Vec<GestureEvent>) {
+        for event in events {
+            (shared.on_gesture)(event);
+        }
+        let deadline = lock(&shared.detector).next_deadline();
+        let mut timer = lock(&shared.timer);
+        if let Some(old) = timer.take() {
+            old.cancel();
+        }
+        if let Some(at) = deadline {
+            let delay = at.saturating_duration_since(Instant::now());
+            let weak = Arc::downgrade(shared);
+            *timer = Some(FrameScheduler::after(delay, move || {
+                if let Some(shared) = weak.upgrade() {
+                    let events = lock(&shared.detector).tick(Instant::now());
+                    Attached::deliver(&shared, events);
+                }
+            }));
+        }
+    }
+}
+
+/// Returned by [`GestureDetector::attach`]. Dropping the handle leaves the
+/// detector attached.
+#[derive(Clone)]
+pub struct GestureHandle {
+    handler: EventHandlerHandle,
+    shared: Arc<Attached>,
+}
+
+impl GestureHandle {
+    /// Stop recognizing. Returns `false` if already detached.
+    pub fn detach(&self) -> bool {
+        if let Some(timer) = lock(&self.shared.timer).take() {
+            timer.cancel();
+        }
+        self.handler.unregister()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurea_foundation::{Modifiers, PointerKind};
+
+    struct Script {
+        detector: GestureDetector,
+        start: Instant,
+        out: Vec<GestureEvent>,
+    }
+
+    impl Script {
+        fn new() -> Self {
+            Self {
+                detector: GestureDetector::new(),
+                start: Instant::now(),
+                out: Vec::new(),
+            }
+        }
+
+        fn at(&self, ms: u64) -> Instant {
+            self.start + Duration::from_millis(ms)
+        }
+
+        fn touch(&mut self, ms: u64, id: u64, phase: PointerPhase, position: (f64, f64)) {
+            let buttons = match phase {
+                PointerPhase::Down | PointerPhase::Move => PointerButtons::PRIMARY,
+                PointerPhase::Up | PointerPhase::Cancel => PointerButtons::empty(),
+            };
+            let event = WindowEvent::Pointer(PointerEvent {
+                pointer_id: id,
+                kind: PointerKind::Touch,
+                phase,
+                position,
+                pressure: 1.0,
+                tilt: (0.0, 0.0),
+                twist: 0.0,
+                buttons,
+                modifiers: Modifiers::new(),
+            });
+            let now = self.at(ms);
+            let events = self.detector.handle(&event, now);
+            self.out.extend(events);
+        }
+
+        fn touchpad(&mut self, ms: u64, phase: TouchpadPhase, scale: f64, rotation: f64) {
+            let event = WindowEvent::TouchpadPinch {
+                phase,
+                position: (50.0, 40.0),
+                scale,
+                rotation,
+            };
+            let now = self.at(ms);
+            let events = self.detector.handle(&event, now);
+            self.out.extend(events);
+        }
+
+        fn tick(&mut self, ms: u64) {
+            let now = self.at(ms);
+            let events = self.detector.tick(now);
+            self.out.extend(events);
+        }
+
+        fn kinds(&self) -> Vec<GestureKind> {
+            self.out.iter().map(GestureEvent::kind).collect()
+        }
+    }
+
+    #[test]
+    fn tap_waits_for_double_tap_window() {
+        let mut s = Script::new();
+        s.touch(0, 1, PointerPhase::Down, (10.0, 10.0));
+        s.touch(50, 1, PointerPhase::Up, (11.0, 10.0));
+        assert!(s.out.is_empty());
+        assert_eq!(
+            s.detector.next_deadline(),
+            Some(s.at(50) + s.detector.config().double_tap_interval)
+        );
+        s.tick(400);
+        assert_eq!(
+            s.out,
+            vec![GestureEvent::Tap {
+                position: (11.0, 10.0)
+            }]
+        );
+    }
+
+    #[test]
+    fn two_quick_taps_make_a_double_tap() {
+        let mut s = Script::new();
+        s.touch(0, 1, PointerPhase::Down, (10.0, 10.0));
+        s.touch(50, 1, PointerPhase::Up, (10.0, 10.0));
+        s.touch(150, 2, PointerPhase::Down, (12.0, 10.0));
+        s.touch(200, 2, PointerPhase::Up, (12.0, 10.0));
+        s.tick(1000);
+        assert_eq!(s.kinds(), vec![GestureKind::DoubleTap]);
+    }
+
+    #[test]
+    fn long_press_wins_over_tap() {
+        let mut s = Script::new();
+        s.touch(0, 1, PointerPhase::Down, (10.0, 10.0));
+        s.tick(600);
+        s.touch(650, 1, PointerPhase::Up, (10.0, 10.0));
+        s.tick(2000);
+        assert_eq!(
+            s.kinds(),
+            vec![GestureKind::LongPress, GestureKind::LongPress]
+        );
+        assert!(matches!(
+            s.out[1],
+            GestureEvent::LongPress {
+                state: GestureState::Ended,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn pan_reports_translation_and_fling_velocity() {
+        let mut s = Script::new();
+        s.touch(0, 1, PointerPhase::Down, (0.0, 0.0));
+        for step in 1..=5_u32 {
+            s.touch(
+                u64::from(step) * 10,
+                1,
+                PointerPhase::Move,
+                (f64::from(step) * 10.0, 0.0),
+            );
+        }
+        s.touch(60, 1, PointerPhase::Up, (60.0, 0.0));
+        // The tap recognizer failed: no tap even after the double-tap window.
+        s.tick(1000);
+        assert!(s.kinds().iter().all(|kind| *kind == GestureKind::Pan));
+        let Some(GestureEvent::Pan {
+            state,
+            translation,
+            velocity,
+            ..
+        }) = s.out.last().copied()
+        else {
+            unreachable!("pan must end with a pan event");
+        };
+        assert_eq!(state, GestureState::Ended);
+        assert_eq!(translation, (60.0, 0.0));
+        assert!((velocity.0 - 1000.0).abs() < 1e-6, "velocity {velocity:?}");
+    }
+
+    #[test]
+    fn pinch_beats_rotate_and_tap() {
+        let mut s = Script::new();
+        s.touch(0, 1, PointerPhase::Down, (0.0, 0.0));
+        s.touch(5, 2, PointerPhase::Down, (100.0, 0.0));
+        s.touch(20, 2, PointerPhase::Move, (150.0, 0.0));
+        s.touch(40, 2, PointerPhase::Move, (200.0, 0.0));
+        s.touch(50, 2, PointerPhase::Up, (200.0, 0.0));
+        s.touch(60, 1, PointerPhase::Up, (0.0, 0.0));
+        s.tick(1000);
+        assert!(s.kinds().iter().all(|kind| *kind == GestureKind::Pinch));
+        assert!(matches!(
+            s.out.last(),
+            Some(GestureEvent::Pinch { state: GestureState::Ended, scale, .. }) if (*scale - 2.0).abs() < 1e-9
+        ));
+    }
+
+    #[test]
+    fn rotation_without_spread_is_a_rotate() {
+        let mut s = Script::new();
+        s.touch(0, 1, PointerPhase::Down, (0.0, 0.0));
+        s.touch(5, 2, PointerPhase::Down, (100.0, 0.0));
+        s.touch(20, 2, PointerPhase::Move, (0.0, 100.0));
+        assert_eq!(s.kinds(), vec![GestureKind::Rotate]);
+        let GestureEvent::Rotate { rotation, .. } = s.out[0] else {
+            unreachable!("checked kind above");
+        };
+        assert!((rotation - PI / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn touchpad_pinch_and_rotate_use_the_same_recognizers() {
+        let mut s = Script::new();
+        s.touchpad(0, TouchpadPhase::Began, 1.0, 0.0);
+        s.touchpad(10, TouchpadPhase::Changed, 1.05, 0.0);
+        assert!(s.out.is_empty(), "below the pinch threshold");
+        s.touchpad(20, TouchpadPhase::Changed, 1.5, 0.0);
+        s.touchpad(30, TouchpadPhase::Ended, 2.0, 0.0);
+        assert_eq!(s.kinds(), vec![GestureKind::Pinch; 2]);
+        assert!(matches!(
+            s.out.last(),
+            Some(GestureEvent::Pinch { state: GestureState::Ended, center: (50.0, 40.0), scale, .. })
+                if (*scale - 2.0).abs() < 1e-9
+        ));
+
+        s.out.clear();
+        s.touchpad(100, TouchpadPhase::Began, 1.0, 0.0);
+        s.touchpad(110, TouchpadPhase::Changed, 1.0, 0.5);
+        s.touchpad(120, TouchpadPhase::Cancelled, 1.0, 0.6);
+        assert!(matches!(
+            s.out.as_slice(),
+            [
+                GestureEvent::Rotate { state: GestureState::Began, .. },
+                GestureEvent::Rotate { state: GestureState::Cancelled, velocity, .. },
+            ] if *velocity == 0.0
+        ));
+    }
+
+    #[test]
+    fn disabled_pan_fails_the_sequence() {
+        let mut s = Script::new();
+        s.detector.set_enabled(&[GestureKind::Tap]);
+        s.touch(0, 1, PointerPhase::Down, (0.0, 0.0));
+        s.touch(10, 1, PointerPhase::Move, (50.0, 0.0));
+        s.touch(20, 1, PointerPhase::Up, (50.0, 0.0));
+        s.tick(1000);
+        assert!(s.out.is_empty());
+    }
+
+    #[test]
+    fn angles_wrap_across_the_seam() {
+        assert!((normalize_angle(1.5 * PI) + 0.5 * PI).abs() < 1e-12);
+        assert_eq!(normalize_angle(-PI), PI);
+    }
+}
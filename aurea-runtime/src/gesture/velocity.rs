@@ -0,0 +1,168 @@
+//! Velocity estimation and fling decay.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Only samples this close to the newest one contribute to the estimate, so
+/// a pointer that stopped before lifting reports no velocity.
+const HORIZON: Duration = Duration::from_millis(100);
+const MAX_SAMPLES: usize = 20;
+
+/// Below this speed (units per second) a fling stops.
+const MIN_FLING_SPEED: f64 = 20.0;
+const DEFAULT_FRICTION: f64 = 4.0;
+
+/// Estimates the velocity of a 2D value from timestamped samples with a
+/// least-squares fit over the most recent [`HORIZON`].
+#[derive(Debug, Clone, Default)]
+pub struct VelocityTracker {
+    samples: VecDeque<((f64, f64), Instant)>,
+}
+
+impl VelocityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, value: (f64, f64), at: Instant) {
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((value, at));
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Units per second along each axis; zero with fewer than two recent
+    /// samples.
+    pub fn velocity(&self) -> (f64, f64) {
+        let Some(&(_, newest)) = self.samples.back() else {
+            return (0.0, 0.0);
+        };
+        let recent: Vec<(f64, (f64, f64))> = self
+            .samples
+            .iter()
+            .filter(|(_, at)| newest.duration_since(*at) <= HORIZON)
+            .map(|(value, at)| (-newest.duration_since(*at).as_secs_f64(), *value))
+            .collect();
+        (
+            slope(recent.iter().map(|(t, v)| (*t, v.0))),
+            slope(recent.iter().map(|(t, v)| (*t, v.1))),
+        )
+    }
+}
+
+/// Least-squares slope of `(t, x)` points.
+fn slope(points: impl Iterator<Item = (f64, f64)> + Clone) -> f64 {
+    let (mut n, mut sum_t, mut sum_x) = (0.0, 0.0, 0.0);
+    for (t, x) in points.clone() {
+        n += 1.0;
+        sum_t += t;
+        sum_x += x;
+    }
+    if n < 2.0 {
+        return 0.0;
+    }
+    let (mean_t, mean_x) = (sum_t / n, sum_x / n);
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (t, x) in points {
+        cov += (t - mean_t) * (x - mean_x);
+        var += (t - mean_t) * (t - mean_t);
+    }
+    if var > 0.0 { cov / var } else { 0.0 }
+}
+
+/// Exponentially decaying motion started from a release velocity, e.g. the
+/// `velocity` of an ended pan.
+///
+/// Call [`Fling::step`] once per frame and apply the returned displacement
+/// (for instance with `Viewport::scroll_by`) until it returns `None`.
+#[derive(Debug, Clone, Copy)]
+pub struct Fling {
+    velocity: (f64, f64),
+    friction: f64,
+}
+
+impl Fling {
+    pub fn new(velocity: (f64, f64)) -> Self {
+        Self::with_friction(velocity, DEFAULT_FRICTION)
+    }
+
+    /// `friction` is the decay rate per second; higher stops sooner.
+    pub fn with_friction(velocity: (f64, f64), friction: f64) -> Self {
+        Self {
+            velocity,
+            friction: friction.max(f64::EPSILON),
+        }
+    }
+
+    pub fn velocity(&self) -> (f64, f64) {
+        self.velocity
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.velocity.0.hypot(self.velocity.1) < MIN_FLING_SPEED
+    }
+
+    /// Advance by `dt` and return the distance travelled, or `None` once the
+    /// fling has slowed to a stop.
+    pub fn step(&mut self, dt: Duration) -> Option<(f64, f64)> {
+        if self.is_finished() {
+            return None;
+        }
+        let decay = (-self.friction * dt.as_secs_f64()).exp();
+        // Integral of v * e^(-kt) over the step.
+        let travelled = (1.0 - decay) / self.friction;
+        let moved = (self.velocity.0 * travelled, self.velocity.1 * travelled);
+        self.velocity = (self.velocity.0 * decay, self.velocity.1 * decay);
+        Some(moved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_motion_has_constant_velocity() {
+        let start = Instant::now();
+        let mut tracker = VelocityTracker::new();
+        for i in 0..6_u32 {
+            let t = f64::from(i) * 0.01;
+            tracker.add(
+                (t * 500.0, -t * 100.0),
+                start + Duration::from_millis(u64::from(i) * 10),
+            );
+        }
+        let (vx, vy) = tracker.velocity();
+        assert!((vx - 500.0).abs() < 1e-6, "vx = {vx}");
+        assert!((vy + 100.0).abs() < 1e-6, "vy = {vy}");
+    }
+
+    #[test]
+    fn stale_samples_are_ignored() {
+        let start = Instant::now();
+        let mut tracker = VelocityTracker::new();
+        tracker.add((0.0, 0.0), start);
+        tracker.add((100.0, 0.0), start + Duration::from_millis(10));
+        // The pointer rested before this last sample.
+        tracker.add((100.0, 0.0), start + Duration::from_millis(300));
+        assert_eq!(tracker.velocity(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn fling_decays_to_a_stop() {
+        let mut fling = Fling::new((1000.0, 0.0));
+        let mut total = 0.0;
+        let mut steps = 0;
+        while let Some((dx, _)) = fling.step(Duration::from_millis(16)) {
+            total += dx;
+            steps += 1;
+            assert!(steps < 1000, "fling never stopped");
+        }
+        // Approaches v / friction = 250 from below.
+        assert!(total > 200.0 && total < 250.0, "travelled {total}");
+    }
+}
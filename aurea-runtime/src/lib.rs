@@ -1,9 +1,35 @@
-//! Runtime state: event queue, frame queue, damage region.
+//! Runtime state: event queue, frame queue, frame statistics, damage region,
+//! timers, event recording, gesture recognition, and the UI-thread executor.
 
+pub mod channel;
 pub mod damage;
 pub mod event_queue;
+pub mod event_router;
+pub mod executor;
 pub mod frame_queue;
+pub mod frame_stats;
+pub mod gesture;
+mod pointer;
+pub mod recording;
+pub mod timer;
+#[cfg(feature = "tokio")]
+pub mod tokio_bridge;
 
+pub use channel::{MainThreadReceiver, MainThreadSender, main_thread_channel};
 pub use damage::DamageRegion;
 pub use event_queue::EventQueue;
+pub use event_router::{
+    EventHandlerHandle, EventPhase, EventRouter, HandlerId, Phase, Propagation, RoutedEvent,
+    ScopeId,
+};
+pub use executor::{Executor, TaskId};
 pub use frame_queue::{FrameCallbackId, FrameInfo, FrameScheduler, TickerId};
+pub use frame_stats::{CanvasFrameStats, FrameStats};
+pub use gesture::{
+    Fling, GestureConfig, GestureDetector, GestureEvent, GestureHandle, GestureKind, GestureState,
+    VelocityTracker,
+};
+pub use recording::{EventRecording, RecordedEvent, RecordingFormat, ReplayHandle, ReplaySpeed};
+pub use timer::{Debouncer, Throttle, TimerHandle, TimerId};
+#[cfg(feature = "tokio")]
+pub use tokio_bridge::TokioBridge;
@@ -0,0 +1,236 @@
+//! Derived events that keep mouse and pointer consumers in sync.
+//!
+//! Platforms report the mouse through `MouseMove`/`MouseButton` and touch or
+//! pen contacts through `Pointer`. Each stream gets the other's equivalent:
+//! mouse events become `PointerKind::Mouse` pointer events, and the primary
+//! touch or pen contact drives synthesised left/right button mouse events so
+//! code written against the mouse keeps working.
+
+use aurea_foundation::{
+    MouseButton, PointerButtons, PointerEvent, PointerKind, PointerPhase, WindowEvent,
+};
+
+/// Pressure reported for devices without a pressure axis while pressed.
+const DEFAULT_PRESSURE: f32 = 0.5;
+
+/// The mouse's fixed pointer id.
+const MOUSE_POINTER_ID: u64 = 0;
+
+#[derive(Default)]
+pub(crate) struct PointerTranslator {
+    mouse_buttons: PointerButtons,
+    /// Touch or pen contact currently driving the emulated mouse, and the
+    /// button it pressed.
+    primary: Option<(u64, MouseButton)>,
+}
+
+impl PointerTranslator {
+    /// Events to push after `event`, in order.
+    pub(crate) fn derive(&mut self, event: &WindowEvent) -> Vec<WindowEvent> {
+        match event {
+            WindowEvent::MouseMove { .. } | WindowEvent::MouseButton { .. } => {
+                self.mouse_pointer(event).into_iter().collect()
+            }
+            WindowEvent::Pointer(pointer) if pointer.kind != PointerKind::Mouse => {
+                self.emulate_mouse(pointer)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn mouse_pointer(&mut self, event: &WindowEvent) -> Option<WindowEvent> {
+        let (phase, position, modifiers) = match *event {
+            WindowEvent::MouseMove { x, y, modifiers } => (PointerPhase::Move, (x, y), modifiers),
+            WindowEvent::MouseButton {
+                button,
+                pressed,
+                modifiers,
+                x,
+                y,
+                ..
+            } => {
+                let bit = button_bit(button)?;
+                if pressed {
+                    self.mouse_buttons.insert(bit);
+                    (PointerPhase::Down, (x, y), modifiers)
+                } else {
+                    self.mouse_buttons.remove(bit);
+                    (PointerPhase::Up, (x, y), modifiers)
+                }
+            }
+            _ => return None,
+        };
+        let pressure = if self.mouse_buttons.is_empty() {
+            0.0
+        } else {
+            DEFAULT_PRESSURE
+        };
+        Some(WindowEvent::Pointer(PointerEvent {
+            pointer_id: MOUSE_POINTER_ID,
+            kind: PointerKind::Mouse,
+            phase,
+            position,
+            pressure,
+            tilt: (0.0, 0.0),
+            twist: 0.0,
+            buttons: self.mouse_buttons,
+            modifiers,
+        }))
+    }
+
+    fn emulate_mouse(&mut self, pointer: &PointerEvent) -> Vec<WindowEvent> {
+        let (x, y) = pointer.position;
+        let is_primary = self.primary.map(|(id, _)| id) == Some(pointer.pointer_id);
+        let motion = WindowEvent::MouseMove {
+            x,
+            y,
+            modifiers: pointer.modifiers,
+        };
+        match pointer.phase {
+            PointerPhase::Down if self.primary.is_none() => {
+                let button = if pointer.buttons.contains(PointerButtons::SECONDARY) {
+                    MouseButton::Right
+                } else {
+                    MouseButton::Left
+                };
+                self.primary = Some((pointer.pointer_id, button));
+                vec![motion, mouse_button(pointer, button, true)]
+            }
+            // A hovering pen moves the cursor even with nothing pressed.
+            PointerPhase::Move
+                if is_primary || (self.primary.is_none() && pointer.kind == PointerKind::Pen) =>
+            {
+                vec![motion]
+            }
+            PointerPhase::Up | PointerPhase::Cancel if is_primary => {
+                let button = self.primary.take().map_or(MouseButton::Left, |(_, b)| b);
+                vec![mouse_button(pointer, button, false)]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn button_bit(button: MouseButton) -> Option<PointerButtons> {
+    match button {
+        MouseButton::Left => Some(PointerButtons::PRIMARY),
+        MouseButton::Right => Some(PointerButtons::SECONDARY),
+        MouseButton::Middle => Some(PointerButtons::MIDDLE),
+        MouseButton::Other(_) => None,
+    }
+}
+
+fn mouse_button(pointer: &PointerEvent, button: MouseButton, pressed: bool) -> WindowEvent {
+    let (x, y) = pointer.position;
+    WindowEvent::MouseButton {
+        button,
+        pressed,
+        modifiers: pointer.modifiers,
+        x,
+        y,
+        click_count: 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurea_foundation::Modifiers;
+
+    fn touch(id: u64, phase: PointerPhase, x: f64) -> WindowEvent {
+        WindowEvent::Pointer(PointerEvent {
+            pointer_id: id,
+            kind: PointerKind::Touch,
+            phase,
+            position: (x, 0.0),
+            pressure: 1.0,
+            tilt: (0.0, 0.0),
+            twist: 0.0,
+            buttons: PointerButtons::PRIMARY,
+            modifiers: Modifiers::new(),
+        })
+    }
+
+    #[test]
+    fn mouse_events_become_pointer_events() {
+        let mut translator = PointerTranslator::default();
+        let derived = translator.derive(&WindowEvent::MouseButton {
+            button: MouseButton::Left,
+            pressed: true,
+            modifiers: Modifiers::new(),
+            x: 3.0,
+            y: 4.0,
+            click_count: 1,
+        });
+        let [WindowEvent::Pointer(down)] = derived.as_slice() else {
+            unreachable!("expected one pointer event, got {derived:?}");
+        };
+        assert_eq!(down.kind, PointerKind::Mouse);
+        assert_eq!(down.phase, PointerPhase::Down);
+        assert!(down.buttons.contains(PointerButtons::PRIMARY));
+        assert!(down.pressure > 0.0);
+
+        let derived = translator.derive(&WindowEvent::MouseMove {
+            x: 5.0,
+            y: 6.0,
+            modifiers: Modifiers {
+                shift: true,
+                ..Modifiers::new()
+            },
+        });
+        let [WindowEvent::Pointer(moved)] = derived.as_slice() else {
+            unreachable!("expected one pointer event, got {derived:?}");
+        };
+        assert_eq!(moved.position, (5.0, 6.0));
+        assert!(moved.modifiers.shift);
+        assert!(moved.buttons.contains(PointerButtons::PRIMARY));
+    }
+
+    #[test]
+    fn only_the_first_touch_emulates_the_mouse() {
+        let mut translator = PointerTranslator::default();
+        assert_eq!(
+            translator.derive(&touch(1, PointerPhase::Down, 1.0)).len(),
+            2
+        );
+        assert!(
+            translator
+                .derive(&touch(2, PointerPhase::Down, 9.0))
+                .is_empty()
+        );
+        assert!(
+            translator
+                .derive(&touch(2, PointerPhase::Move, 8.0))
+                .is_empty()
+        );
+        assert!(matches!(
+            translator.derive(&touch(1, PointerPhase::Move, 2.0)).as_slice(),
+            [WindowEvent::MouseMove { x, .. }] if *x == 2.0
+        ));
+        assert!(matches!(
+            translator
+                .derive(&touch(1, PointerPhase::Up, 2.0))
+                .as_slice(),
+            [WindowEvent::MouseButton {
+                pressed: false,
+                button: MouseButton::Left,
+                ..
+            }]
+        ));
+        // The next contact becomes primary once the first has lifted.
+        assert_eq!(
+            translator.derive(&touch(3, PointerPhase::Down, 5.0)).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn mouse_pointer_events_are_not_re_emulated() {
+        let mut translator = PointerTranslator::default();
+        let WindowEvent::Pointer(mut pointer) = touch(0, PointerPhase::Down, 1.0) else {
+            unreachable!();
+        };
+        pointer.kind = PointerKind::Mouse;
+        assert!(translator.derive(&WindowEvent::Pointer(pointer)).is_empty());
+    }
+}
@@ -0,0 +1,1034 @@
+//! Recording and replay of window event streams.
+//!
+//! An [`EventQueue`] records every pushed event with its offset from the
+//! start of the recording. A finished [`EventRecording`] can be written as
+//! JSON (readable, diffable) or as a compact binary stream, and replayed
+//! into any queue at its original pace, scaled, or all at once for
+//! headless regression tests.
+
+mod binary;
+mod json;
+
+use crate::event_queue::EventQueue;
+use crate::frame_queue::FrameScheduler;
+use crate::timer::TimerHandle;
+use aurea_foundation::{
+    AccessibilityAction, AureaError, AureaResult, DragData, DropEffect, FocusTarget, KeyCode,
+    KeyLocation, LogicalKey, Modifiers, MouseButton, NotificationCloseReason, PhysicalKey,
+    PointerButtons, PointerEvent, PointerKind, PointerPhase, ScrollDeltaKind, ScrollPhase,
+    TouchpadPhase, WindowEvent, lock,
+};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// One event and when it arrived, relative to the start of the recording.
+#[derive(Debug, Clone)]
+pub struct RecordedEvent {
+    pub at: Duration,
+    pub event: WindowEvent,
+}
+
+/// On-disk encoding of an [`EventRecording`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+    Json,
+    Binary,
+}
+
+/// How fast [`EventRecording::replay`] feeds events back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Keep the recorded gaps between events.
+    Original,
+    /// Divide every gap by the factor; `2.0` replays twice as fast.
+    Scaled(f64),
+    /// Push everything on the next loop pass.
+    Immediate,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EventRecording {
+    pub events: Vec<RecordedEvent>,
+}
+
+/// Live capture state held by an [`EventQueue`] while recording.
+pub(crate) struct Recorder {
+    start: Instant,
+    events: Vec<RecordedEvent>,
+}
+
+impl Recorder {
+    pub(crate) fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            events: Vec::new(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, event: &WindowEvent) {
+        self.events.push(RecordedEvent {
+            at: self.start.elapsed(),
+            event: event.clone(),
+        });
+    }
+
+    pub(crate) fn finish(self) -> EventRecording {
+        EventRecording {
+            events: self.events,
+        }
+    }
+}
+
+impl EventRecording {
+    pub fn duration(&self) -> Duration {
+        self.events.last().map_or(Duration::ZERO, |e| e.at)
+    }
+
+    pub fn to_json(&self) -> String {
+        json::encode(self)
+    }
+
+    pub fn from_json(text: &str) -> AureaResult<Self> {
+        json::decode(text)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        binary::encode(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> AureaResult<Self> {
+        binary::decode(bytes)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>, format: RecordingFormat) -> AureaResult<()> {
+        let bytes = match format {
+            RecordingFormat::Json => self.to_json().into_bytes(),
+            RecordingFormat::Binary => self.to_bytes(),
+        };
+        fs::write(path, bytes).map_err(|e| AureaError::Io(e.to_string()))
+    }
+
+    /// Load a recording written by [`Self::save`] in either format.
+    pub fn load(path: impl AsRef<Path>) -> AureaResult<Self> {
+        let bytes = fs::read(path).map_err(|e| AureaError::Io(e.to_string()))?;
+        if binary::has_magic(&bytes) {
+            return Self::from_bytes(&bytes);
+        }
+        let text = String::from_utf8(bytes)
+            .map_err(|_| AureaError::InvalidRecording("not UTF-8 JSON".to_owned()))?;
+        Self::from_json(&text)
+    }
+
+    /// Push every event into `queue` right now, ignoring timing. Suited to
+    /// headless tests that drive `process_events` themselves.
+    pub fn replay_immediately(&self, queue: &EventQueue) {
+        for recorded in &self.events {
+            queue.push(recorded.event.clone());
+        }
+    }
+
+    /// Feed the events into `queue` from the UI loop at `speed`. Each event
+    /// is pushed when due and the loop is woken to process it.
+    pub fn replay(&self, queue: Arc<EventQueue>, speed: ReplaySpeed) -> ReplayHandle {
+        let state = Arc::new(Mutex::new(ReplayState {
+            events: self.events.clone(),
+            next: 0,
+            start: Instant::now(),
+            speed,
+            queue,
+            timer: None,
+            cancelled: false,
+        }));
+        ReplayState::step(&state);
+        ReplayHandle { state }
+    }
+}
+
+struct ReplayState {
+    events: Vec<RecordedEvent>,
+    next: usize,
+    start: Instant,
+    speed: ReplaySpeed,
+    queue: Arc<EventQueue>,
+    timer: Option<TimerHandle>,
+    cancelled: bool,
+}
+
+impl ReplayState {
+    fn due_at(&self, recorded: Duration) -> Instant {
+        match self.speed {
+            ReplaySpeed::Original => self.start + recorded,
+            ReplaySpeed::Scaled(factor) if factor > 0.0 && factor.is_finite() => {
+                self.start + recorded.div_f64(factor)
+            }
+            ReplaySpeed::Scaled(_) | ReplaySpeed::Immediate => self.start,
+        }
+    }
+
+    /// Push every event that is due, then arm a one-shot timer for the next.
+    fn step(shared: &Arc<Mutex<Self>>) {
+        let mut state = lock(shared);
+        state.timer = None;
+        if state.cancelled {
+            return;
+        }
+        let now = Instant::now();
+        while let Some(recorded) = state.events.get(state.next) {
+            let due = state.due_at(recorded.at);
+            if due > now {
+                // The timer owns the replay until it fires, so dropping the
+                // handle does not stop it.
+                let shared = shared.clone();
+                state.timer = Some(FrameScheduler::after(due - now, move || {
+                    Self::step(&shared);
+                }));
+                break;
+            }
+            state.queue.push(recorded.event.clone());
+            state.next += 1;
+        }
+        FrameScheduler::wake();
+    }
+}
+
+/// Controls a replay started with [`EventRecording::replay`]. The replay
+/// keeps running when the handle is dropped.
+pub struct ReplayHandle {
+    state: Arc<Mutex<ReplayState>>,
+}
+
+impl ReplayHandle {
+    pub fn cancel(&self) {
+        let mut state = lock(&self.state);
+        state.cancelled = true;
+        if let Some(timer) = state.timer.take() {
+            timer.cancel();
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        let state = lock(&self.state);
+        state.cancelled || state.next >= state.events.len()
+    }
+
+    /// Events pushed so far.
+    pub fn replayed(&self) -> usize {
+        lock(&self.state).next
+    }
+}
+
+/// Field values of an encoded event, in schema order.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Num(f64),
+    F32(f32),
+    Int(i64),
+    Bool(bool),
+    Text(String),
+    /// A JSON number, kept as written so each field parses it at its own type.
+    Raw(String),
+}
+
+/// Rebuilds one variant from its field values, read in encoding order.
+type Decode = fn(&mut Fields<'_>) -> Option<WindowEvent>;
+
+/// A recorded variant: its name, its field names in encoding order, and how
+/// to decode it.
+struct Variant {
+    name: &'static str,
+    fields: &'static [&'static str],
+    decode: Decode,
+}
+
+impl Variant {
+    /// Rebuild an event from this variant's field values.
+    fn decode(&self, values: &[Value]) -> Option<WindowEvent> {
+        (self.decode)(&mut Fields { values, next: 0 })
+    }
+}
+
+const fn variant(name: &'static str, fields: &'static [&'static str], decode: Decode) -> Variant {
+    Variant {
+        name,
+        fields,
+        decode,
+    }
+}
+
+/// Every recorded variant. The index of a variant is its tag in the binary
+/// format, so entries are append-only.
+const SCHEMA: &[Variant] = &[
+    variant("CloseRequested", &[], |_| Some(WindowEvent::CloseRequested)),
+    variant("Resized", &["width", "height"], |f| {
+        Some(WindowEvent::Resized {
+            width: f.int()?,
+            height: f.int()?,
+        })
+    }),
+    variant("Moved", &["x", "y"], |f| {
+        Some(WindowEvent::Moved {
+            x: f.int()?,
+            y: f.int()?,
+        })
+    }),
+    variant("Focused", &[], |_| Some(WindowEvent::Focused)),
+    variant("Unfocused", &[], |_| Some(WindowEvent::Unfocused)),
+    variant("Minimized", &[], |_| Some(WindowEvent::Minimized)),
+    variant("Restored", &[], |_| Some(WindowEvent::Restored)),
+    variant("ScaleFactorChanged", &["scale_factor"], |f| {
+        Some(WindowEvent::ScaleFactorChanged {
+            scale_factor: f.num32()?,
+        })
+    }),
+    variant("SurfaceLost", &[], |_| Some(WindowEvent::SurfaceLost)),
+    variant("SurfaceRecreated", &[], |_| {
+        Some(WindowEvent::SurfaceRecreated)
+    }),
+    variant("MouseEntered", &[], |_| Some(WindowEvent::MouseEntered)),
+    variant("MouseExited", &[], |_| Some(WindowEvent::MouseExited)),
+    variant("MouseMove", &["x", "y", "modifiers"], |f| {
+        Some(WindowEvent::MouseMove {
+            x: f.num()?,
+            y: f.num()?,
+            modifiers: f.modifiers()?,
+        })
+    }),
+    variant("RawMouseMotion", &["delta_x", "delta_y"], |f| {
+        Some(WindowEvent::RawMouseMotion {
+            delta_x: f.num()?,
+            delta_y: f.num()?,
+        })
+    }),
+    variant(
+        "MouseButton",
+        &["button", "pressed", "modifiers", "x", "y", "click_count"],
+        |f| {
+            Some(WindowEvent::MouseButton {
+                button: MouseButton::from_raw(f.int()?),
+                pressed: f.boolean()?,
+                modifiers: f.modifiers()?,
+                x: f.num()?,
+                y: f.num()?,
+                click_count: f.int()?,
+            })
+        },
+    ),
+    variant(
+        "MouseWheel",
+        &["delta_x", "delta_y", "modifiers", "delta_kind", "phase"],
+        |f| f.wheel(),
+    ),
+    variant(
+        "KeyInput",
+        &[
+            "key",
+            "physical_key",
+            "logical_text",
+            "location",
+            "pressed",
+            "repeat",
+            "modifiers",
+        ],
+        |f| f.key(),
+    ),
+    variant("TextInput", &["text"], |f| {
+        Some(WindowEvent::TextInput { text: f.text()? })
+    }),
+    variant("ImeEnabled", &[], |_| Some(WindowEvent::ImeEnabled)),
+    variant("ImePreedit", &["text", "cursor_start", "cursor_end"], |f| {
+        Some(WindowEvent::ImePreedit {
+            text: f.text()?,
+            cursor_range: f.cursor_range()?,
+        })
+    }),
+    variant("ImeCommit", &["text"], |f| {
+        Some(WindowEvent::ImeCommit { text: f.text()? })
+    }),
+    variant("ImeDisabled", &[], |_| Some(WindowEvent::ImeDisabled)),
+    variant(
+        "Pointer",
+        &[
+            "pointer_id",
+            "kind",
+            "phase",
+            "x",
+            "y",
+            "pressure",
+            "tilt_x",
+            "tilt_y",
+            "twist",
+            "buttons",
+            "modifiers",
+        ],
+        |f| Some(WindowEvent::Pointer(f.pointer()?)),
+    ),
+    variant("DragEntered", &["data_kind", "items", "x", "y"], |f| {
+        Some(WindowEvent::DragEntered {
+            data: f.drag_data()?,
+            x: f.num()?,
+            y: f.num()?,
+        })
+    }),
+    variant("DragOver", &["x", "y"], |f| {
+        Some(WindowEvent::DragOver {
+            x: f.num()?,
+            y: f.num()?,
+        })
+    }),
+    variant("DragLeft", &[], |_| Some(WindowEvent::DragLeft)),
+    variant(
+        "Dropped",
+        &["data_kind", "items", "x", "y", "effect"],
+        |f| {
+            Some(WindowEvent::Dropped {
+                data: f.drag_data()?,
+                x: f.num()?,
+                y: f.num()?,
+                effect: DropEffect::from_raw(f.int()?),
+            })
+        },
+    ),
+    variant("Maximized", &[], |_| Some(WindowEvent::Maximized)),
+    variant("Unmaximized", &[], |_| Some(WindowEvent::Unmaximized)),
+    variant("EnteredFullscreen", &[], |_| {
+        Some(WindowEvent::EnteredFullscreen)
+    }),
+    variant("ExitedFullscreen", &[], |_| {
+        Some(WindowEvent::ExitedFullscreen)
+    }),
+    variant("AlwaysOnTopChanged", &["always_on_top"], |f| {
+        Some(WindowEvent::AlwaysOnTopChanged {
+            always_on_top: f.boolean()?,
+        })
+    }),
+    variant("ResizableChanged", &["resizable"], |f| {
+        Some(WindowEvent::ResizableChanged {
+            resizable: f.boolean()?,
+        })
+    }),
+    variant("DecorationsChanged", &["decorated"], |f| {
+        Some(WindowEvent::DecorationsChanged {
+            decorated: f.boolean()?,
+        })
+    }),
+    variant("TrayClicked", &["button", "x", "y"], |f| {
+        Some(WindowEvent::TrayClicked {
+            button: MouseButton::from_raw(f.int()?),
+            x: f.int()?,
+            y: f.int()?,
+        })
+    }),
+    variant("TrayDoubleClicked", &["x", "y"], |f| {
+        Some(WindowEvent::TrayDoubleClicked {
+            x: f.int()?,
+            y: f.int()?,
+        })
+    }),
+    variant("NotificationAction", &["action"], |f| {
+        Some(WindowEvent::NotificationAction { action: f.text()? })
+    }),
+    variant("NotificationClosed", &["reason"], |f| {
+        Some(WindowEvent::NotificationClosed {
+            reason: NotificationCloseReason::from_raw(f.int()?),
+        })
+    }),
+    variant("MonitorsChanged", &[], |_| {
+        Some(WindowEvent::MonitorsChanged)
+    }),
+    variant(
+        "AccessibilityAction",
+        &["canvas", "target", "action"],
+        |f| {
+            Some(WindowEvent::AccessibilityAction {
+                canvas: f.handle()?,
+                target: u64::from_ne_bytes(f.int::<i64>()?.to_ne_bytes()),
+                action: AccessibilityAction::from_raw(f.int()?)?,
+            })
+        },
+    ),
+    variant("FocusGained", &["kind", "handle", "id"], |f| {
+        Some(WindowEvent::FocusGained {
+            target: f.focus_target()?,
+        })
+    }),
+    variant("FocusLost", &["kind", "handle", "id"], |f| {
+        Some(WindowEvent::FocusLost {
+            target: f.focus_target()?,
+        })
+    }),
+    variant(
+        "TouchpadPinch",
+        &["phase", "x", "y", "scale", "rotation"],
+        |f| {
+            Some(WindowEvent::TouchpadPinch {
+                phase: TouchpadPhase::from_raw(f.int()?),
+                position: (f.num()?, f.num()?),
+                scale: f.num()?,
+                rotation: f.num()?,
+            })
+        },
+    ),
+];
+
+fn find_variant(name: &str) -> Option<&'static Variant> {
+    SCHEMA.iter().find(|variant| variant.name == name)
+}
+
+fn mods(modifiers: &Modifiers) -> Value {
+    Value::Int(i64::from(modifiers.to_bits()))
+}
+
+/// Split an event into its variant name and field values.
+fn encode_event(event: &WindowEvent) -> (&'static str, Vec<Value>) {
+    use Value::{Bool, Int, Num, Text};
+    match event {
+        WindowEvent::CloseRequested => ("CloseRequested", vec![]),
+        WindowEvent::Resized { width, height } => (
+            "Resized",
+            vec![Int(i64::from(*width)), Int(i64::from(*height))],
+        ),
+        WindowEvent::Moved { x, y } => ("Moved", vec![Int(i64::from(*x)), Int(i64::from(*y))]),
+        WindowEvent::Focused => ("Focused", vec![]),
+        WindowEvent::Unfocused => ("Unfocused", vec![]),
+        WindowEvent::Minimized => ("Minimized", vec![]),
+        WindowEvent::Restored => ("Restored", vec![]),
+        WindowEvent::Maximized => ("Maximized", vec![]),
+        WindowEvent::Unmaximized => ("Unmaximized", vec![]),
+        WindowEvent::EnteredFullscreen => ("EnteredFullscreen", vec![]),
+        WindowEvent::ExitedFullscreen => ("ExitedFullscreen", vec![]),
+        WindowEvent::AlwaysOnTopChanged { always_on_top } => {
+            ("AlwaysOnTopChanged", vec![Bool(*always_on_top)])
+        }
+        WindowEvent::ResizableChanged { resizable } => ("ResizableChanged", vec![Bool(*resizable)]),
+        WindowEvent::DecorationsChanged { decorated } => {
+            ("DecorationsChanged", vec![Bool(*decorated)])
+        }
+        WindowEvent::ScaleFactorChanged { scale_factor } => {
+            ("ScaleFactorChanged", vec![Value::F32(*scale_factor)])
+        }
+        WindowEvent::MonitorsChanged => ("MonitorsChanged", vec![]),
+        WindowEvent::SurfaceLost => ("SurfaceLost", vec![]),
+        WindowEvent::SurfaceRecreated => ("SurfaceRecreated", vec![]),
+        WindowEvent::MouseEntered => ("MouseEntered", vec![]),
+        WindowEvent::MouseExited => ("MouseExited", vec![]),
+        WindowEvent::MouseMove { x, y, modifiers } => {
+            ("MouseMove", vec![Num(*x), Num(*y), mods(modifiers)])
+        }
+        WindowEvent::RawMouseMotion { delta_x, delta_y } => {
+            ("RawMouseMotion", vec![Num(*delta_x), Num(*delta_y)])
+        }
+        WindowEvent::MouseButton {
+            button,
+            pressed,
+            modifiers,
+            x,
+            y,
+            click_count,
+        } => (
+            "MouseButton",
+            vec![
+                Int(i64::from(button.to_raw())),
+                Bool(*pressed),
+                mods(modifiers),
+                Num(*x),
+                Num(*y),
+                Int(i64::from(*click_count)),
+            ],
+        ),
+        WindowEvent::MouseWheel {
+            delta_x,
+            delta_y,
+            modifiers,
+            delta_kind,
+            phase,
+        } => (
+            "MouseWheel",
+            vec![
+                Num(*delta_x),
+                Num(*delta_y),
+                mods(modifiers),
+                Int(i64::from(delta_kind.to_raw())),
+                Int(i64::from(phase.to_raw())),
+            ],
+        ),
+        WindowEvent::KeyInput {
+            key,
+            physical_key,
+            logical_key,
+            location,
+            pressed,
+            repeat,
+            modifiers,
+        } => (
+            "KeyInput",
+            vec![
+                Int(i64::from(key.to_raw())),
+                Int(i64::from(physical_key.to_raw())),
+                // Non-character logical keys are rebuilt from `key`.
+                Text(logical_key.text().unwrap_or_default().to_owned()),
+                Int(i64::from(location.to_raw())),
+                Bool(*pressed),
+                Bool(*repeat),
+                mods(modifiers),
+            ],
+        ),
+        WindowEvent::TextInput { text } => ("TextInput", vec![Text(text.clone())]),
+        WindowEvent::ImeEnabled => ("ImeEnabled", vec![]),
+        WindowEvent::ImePreedit { text, cursor_range } => {
+            ("ImePreedit", preedit_values(text, *cursor_range))
+        }
+        WindowEvent::ImeCommit { text } => ("ImeCommit", vec![Text(text.clone())]),
+        WindowEvent::ImeDisabled => ("ImeDisabled", vec![]),
+        WindowEvent::Pointer(pointer) => ("Pointer", pointer_values(pointer)),
+        WindowEvent::TouchpadPinch {
+            phase,
+            position,
+            scale,
+            rotation,
+        } => (
+            "TouchpadPinch",
+            vec![
+                Int(i64::from(phase.to_raw())),
+                Num(position.0),
+                Num(position.1),
+                Num(*scale),
+                Num(*rotation),
+            ],
+        ),
+        WindowEvent::DragEntered { data, x, y } => {
+            let mut values = drag_values(data);
+            values.extend([Num(*x), Num(*y)]);
+            ("DragEntered", values)
+        }
+        WindowEvent::DragOver { x, y } => ("DragOver", vec![Num(*x), Num(*y)]),
+        WindowEvent::DragLeft => ("DragLeft", vec![]),
+        WindowEvent::Dropped { data, x, y, effect } => {
+            let mut values = drag_values(data);
+            values.extend([Num(*x), Num(*y), Int(i64::from(effect.to_raw()))]);
+            ("Dropped", values)
+        }
+        WindowEvent::TrayClicked { button, x, y } => (
+            "TrayClicked",
+            vec![
+                Int(i64::from(button.to_raw())),
+                Int(i64::from(*x)),
+                Int(i64::from(*y)),
+            ],
+        ),
+        WindowEvent::TrayDoubleClicked { x, y } => (
+            "TrayDoubleClicked",
+            vec![Int(i64::from(*x)), Int(i64::from(*y))],
+        ),
+        WindowEvent::NotificationAction { action } => {
+            ("NotificationAction", vec![Text(action.clone())])
+        }
+        WindowEvent::AccessibilityAction {
+            canvas,
+            target,
+            action,
+        } => (
+            "AccessibilityAction",
+            vec![
+                handle_value(*canvas),
+                Int(i64::from_ne_bytes(target.to_ne_bytes())),
+                Int(i64::from(action.to_raw())),
+            ],
+        ),
+        WindowEvent::NotificationClosed { reason } => {
+            ("NotificationClosed", vec![Int(i64::from(reason.to_raw()))])
+        }
+        WindowEvent::FocusGained { target } => ("FocusGained", focus_values(*target)),
+        WindowEvent::FocusLost { target } => ("FocusLost", focus_values(*target)),
+    }
+}
+
+/// Handles are addresses and only meaningful within the recording process.
+fn focus_values(target: FocusTarget) -> Vec<Value> {
+    let (kind, handle, id) = target.to_raw();
+    vec![
+        Value::Int(i64::from(kind)),
+        handle_value(handle),
+        Value::Int(i64::from_ne_bytes(id.to_ne_bytes())),
+    ]
+}
+
+fn handle_value(handle: usize) -> Value {
+    Value::Int(i64::from_ne_bytes((handle as u64).to_ne_bytes()))
+}
+
+/// Items are stored NUL-separated, which no path, URI or text contains.
+fn drag_values(data: &DragData) -> Vec<Value> {
+    let (kind, items) = data.to_raw();
+    vec![Value::Int(i64::from(kind)), Value::Text(items.join("\0"))]
+}
+
+fn pointer_values(pointer: &PointerEvent) -> Vec<Value> {
+    use Value::{F32, Int, Num};
+    vec![
+        // Platform ids may use the full u64 range; store the bit pattern.
+        Int(i64::from_ne_bytes(pointer.pointer_id.to_ne_bytes())),
+        Int(i64::from(pointer.kind.to_raw())),
+        Int(i64::from(pointer.phase.to_raw())),
+        Num(pointer.position.0),
+        Num(pointer.position.1),
+        F32(pointer.pressure),
+        F32(pointer.tilt.0),
+        F32(pointer.tilt.1),
+        F32(pointer.twist),
+        Int(i64::from(pointer.buttons.bits())),
+        mods(&pointer.modifiers),
+    ]
+}
+
+/// A missing preedit cursor is stored as `-1, -1`.
+fn preedit_values(text: &str, cursor_range: Option<(usize, usize)>) -> Vec<Value> {
+    let offset = |n: usize| Value::Int(i64::try_from(n).unwrap_or(i64::MAX));
+    let (start, end) = match cursor_range {
+        Some((start, end)) => (offset(start), offset(end)),
+        None => (Value::Int(-1), Value::Int(-1)),
+    };
+    vec![Value::Text(text.to_owned()), start, end]
+}
+
+/// Sequential reader over decoded field values.
+struct Fields<'a> {
+    values: &'a [Value],
+    next: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn take(&mut self) -> Option<&'a Value> {
+        let value = self.values.get(self.next);
+        self.next += 1;
+        value
+    }
+
+    fn num(&mut self) -> Option<f64> {
+        match self.take()? {
+            Value::Num(n) => Some(*n),
+            Value::Raw(raw) => raw.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn num32(&mut self) -> Option<f32> {
+        match self.take()? {
+            Value::F32(n) => Some(*n),
+            Value::Raw(raw) => raw.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn int<T: TryFrom<i64>>(&mut self) -> Option<T> {
+        let raw = match self.take()? {
+            Value::Int(n) => *n,
+            Value::Raw(raw) => raw.parse().ok()?,
+            _ => return None,
+        };
+        T::try_from(raw).ok()
+    }
+
+    fn boolean(&mut self) -> Option<bool> {
+        match self.take()? {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    fn text(&mut self) -> Option<String> {
+        match self.take()? {
+            Value::Text(t) => Some(t.clone()),
+            _ => None,
+        }
+    }
+
+    fn modifiers(&mut self) -> Option<Modifiers> {
+        self.int::<u32>().map(Modifiers::from_bits)
+    }
+
+    fn wheel(&mut self) -> Option<WindowEvent> {
+        Some(WindowEvent::MouseWheel {
+            delta_x: self.num()?,
+            delta_y: self.num()?,
+            modifiers: self.modifiers()?,
+            delta_kind: ScrollDeltaKind::from_raw(self.int()?),
+            phase: ScrollPhase::from_raw(self.int()?),
+        })
+    }
+
+    fn key(&mut self) -> Option<WindowEvent> {
+        let key = KeyCode::from_raw(self.int()?);
+        Some(WindowEvent::KeyInput {
+            key,
+            physical_key: PhysicalKey::from_raw(self.int()?),
+            logical_key: LogicalKey::new(key, Some(&self.text()?)),
+            location: KeyLocation::from_raw(self.int()?),
+            pressed: self.boolean()?,
+            repeat: self.boolean()?,
+            modifiers: self.modifiers()?,
+        })
+    }
+
+    fn pointer(&mut self) -> Option<PointerEvent> {
+        Some(PointerEvent {
+            pointer_id: u64::from_ne_bytes(self.int::<i64>()?.to_ne_bytes()),
+            kind: PointerKind::from_raw(self.int()?),
+            phase: PointerPhase::from_raw(self.int()?),
+            position: (self.num()?, self.num()?),
+            pressure: self.num32()?,
+            tilt: (self.num32()?, self.num32()?),
+            twist: self.num32()?,
+            buttons: PointerButtons::from_bits(self.int()?),
+            modifiers: self.modifiers()?,
+        })
+    }
+
+    fn drag_data(&mut self) -> Option<DragData> {
+        let kind = self.int()?;
+        let items = self.text()?;
+        let items = if items.is_empty() {
+            Vec::new()
+        } else {
+            items.split('\0').map(str::to_owned).collect()
+        };
+        Some(DragData::from_raw(kind, items))
+    }
+
+    fn focus_target(&mut self) -> Option<FocusTarget> {
+        let kind = self.int()?;
+        let handle = self.handle()?;
+        let id = u64::from_ne_bytes(self.int::<i64>()?.to_ne_bytes());
+        FocusTarget::from_raw(kind, handle, id)
+    }
+
+    fn handle(&mut self) -> Option<usize> {
+        let handle = u64::from_ne_bytes(self.int::<i64>()?.to_ne_bytes());
+        usize::try_from(handle).ok()
+    }
+
+    /// Two offsets, either both valid or both negative (no cursor).
+    fn cursor_range(&mut self) -> Option<Option<(usize, usize)>> {
+        let start = self.int::<i64>()?;
+        let end = self.int::<i64>()?;
+        if start < 0 && end < 0 {
+            return Some(None);
+        }
+        Some(Some((
+            usize::try_from(start).ok()?,
+            usize::try_from(end).ok()?,
+        )))
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> AureaError {
+    AureaError::InvalidRecording(msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> EventRecording {
+        let events = vec![
+            WindowEvent::Resized {
+                width: 800,
+                height: 600,
+            },
+            WindowEvent::MouseMove {
+                x: 10.5,
+                y: -3.25,
+                modifiers: Modifiers {
+                    alt: true,
+                    ..Modifiers::new()
+                },
+            },
+            WindowEvent::MouseWheel {
+                delta_x: 0.0,
+                delta_y: -14.5,
+                modifiers: Modifiers::new(),
+                delta_kind: ScrollDeltaKind::Pixel,
+                phase: ScrollPhase::MomentumBegan,
+            },
+            WindowEvent::MouseButton {
+                button: MouseButton::Other(7),
+                pressed: true,
+                modifiers: Modifiers::from_bits(0b0101),
+                x: 10.5,
+                y: 20.0,
+                click_count: 2,
+            },
+            WindowEvent::KeyInput {
+                key: KeyCode::Unknown(9000),
+                physical_key: PhysicalKey::Unidentified(0x1F0),
+                logical_key: LogicalKey::Unidentified,
+                location: KeyLocation::Standard,
+                pressed: false,
+                repeat: false,
+                modifiers: Modifiers::from_bits(0b0010),
+            },
+            WindowEvent::KeyInput {
+                key: KeyCode::Z,
+                physical_key: PhysicalKey::KeyY,
+                logical_key: LogicalKey::Character("Z".to_owned()),
+                location: KeyLocation::Standard,
+                pressed: true,
+                repeat: true,
+                modifiers: Modifiers::from_bits(0b01_0001),
+            },
+            WindowEvent::TextInput {
+                text: "héllo \"quoted\"\n\u{1F600}".to_owned(),
+            },
+            WindowEvent::ScaleFactorChanged { scale_factor: 1.5 },
+            WindowEvent::MonitorsChanged,
+            WindowEvent::AccessibilityAction {
+                canvas: 0x5555_1000,
+                target: u64::MAX - 1,
+                action: AccessibilityAction::ShowContextMenu,
+            },
+            WindowEvent::FocusGained {
+                target: FocusTarget::CanvasItem {
+                    canvas: 0x5555_0000,
+                    id: u64::MAX,
+                },
+            },
+            WindowEvent::FocusLost {
+                target: FocusTarget::Element(0x5555_1000),
+            },
+            WindowEvent::ImeEnabled,
+            WindowEvent::ImePreedit {
+                text: "にほ".to_owned(),
+                cursor_range: Some((3, 6)),
+            },
+            WindowEvent::ImePreedit {
+                text: String::new(),
+                cursor_range: None,
+            },
+            WindowEvent::ImeCommit {
+                text: "日本".to_owned(),
+            },
+            WindowEvent::ImeDisabled,
+            WindowEvent::Pointer(PointerEvent {
+                pointer_id: u64::MAX - 1,
+                kind: PointerKind::Pen,
+                phase: PointerPhase::Move,
+                position: (12.25, 40.5),
+                pressure: 0.625,
+                tilt: (-30.0, 12.5),
+                twist: 270.0,
+                buttons: PointerButtons::PRIMARY | PointerButtons::ERASER,
+                modifiers: Modifiers::from_bits(0b1000),
+            }),
+            WindowEvent::TouchpadPinch {
+                phase: TouchpadPhase::Changed,
+                position: (120.5, 64.0),
+                scale: 0.875,
+                rotation: -0.25,
+            },
+            WindowEvent::DragEntered {
+                data: DragData::Files(vec!["/tmp/a b.png".into(), "c.txt".into()]),
+                x: 4.5,
+                y: 8.0,
+            },
+            WindowEvent::DragOver { x: 5.0, y: 9.25 },
+            WindowEvent::DragLeft,
+            WindowEvent::Dropped {
+                data: DragData::Text("line\nbreak".to_owned()),
+                x: 6.0,
+                y: 1.5,
+                effect: DropEffect::Copy,
+            },
+            WindowEvent::Maximized,
+            WindowEvent::EnteredFullscreen,
+            WindowEvent::AlwaysOnTopChanged {
+                always_on_top: true,
+            },
+            WindowEvent::DecorationsChanged { decorated: false },
+            WindowEvent::TrayClicked {
+                button: MouseButton::Right,
+                x: 1910,
+                y: -4,
+            },
+            WindowEvent::TrayDoubleClicked { x: 1910, y: 2 },
+            WindowEvent::NotificationAction {
+                action: "reply".to_owned(),
+            },
+            WindowEvent::NotificationClosed {
+                reason: NotificationCloseReason::Dismissed,
+            },
+        ];
+        EventRecording {
+            events: events
+                .into_iter()
+                .enumerate()
+                .map(|(i, event)| RecordedEvent {
+                    at: Duration::from_micros(1_000 * i as u64 + 7),
+                    event,
+                })
+                .collect(),
+        }
+    }
+
+    fn assert_same(a: &EventRecording, b: &EventRecording) {
+        assert_eq!(a.events.len(), b.events.len());
+        for (x, y) in a.events.iter().zip(&b.events) {
+            assert_eq!(x.at, y.at);
+            assert_eq!(format!("{:?}", x.event), format!("{:?}", y.event));
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let recording = sample();
+        let decoded = EventRecording::from_json(&recording.to_json()).unwrap();
+        assert_same(&recording, &decoded);
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let recording = sample();
+        let decoded = EventRecording::from_bytes(&recording.to_bytes()).unwrap();
+        assert_same(&recording, &decoded);
+    }
+
+    #[test]
+    fn schema_names_are_unique() {
+        for (i, variant) in SCHEMA.iter().enumerate() {
+            assert!(
+                SCHEMA[..i]
+                    .iter()
+                    .all(|earlier| earlier.name != variant.name),
+                "{} listed twice",
+                variant.name
+            );
+        }
+    }
+
+    #[test]
+    fn corrupt_input_is_rejected() {
+        assert!(EventRecording::from_json("{\"events\": [{\"event\": \"Nope\"}]}").is_err());
+        let mut bytes = sample().to_bytes();
+        bytes.truncate(bytes.len() - 3);
+        assert!(EventRecording::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn queue_records_pushes_and_replays_immediately() {
+        let source = EventQueue::new();
+        source.start_recording();
+        source.push(WindowEvent::Focused);
+        source.push(WindowEvent::TextInput {
+            text: "x".to_owned(),
+        });
+        let recording = source.stop_recording().unwrap();
+        assert_eq!(recording.events.len(), 2);
+        assert!(source.stop_recording().is_none());
+
+        let target = EventQueue::new();
+        recording.replay_immediately(&target);
+        let replayed = target.pop_all();
+        assert!(matches!(replayed[0], WindowEvent::Focused));
+        assert!(matches!(&replayed[1], WindowEvent::TextInput { text } if text == "x"));
+    }
+}
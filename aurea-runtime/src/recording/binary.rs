@@ -0,0 +1,182 @@
+//! Compact binary encoding of event recordings.
+//!
+//! Layout: the 8-byte magic (whose last byte is the format version), the
+//! event count, then per event its offset in microseconds, its schema tag
+//! and each field as a kind byte plus payload. Counts, offsets, integers and
+//! string lengths are LEB128 varints; integers are zigzag-encoded first.
+
+use super::{EventRecording, RecordedEvent, SCHEMA, Value, encode_event, invalid};
+use aurea_foundation::AureaResult;
+use std::time::Duration;
+
+const MAGIC: &[u8; 8] = b"AUREAEV\x05";
+
+const KIND_F64: u8 = 0;
+const KIND_F32: u8 = 1;
+const KIND_INT: u8 = 2;
+const KIND_BOOL: u8 = 3;
+const KIND_TEXT: u8 = 4;
+
+pub(super) fn has_magic(bytes: &[u8]) -> bool {
+    bytes.starts_with(&MAGIC[..7])
+}
+
+pub(super) fn encode(recording: &EventRecording) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    write_varint(&mut out, recording.events.len() as u64);
+    for recorded in &recording.events {
+        let (name, values) = encode_event(&recorded.event);
+        let tag = SCHEMA
+            .iter()
+            .position(|variant| variant.name == name)
+            .and_then(|tag| u8::try_from(tag).ok())
+            .unwrap_or(u8::MAX);
+        write_varint(
+            &mut out,
+            u64::try_from(recorded.at.as_micros()).unwrap_or(u64::MAX),
+        );
+        out.push(tag);
+        for value in &values {
+            write_value(&mut out, value);
+        }
+    }
+    out
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Num(n) => {
+            out.push(KIND_F64);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        Value::F32(n) => {
+            out.push(KIND_F32);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        Value::Int(n) => {
+            out.push(KIND_INT);
+            write_varint(out, zigzag(*n));
+        }
+        Value::Bool(b) => {
+            out.push(KIND_BOOL);
+            out.push(u8::from(*b));
+        }
+        Value::Text(text) | Value::Raw(text) => {
+            out.push(KIND_TEXT);
+            write_varint(out, text.len() as u64);
+            out.extend_from_slice(text.as_bytes());
+        }
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut n: u64) {
+    loop {
+        let byte = u8::try_from(n & 0x7F).unwrap_or_default();
+        n >>= 7;
+        if n == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn zigzag(n: i64) -> u64 {
+    u64::from_ne_bytes(((n << 1) ^ (n >> 63)).to_ne_bytes())
+}
+
+fn unzigzag(n: u64) -> i64 {
+    i64::from_ne_bytes((n >> 1).to_ne_bytes()) ^ -i64::from_ne_bytes((n & 1).to_ne_bytes())
+}
+
+pub(super) fn decode(bytes: &[u8]) -> AureaResult<EventRecording> {
+    if !bytes.starts_with(MAGIC) {
+        return Err(invalid("missing binary header or unsupported version"));
+    }
+    let mut reader = Reader {
+        bytes,
+        pos: MAGIC.len(),
+    };
+    let count = reader.varint()?;
+    let mut events = Vec::new();
+    for _ in 0..count {
+        events.push(reader.event()?);
+    }
+    if reader.pos != bytes.len() {
+        return Err(invalid("trailing bytes after last event"));
+    }
+    Ok(EventRecording { events })
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> AureaResult<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or_else(|| invalid("truncated binary recording"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> AureaResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> AureaResult<u64> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            n |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(invalid("varint too long"))
+    }
+
+    fn event(&mut self) -> AureaResult<RecordedEvent> {
+        let at = Duration::from_micros(self.varint()?);
+        let tag = usize::from(self.byte()?);
+        let variant = SCHEMA
+            .get(tag)
+            .ok_or_else(|| invalid(format!("unknown event tag {tag}")))?;
+        let values = (0..variant.fields.len())
+            .map(|_| self.value())
+            .collect::<AureaResult<Vec<_>>>()?;
+        let event = variant
+            .decode(&values)
+            .ok_or_else(|| invalid(format!("malformed {} event", variant.name)))?;
+        Ok(RecordedEvent { at, event })
+    }
+
+    fn value(&mut self) -> AureaResult<Value> {
+        let value = match self.byte()? {
+            KIND_F64 => Value::Num(f64::from_le_bytes(self.array()?)),
+            KIND_F32 => Value::F32(f32::from_le_bytes(self.array()?)),
+            KIND_INT => Value::Int(unzigzag(self.varint()?)),
+            KIND_BOOL => Value::Bool(self.byte()? != 0),
+            KIND_TEXT => {
+                let len = usize::try_from(self.varint()?)
+                    .map_err(|_| invalid("string length overflow"))?;
+                let text = String::from_utf8(self.take(len)?.to_vec())
+                    .map_err(|_| invalid("string is not UTF-8"))?;
+                Value::Text(text)
+            }
+            kind => return Err(invalid(format!("unknown field kind {kind}"))),
+        };
+        Ok(value)
+    }
+
+    fn array<const N: usize>(&mut self) -> AureaResult<[u8; N]> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+}
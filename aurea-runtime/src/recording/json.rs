@@ -0,0 +1,293 @@
+//! JSON encoding of event recordings.
+//!
+//! One object per event with the offset in microseconds, the variant name
+//! and the variant's fields flattened alongside:
+//!
+//! ```text
+//! {"format": "aurea-events", "version": 5, "events": [
+//!   {"at_us": 1200, "event": "MouseMove", "x": 10.5, "y": 4, "modifiers": 0}
+//! ]}
+//! ```
+
+use super::{EventRecording, RecordedEvent, Value, encode_event, find_variant, invalid};
+use aurea_foundation::AureaResult;
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::time::Duration;
+
+const FORMAT_NAME: &str = "aurea-events";
+const VERSION: u32 = 5;
+
+pub(super) fn encode(recording: &EventRecording) -> String {
+    let mut out = format!("{{\"format\": \"{FORMAT_NAME}\", \"version\": {VERSION}, \"events\": [");
+    for (i, recorded) in recording.events.iter().enumerate() {
+        out.push_str(if i == 0 { "\n  " } else { ",\n  " });
+        encode_one(&mut out, recorded);
+    }
+    out.push_str("\n]}\n");
+    out
+}
+
+fn encode_one(out: &mut String, recorded: &RecordedEvent) {
+    let (name, values) = encode_event(&recorded.event);
+    let fields = find_variant(name).map_or(&[][..], |variant| variant.fields);
+    let _ = write!(
+        out,
+        "{{\"at_us\": {}, \"event\": \"{name}\"",
+        recorded.at.as_micros()
+    );
+    for (field, value) in fields.iter().zip(&values) {
+        let _ = write!(out, ", \"{field}\": ");
+        write_value(out, value);
+    }
+    out.push('}');
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    let _ = match value {
+        Value::Num(n) if n.is_finite() => write!(out, "{n}"),
+        Value::F32(n) if n.is_finite() => write!(out, "{n}"),
+        Value::Num(_) | Value::F32(_) => write!(out, "null"),
+        Value::Int(n) => write!(out, "{n}"),
+        Value::Bool(b) => write!(out, "{b}"),
+        Value::Raw(raw) => write!(out, "{raw}"),
+        Value::Text(text) => {
+            write_string(out, text);
+            Ok(())
+        }
+    };
+}
+
+fn write_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+pub(super) fn decode(text: &str) -> AureaResult<EventRecording> {
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+    };
+    let root = parser.value()?;
+    parser.skip_ws();
+    if parser.chars.peek().is_some() {
+        return Err(invalid("trailing characters after JSON document"));
+    }
+    let Json::Object(members) = root else {
+        return Err(invalid("expected a JSON object"));
+    };
+    if let Some(Json::Number(version)) = member(&members, "version")
+        && version.parse::<u32>().ok() != Some(VERSION)
+    {
+        return Err(invalid(format!("unsupported version {version}")));
+    }
+    let Some(Json::Array(items)) = member(&members, "events") else {
+        return Err(invalid("missing \"events\" array"));
+    };
+    let events = items
+        .iter()
+        .map(decode_one)
+        .collect::<AureaResult<Vec<_>>>()?;
+    Ok(EventRecording { events })
+}
+
+fn decode_one(item: &Json) -> AureaResult<RecordedEvent> {
+    let Json::Object(members) = item else {
+        return Err(invalid("event entry is not an object"));
+    };
+    let at = match member(members, "at_us") {
+        Some(Json::Number(raw)) => raw
+            .parse::<u64>()
+            .map(Duration::from_micros)
+            .map_err(|_| invalid(format!("bad at_us {raw}")))?,
+        _ => return Err(invalid("event entry without at_us")),
+    };
+    let Some(Json::String(name)) = member(members, "event") else {
+        return Err(invalid("event entry without a name"));
+    };
+    let variant = find_variant(name).ok_or_else(|| invalid(format!("unknown event {name}")))?;
+    let values: Vec<Value> = variant
+        .fields
+        .iter()
+        .map(|field| member(members, field).map_or(Value::Raw(String::new()), to_value))
+        .collect();
+    let event = variant
+        .decode(&values)
+        .ok_or_else(|| invalid(format!("malformed {name} event")))?;
+    Ok(RecordedEvent { at, event })
+}
+
+fn to_value(json: &Json) -> Value {
+    match json {
+        Json::Number(raw) => Value::Raw(raw.clone()),
+        Json::Bool(b) => Value::Bool(*b),
+        Json::String(s) => Value::Text(s.clone()),
+        // Non-finite floats are written as null.
+        Json::Null => Value::Num(f64::NAN),
+        Json::Array(_) | Json::Object(_) => Value::Raw(String::new()),
+    }
+}
+
+fn member<'a>(members: &'a [(String, Json)], key: &str) -> Option<&'a Json> {
+    members.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+enum Json {
+    Null,
+    Bool(bool),
+    /// Kept as source text so integers never round-trip through f64.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_ws(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, want: char) -> AureaResult<()> {
+        self.skip_ws();
+        match self.chars.next() {
+            Some(c) if c == want => Ok(()),
+            other => Err(invalid(format!("expected '{want}', found {other:?}"))),
+        }
+    }
+
+    fn value(&mut self) -> AureaResult<Json> {
+        self.skip_ws();
+        match self.chars.peek().copied() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::String),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('n') => self.keyword("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => Ok(self.number()),
+            other => Err(invalid(format!("unexpected {other:?}"))),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> AureaResult<Json> {
+        for want in word.chars() {
+            if self.chars.next() != Some(want) {
+                return Err(invalid(format!("expected {word}")));
+            }
+        }
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Json {
+        let mut raw = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            raw.push(c);
+        }
+        Json::Number(raw)
+    }
+
+    fn string(&mut self) -> AureaResult<String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(out),
+                Some('\\') => out.push(self.escape()?),
+                Some(c) => out.push(c),
+                None => return Err(invalid("unterminated string")),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> AureaResult<char> {
+        let c = match self.chars.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('u') => return self.unicode_escape(),
+            Some(c @ ('"' | '\\' | '/')) => c,
+            other => return Err(invalid(format!("bad escape {other:?}"))),
+        };
+        Ok(c)
+    }
+
+    fn unicode_escape(&mut self) -> AureaResult<char> {
+        let high = self.hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| invalid("bad \\u escape"));
+        }
+        // Surrogate pair: a second \uXXXX must follow.
+        if self.chars.next() != Some('\\') || self.chars.next() != Some('u') {
+            return Err(invalid("unpaired surrogate"));
+        }
+        let low = self.hex4()?;
+        let code = 0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+        char::from_u32(code).ok_or_else(|| invalid("bad surrogate pair"))
+    }
+
+    fn hex4(&mut self) -> AureaResult<u32> {
+        let digits: String = (0..4).filter_map(|_| self.chars.next()).collect();
+        u32::from_str_radix(&digits, 16).map_err(|_| invalid("bad \\u escape"))
+    }
+
+    fn array(&mut self) -> AureaResult<Json> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_ws();
+        if self.chars.next_if_eq(&']').is_some() {
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_ws();
+            match self.chars.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Json::Array(items)),
+                other => return Err(invalid(format!("expected ',' or ']', found {other:?}"))),
+            }
+        }
+    }
+
+    fn object(&mut self) -> AureaResult<Json> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        self.skip_ws();
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_ws();
+            let key = self.string()?;
+            self.expect(':')?;
+            members.push((key, self.value()?));
+            self.skip_ws();
+            match self.chars.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Json::Object(members)),
+                other => return Err(invalid(format!("expected ',' or '}}', found {other:?}"))),
+            }
+        }
+    }
+}
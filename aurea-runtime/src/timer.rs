@@ -0,0 +1,462 @@
+//! One-shot and repeating timers on the UI loop.
+//!
+//! Timers live in a hashed timer wheel. The frame scheduler fires due timers
+//! at the start of each frame pass and asks the platform for a delayed
+//! wake-up at the earliest remaining deadline, so an idle loop sleeps until
+//! the next timer instead of ticking every vsync.
+
+use aurea_foundation::lock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::frame_queue::FrameScheduler;
+
+/// Number of buckets in the wheel; one rotation spans `WHEEL_SLOTS * WHEEL_TICK`.
+const WHEEL_SLOTS: usize = 256;
+/// Bucket granularity. Deadlines themselves are exact; the tick only decides
+/// which bucket a timer is filed under.
+const WHEEL_TICK: Duration = Duration::from_millis(4);
+/// Timers this close to their deadline fire now rather than costing another
+/// platform wake-up.
+const TIMER_SLACK: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+enum TimerCallback {
+    Once(Box<dyn FnOnce() + Send>),
+    Repeat(Box<dyn FnMut() + Send>, Duration),
+}
+
+struct TimerEntry {
+    deadline: Instant,
+    tick: u64,
+    callback: TimerCallback,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Handle returned by [`FrameScheduler::after`] and [`FrameScheduler::every`].
+///
+/// Dropping the handle does not cancel the timer; call [`TimerHandle::cancel`].
+#[derive(Debug, Clone)]
+pub struct TimerHandle {
+    id: TimerId,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TimerHandle {
+    pub fn id(&self) -> TimerId {
+        self.id
+    }
+
+    /// Stop the timer. Safe to call from inside its own callback, and more
+    /// than once.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        lock(&TIMERS).remove(self.id);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Hashed timer wheel keyed by absolute tick since `origin`.
+pub(crate) struct TimerWheel {
+    origin: Instant,
+    current_tick: u64,
+    slots: Vec<Vec<TimerId>>,
+    entries: HashMap<TimerId, TimerEntry>,
+}
+
+/// A timer taken out of the wheel because it is due.
+pub(crate) struct DueTimer {
+    id: TimerId,
+    deadline: Instant,
+    callback: TimerCallback,
+    cancelled: Arc<AtomicBool>,
+}
+
+static TIMER_COUNTER: AtomicU64 = AtomicU64::new(0);
+pub(crate) static TIMERS: LazyLock<Mutex<TimerWheel>> =
+    LazyLock::new(|| Mutex::new(TimerWheel::new(Instant::now())));
+
+impl TimerWheel {
+    pub(crate) fn new(origin: Instant) -> Self {
+        Self {
+            origin,
+            current_tick: 0,
+            slots: (0..WHEEL_SLOTS).map(|_| Vec::new()).collect(),
+            entries: HashMap::new(),
+        }
+    }
+
+    fn tick_of(&self, at: Instant) -> u64 {
+        let since = at.saturating_duration_since(self.origin);
+        u64::try_from(since.as_nanos() / WHEEL_TICK.as_nanos()).unwrap_or(u64::MAX)
+    }
+
+    fn slot_of(tick: u64) -> usize {
+        usize::try_from(tick % WHEEL_SLOTS as u64).unwrap_or(0)
+    }
+
+    fn insert(
+        &mut self,
+        id: TimerId,
+        deadline: Instant,
+        callback: TimerCallback,
+        cancelled: Arc<AtomicBool>,
+    ) {
+        // Never file a timer behind the cursor, or it would wait a whole rotation.
+        let tick = self.tick_of(deadline).max(self.current_tick);
+        self.slots[Self::slot_of(tick)].push(id);
+        self.entries.insert(
+            id,
+            TimerEntry {
+                deadline,
+                tick,
+                callback,
+                cancelled,
+            },
+        );
+    }
+
+    pub(crate) fn remove(&mut self, id: TimerId) {
+        if let Some(entry) = self.entries.remove(&id) {
+            self.slots[Self::slot_of(entry.tick)].retain(|other| *other != id);
+        }
+    }
+
+    /// Earliest deadline in the wheel. Walks buckets from the cursor and stops
+    /// at the first one holding a timer for the current rotation; timers
+    /// filed a rotation or more ahead only need the full scan when nothing
+    /// nearer exists.
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        for offset in 0..WHEEL_SLOTS as u64 {
+            let tick = self.current_tick + offset;
+            let earliest = self.slots[Self::slot_of(tick)]
+                .iter()
+                .filter_map(|id| self.entries.get(id))
+                .filter(|entry| entry.tick <= tick)
+                .map(|entry| entry.deadline)
+                .min();
+            if earliest.is_some() {
+                return earliest;
+            }
+        }
+        self.entries.values().map(|entry| entry.deadline).min()
+    }
+
+    /// Remove and return every timer due at `now`, earliest deadline first.
+    pub(crate) fn take_due(&mut self, now: Instant) -> Vec<DueTimer> {
+        let target = self.tick_of(now + TIMER_SLACK).max(self.current_tick);
+        // After a long sleep every bucket may hold due timers; visit each once.
+        let steps = (target - self.current_tick).min(WHEEL_SLOTS as u64 - 1);
+        let mut due_ids = Vec::new();
+        for offset in 0..=steps {
+            let slot = &mut self.slots[Self::slot_of(target - offset)];
+            let entries = &self.entries;
+            slot.retain(|id| {
+                let is_due = entries
+                    .get(id)
+                    .is_some_and(|entry| entry.deadline <= now + TIMER_SLACK);
+                if is_due {
+                    due_ids.push(*id);
+                }
+                !is_due
+            });
+        }
+        self.current_tick = target;
+
+        let mut due: Vec<DueTimer> = due_ids
+            .into_iter()
+            .filter_map(|id| {
+                self.entries.remove(&id).map(|entry| DueTimer {
+                    id,
+                    deadline: entry.deadline,
+                    callback: entry.callback,
+                    cancelled: entry.cancelled,
+                })
+            })
+            .collect();
+        due.sort_by_key(|timer| timer.deadline);
+        due
+    }
+}
+
+impl FrameScheduler {
+    /// Run `f` once on the UI loop after `delay`.
+    pub fn after<F>(delay: Duration, f: F) -> TimerHandle
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        Self::insert_timer(delay, TimerCallback::Once(Box::new(f)))
+    }
+
+    /// Run `f` on the UI loop every `interval`, first after one interval.
+    /// A loop that falls behind skips missed runs rather than bursting.
+    pub fn every<F>(interval: Duration, f: F) -> TimerHandle
+    where
+        F: FnMut() + Send + 'static,
+    {
+        // A zero interval would keep the loop awake firing on every pass.
+        let interval = interval.max(TIMER_SLACK);
+        Self::insert_timer(interval, TimerCallback::Repeat(Box::new(f), interval))
+    }
+
+    fn insert_timer(delay: Duration, callback: TimerCallback) -> TimerHandle {
+        let id = TimerId(TIMER_COUNTER.fetch_add(1, Ordering::Relaxed));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let deadline = Instant::now() + delay;
+        lock(&TIMERS).insert(id, deadline, callback, cancelled.clone());
+        Self::schedule_timer_wake();
+        TimerHandle { id, cancelled }
+    }
+
+    /// Fire every due timer. The wheel lock is released while callbacks run,
+    /// so they may add or cancel timers, including their own.
+    pub(crate) fn run_timers(now: Instant) {
+        let due = lock(&TIMERS).take_due(now);
+        for timer in due {
+            if timer.cancelled.load(Ordering::Relaxed) {
+                continue;
+            }
+            match timer.callback {
+                TimerCallback::Once(f) => f(),
+                TimerCallback::Repeat(mut f, interval) => {
+                    f();
+                    if timer.cancelled.load(Ordering::Relaxed) {
+                        continue;
+                    }
+                    let mut next = timer.deadline + interval;
+                    if next <= now {
+                        next = now + interval;
+                    }
+                    lock(&TIMERS).insert(
+                        timer.id,
+                        next,
+                        TimerCallback::Repeat(f, interval),
+                        timer.cancelled,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Request a platform wake-up at the earliest pending timer deadline.
+    pub(crate) fn schedule_timer_wake() {
+        let next = lock(&TIMERS).next_deadline();
+        if let Some(at) = next {
+            Self::schedule_wake(at);
+        }
+    }
+}
+
+/// Collapses bursts of calls into one, run `delay` after the last call.
+///
+/// Typical use is a search field that should query only once typing pauses.
+/// Dropping the debouncer cancels the waiting call.
+pub struct Debouncer {
+    delay: Duration,
+    pending: Mutex<Option<TimerHandle>>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: Mutex::new(None),
+        }
+    }
+
+    /// Schedule `f`, replacing any call still waiting for the delay to pass.
+    pub fn call<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut pending = lock(&self.pending);
+        if let Some(timer) = pending.take() {
+            timer.cancel();
+        }
+        *pending = Some(FrameScheduler::after(self.delay, f));
+    }
+
+    /// Drop the waiting call, if any.
+    pub fn cancel(&self) {
+        if let Some(timer) = lock(&self.pending).take() {
+            timer.cancel();
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        lock(&self.pending).as_ref().is_some_and(|timer| {
+            !timer.is_cancelled() && lock(&TIMERS).entries.contains_key(&timer.id)
+        })
+    }
+}
+
+type TrailingFn = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct ThrottleState {
+    last_run: Option<Instant>,
+    trailing: Option<TrailingFn>,
+    timer: Option<TimerHandle>,
+}
+
+/// Runs at most one call per `interval`.
+///
+/// The first call runs immediately; calls inside the interval collapse into a
+/// single trailing call at the end of it, so the latest value (e.g. the final
+/// window size during a resize drag) is never lost. Dropping the throttle
+/// cancels the trailing call.
+pub struct Throttle {
+    interval: Duration,
+    state: Arc<Mutex<ThrottleState>>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            state: Arc::new(Mutex::new(ThrottleState::default())),
+        }
+    }
+
+    pub fn call<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let now = Instant::now();
+        let mut state = lock(&self.state);
+        let remaining = state.last_run.map_or(Duration::ZERO, |last| {
+            self.interval.saturating_sub(now.duration_since(last))
+        });
+        if remaining.is_zero() && state.timer.is_none() {
+            state.last_run = Some(now);
+            drop(state);
+            f();
+            return;
+        }
+
+        state.trailing = Some(Box::new(f));
+        if state.timer.is_none() {
+            let shared = self.state.clone();
+            state.timer = Some(FrameScheduler::after(remaining, move || {
+                let trailing = {
+                    let mut state = lock(&shared);
+                    state.timer = None;
+                    state.last_run = Some(Instant::now());
+                    state.trailing.take()
+                };
+                if let Some(f) = trailing {
+                    f();
+                }
+            }));
+        }
+    }
+
+    /// Drop the pending trailing call, if any.
+    pub fn cancel(&self) {
+        let mut state = lock(&self.state);
+        state.trailing = None;
+        if let Some(timer) = state.timer.take() {
+            timer.cancel();
+        }
+    }
+}
+
+impl Drop for Throttle {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl Drop for Debouncer {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Swap out the global wheel; used by scheduler tests to start clean.
+#[cfg(test)]
+pub(crate) fn reset_timers() {
+    *lock(&TIMERS) = TimerWheel::new(Instant::now());
+    TIMER_COUNTER.store(0, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn once(log: &Arc<Mutex<Vec<u64>>>, n: u64) -> TimerCallback {
+        let log = log.clone();
+        TimerCallback::Once(Box::new(move || lock(&log).push(n)))
+    }
+
+    fn fire(due: Vec<DueTimer>) {
+        for timer in due {
+            if let TimerCallback::Once(f) = timer.callback {
+                f();
+            }
+        }
+    }
+
+    #[test]
+    fn due_timers_fire_in_deadline_order() {
+        let origin = Instant::now();
+        let mut wheel = TimerWheel::new(origin);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        for (id, delay) in [(0, 30), (1, 10), (2, 20), (3, 500)] {
+            let flag = Arc::new(AtomicBool::new(false));
+            wheel.insert(TimerId(id), origin + ms(delay), once(&log, id), flag);
+        }
+
+        assert_eq!(wheel.next_deadline(), Some(origin + ms(10)));
+        fire(wheel.take_due(origin + ms(5)));
+        assert!(lock(&log).is_empty());
+
+        fire(wheel.take_due(origin + ms(40)));
+        assert_eq!(*lock(&log), vec![1, 2, 0]);
+        assert_eq!(wheel.next_deadline(), Some(origin + ms(500)));
+    }
+
+    #[test]
+    fn timers_beyond_one_rotation_wait_for_their_deadline() {
+        let origin = Instant::now();
+        let mut wheel = TimerWheel::new(origin);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let rotation = WHEEL_TICK * u32::try_from(WHEEL_SLOTS).unwrap();
+        let flag = Arc::new(AtomicBool::new(false));
+        wheel.insert(TimerId(0), origin + rotation + ms(8), once(&log, 0), flag);
+
+        // Same bucket one rotation earlier: must not fire.
+        fire(wheel.take_due(origin + ms(8)));
+        assert!(lock(&log).is_empty());
+        assert_eq!(wheel.next_deadline(), Some(origin + rotation + ms(8)));
+
+        fire(wheel.take_due(origin + rotation * 3));
+        assert_eq!(*lock(&log), vec![0]);
+        assert_eq!(wheel.next_deadline(), None);
+    }
+
+    #[test]
+    fn removed_timer_never_fires() {
+        let origin = Instant::now();
+        let mut wheel = TimerWheel::new(origin);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let flag = Arc::new(AtomicBool::new(false));
+        wheel.insert(TimerId(7), origin + ms(4), once(&log, 7), flag);
+        wheel.remove(TimerId(7));
+
+        fire(wheel.take_due(origin + ms(100)));
+        assert!(lock(&log).is_empty());
+        assert_eq!(wheel.next_deadline(), None);
+    }
+}
@@ -0,0 +1,62 @@
+//! Adapter for posting results of tokio work back to the UI thread.
+//!
+//! Enabled by the `tokio` feature. The UI thread keeps running the platform
+//! loop; tokio owns its own worker threads. `JoinHandle`s returned by tokio
+//! can also be awaited directly inside [`Executor::spawn`] tasks.
+
+use crate::executor::Executor;
+use std::future::Future;
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+
+/// Spawns work on a tokio runtime and delivers its output on the UI thread.
+#[derive(Debug, Clone)]
+pub struct TokioBridge {
+    handle: Handle,
+}
+
+impl TokioBridge {
+    pub fn new(handle: Handle) -> Self {
+        Self { handle }
+    }
+
+    /// Bridge to the runtime entered on the calling thread, if any.
+    pub fn current() -> Option<Self> {
+        Handle::try_current().ok().map(Self::new)
+    }
+
+    pub fn handle(&self) -> &Handle {
+        &self.handle
+    }
+
+    /// Run `future` on tokio, then call `on_ui` with its output on the UI
+    /// thread. `on_ui` is dropped without running if the future panics or the
+    /// task is aborted.
+    pub fn spawn_then<F, T, C>(&self, future: F, on_ui: C) -> JoinHandle<()>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+        C: FnOnce(T) + Send + 'static,
+    {
+        self.handle.spawn(async move {
+            let output = future.await;
+            Executor::post(move || on_ui(output));
+        })
+    }
+
+    /// Run a blocking closure on tokio's blocking pool, then hand the result
+    /// to `on_ui` on the UI thread. Suited to file loading.
+    pub fn spawn_blocking_then<F, T, C>(&self, f: F, on_ui: C) -> JoinHandle<()>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+        C: FnOnce(T) + Send + 'static,
+    {
+        let blocking = self.handle.spawn_blocking(f);
+        self.handle.spawn(async move {
+            if let Ok(output) = blocking.await {
+                Executor::post(move || on_ui(output));
+            }
+        })
+    }
+}
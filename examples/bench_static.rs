@@ -1,17 +1,22 @@
 //! Frame-timing benchmark for the P6-A damage/tile-cache pipeline.
 //!
-//! Drives `CpuRasterizer` directly (no window/event loop) through 1000
-//! frames of an unchanged complex scene, then 1000 frames of the same
-//! scene with one small rect sweeping across the canvas. Run with:
+//! Drives `CpuRasterizer` (no window/event loop) through 1000 frames of an
+//! unchanged complex scene, then 1000 frames of the same scene with one
+//! small rect sweeping across the canvas. Each frame runs through
+//! `FrameScheduler::process_frames`, and the report comes from
+//! `FrameScheduler::stats()`. Run with:
 //!
 //! ```text
 //! cargo run --release --example bench_static
 //! ```
 
-use std::time::Instant;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use aurea::AureaResult;
 use aurea::render::{Color, CpuRasterizer, DrawingContext, Paint, Rect, Renderer};
+use aurea::{AureaError, AureaResult, FrameScheduler, FrameStats};
 
 const WIDTH: u32 = 1280;
 const HEIGHT: u32 = 800;
@@ -45,40 +50,93 @@ fn draw_background(ctx: &mut dyn DrawingContext) -> AureaResult<()> {
     Ok(())
 }
 
+/// Any unique non-null pointer works as a canvas handle here; it is never
+/// dereferenced.
+fn canvas_handle() -> *mut c_void {
+    static HANDLE: u8 = 0;
+    (&raw const HANDLE).cast_mut().cast::<c_void>()
+}
+
+/// Drives `frames` redraws of a dummy canvas through `FrameScheduler` so
+/// the timings come from the same statistics a windowed app would see.
 fn run_frames(
-    r: &mut CpuRasterizer,
+    r: CpuRasterizer,
     frames: u32,
-    mut draw: impl FnMut(&mut dyn DrawingContext, u32) -> AureaResult<()>,
-) -> AureaResult<f64> {
-    let start = Instant::now();
-    for frame in 0..frames {
-        let mut ctx = r.begin_frame()?;
-        draw(ctx.as_mut(), frame)?;
-        drop(ctx);
-        r.end_frame()?;
+    draw: impl Fn(&mut dyn DrawingContext, u32) -> AureaResult<()> + Send + Sync + 'static,
+) -> AureaResult<FrameStats> {
+    let handle = canvas_handle();
+    let r = Mutex::new(r);
+    let frame = AtomicU32::new(0);
+
+    FrameScheduler::reset_stats();
+    FrameScheduler::register_canvas(
+        handle,
+        Arc::new(move || {
+            let mut r = r.lock().map_err(|_| AureaError::EventLoopError)?;
+            let start = Instant::now();
+            let mut ctx = r.begin_frame()?;
+            draw(ctx.as_mut(), frame.fetch_add(1, Ordering::Relaxed))?;
+            drop(ctx);
+            r.end_frame()?;
+            // No platform surface to present to; report render time only.
+            FrameScheduler::record_canvas_timing(canvas_handle(), start.elapsed(), Duration::ZERO);
+            Ok(())
+        }),
+    );
+    for _ in 0..frames {
+        FrameScheduler::schedule_canvas(handle);
+        FrameScheduler::process_frames()?;
     }
-    Ok(start.elapsed().as_secs_f64() * 1000.0 / f64::from(frames))
+    let stats = FrameScheduler::stats();
+    FrameScheduler::unregister_canvas(handle);
+    Ok(stats)
+}
+
+fn report(label: &str, stats: &FrameStats) {
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let (render_p50, render_p95) = stats
+        .canvases
+        .first()
+        .map_or((Duration::ZERO, Duration::ZERO), |c| {
+            (c.render_p50, c.render_p95)
+        });
+    println!(
+        "{label:<19} frame p50 {:.4} ms, p95 {:.4} ms, max {:.4} ms | render p50 {:.4} ms, p95 {:.4} ms | missed {}/{} deadlines of {:.2} ms",
+        ms(stats.frame_time_p50),
+        ms(stats.frame_time_p95),
+        ms(stats.frame_time_max),
+        ms(render_p50),
+        ms(render_p95),
+        stats.missed_deadlines,
+        stats.frames,
+        ms(stats.deadline),
+    );
 }
 
 fn main() -> AureaResult<()> {
     // Identical display list every frame: after frame 1, stage-1's diff
     // sees `Unchanged` and `end_frame` early-returns with zero pixels
     // touched, regardless of how many items the scene contains.
-    let mut r = CpuRasterizer::new(WIDTH, HEIGHT);
-    let ms_static = run_frames(&mut r, FRAMES, |ctx, _| draw_background(ctx))?;
-    println!("unchanged scene:    {ms_static:.4} ms/frame ({FRAMES} frames, {GRID}x{GRID} grid)");
+    println!("{FRAMES} frames per scene, {GRID}x{GRID} grid, {WIDTH}x{HEIGHT}");
+    let stats = run_frames(CpuRasterizer::new(WIDTH, HEIGHT), FRAMES, |ctx, _| {
+        draw_background(ctx)
+    })?;
+    report("unchanged scene:", &stats);
 
     // Same static background plus a small rect that sweeps one pixel per
     // frame. Only the tile(s) the rect's old/new bounds overlap should be
     // marked dirty and redrawn.
-    let mut r = CpuRasterizer::new(WIDTH, HEIGHT);
     let cursor = Paint::new().color(Color::rgb(255, 64, 64));
-    let ms_moving = run_frames(&mut r, FRAMES, |ctx, frame| {
-        draw_background(ctx)?;
-        let x = (frame % WIDTH) as f32;
-        ctx.draw_rect(Rect::new(x, HEIGHT as f32 / 2.0, 16.0, 16.0), &cursor)
-    })?;
-    println!("moving-rect scene:  {ms_moving:.4} ms/frame ({FRAMES} frames)");
+    let stats = run_frames(
+        CpuRasterizer::new(WIDTH, HEIGHT),
+        FRAMES,
+        move |ctx, frame| {
+            draw_background(ctx)?;
+            let x = (frame % WIDTH) as f32;
+            ctx.draw_rect(Rect::new(x, HEIGHT as f32 / 2.0, 16.0, 16.0), &cursor)
+        },
+    )?;
+    report("moving-rect scene:", &stats);
 
     Ok(())
 }
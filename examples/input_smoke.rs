@@ -15,10 +15,18 @@ fn main() -> Result<(), Box<dyn Error>> {
     window.on_event(move |event| match event {
         WindowEvent::KeyInput {
             key,
+            physical_key,
+            logical_key,
+            location,
             pressed,
+            repeat,
             modifiers,
         } => {
-            let action = if pressed { "key down" } else { "key up" };
+            let action = match (pressed, repeat) {
+                (true, true) => "key repeat",
+                (true, false) => "key down",
+                (false, _) => "key up",
+            };
             let mods = if modifiers.is_any() {
                 format!(
                     " [shift={} ctrl={} alt={} meta={}]",
@@ -27,7 +35,10 @@ fn main() -> Result<(), Box<dyn Error>> {
             } else {
                 String::new()
             };
-            println!("{} {:?}{}", action, key, mods);
+            println!(
+                "{} {:?} physical={:?} logical={:?} location={:?}{}",
+                action, key, physical_key, logical_key, location, mods
+            );
             if pressed && key == KeyCode::Escape {
                 w.request_close();
             }
@@ -38,7 +49,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             let action = if pressed { "mouse down" } else { "mouse up" };
             println!("{} {:?}", action, button);
         }
-        WindowEvent::MouseMove { x, y } => {
+        WindowEvent::MouseMove { x, y, .. } => {
             println!("mouse move ({:.1}, {:.1})", x, y);
         }
         WindowEvent::MouseWheel {
@@ -0,0 +1,241 @@
+//! Publishes an [`AccessTree`] over AT-SPI through AccessKit.
+//!
+//! AccessKit calls the handlers on its own thread: the initial tree is built
+//! from the shared tree there, and action requests go straight into the
+//! window's event queue.
+
+use super::{AccessNode, AccessTree, Role};
+use crate::render::Rect;
+use crate::sync::lock;
+use crate::window::events::EventQueue;
+use crate::window::{AccessibilityAction, WindowEvent};
+use accesskit::{
+    Action, ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Node, NodeId,
+    Rect as AkRect, Role as AkRole, Tree, TreeUpdate,
+};
+use accesskit_unix::Adapter;
+use std::sync::{Arc, Mutex};
+
+/// The window itself; shapes are published under the node ids their
+/// entries were given, which start at 1.
+const ROOT: NodeId = NodeId(0);
+
+pub(super) struct Bridge {
+    adapter: Mutex<Adapter>,
+}
+
+impl Bridge {
+    pub(super) fn new(tree: &Arc<Mutex<AccessTree>>, event_queue: &Arc<EventQueue>) -> Self {
+        let adapter = Adapter::new(
+            InitialTree(Arc::clone(tree)),
+            Actions {
+                tree: Arc::clone(tree),
+                event_queue: Arc::clone(event_queue),
+            },
+            Inactive,
+        );
+        Self {
+            adapter: Mutex::new(adapter),
+        }
+    }
+
+    /// Send the whole tree, if an assistive technology is listening.
+    pub(super) fn publish(&self, tree: &Mutex<AccessTree>) {
+        lock(&self.adapter).update_if_active(|| tree_update(&lock(tree)));
+    }
+
+    pub(super) fn set_window_bounds(&self, outer: Rect, content: Rect) {
+        lock(&self.adapter)
+            .set_root_window_bounds(to_accesskit_rect(outer), to_accesskit_rect(content));
+    }
+
+    pub(super) fn set_window_focused(&self, focused: bool) {
+        lock(&self.adapter).update_window_focus_state(focused);
+    }
+}
+
+struct InitialTree(Arc<Mutex<AccessTree>>);
+
+impl ActivationHandler for InitialTree {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        Some(tree_update(&lock(&self.0)))
+    }
+}
+
+struct Actions {
+    tree: Arc<Mutex<AccessTree>>,
+    event_queue: Arc<EventQueue>,
+}
+
+impl ActionHandler for Actions {
+    fn do_action(&mut self, request: ActionRequest) {
+        let Some(action) = from_accesskit_action(request.action) else {
+            return;
+        };
+        let Some((canvas, target)) = lock(&self.tree)
+            .by_node_id(request.target.0)
+            .map(|entry| (entry.canvas, entry.id))
+        else {
+            return;
+        };
+        self.event_queue
+            .push_platform(WindowEvent::AccessibilityAction {
+                canvas,
+                target: target.0,
+                action,
+            });
+    }
+}
+
+struct Inactive;
+
+impl DeactivationHandler for Inactive {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+fn tree_update(tree: &AccessTree) -> TreeUpdate {
+    let layout = tree.layout();
+    let node_id = |index: usize| NodeId(tree.nodes[index].node_id);
+
+    let mut root = Node::new(AkRole::Window);
+    root.set_label(tree.title.as_str());
+    root.set_children(
+        layout
+            .top_level
+            .iter()
+            .copied()
+            .map(node_id)
+            .collect::<Vec<_>>(),
+    );
+
+    let mut nodes = vec![(ROOT, root)];
+    for (index, children) in layout.children.into_iter().enumerate() {
+        let children = children.into_iter().map(node_id).collect();
+        let node = to_accesskit_node(&tree.nodes[index].node, children);
+        nodes.push((node_id(index), node));
+    }
+
+    let mut info = Tree::new(ROOT);
+    info.toolkit_name = Some("aurea".to_owned());
+    info.toolkit_version = Some(env!("CARGO_PKG_VERSION").to_owned());
+    TreeUpdate {
+        nodes,
+        tree: Some(info),
+        // AccessKit rejects a focus outside the node list, and a focused
+        // shape need not be described.
+        focus: tree.focused().map_or(ROOT, |entry| NodeId(entry.node_id)),
+    }
+}
+
+fn to_accesskit_node(node: &AccessNode, children: Vec<NodeId>) -> Node {
+    let mut out = Node::new(to_accesskit_role(node.role));
+    if let Some(name) = &node.name {
+        out.set_label(name.as_str());
+    }
+    if let Some(value) = &node.value {
+        out.set_value(value.as_str());
+    }
+    if let Some(description) = &node.description {
+        out.set_description(description.as_str());
+    }
+    if node.disabled {
+        out.set_disabled();
+    }
+    if let Some(checked) = node.checked {
+        out.set_toggled(checked.into());
+    }
+    if let Some(selected) = node.selected {
+        out.set_selected(selected);
+    }
+    if let Some(expanded) = node.expanded {
+        out.set_expanded(expanded);
+    }
+    for action in &node.actions {
+        out.add_action(to_accesskit_action(*action));
+    }
+    if let Some(bounds) = node.bounds {
+        out.set_bounds(to_accesskit_rect(bounds));
+    }
+    out.set_children(children);
+    out
+}
+
+fn to_accesskit_rect(rect: Rect) -> AkRect {
+    let (x, y) = (f64::from(rect.x), f64::from(rect.y));
+    AkRect {
+        x0: x,
+        y0: y,
+        x1: x + f64::from(rect.width),
+        y1: y + f64::from(rect.height),
+    }
+}
+
+fn to_accesskit_role(role: Role) -> AkRole {
+    match role {
+        Role::Group => AkRole::Group,
+        Role::Button => AkRole::Button,
+        Role::CheckBox => AkRole::CheckBox,
+        Role::RadioButton => AkRole::RadioButton,
+        Role::Switch => AkRole::Switch,
+        Role::Label => AkRole::Label,
+        Role::Heading => AkRole::Heading,
+        Role::TextInput => AkRole::TextInput,
+        Role::Slider => AkRole::Slider,
+        Role::ProgressIndicator => AkRole::ProgressIndicator,
+        Role::Image => AkRole::Image,
+        Role::Link => AkRole::Link,
+        Role::List => AkRole::List,
+        Role::ListItem => AkRole::ListItem,
+        Role::TabList => AkRole::TabList,
+        Role::Tab => AkRole::Tab,
+        Role::MenuItem => AkRole::MenuItem,
+    }
+}
+
+fn to_accesskit_action(action: AccessibilityAction) -> Action {
+    match action {
+        AccessibilityAction::Click => Action::Click,
+        AccessibilityAction::Focus => Action::Focus,
+        AccessibilityAction::Increment => Action::Increment,
+        AccessibilityAction::Decrement => Action::Decrement,
+        AccessibilityAction::Expand => Action::Expand,
+        AccessibilityAction::Collapse => Action::Collapse,
+        AccessibilityAction::ScrollIntoView => Action::ScrollIntoView,
+        AccessibilityAction::ShowContextMenu => Action::ShowContextMenu,
+    }
+}
+
+fn from_accesskit_action(action: Action) -> Option<AccessibilityAction> {
+    Some(match action {
+        Action::Click => AccessibilityAction::Click,
+        Action::Focus => AccessibilityAction::Focus,
+        Action::Increment => AccessibilityAction::Increment,
+        Action::Decrement => AccessibilityAction::Decrement,
+        Action::Expand => AccessibilityAction::Expand,
+        Action::Collapse => AccessibilityAction::Collapse,
+        Action::ScrollIntoView => AccessibilityAction::ScrollIntoView,
+        Action::ShowContextMenu => AccessibilityAction::ShowContextMenu,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::InteractiveId;
+    use crate::window::FocusTarget;
+
+    #[test]
+    fn focus_without_a_node_stays_on_the_window() {
+        let mut tree = AccessTree::default();
+        tree.set(0x10, InteractiveId(u64::MAX), AccessNode::new(Role::Button));
+        let focus = |canvas, id| Some(FocusTarget::CanvasItem { canvas, id });
+        tree.focus = focus(0x10, 2);
+        assert_eq!(tree_update(&tree).focus, ROOT);
+        tree.focus = focus(0x20, u64::MAX);
+        assert_eq!(tree_update(&tree).focus, ROOT);
+
+        tree.focus = focus(0x10, u64::MAX);
+        assert_eq!(tree_update(&tree).focus, NodeId(1));
+    }
+}
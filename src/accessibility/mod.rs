@@ -0,0 +1,454 @@
+//! Accessibility trees for assistive technologies.
+//!
+//! Native widgets are described by the platform toolkit; name them with
+//! [`Element::set_accessible_label`](crate::elements::Element::set_accessible_label).
+//! What a [`Canvas`] draws is invisible to screen readers until it is
+//! described with [`AccessNode`]s attached to the [`InteractiveId`]s of its
+//! shapes through [`Window::accessibility`]. Each canvas has its own ids.
+//!
+//! Requests from assistive technologies, including moving focus, arrive as
+//! [`WindowEvent::AccessibilityAction`] on the window's event queue; report
+//! the resulting focus back with [`Accessibility::set_focus`].
+//!
+//! With the `accesskit` feature the tree is published over AT-SPI on Linux,
+//! as its own top-level accessible next to the one GTK exposes for native
+//! widgets. Without it, or on other platforms, the tree is kept but not
+//! published.
+//!
+//! [`Window::accessibility`]: crate::Window::accessibility
+//! [`WindowEvent::AccessibilityAction`]: crate::WindowEvent::AccessibilityAction
+
+#[cfg(all(feature = "accesskit", target_os = "linux"))]
+mod atspi;
+
+use crate::render::{Canvas, InteractiveId, Rect};
+use crate::sync::lock;
+use crate::window::events::EventQueue;
+use crate::window::{AccessibilityAction, FocusTarget};
+use std::collections::VecDeque;
+use std::mem::replace;
+use std::sync::{Arc, Mutex};
+
+#[cfg(all(feature = "accesskit", target_os = "linux"))]
+use atspi::Bridge;
+
+/// What kind of control or content a node is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Role {
+    #[default]
+    Group,
+    Button,
+    CheckBox,
+    RadioButton,
+    Switch,
+    Label,
+    Heading,
+    TextInput,
+    Slider,
+    ProgressIndicator,
+    Image,
+    Link,
+    List,
+    ListItem,
+    TabList,
+    Tab,
+    MenuItem,
+}
+
+/// The accessible description of one canvas shape.
+///
+/// ```rust,no_run
+/// use aurea::accessibility::{AccessNode, Role};
+/// use aurea::render::Rect;
+/// use aurea::AccessibilityAction;
+///
+/// let play = AccessNode::new(Role::Button)
+///     .name("Play")
+///     .bounds(Rect::new(16.0, 16.0, 48.0, 48.0))
+///     .focusable(true)
+///     .action(AccessibilityAction::Click);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccessNode {
+    role: Role,
+    name: Option<String>,
+    value: Option<String>,
+    description: Option<String>,
+    disabled: bool,
+    checked: Option<bool>,
+    selected: Option<bool>,
+    expanded: Option<bool>,
+    actions: Vec<AccessibilityAction>,
+    bounds: Option<Rect>,
+    children: Vec<InteractiveId>,
+}
+
+impl AccessNode {
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            ..Self::default()
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The current value, such as a slider position or field contents.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = Some(expanded);
+        self
+    }
+
+    /// Offer `action` to assistive technologies.
+    pub fn action(mut self, action: AccessibilityAction) -> Self {
+        if !self.actions.contains(&action) {
+            self.actions.push(action);
+        }
+        self
+    }
+
+    /// Whether the node can take keyboard focus; shorthand for offering
+    /// [`AccessibilityAction::Focus`].
+    pub fn focusable(self, focusable: bool) -> Self {
+        if focusable {
+            self.action(AccessibilityAction::Focus)
+        } else {
+            self
+        }
+    }
+
+    /// Where the node is, in logical pixels relative to the window's
+    /// content area.
+    pub fn bounds(mut self, bounds: Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Nest `child`, a shape on the same canvas, under this node. Nodes no
+    /// other node lists as a child
+    /// sit directly under the window; one listed by several nodes sits under
+    /// the one nearest the window, or the first set among equals.
+    pub fn child(mut self, child: InteractiveId) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn children(mut self, children: impl IntoIterator<Item = InteractiveId>) -> Self {
+        self.children.extend(children);
+        self
+    }
+}
+
+/// A described shape and the id it is published under.
+#[derive(Debug)]
+struct Entry {
+    /// The address of the canvas handle, as in [`FocusTarget::CanvasItem`].
+    canvas: usize,
+    id: InteractiveId,
+    /// Assigned when the shape is first described; 0 is the window.
+    node_id: u64,
+    node: AccessNode,
+}
+
+/// The nodes of one window, in the order they were first set.
+#[derive(Debug, Default)]
+struct AccessTree {
+    title: String,
+    nodes: Vec<Entry>,
+    focus: Option<FocusTarget>,
+    last_node_id: u64,
+}
+
+/// The nesting of an [`AccessTree`] as published, by index into its nodes.
+#[derive(Debug, PartialEq)]
+struct Layout {
+    top_level: Vec<usize>,
+    children: Vec<Vec<usize>>,
+}
+
+// `layout` and `by_node_id` only feed the AT-SPI bridge.
+#[cfg_attr(not(all(feature = "accesskit", target_os = "linux")), allow(dead_code))]
+impl AccessTree {
+    fn position(&self, canvas: usize, id: InteractiveId) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|entry| entry.canvas == canvas && entry.id == id)
+    }
+
+    fn get(&self, canvas: usize, id: InteractiveId) -> Option<&Entry> {
+        self.position(canvas, id).map(|index| &self.nodes[index])
+    }
+
+    fn set(&mut self, canvas: usize, id: InteractiveId, node: AccessNode) {
+        match self.position(canvas, id) {
+            Some(index) => self.nodes[index].node = node,
+            None => {
+                self.last_node_id += 1;
+                self.nodes.push(Entry {
+                    canvas,
+                    id,
+                    node_id: self.last_node_id,
+                    node,
+                });
+            }
+        }
+    }
+
+    fn remove(&mut self, canvas: usize, id: InteractiveId) {
+        self.nodes
+            .retain(|entry| entry.canvas != canvas || entry.id != id);
+        if self.focus == Some(focus_target(canvas, id)) {
+            self.focus = None;
+        }
+    }
+
+    fn by_node_id(&self, node_id: u64) -> Option<&Entry> {
+        self.nodes.iter().find(|entry| entry.node_id == node_id)
+    }
+
+    /// The focused node, if focus is on a described shape.
+    fn focused(&self) -> Option<&Entry> {
+        match self.focus? {
+            FocusTarget::CanvasItem { canvas, id } => self.get(canvas, InteractiveId(id)),
+            FocusTarget::Element(_) => None,
+        }
+    }
+
+    /// Nodes no other node claims as a child sit under the window. Each
+    /// node is nested under the first parent to reach it, so a child listed
+    /// twice, by itself or in a cycle appears once; dangling ids are
+    /// skipped until their node is set.
+    fn layout(&self) -> Layout {
+        let listed: Vec<Vec<usize>> = self
+            .nodes
+            .iter()
+            .map(|entry| {
+                let children = entry.node.children.iter();
+                children
+                    .filter_map(|child| self.position(entry.canvas, *child))
+                    .collect()
+            })
+            .collect();
+        let claimed = |index: usize| {
+            listed
+                .iter()
+                .enumerate()
+                .any(|(parent, children)| parent != index && children.contains(&index))
+        };
+
+        let mut layout = Layout {
+            top_level: Vec::new(),
+            children: vec![Vec::new(); self.nodes.len()],
+        };
+        let mut placed = vec![false; self.nodes.len()];
+        // Unclaimed nodes first; then whatever only a cycle reaches.
+        let starts = (0..self.nodes.len()).filter(|index| !claimed(*index));
+        for start in starts.chain(0..self.nodes.len()) {
+            if replace(&mut placed[start], true) {
+                continue;
+            }
+            layout.top_level.push(start);
+            let mut queue = VecDeque::from([start]);
+            while let Some(parent) = queue.pop_front() {
+                for &child in &listed[parent] {
+                    if !replace(&mut placed[child], true) {
+                        layout.children[parent].push(child);
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+        layout
+    }
+}
+
+/// A window's accessibility tree. Changes are published to assistive
+/// technologies as they are made.
+pub struct Accessibility {
+    tree: Arc<Mutex<AccessTree>>,
+    #[cfg(all(feature = "accesskit", target_os = "linux"))]
+    bridge: Bridge,
+}
+
+impl Accessibility {
+    pub(crate) fn new(title: &str, event_queue: &Arc<EventQueue>) -> Self {
+        let tree = Arc::new(Mutex::new(AccessTree {
+            title: title.to_owned(),
+            ..AccessTree::default()
+        }));
+        #[cfg(not(all(feature = "accesskit", target_os = "linux")))]
+        let _ = event_queue;
+        Self {
+            #[cfg(all(feature = "accesskit", target_os = "linux"))]
+            bridge: Bridge::new(&tree, event_queue),
+            tree,
+        }
+    }
+
+    /// Describe the shape `id` on `canvas`, replacing any earlier
+    /// description.
+    pub fn set_node(&self, canvas: &Canvas, id: InteractiveId, node: AccessNode) {
+        lock(&self.tree).set(canvas.handle as usize, id, node);
+        self.publish();
+    }
+
+    /// Remove the description of `id` on `canvas`; its children move up to
+    /// the window unless another node still lists them.
+    pub fn remove_node(&self, canvas: &Canvas, id: InteractiveId) {
+        lock(&self.tree).remove(canvas.handle as usize, id);
+        self.publish();
+    }
+
+    pub fn node(&self, canvas: &Canvas, id: InteractiveId) -> Option<AccessNode> {
+        let tree = lock(&self.tree);
+        let entry = tree.get(canvas.handle as usize, id)?;
+        Some(entry.node.clone())
+    }
+
+    /// Report what has keyboard focus, or `None` for nothing. Only a
+    /// described [`FocusTarget::CanvasItem`] is announced as a node; any
+    /// other target leaves focus on the window.
+    pub fn set_focus(&self, target: Option<FocusTarget>) {
+        lock(&self.tree).focus = target;
+        self.publish();
+    }
+
+    pub fn focus(&self) -> Option<FocusTarget> {
+        lock(&self.tree).focus
+    }
+
+    pub(crate) fn set_title(&self, title: &str) {
+        title.clone_into(&mut lock(&self.tree).title);
+        self.publish();
+    }
+
+    /// The window moved or resized; `outer` is its frame on the desktop,
+    /// including decorations, and `content` its client area.
+    pub(crate) fn set_window_bounds(&self, outer: Rect, content: Rect) {
+        #[cfg(all(feature = "accesskit", target_os = "linux"))]
+        self.bridge.set_window_bounds(outer, content);
+        #[cfg(not(all(feature = "accesskit", target_os = "linux")))]
+        let _ = (outer, content);
+    }
+
+    pub(crate) fn set_window_focused(&self, focused: bool) {
+        #[cfg(all(feature = "accesskit", target_os = "linux"))]
+        self.bridge.set_window_focused(focused);
+        #[cfg(not(all(feature = "accesskit", target_os = "linux")))]
+        let _ = focused;
+    }
+
+    fn publish(&self) {
+        #[cfg(all(feature = "accesskit", target_os = "linux"))]
+        self.bridge.publish(&self.tree);
+    }
+}
+
+fn focus_target(canvas: usize, id: InteractiveId) -> FocusTarget {
+    FocusTarget::CanvasItem { canvas, id: id.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANVAS: usize = 0x10;
+
+    #[test]
+    fn unclaimed_nodes_sit_under_the_window() {
+        let mut tree = AccessTree::default();
+        let (toolbar, play, stop) = (InteractiveId(1), InteractiveId(2), InteractiveId(3));
+
+        tree.set(CANVAS, play, AccessNode::new(Role::Button).name("Play"));
+        let group = AccessNode::new(Role::Group).children([play, stop]);
+        tree.set(CANVAS, toolbar, group);
+        let layout = tree.layout();
+        assert_eq!(layout.top_level, [1]);
+        assert_eq!(layout.children, [vec![], vec![0]]);
+
+        tree.focus = Some(focus_target(CANVAS, play));
+        tree.remove(CANVAS, play);
+        tree.remove(CANVAS, toolbar);
+        assert_eq!(tree.focus, None);
+        assert!(tree.layout().top_level.is_empty());
+    }
+
+    #[test]
+    fn each_node_is_published_once() {
+        let mut tree = AccessTree::default();
+        let (a, b, c) = (InteractiveId(1), InteractiveId(2), InteractiveId(u64::MAX));
+
+        tree.set(
+            CANVAS,
+            a,
+            AccessNode::new(Role::Group).children([a, b, b, c]),
+        );
+        tree.set(CANVAS, b, AccessNode::new(Role::Group).child(c));
+        tree.set(CANVAS, c, AccessNode::new(Role::Button).child(a));
+        // `c` stays under `a`, which listed it first; the cycle back to `a`
+        // is cut.
+        assert_eq!(
+            tree.layout(),
+            Layout {
+                top_level: vec![0],
+                children: vec![vec![1, 2], vec![], vec![]],
+            }
+        );
+        let node_ids: Vec<u64> = tree.nodes.iter().map(|entry| entry.node_id).collect();
+        assert_eq!(node_ids, [1, 2, 3]);
+    }
+
+    #[test]
+    fn canvases_keep_their_own_ids() {
+        let mut tree = AccessTree::default();
+        let (other, id) = (0x20, InteractiveId(1));
+        tree.set(
+            CANVAS,
+            id,
+            AccessNode::new(Role::Group).child(InteractiveId(2)),
+        );
+        tree.set(other, id, AccessNode::new(Role::Button).name("Mute"));
+        tree.set(other, InteractiveId(2), AccessNode::new(Role::Label));
+
+        assert_eq!(tree.nodes.len(), 3);
+        assert_eq!(
+            tree.get(other, id).unwrap().node.name.as_deref(),
+            Some("Mute")
+        );
+        // A child id resolves on its parent's canvas only.
+        assert_eq!(tree.layout().top_level, [0, 1, 2]);
+
+        tree.focus = Some(focus_target(other, id));
+        assert_eq!(tree.focused().map(|entry| entry.node_id), Some(2));
+        tree.focus = Some(FocusTarget::Element(other));
+        assert!(tree.focused().is_none());
+    }
+}
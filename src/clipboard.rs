@@ -0,0 +1,380 @@
+//! System clipboard with MIME-typed content.
+//!
+//! A copy can carry several representations of the same data, e.g. an HTML
+//! fragment with a plain-text fallback, and the pasting application picks
+//! the one it understands. Images travel as RGBA8 [`Image`]s; the platform
+//! converts them to and from `image/png`.
+
+use crate::ffi::*;
+use crate::registry::clipboard::{register_clipboard_listener, unregister_clipboard_listener};
+use crate::render::Image;
+use crate::window::is_rgba_image;
+use crate::{AureaError, AureaResult};
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::slice;
+
+pub const MIME_TEXT: &str = "text/plain";
+pub const MIME_HTML: &str = "text/html";
+pub const MIME_PNG: &str = "image/png";
+pub const MIME_URI_LIST: &str = "text/uri-list";
+
+/// Which system clipboard to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ClipboardSelection {
+    /// The clipboard behind Copy and Paste.
+    #[default]
+    Standard,
+    /// The X11/Wayland PRIMARY selection: the last selected text, pasted
+    /// with a middle click. Linux only; elsewhere reads find nothing and
+    /// writes fail.
+    Primary,
+}
+
+impl ClipboardSelection {
+    pub fn from_raw(selection: u32) -> Self {
+        match selection {
+            1 => Self::Primary,
+            _ => Self::Standard,
+        }
+    }
+
+    /// Inverse of [`Self::from_raw`].
+    pub fn to_raw(self) -> u32 {
+        match self {
+            Self::Standard => 0,
+            Self::Primary => 1,
+        }
+    }
+}
+
+/// One representation of clipboard content.
+#[derive(Debug, Clone)]
+pub enum ClipboardContent {
+    /// `text/plain`
+    Text(String),
+    /// `text/html`
+    Html(String),
+    /// `image/png`, decoded to RGBA8.
+    Image(Image),
+    /// `text/uri-list`
+    UriList(Vec<String>),
+    /// Any other MIME type, as raw bytes.
+    Other { mime_type: String, data: Vec<u8> },
+}
+
+impl ClipboardContent {
+    pub fn mime_type(&self) -> &str {
+        match self {
+            Self::Text(_) => MIME_TEXT,
+            Self::Html(_) => MIME_HTML,
+            Self::Image(_) => MIME_PNG,
+            Self::UriList(_) => MIME_URI_LIST,
+            Self::Other { mime_type, .. } => mime_type,
+        }
+    }
+
+    /// The bytes handed to the platform; images are raw RGBA8.
+    fn payload(&self) -> AureaResult<Cow<'_, [u8]>> {
+        let payload = match self {
+            Self::Text(text) | Self::Html(text) => Cow::Borrowed(text.as_bytes()),
+            Self::Image(image) if is_rgba_image(image) => Cow::Borrowed(&image.data[..]),
+            Self::Image(_) => return Err(AureaError::ElementOperationFailed),
+            Self::UriList(uris) if uris.iter().any(|uri| uri.contains(['\r', '\n'])) => {
+                return Err(AureaError::ElementOperationFailed);
+            }
+            Self::UriList(uris) => Cow::Owned(uris.join("\n").into_bytes()),
+            Self::Other { data, .. } => Cow::Borrowed(data.as_slice()),
+        };
+        Ok(payload)
+    }
+
+    fn decode(mime_type: &str, bytes: Vec<u8>, width: u32, height: u32) -> Option<Self> {
+        let content = match mime_type {
+            MIME_TEXT => Self::Text(decode_text(&bytes)),
+            MIME_HTML => Self::Html(decode_text(&bytes)),
+            MIME_PNG => {
+                let image = Image::new(width, height, bytes);
+                if !is_rgba_image(&image) {
+                    return None;
+                }
+                Self::Image(image)
+            }
+            MIME_URI_LIST => Self::UriList(parse_uri_list(&decode_text(&bytes))),
+            _ => Self::Other {
+                mime_type: mime_type.to_owned(),
+                data: bytes,
+            },
+        };
+        Some(content)
+    }
+}
+
+/// UTF-8, or UTF-16LE when it starts with a byte-order mark as some
+/// browsers' `text/html` does.
+fn decode_text(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    let utf8 = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    String::from_utf8_lossy(utf8).into_owned()
+}
+
+/// RFC 2483: one URI per line, `#` starts a comment line.
+fn parse_uri_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Handle to a system clipboard.
+///
+/// ```rust,no_run
+/// use aurea::clipboard::{Clipboard, ClipboardContent};
+///
+/// # fn main() -> aurea::AureaResult<()> {
+/// Clipboard::new().set(&[
+///     ClipboardContent::Html("<b>bold</b>".to_owned()),
+///     ClipboardContent::Text("bold".to_owned()),
+/// ])?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Clipboard {
+    selection: ClipboardSelection,
+}
+
+impl Clipboard {
+    /// The standard clipboard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The Linux PRIMARY selection.
+    pub fn primary() -> Self {
+        Self {
+            selection: ClipboardSelection::Primary,
+        }
+    }
+
+    pub fn selection(&self) -> ClipboardSelection {
+        self.selection
+    }
+
+    /// Replace the clipboard with `contents`, each a representation of the
+    /// same data, most specific first. An empty slice clears it.
+    pub fn set(&self, contents: &[ClipboardContent]) -> AureaResult<()> {
+        let mut mime_types = Vec::with_capacity(contents.len());
+        let mut payloads = Vec::with_capacity(contents.len());
+        for content in contents {
+            mime_types.push(
+                CString::new(content.mime_type())
+                    .map_err(|_| AureaError::ElementOperationFailed)?,
+            );
+            payloads.push(content.payload()?);
+        }
+        let items: Vec<NGClipboardItem> = contents
+            .iter()
+            .zip(mime_types.iter().zip(&payloads))
+            .map(|(content, (mime_type, payload))| {
+                let (width, height) = match content {
+                    ClipboardContent::Image(image) => (image.width, image.height),
+                    _ => (0, 0),
+                };
+                NGClipboardItem {
+                    mime_type: mime_type.as_ptr(),
+                    data: payload.as_ptr(),
+                    length: payload.len(),
+                    width,
+                    height,
+                }
+            })
+            .collect();
+        let count = i32::try_from(items.len()).map_err(|_| AureaError::ElementOperationFailed)?;
+        let result =
+            unsafe { ng_platform_clipboard_set(self.selection.to_raw(), items.as_ptr(), count) };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+
+    pub fn clear(&self) -> AureaResult<()> {
+        self.set(&[])
+    }
+
+    /// MIME types currently on offer. `text/plain`, `image/png` and
+    /// `text/uri-list` are listed whenever the platform can convert to them.
+    pub fn available_types(&self) -> Vec<String> {
+        let ptr = unsafe { ng_platform_clipboard_types(self.selection.to_raw()) };
+        if ptr.is_null() {
+            return Vec::new();
+        }
+        let types = unsafe {
+            let list = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+            ng_platform_free_clipboard_text(ptr);
+            list
+        };
+        types
+            .lines()
+            .filter(|mime_type| !mime_type.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    pub fn has(&self, mime_type: &str) -> bool {
+        self.available_types()
+            .iter()
+            .any(|available| available == mime_type)
+    }
+
+    /// Read the clipboard as `mime_type`. Blocks until the owning
+    /// application answers.
+    pub fn get(&self, mime_type: &str) -> Option<ClipboardContent> {
+        let c_mime_type = CString::new(mime_type).ok()?;
+        let (mut length, mut width, mut height) = (0usize, 0u32, 0u32);
+        let data = unsafe {
+            ng_platform_clipboard_get(
+                self.selection.to_raw(),
+                c_mime_type.as_ptr(),
+                &mut length,
+                &mut width,
+                &mut height,
+            )
+        };
+        if data.is_null() {
+            return None;
+        }
+        let bytes = unsafe {
+            let bytes = slice::from_raw_parts(data, length).to_vec();
+            ng_platform_clipboard_free(data);
+            bytes
+        };
+        ClipboardContent::decode(mime_type, bytes, width, height)
+    }
+
+    pub fn text(&self) -> Option<String> {
+        match self.get(MIME_TEXT)? {
+            ClipboardContent::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn html(&self) -> Option<String> {
+        match self.get(MIME_HTML)? {
+            ClipboardContent::Html(html) => Some(html),
+            _ => None,
+        }
+    }
+
+    pub fn image(&self) -> Option<Image> {
+        match self.get(MIME_PNG)? {
+            ClipboardContent::Image(image) => Some(image),
+            _ => None,
+        }
+    }
+
+    pub fn uri_list(&self) -> Option<Vec<String>> {
+        match self.get(MIME_URI_LIST)? {
+            ClipboardContent::UriList(uris) => Some(uris),
+            _ => None,
+        }
+    }
+
+    /// Call `callback` on the UI thread whenever this clipboard's owner
+    /// changes, including after this application's own copies, until the
+    /// returned handle is unregistered.
+    pub fn on_change<F>(&self, callback: F) -> AureaResult<ClipboardListenerHandle>
+    where
+        F: Fn(ClipboardSelection) + Send + Sync + 'static,
+    {
+        let result = unsafe { ng_platform_clipboard_watch(self.selection.to_raw()) };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(ClipboardListenerHandle {
+            id: register_clipboard_listener(self.selection, callback),
+        })
+    }
+}
+
+/// A listener added with [`Clipboard::on_change`]. Dropping the handle keeps
+/// the listener.
+#[derive(Debug)]
+pub struct ClipboardListenerHandle {
+    id: u64,
+}
+
+impl ClipboardListenerHandle {
+    /// Remove the listener. Returns `false` if it was already gone.
+    pub fn unregister(&self) -> bool {
+        unregister_clipboard_listener(self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_text_payloads() {
+        let html = [0xFF, 0xFE, b'<', 0, b'b', 0, b'>', 0];
+        assert!(matches!(
+            ClipboardContent::decode(MIME_HTML, html.to_vec(), 0, 0),
+            Some(ClipboardContent::Html(text)) if text == "<b>"
+        ));
+        let uris = b"# comment\r\nfile:///tmp/a.png\r\n\r\nhttps://example.com/\r\n".to_vec();
+        assert!(matches!(
+            ClipboardContent::decode(MIME_URI_LIST, uris, 0, 0),
+            Some(ClipboardContent::UriList(list))
+                if list == ["file:///tmp/a.png", "https://example.com/"]
+        ));
+    }
+
+    #[test]
+    fn decodes_binary_payloads() {
+        assert!(ClipboardContent::decode(MIME_PNG, vec![0; 7], 1, 2).is_none());
+        assert!(matches!(
+            ClipboardContent::decode("application/x-aurea", vec![1, 2], 0, 0),
+            Some(ClipboardContent::Other { mime_type, data })
+                if mime_type == "application/x-aurea" && data == [1, 2]
+        ));
+    }
+
+    #[test]
+    fn rejects_unrepresentable_content() {
+        let bad_image = ClipboardContent::Image(Image::new(2, 2, vec![0; 3]));
+        assert!(bad_image.payload().is_err());
+        let bad_uris = ClipboardContent::UriList(vec!["a\nb".to_owned()]);
+        assert!(bad_uris.payload().is_err());
+        let uris = ClipboardContent::UriList(vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(uris.payload().ok().as_deref(), Some(&b"a\nb"[..]));
+    }
+
+    #[test]
+    fn unregistered_listeners_stop_firing() {
+        use crate::registry::clipboard::invoke_clipboard_listeners;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let handle = ClipboardListenerHandle {
+            id: register_clipboard_listener(ClipboardSelection::Primary, move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }),
+        };
+        invoke_clipboard_listeners(ClipboardSelection::Primary);
+        assert!(handle.unregister());
+        assert!(!handle.unregister());
+        invoke_clipboard_listeners(ClipboardSelection::Primary);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,245 @@
+use super::{block_on, c_string, opt_c_string, opt_ptr, request};
+use crate::ffi::*;
+use crate::registry::dialog::DialogResult;
+use crate::window::Window;
+use crate::{AureaError, AureaResult};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+/// A named group of file extensions offered by a [`FileDialog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    pub name: String,
+    /// Extensions without the dot, e.g. `"png"`; `"*"` matches every file.
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    pub fn new(name: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            extensions: extensions
+                .iter()
+                .map(|ext| ext.trim_start_matches('.').to_owned())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileDialogMode {
+    Open,
+    Save,
+    Folder,
+}
+
+impl FileDialogMode {
+    fn to_raw(self) -> u32 {
+        match self {
+            Self::Open => 0,
+            Self::Save => 1,
+            Self::Folder => 2,
+        }
+    }
+}
+
+/// Native open, save and folder pickers.
+///
+/// ```rust,no_run
+/// use aurea::dialog::FileDialog;
+///
+/// let images = FileDialog::new()
+///     .title("Import images")
+///     .filter("Images", &["png", "jpg"])
+///     .multiple(true)
+///     .open()?;
+/// # Ok::<(), aurea::AureaError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FileDialog {
+    title: Option<String>,
+    directory: Option<PathBuf>,
+    file_name: Option<String>,
+    filters: Vec<FileFilter>,
+    multiple: bool,
+    parent: Option<*mut c_void>,
+}
+
+impl FileDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Folder the dialog starts in.
+    pub fn directory(mut self, directory: impl AsRef<Path>) -> Self {
+        self.directory = Some(directory.as_ref().to_path_buf());
+        self
+    }
+
+    /// Suggested name for [`Self::save`].
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Offer a filter; the first one added is selected initially.
+    pub fn filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileFilter::new(name, extensions));
+        self
+    }
+
+    /// Allow [`Self::open`] to return several files.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    pub fn parent(mut self, window: &Window) -> Self {
+        self.parent = Some(window.handle);
+        self
+    }
+
+    /// Pick existing files. Empty when cancelled.
+    pub fn open(&self) -> AureaResult<Vec<PathBuf>> {
+        self.run(FileDialogMode::Open)
+    }
+
+    /// Pick a path to save to; the platform confirms overwriting.
+    pub fn save(&self) -> AureaResult<Option<PathBuf>> {
+        Ok(self.run(FileDialogMode::Save)?.into_iter().next())
+    }
+
+    pub fn pick_folder(&self) -> AureaResult<Option<PathBuf>> {
+        Ok(self.run(FileDialogMode::Folder)?.into_iter().next())
+    }
+
+    /// Like [`Self::open`], without blocking the event loop.
+    pub fn open_with<F>(&self, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Vec<PathBuf>) + Send + 'static,
+    {
+        self.show(FileDialogMode::Open, false, callback)
+    }
+
+    /// Like [`Self::save`], without blocking the event loop.
+    pub fn save_with<F>(&self, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<PathBuf>) + Send + 'static,
+    {
+        self.show(FileDialogMode::Save, false, |paths| {
+            callback(paths.into_iter().next());
+        })
+    }
+
+    /// Like [`Self::pick_folder`], without blocking the event loop.
+    pub fn pick_folder_with<F>(&self, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<PathBuf>) + Send + 'static,
+    {
+        self.show(FileDialogMode::Folder, false, |paths| {
+            callback(paths.into_iter().next());
+        })
+    }
+
+    fn run(&self, mode: FileDialogMode) -> AureaResult<Vec<PathBuf>> {
+        block_on(|reply| self.show(mode, true, reply))
+    }
+
+    fn show<F>(&self, mode: FileDialogMode, blocking: bool, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Vec<PathBuf>) + Send + 'static,
+    {
+        let strings = DialogStrings::new(self)?;
+        let filter_count =
+            c_int::try_from(self.filters.len()).map_err(|_| AureaError::ElementOperationFailed)?;
+        let options = NGFileDialogOptions {
+            mode: mode.to_raw(),
+            title: opt_ptr(&strings.title),
+            directory: opt_ptr(&strings.directory),
+            file_name: opt_ptr(&strings.file_name),
+            filter_names: strings.filter_names.as_ptr(),
+            filter_extensions: strings.filter_extensions.as_ptr(),
+            filter_count,
+            multiple: c_int::from(self.multiple && mode == FileDialogMode::Open),
+            blocking: c_int::from(blocking),
+        };
+
+        let parent = self.parent.unwrap_or(ptr::null_mut());
+        request(
+            |request_id| unsafe { ng_platform_file_dialog_show(parent, &options, request_id) },
+            move |result| {
+                if let DialogResult::Files(paths) = result {
+                    callback(paths);
+                }
+            },
+        )
+    }
+}
+
+/// C strings backing an `NGFileDialogOptions`, kept alive for the call.
+struct DialogStrings {
+    title: Option<CString>,
+    directory: Option<CString>,
+    file_name: Option<CString>,
+    _filter_storage: Vec<CString>,
+    filter_names: Vec<*const c_char>,
+    filter_extensions: Vec<*const c_char>,
+}
+
+impl DialogStrings {
+    fn new(dialog: &FileDialog) -> AureaResult<Self> {
+        let mut storage = Vec::with_capacity(dialog.filters.len() * 2);
+        for filter in &dialog.filters {
+            storage.push(c_string(&filter.name)?);
+            storage.push(c_string(&filter.extensions.join(";"))?);
+        }
+        let filter_names = storage.iter().step_by(2).map(|s| s.as_ptr()).collect();
+        let filter_extensions = storage
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .map(|s| s.as_ptr())
+            .collect();
+        Ok(Self {
+            title: opt_c_string(dialog.title.as_deref())?,
+            directory: dialog
+                .directory
+                .as_ref()
+                .map(|dir| c_string(&dir.to_string_lossy()))
+                .transpose()?,
+            file_name: opt_c_string(dialog.file_name.as_deref())?,
+            _filter_storage: storage,
+            filter_names,
+            filter_extensions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn filters_pair_names_with_extensions() {
+        let dialog = FileDialog::new()
+            .filter("Images", &[".png", "jpg"])
+            .filter("All files", &["*"]);
+        let strings = DialogStrings::new(&dialog).unwrap();
+        let read = |ptrs: &[*const c_char]| -> Vec<String> {
+            ptrs.iter()
+                .map(|p| unsafe { CStr::from_ptr(*p) }.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(read(&strings.filter_names), ["Images", "All files"]);
+        assert_eq!(read(&strings.filter_extensions), ["png;jpg", "*"]);
+        assert!(strings.title.is_none());
+    }
+}
@@ -0,0 +1,238 @@
+use super::{block_on, c_string, opt_c_string, opt_ptr, request};
+use crate::ffi::*;
+use crate::registry::dialog::DialogResult;
+use crate::window::Window;
+use crate::{AureaError, AureaResult};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+/// The icon and tone of a [`MessageDialog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MessageKind {
+    #[default]
+    Info,
+    Warning,
+    Error,
+    Question,
+}
+
+impl MessageKind {
+    fn to_raw(self) -> u32 {
+        match self {
+            Self::Info => 0,
+            Self::Warning => 1,
+            Self::Error => 2,
+            Self::Question => 3,
+        }
+    }
+}
+
+/// A message box with custom buttons that reports which one was pressed.
+///
+/// Buttons are identified by the order they were added in. Without any, a
+/// single OK button is shown as index 0.
+///
+/// ```rust,no_run
+/// use aurea::dialog::MessageDialog;
+///
+/// let choice = MessageDialog::question("Save changes before closing?")
+///     .detail("Unsaved changes will be lost.")
+///     .button("Save")
+///     .button("Discard")
+///     .button("Cancel")
+///     .default_button(0)
+///     .cancel_button(2)
+///     .show()?;
+/// if choice == Some(0) {
+///     // save
+/// }
+/// # Ok::<(), aurea::AureaError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageDialog {
+    kind: MessageKind,
+    message: String,
+    title: Option<String>,
+    detail: Option<String>,
+    buttons: Vec<String>,
+    default_button: Option<usize>,
+    cancel_button: Option<usize>,
+    parent: Option<*mut c_void>,
+}
+
+impl MessageDialog {
+    pub fn new(kind: MessageKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(MessageKind::Info, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(MessageKind::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(MessageKind::Error, message)
+    }
+
+    pub fn question(message: impl Into<String>) -> Self {
+        Self::new(MessageKind::Question, message)
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Secondary text shown below the message.
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn button(mut self, label: impl Into<String>) -> Self {
+        self.buttons.push(label.into());
+        self
+    }
+
+    /// Button activated by Enter.
+    pub fn default_button(mut self, index: usize) -> Self {
+        self.default_button = Some(index);
+        self
+    }
+
+    /// Button reported when the dialog is dismissed with Escape or closed
+    /// from the title bar. Without one, dismissing reports `None`.
+    pub fn cancel_button(mut self, index: usize) -> Self {
+        self.cancel_button = Some(index);
+        self
+    }
+
+    pub fn parent(mut self, window: &Window) -> Self {
+        self.parent = Some(window.handle);
+        self
+    }
+
+    /// Index of the pressed button, or `None` when dismissed.
+    pub fn show(&self) -> AureaResult<Option<usize>> {
+        block_on(|reply| self.open(true, reply))
+    }
+
+    /// Like [`Self::show`], without blocking the event loop.
+    pub fn show_with<F>(&self, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<usize>) + Send + 'static,
+    {
+        self.open(false, callback)
+    }
+
+    /// Ask for confirmation, offering OK and Cancel unless buttons were
+    /// added. True when the first button was pressed.
+    pub fn confirm(&self) -> AureaResult<bool> {
+        let choice = if self.buttons.is_empty() {
+            self.clone()
+                .button("OK")
+                .button("Cancel")
+                .default_button(0)
+                .cancel_button(1)
+                .show()?
+        } else {
+            self.show()?
+        };
+        Ok(choice == Some(0))
+    }
+
+    fn open<F>(&self, blocking: bool, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<usize>) + Send + 'static,
+    {
+        let strings = MessageStrings::new(self)?;
+        let options = NGMessageDialogOptions {
+            kind: self.kind.to_raw(),
+            title: opt_ptr(&strings.title),
+            message: strings.message.as_ptr(),
+            detail: opt_ptr(&strings.detail),
+            buttons: strings.buttons.as_ptr(),
+            button_count: c_int::try_from(strings.buttons.len())
+                .map_err(|_| AureaError::ElementOperationFailed)?,
+            default_button: raw_index(self.default_button)?,
+            cancel_button: raw_index(self.cancel_button)?,
+            blocking: c_int::from(blocking),
+        };
+
+        let parent = self.parent.unwrap_or(ptr::null_mut());
+        request(
+            |request_id| unsafe { ng_platform_message_dialog_show(parent, &options, request_id) },
+            move |result| {
+                if let DialogResult::Button(button) = result {
+                    callback(button);
+                }
+            },
+        )
+    }
+}
+
+/// A button index for the platform, -1 for none.
+fn raw_index(index: Option<usize>) -> AureaResult<c_int> {
+    index.map_or(Ok(-1), |index| {
+        c_int::try_from(index).map_err(|_| AureaError::ElementOperationFailed)
+    })
+}
+
+/// C strings backing an `NGMessageDialogOptions`, kept alive for the call.
+struct MessageStrings {
+    title: Option<CString>,
+    message: CString,
+    detail: Option<CString>,
+    _button_storage: Vec<CString>,
+    buttons: Vec<*const c_char>,
+}
+
+impl MessageStrings {
+    fn new(dialog: &MessageDialog) -> AureaResult<Self> {
+        let storage = dialog
+            .buttons
+            .iter()
+            .map(|label| c_string(label))
+            .collect::<AureaResult<Vec<_>>>()?;
+        let buttons = storage.iter().map(|s| s.as_ptr()).collect();
+        Ok(Self {
+            title: opt_c_string(dialog.title.as_deref())?,
+            message: c_string(&dialog.message)?,
+            detail: opt_c_string(dialog.detail.as_deref())?,
+            _button_storage: storage,
+            buttons,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn buttons_keep_their_order() {
+        let dialog = MessageDialog::warning("Delete?")
+            .button("Delete")
+            .button("Keep")
+            .cancel_button(1);
+        let strings = MessageStrings::new(&dialog).unwrap();
+        let labels: Vec<_> = strings
+            .buttons
+            .iter()
+            .map(|p| unsafe { CStr::from_ptr(*p) }.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(labels, ["Delete", "Keep"]);
+        assert_eq!(raw_index(dialog.cancel_button).unwrap(), 1);
+        assert_eq!(raw_index(dialog.default_button).unwrap(), -1);
+        assert!(MessageStrings::new(&MessageDialog::info("a\0b")).is_err());
+    }
+}
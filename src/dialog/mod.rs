@@ -0,0 +1,62 @@
+//! Native dialogs: file pickers, message boxes and colour and font pickers.
+//!
+//! Each dialog comes in a blocking form, which runs a nested event loop
+//! until the user answers, and a `*_with` form that returns immediately and
+//! calls back on the UI thread. Set a `parent` to make the dialog modal to
+//! that window.
+
+mod file;
+mod message;
+mod picker;
+
+pub use file::{FileDialog, FileFilter};
+pub use message::{MessageDialog, MessageKind};
+pub use picker::{ColorPicker, FontPicker};
+
+use crate::registry::dialog::{DialogResult, cancel_dialog_callback, register_dialog_callback};
+use crate::sync::lock;
+use crate::{AureaError, AureaResult};
+use std::ffi::CString;
+use std::mem;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+type Reply<T> = Box<dyn FnOnce(T) + Send>;
+
+/// Open a dialog with `show`, which receives the request id and returns the
+/// platform status; `callback` gets the result when the dialog closes.
+fn request<F>(show: impl FnOnce(u32) -> c_int, callback: F) -> AureaResult<()>
+where
+    F: FnOnce(DialogResult) + Send + 'static,
+{
+    let request_id = register_dialog_callback(callback);
+    if show(request_id) != 0 {
+        cancel_dialog_callback(request_id);
+        return Err(AureaError::ElementOperationFailed);
+    }
+    Ok(())
+}
+
+/// Run a blocking `show`, which reports before it returns.
+fn block_on<T>(show: impl FnOnce(Reply<T>) -> AureaResult<()>) -> AureaResult<T>
+where
+    T: Default + Send + 'static,
+{
+    let result = Arc::new(Mutex::new(T::default()));
+    let slot = Arc::clone(&result);
+    show(Box::new(move |value| *lock(&slot) = value))?;
+    Ok(mem::take(&mut *lock(&result)))
+}
+
+fn c_string(s: &str) -> AureaResult<CString> {
+    CString::new(s).map_err(|_| AureaError::ElementOperationFailed)
+}
+
+fn opt_c_string(s: Option<&str>) -> AureaResult<Option<CString>> {
+    s.map(c_string).transpose()
+}
+
+fn opt_ptr(s: &Option<CString>) -> *const c_char {
+    s.as_ref().map_or(ptr::null(), |s| s.as_ptr())
+}
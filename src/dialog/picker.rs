@@ -0,0 +1,181 @@
+use super::{block_on, c_string, opt_c_string, opt_ptr, request};
+use crate::AureaResult;
+use crate::ffi::*;
+use crate::registry::dialog::DialogResult;
+use crate::render::{Color, Font, FontStyle, FontWeight};
+use crate::window::Window;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+/// A native colour chooser.
+///
+/// ```rust,no_run
+/// use aurea::dialog::ColorPicker;
+/// use aurea::render::Color;
+///
+/// let color = ColorPicker::new()
+///     .initial(Color::rgb(255, 128, 0))
+///     .alpha(true)
+///     .pick()?;
+/// # Ok::<(), aurea::AureaError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ColorPicker {
+    title: Option<String>,
+    initial: Option<Color>,
+    alpha: bool,
+    parent: Option<*mut c_void>,
+}
+
+impl ColorPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn initial(mut self, color: Color) -> Self {
+        self.initial = Some(color);
+        self
+    }
+
+    /// Let the user choose an opacity. Otherwise picked colours are opaque.
+    pub fn alpha(mut self, alpha: bool) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn parent(mut self, window: &Window) -> Self {
+        self.parent = Some(window.handle);
+        self
+    }
+
+    /// The chosen colour, or `None` when cancelled.
+    pub fn pick(&self) -> AureaResult<Option<Color>> {
+        block_on(|reply| self.open(true, reply))
+    }
+
+    /// Like [`Self::pick`], without blocking the event loop.
+    pub fn pick_with<F>(&self, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<Color>) + Send + 'static,
+    {
+        self.open(false, callback)
+    }
+
+    fn open<F>(&self, blocking: bool, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<Color>) + Send + 'static,
+    {
+        let title = opt_c_string(self.title.as_deref())?;
+        let initial = self.initial.map(|c| [c.r, c.g, c.b, c.a]);
+        let initial_ptr = initial.as_ref().map_or(ptr::null(), |rgba| rgba.as_ptr());
+        let parent = self.parent.unwrap_or(ptr::null_mut());
+        let alpha = self.alpha;
+        request(
+            |request_id| unsafe {
+                ng_platform_color_picker_show(
+                    parent,
+                    opt_ptr(&title),
+                    initial_ptr,
+                    c_int::from(alpha),
+                    c_int::from(blocking),
+                    request_id,
+                )
+            },
+            move |result| {
+                if let DialogResult::Color(color) = result {
+                    callback(color.map(|c| if alpha { c } else { Color { a: 255, ..c } }));
+                }
+            },
+        )
+    }
+}
+
+/// A native font chooser.
+///
+/// Picked fonts carry the family, size in points, and whether the chosen
+/// face is bold or italic.
+#[derive(Debug, Clone, Default)]
+pub struct FontPicker {
+    title: Option<String>,
+    initial: Option<Font>,
+    parent: Option<*mut c_void>,
+}
+
+impl FontPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn initial(mut self, font: Font) -> Self {
+        self.initial = Some(font);
+        self
+    }
+
+    pub fn parent(mut self, window: &Window) -> Self {
+        self.parent = Some(window.handle);
+        self
+    }
+
+    /// The chosen font, or `None` when cancelled.
+    pub fn pick(&self) -> AureaResult<Option<Font>> {
+        block_on(|reply| self.open(true, reply))
+    }
+
+    /// Like [`Self::pick`], without blocking the event loop.
+    pub fn pick_with<F>(&self, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<Font>) + Send + 'static,
+    {
+        self.open(false, callback)
+    }
+
+    fn open<F>(&self, blocking: bool, callback: F) -> AureaResult<()>
+    where
+        F: FnOnce(Option<Font>) + Send + 'static,
+    {
+        let title = opt_c_string(self.title.as_deref())?;
+        let family = self
+            .initial
+            .as_ref()
+            .map(|font| c_string(&font.family))
+            .transpose()?;
+        let initial = self
+            .initial
+            .as_ref()
+            .zip(family.as_ref())
+            .map(|(font, family)| NGFontDescription {
+                family: family.as_ptr(),
+                size: font.size,
+                bold: c_int::from(font.weight == FontWeight::Bold),
+                italic: c_int::from(font.style == FontStyle::Italic),
+            });
+        let initial_ptr = initial.as_ref().map_or(ptr::null(), ptr::from_ref);
+        let parent = self.parent.unwrap_or(ptr::null_mut());
+        request(
+            |request_id| unsafe {
+                ng_platform_font_picker_show(
+                    parent,
+                    opt_ptr(&title),
+                    initial_ptr,
+                    c_int::from(blocking),
+                    request_id,
+                )
+            },
+            move |result| {
+                if let DialogResult::Font(font) = result {
+                    callback(font);
+                }
+            },
+        )
+    }
+}
@@ -1,6 +1,9 @@
-use crate::AureaResult;
+use crate::ffi::ng_platform_element_set_accessible;
 use crate::render::Rect;
+use crate::{AureaError, AureaResult};
+use std::ffi::CString;
 use std::os::raw::c_void;
+use std::ptr;
 
 /// Base trait for all native GUI elements.
 pub trait Element {
@@ -37,6 +40,26 @@ pub trait Element {
     unsafe fn invalidate_platform(&self, rect: Option<Rect>);
 
     fn request_layout(&self) {}
+
+    /// Name the element for screen readers, replacing the name the platform
+    /// derives from its contents; useful for icon-only buttons and unlabeled
+    /// fields.
+    fn set_accessible_label(&self, name: &str, description: Option<&str>) -> AureaResult<()> {
+        let to_c = |s: &str| CString::new(s).map_err(|_| AureaError::ElementOperationFailed);
+        let name = to_c(name)?;
+        let description = description.map(to_c).transpose()?;
+        let result = unsafe {
+            ng_platform_element_set_accessible(
+                self.handle(),
+                name.as_ptr(),
+                description.as_ref().map_or(ptr::null(), |d| d.as_ptr()),
+            )
+        };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
 }
 
 /// A container element that can hold child elements.
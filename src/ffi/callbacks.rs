@@ -1,15 +1,30 @@
-use std::ffi::CStr;
+use std::ffi::{CStr, OsStr};
 use std::os::raw::{c_char, c_int, c_void};
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::slice;
 
+use crate::clipboard::ClipboardSelection;
 use crate::elements::{
     invoke_button_callback, invoke_sidebar_list_selected, invoke_tab_bar_detach,
     invoke_tab_bar_selected, invoke_text_callback, invoke_textview_callback,
 };
 use crate::menu::invoke_menu_callback;
+use crate::registry::clipboard::invoke_clipboard_listeners;
 use crate::registry::custom::invoke_custom_callback;
+use crate::registry::dialog::{DialogResult, invoke_dialog_callback};
+use crate::registry::focus::invoke_native_focus_callback;
+use crate::render::{Color, Font, FontStyle, FontWeight};
+use crate::tray::tray_events;
 use crate::view::FrameScheduler;
-use crate::window::{KeyCode, Modifiers, MouseButton, WindowEvent, push_window_event};
-use aurea_ffi::ng_platform_get_scale_factor;
+use crate::window::{
+    DragData, DropEffect, KeyCode, LogicalKey, Modifiers, MouseButton, NotificationCloseReason,
+    PhysicalKey, PointerButtons, PointerEvent, PointerKind, PointerPhase, ScrollDeltaKind,
+    ScrollPhase, TouchpadPhase, WindowEvent, broadcast_window_event, push_window_event,
+    state_event,
+};
+use aurea_ffi::{NGFontDescription, ng_platform_get_scale_factor};
 
 #[inline]
 fn c_string(ptr: *const c_char) -> Option<String> {
@@ -46,6 +61,126 @@ pub extern "C" fn ng_invoke_sidebar_list_selected(id: u32, index: i32) {
     invoke_sidebar_list_selected(id, index);
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_clipboard_changed(selection: u32) {
+    invoke_clipboard_listeners(ClipboardSelection::from_raw(selection));
+}
+
+/// Native paths are bytes on Unix; keep them intact rather than as UTF-8.
+#[cfg(unix)]
+fn c_path(ptr: *const c_char) -> Option<PathBuf> {
+    if ptr.is_null() {
+        return None;
+    }
+    let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes();
+    Some(PathBuf::from(OsStr::from_bytes(bytes)))
+}
+
+#[cfg(not(unix))]
+fn c_path(ptr: *const c_char) -> Option<PathBuf> {
+    c_string(ptr).map(PathBuf::from)
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn ng_invoke_file_dialog_result(
+    request_id: u32,
+    paths: *const *const c_char,
+    count: c_int,
+) {
+    let count = usize::try_from(count).unwrap_or(0);
+    let paths = if paths.is_null() || count == 0 {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(paths, count) }
+            .iter()
+            .filter_map(|path| c_path(*path))
+            .collect()
+    };
+    invoke_dialog_callback(request_id, DialogResult::Files(paths));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_message_dialog_result(request_id: u32, button: c_int) {
+    let button = usize::try_from(button).ok();
+    invoke_dialog_callback(request_id, DialogResult::Button(button));
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn ng_invoke_color_picker_result(request_id: u32, rgba: *const u8) {
+    let color = (!rgba.is_null()).then(|| {
+        let rgba = unsafe { slice::from_raw_parts(rgba, 4) };
+        Color::rgba(rgba[0], rgba[1], rgba[2], rgba[3])
+    });
+    invoke_dialog_callback(request_id, DialogResult::Color(color));
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn ng_invoke_font_picker_result(request_id: u32, font: *const NGFontDescription) {
+    let font = unsafe { font.as_ref() }.map(|font| {
+        let family = c_string(font.family).unwrap_or_default();
+        let weight = if font.bold != 0 {
+            FontWeight::Bold
+        } else {
+            FontWeight::Normal
+        };
+        let style = if font.italic != 0 {
+            FontStyle::Italic
+        } else {
+            FontStyle::Normal
+        };
+        Font::new(&family, font.size)
+            .with_weight(weight)
+            .with_style(style)
+    });
+    invoke_dialog_callback(request_id, DialogResult::Font(font));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_window_state_changed(window: *mut c_void, flag: u32, enabled: c_int) {
+    if let Some(event) = state_event(flag, enabled != 0) {
+        push_window_event(window, event);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_monitors_changed() {
+    broadcast_window_event(&WindowEvent::MonitorsChanged);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_tray_event(
+    tray: *mut c_void,
+    button: c_int,
+    click_count: c_int,
+    x: c_int,
+    y: c_int,
+) {
+    for event in tray_events(button, click_count, x, y) {
+        push_window_event(tray, event);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_notification_action(notification: *mut c_void, action: *const c_char) {
+    if let Some(action) = c_string(action) {
+        push_window_event(notification, WindowEvent::NotificationAction { action });
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_notification_closed(notification: *mut c_void, reason: u32) {
+    let reason = NotificationCloseReason::from_raw(reason);
+    push_window_event(notification, WindowEvent::NotificationClosed { reason });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_native_focus_changed(window: *mut c_void, element: *mut c_void) {
+    invoke_native_focus_callback(window as usize, element as usize);
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn ng_invoke_text_callback(id: u32, content: *const c_char) {
     if let Some(content) = c_string(content) {
@@ -72,17 +207,33 @@ pub extern "C" fn ng_invoke_lifecycle_callback(window: *mut c_void, event_id: u3
     }
 }
 
-#[unsafe(no_mangle)]
-pub extern "C" fn ng_invoke_key_event(
-    window: *mut c_void,
+/// Mirror of `NGKeyEvent` in `common/input.h`.
+#[repr(C)]
+pub struct NGKeyEvent {
     keycode: u32,
+    physical: u32,
+    text: *const c_char,
     pressed: c_int,
+    repeat: c_int,
     modifiers: u32,
-) {
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn ng_invoke_key_event(window: *mut c_void, event: *const NGKeyEvent) {
+    let Some(raw) = (unsafe { event.as_ref() }) else {
+        return;
+    };
+    let key = KeyCode::from_raw(raw.keycode);
+    let physical_key = PhysicalKey::from_raw(raw.physical);
     let event = WindowEvent::KeyInput {
-        key: KeyCode::from_raw(keycode),
-        pressed: pressed != 0,
-        modifiers: Modifiers::from_bits(modifiers),
+        key,
+        physical_key,
+        logical_key: LogicalKey::new(key, c_string(raw.text).as_deref()),
+        location: physical_key.location(),
+        pressed: raw.pressed != 0,
+        repeat: raw.repeat != 0,
+        modifiers: Modifiers::from_bits(raw.modifiers),
     };
     push_window_event(window, event);
 }
@@ -114,26 +265,154 @@ pub extern "C" fn ng_invoke_mouse_button(
 
 #[unsafe(no_mangle)]
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
-pub extern "C" fn ng_invoke_mouse_move(window: *mut c_void, x: f64, y: f64) {
+pub extern "C" fn ng_invoke_mouse_move(window: *mut c_void, x: f64, y: f64, modifiers: u32) {
     let scale = f64::from(unsafe { ng_platform_get_scale_factor(window) }).max(1.0);
     let event = WindowEvent::MouseMove {
         x: x / scale,
         y: y / scale,
+        modifiers: Modifiers::from_bits(modifiers),
     };
     push_window_event(window, event);
 }
 
 #[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn ng_invoke_mouse_wheel(
     window: *mut c_void,
     delta_x: f64,
     delta_y: f64,
     modifiers: u32,
+    delta_kind: u32,
+    phase: u32,
 ) {
+    let delta_kind = ScrollDeltaKind::from_raw(delta_kind);
+    // Pixel deltas are physical on HiDPI platforms; lines have no scale.
+    let scale = match delta_kind {
+        ScrollDeltaKind::Pixel => {
+            f64::from(unsafe { ng_platform_get_scale_factor(window) }).max(1.0)
+        }
+        ScrollDeltaKind::Line => 1.0,
+    };
     let event = WindowEvent::MouseWheel {
-        delta_x,
-        delta_y,
+        delta_x: delta_x / scale,
+        delta_y: delta_y / scale,
         modifiers: Modifiers::from_bits(modifiers),
+        delta_kind,
+        phase: ScrollPhase::from_raw(phase),
+    };
+    push_window_event(window, event);
+}
+
+/// Mirror of `NGPointerEvent` in `common/input.h`.
+#[repr(C)]
+pub struct NGPointerEvent {
+    pointer_id: u64,
+    kind: u32,
+    phase: u32,
+    x: f64,
+    y: f64,
+    pressure: f32,
+    tilt_x: f32,
+    tilt_y: f32,
+    twist: f32,
+    buttons: u32,
+    modifiers: u32,
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn ng_invoke_pointer_event(window: *mut c_void, event: *const NGPointerEvent) {
+    let Some(raw) = (unsafe { event.as_ref() }) else {
+        return;
+    };
+    let scale = f64::from(unsafe { ng_platform_get_scale_factor(window) }).max(1.0);
+    let event = WindowEvent::Pointer(PointerEvent {
+        pointer_id: raw.pointer_id,
+        kind: PointerKind::from_raw(raw.kind),
+        phase: PointerPhase::from_raw(raw.phase),
+        position: (raw.x / scale, raw.y / scale),
+        pressure: raw.pressure.clamp(0.0, 1.0),
+        tilt: (raw.tilt_x, raw.tilt_y),
+        twist: raw.twist,
+        buttons: PointerButtons::from_bits(raw.buttons),
+        modifiers: Modifiers::from_bits(raw.modifiers),
+    });
+    push_window_event(window, event);
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn ng_invoke_touchpad_pinch(
+    window: *mut c_void,
+    phase: u32,
+    x: f64,
+    y: f64,
+    scale: f64,
+    rotation: f64,
+) {
+    let factor = f64::from(unsafe { ng_platform_get_scale_factor(window) }).max(1.0);
+    push_window_event(
+        window,
+        WindowEvent::TouchpadPinch {
+            phase: TouchpadPhase::from_raw(phase),
+            position: (x / factor, y / factor),
+            scale,
+            rotation,
+        },
+    );
+}
+
+/// Mirror of `NGDragEvent` in `common/input.h`.
+#[repr(C)]
+pub struct NGDragEvent {
+    phase: u32,
+    x: f64,
+    y: f64,
+    data_kind: u32,
+    items: *const *const c_char,
+    item_count: c_int,
+    effect: u32,
+}
+
+const NG_DRAG_ENTERED: u32 = 0;
+const NG_DRAG_OVER: u32 = 1;
+const NG_DRAG_DROPPED: u32 = 3;
+
+fn drag_data(raw: &NGDragEvent) -> DragData {
+    let count = usize::try_from(raw.item_count).unwrap_or(0);
+    let items = if raw.items.is_null() || count == 0 {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(raw.items, count) }
+            .iter()
+            .filter_map(|item| c_string(*item))
+            .collect()
+    };
+    DragData::from_raw(raw.data_kind, items)
+}
+
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn ng_invoke_drag_event(window: *mut c_void, event: *const NGDragEvent) {
+    let Some(raw) = (unsafe { event.as_ref() }) else {
+        return;
+    };
+    let scale = f64::from(unsafe { ng_platform_get_scale_factor(window) }).max(1.0);
+    let (x, y) = (raw.x / scale, raw.y / scale);
+    let event = match raw.phase {
+        NG_DRAG_ENTERED => WindowEvent::DragEntered {
+            data: drag_data(raw),
+            x,
+            y,
+        },
+        NG_DRAG_OVER => WindowEvent::DragOver { x, y },
+        NG_DRAG_DROPPED => WindowEvent::Dropped {
+            data: drag_data(raw),
+            x,
+            y,
+            effect: DropEffect::from_raw(raw.effect),
+        },
+        _ => WindowEvent::DragLeft,
     };
     push_window_event(window, event);
 }
@@ -146,6 +425,43 @@ pub extern "C" fn ng_invoke_text_input(window: *mut c_void, text: *const c_char)
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_ime_state(window: *mut c_void, enabled: c_int) {
+    let event = if enabled != 0 {
+        WindowEvent::ImeEnabled
+    } else {
+        WindowEvent::ImeDisabled
+    };
+    push_window_event(window, event);
+}
+
+/// `cursor_start`/`cursor_end` are byte offsets into `text`; negative means
+/// no cursor.
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_ime_preedit(
+    window: *mut c_void,
+    text: *const c_char,
+    cursor_start: c_int,
+    cursor_end: c_int,
+) {
+    let cursor_range = usize::try_from(cursor_start)
+        .ok()
+        .zip(usize::try_from(cursor_end).ok());
+    let event = WindowEvent::ImePreedit {
+        text: c_string(text).unwrap_or_default(),
+        cursor_range,
+    };
+    push_window_event(window, event);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ng_invoke_ime_commit(window: *mut c_void, text: *const c_char) {
+    if let Some(text) = c_string(text) {
+        let event = WindowEvent::ImeCommit { text };
+        push_window_event(window, event);
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn ng_invoke_focus_changed(window: *mut c_void, focused: c_int) {
     let event = if focused != 0 {
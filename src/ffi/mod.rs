@@ -4,3 +4,15 @@ mod logging;
 pub use aurea_ffi::*;
 pub use callbacks::*;
 pub use logging::*;
+
+use crate::{AureaError, AureaResult};
+use std::os::raw::c_int;
+
+/// Map a native status code to a result; every failure is reported as
+/// [`AureaError::ElementOperationFailed`].
+pub(crate) fn check(result: c_int) -> AureaResult<()> {
+    if result != NG_SUCCESS {
+        return Err(AureaError::ElementOperationFailed);
+    }
+    Ok(())
+}
@@ -26,6 +26,11 @@
 /// - **`integration`** - External renderer integrations (wgpu, etc.)
 /// - **`lifecycle`** - Application lifecycle events
 /// - **`menu`** - Menu bar and menu management
+/// - **`clipboard`** - System clipboard and the Linux primary selection
+/// - **`dialog`** - Native file, message, colour and font dialogs
+/// - **`tray`** - System tray icons with menus and click events
+/// - **`notification`** - Desktop notifications with actions
+/// - **`accessibility`** - Accessibility trees for canvas content and widget labels
 ///
 /// # Features
 ///
@@ -61,6 +66,9 @@ mod sync {
 #[cfg(target_os = "android")]
 mod android;
 
+pub mod accessibility;
+pub mod clipboard;
+pub mod dialog;
 pub mod elements;
 pub mod embed;
 pub mod ffi;
@@ -68,27 +76,58 @@ pub mod integration;
 pub mod lifecycle;
 pub mod logger;
 pub mod menu;
+pub mod notification;
 pub mod registry;
 pub mod render;
+pub mod tray;
 pub mod view;
 pub mod window;
 
+#[cfg(all(test, target_os = "linux"))]
+mod test_bus;
+
 pub use crate::view::FrameScheduler;
 pub use crate::view::damage::DamageRegion;
-pub use aurea_runtime::FrameInfo;
+#[cfg(feature = "tokio")]
+pub use aurea_runtime::TokioBridge;
+pub use aurea_runtime::{CanvasFrameStats, FrameInfo, FrameStats};
+pub use aurea_runtime::{
+    Debouncer, Executor, MainThreadReceiver, MainThreadSender, TaskId, Throttle, TimerHandle,
+    main_thread_channel,
+};
+pub use aurea_runtime::{
+    Fling, GestureConfig, GestureDetector, GestureEvent, GestureHandle, GestureKind, GestureState,
+    VelocityTracker,
+};
 
+pub use crate::clipboard::{
+    Clipboard, ClipboardContent, ClipboardListenerHandle, ClipboardSelection,
+};
+pub use crate::dialog::{
+    ColorPicker, FileDialog, FileFilter, FontPicker, MessageDialog, MessageKind,
+};
 // Re-export the elements, window, and menu modules
 pub use crate::elements::{
     Box, BoxOrientation, Container, Divider, Element, Label, Spacer, SplitOrientation, SplitView,
     TextField,
 };
-pub use crate::menu::{MenuBar, MenuShortcut, ShortcutKey, SubMenu};
+pub use crate::menu::{
+    ContextMenu, MenuBar, MenuItem, MenuItemOptions, MenuShortcut, ShortcutKey, SubMenu,
+};
+pub use crate::notification::{Notification, NotificationHandle, Urgency};
+pub use crate::tray::TrayIcon;
 pub use crate::window::{
-    CursorGrabMode, Window, WindowId, WindowManager, WindowType, clipboard_text, set_clipboard_text,
+    CursorGrabMode, CursorIcon, FocusManager, Monitor, Window, WindowBuilder, WindowId,
+    WindowManager, WindowState, WindowType, clipboard_text, set_clipboard_text,
 };
 
 // Re-export window event types
-pub use crate::window::{EventCallback, KeyCode, Modifiers, MouseButton, WindowEvent};
+pub use crate::window::{
+    AccessibilityAction, DragData, DropEffect, EventCallback, FocusTarget, KeyCode, KeyLocation,
+    LogicalKey, Modifiers, MouseButton, NotificationCloseReason, PhysicalKey, PointerButtons,
+    PointerEvent, PointerKind, PointerPhase, ScrollDeltaKind, ScrollPhase, TouchpadPhase,
+    WindowEvent,
+};
 
 #[cfg(feature = "wgpu")]
 pub use crate::integration::NativeWindowHandle;
@@ -3,10 +3,18 @@
 use crate::ffi::*;
 use crate::registry::menu::{
     invoke_menu_callback as invoke_registered_menu_callback, next_menu_item_id,
-    register_menu_callback,
+    register_menu_callback, unregister_menu_callback,
+};
+use crate::render::Image;
+use crate::window::is_rgba_image;
+use crate::{AureaError, AureaResult, Capability, CapabilityChecker, KeyCode, Modifiers};
+use std::{
+    ffi::CString,
+    ops::{Deref, DerefMut},
+    os::raw::{c_int, c_void},
+    ptr,
+    sync::{Arc, Weak},
 };
-use crate::{AureaError, AureaResult};
-use std::{ffi::CString, os::raw::c_void};
 
 use log::debug;
 
@@ -114,35 +122,233 @@ impl MenuShortcut {
 
         parts.join("+")
     }
+
+    /// Parse shortcut text such as `"Ctrl+Shift+S"` or `"Alt+F4"`.
+    ///
+    /// Accepts the names [`Self::encode_for_platform`] produces plus the usual
+    /// aliases (`Control`, `Option`, `Cmd`, `Esc`); returns `None` without a key.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut key = None;
+        let mut shortcut = Self::new(ShortcutKey::Space);
+        for part in text.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => shortcut.ctrl = true,
+                "shift" => shortcut.shift = true,
+                "alt" | "option" => shortcut.alt = true,
+                "cmd" | "command" | "meta" => shortcut.meta = true,
+                "enter" => key = Some(ShortcutKey::Enter),
+                "tab" => key = Some(ShortcutKey::Tab),
+                "esc" | "escape" => key = Some(ShortcutKey::Escape),
+                "space" => key = Some(ShortcutKey::Space),
+                "backspace" => key = Some(ShortcutKey::Backspace),
+                "delete" => key = Some(ShortcutKey::Delete),
+                lower => key = parse_key(lower).or(key),
+            }
+        }
+        shortcut.key = key?;
+        Some(shortcut)
+    }
+
+    /// `(accel_char, accel_key, accel_modifiers)` for `NGMenuItemOptions`.
+    fn to_raw(self) -> (u32, u32, u32) {
+        let modifiers = Modifiers {
+            shift: self.shift,
+            ctrl: self.ctrl || (self.primary && !cfg!(target_os = "macos")),
+            alt: self.alt,
+            meta: self.meta || (self.primary && cfg!(target_os = "macos")),
+            ..Modifiers::default()
+        }
+        .to_bits();
+
+        let key = match self.key {
+            ShortcutKey::Char(c) => return (u32::from(c.to_ascii_lowercase()), NO_KEY, modifiers),
+            ShortcutKey::F(n @ 1..=12) => KeyCode::F1.to_raw() + u32::from(n - 1),
+            ShortcutKey::F(n @ 13..=24) => KeyCode::F13.to_raw() + u32::from(n - 13),
+            ShortcutKey::F(_) => NO_KEY,
+            ShortcutKey::Enter => KeyCode::Enter.to_raw(),
+            ShortcutKey::Tab => KeyCode::Tab.to_raw(),
+            ShortcutKey::Escape => KeyCode::Escape.to_raw(),
+            ShortcutKey::Space => KeyCode::Space.to_raw(),
+            ShortcutKey::Backspace => KeyCode::Backspace.to_raw(),
+            ShortcutKey::Delete => KeyCode::Delete.to_raw(),
+        };
+        (0, key, modifiers)
+    }
+}
+
+fn parse_key(lower: &str) -> Option<ShortcutKey> {
+    let mut chars = lower.chars();
+    match (chars.next(), chars.as_str()) {
+        (Some(c), "") if c.is_ascii_alphanumeric() => {
+            Some(ShortcutKey::Char(c.to_ascii_uppercase()))
+        }
+        (Some('f'), digits) => match digits.parse::<u8>() {
+            Ok(n @ 1..=24) => Some(ShortcutKey::F(n)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `NG_KEY_UNKNOWN`: no accelerator key.
+const NO_KEY: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuItemKind {
+    Normal,
+    Check,
+    Radio(u32),
+}
+
+/// Describes a menu item for [`SubMenu::add`].
+///
+/// Items are plain, checkable or part of a radio group; radio items sharing a
+/// group within one submenu are mutually exclusive.
+#[derive(Debug, Clone)]
+pub struct MenuItemOptions {
+    title: String,
+    kind: MenuItemKind,
+    checked: bool,
+    enabled: bool,
+    shortcut: Option<MenuShortcut>,
+    icon: Option<Image>,
+}
+
+impl MenuItemOptions {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            kind: MenuItemKind::Normal,
+            checked: false,
+            enabled: true,
+            shortcut: None,
+            icon: None,
+        }
+    }
+
+    /// Show a check mark that toggles each time the item is activated.
+    pub fn checkable(mut self, checked: bool) -> Self {
+        self.kind = MenuItemKind::Check;
+        self.checked = checked;
+        self
+    }
+
+    /// Make this item a member of radio `group`.
+    pub fn radio(mut self, group: u32, checked: bool) -> Self {
+        self.kind = MenuItemKind::Radio(group);
+        self.checked = checked;
+        self
+    }
+
+    /// Bind a keyboard accelerator that activates the item.
+    pub fn shortcut(mut self, shortcut: MenuShortcut) -> Self {
+        self.shortcut = Some(shortcut);
+        self
+    }
+
+    /// Show an RGBA8 icon next to the label.
+    pub fn icon(mut self, icon: Image) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Title with the shortcut appended the way `ng_platform_add_menu_item` expects.
+    fn legacy_label(&self) -> String {
+        match self.shortcut {
+            Some(shortcut) => format!("{}\t{}", self.title, shortcut.encode_for_platform()),
+            None => self.title.clone(),
+        }
+    }
+}
+
+/// A handle to an item added to a [`SubMenu`].
+///
+/// Dropping the handle leaves the item in place; use [`MenuItem::remove`] to
+/// take it out of the menu. On backends without structured menu items the
+/// handle is detached and every update returns an error, as it does once the
+/// owning [`MenuBar`] or [`ContextMenu`] has been dropped.
+#[derive(Debug)]
+pub struct MenuItem {
+    id: u32,
+    handle: *mut c_void,
+    menu: Weak<()>,
+}
+
+impl MenuItem {
+    /// The id this item's callback is registered under.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The native item, while it and its menu still exist.
+    fn live_handle(&self) -> AureaResult<*mut c_void> {
+        if self.handle.is_null() || self.menu.strong_count() == 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(self.handle)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) -> AureaResult<()> {
+        let handle = self.live_handle()?;
+        check(unsafe { ng_platform_menu_item_set_enabled(handle, c_int::from(enabled)) })
+    }
+
+    /// Change the check state without invoking the callback. Only checkable and
+    /// radio items accept this.
+    pub fn set_checked(&self, checked: bool) -> AureaResult<()> {
+        let handle = self.live_handle()?;
+        check(unsafe { ng_platform_menu_item_set_checked(handle, c_int::from(checked)) })
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.live_handle()
+            .is_ok_and(|handle| unsafe { ng_platform_menu_item_is_checked(handle) } != 0)
+    }
+
+    pub fn set_label(&self, label: &str) -> AureaResult<()> {
+        let handle = self.live_handle()?;
+        let label = CString::new(label).map_err(|_| AureaError::InvalidTitle)?;
+        check(unsafe { ng_platform_menu_item_set_label(handle, label.as_ptr()) })
+    }
+
+    /// Remove the item from its menu and drop its callback.
+    pub fn remove(self) -> AureaResult<()> {
+        unregister_menu_callback(self.id);
+        let handle = self.live_handle()?;
+        check(unsafe { ng_platform_menu_item_remove(handle) })
+    }
 }
 
 /// A native menu bar attached to a window.
 pub struct MenuBar {
     pub handle: *mut c_void,
+    /// Dropped with the native menu; submenus and items hold weak references
+    /// so they never touch it afterwards.
+    alive: Arc<()>,
 }
 
 /// A submenu inside a menu bar.
 pub struct SubMenu {
     pub handle: *mut c_void,
+    menu: Weak<()>,
 }
 
 impl MenuBar {
     pub fn new(handle: *mut c_void) -> Self {
-        Self { handle }
+        Self {
+            handle,
+            alive: Arc::new(()),
+        }
     }
 
     /// Add a submenu to this menu bar.
     pub fn add_submenu(&mut self, title: &str) -> AureaResult<SubMenu> {
-        let title = CString::new(title).map_err(|_| AureaError::InvalidTitle)?;
-        let handle = unsafe { ng_platform_create_submenu(self.handle, title.as_ptr()) };
-
-        if handle.is_null() {
-            return Err(AureaError::MenuItemAddFailed);
-        }
-
-        debug!("Added submenu '{}'", title.to_string_lossy());
-
-        Ok(SubMenu { handle })
+        create_submenu(self.handle, Arc::downgrade(&self.alive), title)
     }
 
     /// Return the underlying native handle.
@@ -152,15 +358,116 @@ impl MenuBar {
 }
 
 impl SubMenu {
+    /// The native menu, while the menu bar or context menu owning it exists.
+    fn live_handle(&self) -> AureaResult<*mut c_void> {
+        if self.menu.strong_count() == 0 {
+            return Err(AureaError::MenuItemAddFailed);
+        }
+        Ok(self.handle)
+    }
+
     /// Add a clickable menu item with a callback.
-    pub fn add_item<F>(&mut self, title: &str, callback: F) -> AureaResult<()>
+    ///
+    /// A `"Title\tCtrl+S"` title binds the shortcut after the tab.
+    pub fn add_item<F>(&mut self, title: &str, callback: F) -> AureaResult<MenuItem>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let options = match title.split_once('\t') {
+            Some((title, shortcut)) => {
+                let options = MenuItemOptions::new(title);
+                match MenuShortcut::parse(shortcut) {
+                    Some(shortcut) => options.shortcut(shortcut),
+                    None => options,
+                }
+            }
+            None => MenuItemOptions::new(title),
+        };
+        self.add(options, callback)
+    }
+
+    /// Add a clickable menu item with a portable keyboard shortcut.
+    pub fn add_item_with_shortcut<F>(
+        &mut self,
+        title: &str,
+        shortcut: MenuShortcut,
+        callback: F,
+    ) -> AureaResult<MenuItem>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.add(MenuItemOptions::new(title).shortcut(shortcut), callback)
+    }
+
+    /// Add an item described by `options`.
+    pub fn add<F>(&mut self, options: MenuItemOptions, callback: F) -> AureaResult<MenuItem>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        if options
+            .icon
+            .as_ref()
+            .is_some_and(|icon| !is_rgba_image(icon))
+        {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let menu = self.live_handle()?;
+        let id = next_menu_item_id();
+        let title = CString::new(options.title.as_str()).map_err(|_| AureaError::InvalidTitle)?;
+        let (accel_char, accel_key, accel_modifiers) = options
+            .shortcut
+            .map_or((0, NO_KEY, 0), MenuShortcut::to_raw);
+        let (kind, radio_group) = match options.kind {
+            MenuItemKind::Normal => (0, 0),
+            MenuItemKind::Check => (1, 0),
+            MenuItemKind::Radio(group) => (2, group),
+        };
+        let icon = options.icon.as_ref();
+        let raw = NGMenuItemOptions {
+            kind,
+            title: title.as_ptr(),
+            id,
+            radio_group,
+            checked: c_int::from(options.checked),
+            enabled: c_int::from(options.enabled),
+            accel_char,
+            accel_key,
+            accel_modifiers,
+            icon_rgba: icon.map_or(ptr::null(), |icon| icon.data.as_ptr()),
+            icon_width: icon.map_or(0, |icon| icon.width),
+            icon_height: icon.map_or(0, |icon| icon.height),
+        };
+
+        let mut handle = ptr::null_mut();
+        let result = unsafe { ng_platform_menu_add_item(menu, &raw, &mut handle) };
+        // Backends without structured items only support the legacy call.
+        if result == NG_ERROR_PLATFORM_SPECIFIC {
+            return self.add_legacy(&options.legacy_label(), callback);
+        }
+        if result != 0 {
+            return Err(AureaError::MenuItemAddFailed);
+        }
+
+        register_menu_callback(id, callback);
+        debug!("Added menu item '{}' with id {}", options.title, id);
+
+        Ok(MenuItem {
+            id,
+            handle,
+            menu: Weak::clone(&self.menu),
+        })
+    }
+
+    /// Add an item through the label-only API; the returned handle is detached.
+    fn add_legacy<F>(&mut self, label: &str, callback: F) -> AureaResult<MenuItem>
     where
         F: Fn() + Send + Sync + 'static,
     {
+        let menu = self.live_handle()?;
         let id = next_menu_item_id();
 
-        let title = CString::new(title).map_err(|_| AureaError::InvalidTitle)?;
-        let result = unsafe { ng_platform_add_menu_item(self.handle, title.as_ptr(), id) };
+        let label = CString::new(label).map_err(|_| AureaError::InvalidTitle)?;
+        let result = unsafe { ng_platform_add_menu_item(menu, label.as_ptr(), id) };
 
         if result != 0 {
             return Err(AureaError::MenuItemAddFailed);
@@ -169,30 +476,25 @@ impl SubMenu {
         register_menu_callback(id, callback);
         debug!(
             "Added menu item '{}' with id {}",
-            title.to_string_lossy(),
+            label.to_string_lossy(),
             id
         );
 
-        Ok(())
+        Ok(MenuItem {
+            id,
+            handle: ptr::null_mut(),
+            menu: Weak::clone(&self.menu),
+        })
     }
 
-    /// Add a clickable menu item with a portable keyboard shortcut.
-    pub fn add_item_with_shortcut<F>(
-        &mut self,
-        title: &str,
-        shortcut: MenuShortcut,
-        callback: F,
-    ) -> AureaResult<()>
-    where
-        F: Fn() + Send + Sync + 'static,
-    {
-        let label = format!("{}\t{}", title, shortcut.encode_for_platform());
-        self.add_item(&label, callback)
+    /// Add a nested submenu.
+    pub fn add_submenu(&mut self, title: &str) -> AureaResult<SubMenu> {
+        create_submenu(self.live_handle()?, Weak::clone(&self.menu), title)
     }
 
     /// Add a visual separator in the submenu.
     pub fn add_separator(&mut self) -> AureaResult<()> {
-        let result = unsafe { ng_platform_add_menu_separator(self.handle) };
+        let result = unsafe { ng_platform_add_menu_separator(self.live_handle()?) };
         if result != 0 {
             return Err(AureaError::MenuItemAddFailed);
         }
@@ -205,6 +507,84 @@ impl SubMenu {
     }
 }
 
+fn create_submenu(parent: *mut c_void, menu: Weak<()>, title: &str) -> AureaResult<SubMenu> {
+    let title = CString::new(title).map_err(|_| AureaError::InvalidTitle)?;
+    let handle = unsafe { ng_platform_create_submenu(parent, title.as_ptr()) };
+
+    if handle.is_null() {
+        return Err(AureaError::MenuItemAddFailed);
+    }
+
+    debug!("Added submenu '{}'", title.to_string_lossy());
+
+    Ok(SubMenu { handle, menu })
+}
+
+/// A popup menu shown on demand, e.g. on right-click.
+///
+/// Items are added through the [`SubMenu`] API it dereferences to. Show it
+/// with [`crate::Window::show_context_menu`] or bind it to a canvas shape with
+/// [`crate::render::Canvas::on_context_menu`]. The native menu is destroyed on drop.
+pub struct ContextMenu {
+    menu: SubMenu,
+    /// Dropped with the native menu; see [`MenuBar`].
+    _alive: Arc<()>,
+}
+
+// Native menus are created and shown on the main thread; the handle is only
+// carried across threads by owners such as `Canvas`.
+unsafe impl Send for ContextMenu {}
+
+impl ContextMenu {
+    pub fn new() -> AureaResult<Self> {
+        if !CapabilityChecker::new().has(Capability::ContextMenus) {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let handle = unsafe { ng_platform_create_context_menu() };
+        if handle.is_null() {
+            return Err(AureaError::MenuCreationFailed);
+        }
+        let alive = Arc::new(());
+        Ok(Self {
+            menu: SubMenu {
+                handle,
+                menu: Arc::downgrade(&alive),
+            },
+            _alive: alive,
+        })
+    }
+
+    /// Pop the menu up at `(x, y)` logical points relative to `anchor`, a
+    /// window or element handle.
+    pub(crate) fn show_at(&self, anchor: *mut c_void, x: f32, y: f32) -> AureaResult<()> {
+        let result = unsafe { ng_platform_show_context_menu(anchor, self.menu.handle, x, y) };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+}
+
+impl Deref for ContextMenu {
+    type Target = SubMenu;
+
+    fn deref(&self) -> &SubMenu {
+        &self.menu
+    }
+}
+
+impl DerefMut for ContextMenu {
+    fn deref_mut(&mut self) -> &mut SubMenu {
+        &mut self.menu
+    }
+}
+
+impl Drop for ContextMenu {
+    fn drop(&mut self) {
+        unsafe { ng_platform_destroy_menu(self.menu.handle) };
+    }
+}
+
 pub fn invoke_menu_callback(id: u32) {
     invoke_registered_menu_callback(id);
 }
@@ -218,3 +598,52 @@ impl Drop for MenuBar {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcut_maps_to_native_accelerator() {
+        let save = MenuShortcut::new(ShortcutKey::Char('S')).primary().shift();
+        let primary = if cfg!(target_os = "macos") {
+            0b1000
+        } else {
+            0b0010
+        };
+        assert_eq!(save.to_raw(), (u32::from('s'), NO_KEY, primary | 0b0001));
+
+        let help = MenuShortcut::new(ShortcutKey::F(1)).alt();
+        assert_eq!(help.to_raw(), (0, KeyCode::F1.to_raw(), 0b0100));
+        assert_eq!(MenuShortcut::new(ShortcutKey::F(30)).to_raw().1, NO_KEY);
+    }
+
+    #[test]
+    fn parses_label_shortcut_text() {
+        let save_as = MenuShortcut::new(ShortcutKey::Char('S')).ctrl().shift();
+        assert_eq!(MenuShortcut::parse("Ctrl+Shift+S"), Some(save_as));
+        assert_eq!(
+            MenuShortcut::parse("Alt + F4"),
+            Some(MenuShortcut::new(ShortcutKey::F(4)).alt())
+        );
+        assert_eq!(
+            MenuShortcut::parse("Esc"),
+            Some(MenuShortcut::new(ShortcutKey::Escape))
+        );
+        assert_eq!(MenuShortcut::parse("Ctrl+Shift"), None);
+    }
+
+    #[test]
+    fn items_of_a_dropped_menu_are_detached() {
+        let alive = Arc::new(());
+        let item = MenuItem {
+            id: 0,
+            handle: ptr::dangling_mut(),
+            menu: Arc::downgrade(&alive),
+        };
+        drop(alive);
+        assert!(item.set_label("Gone").is_err());
+        assert!(item.set_enabled(false).is_err());
+        assert!(!item.is_checked());
+    }
+}
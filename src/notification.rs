@@ -0,0 +1,329 @@
+//! Desktop notifications.
+//!
+//! [`Notification`] describes a notification and [`Notification::show`]
+//! hands it to the desktop, returning a [`NotificationHandle`] that can
+//! replace or close it. Action clicks and the notification going away arrive
+//! as [`WindowEvent::NotificationAction`] and
+//! [`WindowEvent::NotificationClosed`] on the handle's own event queue.
+//!
+//! On Linux notifications go to the `org.freedesktop.Notifications` server on
+//! the session bus; showing fails when no server is running.
+
+use crate::ffi::*;
+use crate::registry::window::{register_handle_event_queue, unregister_event_queue};
+use crate::render::Image;
+use crate::window::events::EventQueue;
+use crate::window::{WindowEvent, is_rgba_image};
+use crate::{AureaError, AureaResult, Capability, CapabilityChecker};
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+use std::sync::Arc;
+
+/// How insistently the desktop should present a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Urgency {
+    Low,
+    #[default]
+    Normal,
+    /// Usually stays up until the user dismisses it.
+    Critical,
+}
+
+impl Urgency {
+    fn to_raw(self) -> u32 {
+        match self {
+            Urgency::Low => 0,
+            Urgency::Normal => 1,
+            Urgency::Critical => 2,
+        }
+    }
+}
+
+/// The contents of a desktop notification.
+#[derive(Debug, Clone, Default)]
+pub struct Notification {
+    title: String,
+    body: String,
+    icon: Option<Image>,
+    actions: Vec<(String, String)>,
+    urgency: Urgency,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// An RGBA8 image shown alongside the text.
+    pub fn icon(mut self, icon: Image) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Buttons as `(id, label)` pairs. The id comes back in
+    /// [`WindowEvent::NotificationAction`]; `"default"` is reserved for a
+    /// click on the notification itself.
+    pub fn actions<I, A, L>(mut self, actions: I) -> Self
+    where
+        I: IntoIterator<Item = (A, L)>,
+        A: Into<String>,
+        L: Into<String>,
+    {
+        self.actions = actions
+            .into_iter()
+            .map(|(id, label)| (id.into(), label.into()))
+            .collect();
+        self
+    }
+
+    pub fn urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    pub fn show(&self) -> AureaResult<NotificationHandle> {
+        if !CapabilityChecker::new().has(Capability::SystemNotifications) {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let raw = RawNotification::new(self)?;
+        let options = raw.options();
+        let handle = unsafe { ng_platform_notification_show(&options) };
+        if handle.is_null() {
+            return Err(AureaError::ElementOperationFailed);
+        }
+
+        let event_queue = register_handle_event_queue(handle);
+        Ok(NotificationHandle {
+            handle,
+            event_queue,
+        })
+    }
+}
+
+/// A shown notification. Dropping the handle stops its events but leaves
+/// the notification up; use [`NotificationHandle::close`] to take it down.
+pub struct NotificationHandle {
+    handle: *mut c_void,
+    event_queue: Arc<EventQueue>,
+}
+
+impl NotificationHandle {
+    /// Replace the notification's contents in place, or show it again if it
+    /// has already closed.
+    pub fn update(&self, notification: &Notification) -> AureaResult<()> {
+        let raw = RawNotification::new(notification)?;
+        let options = raw.options();
+        check(unsafe { ng_platform_notification_update(self.handle, &options) })
+    }
+
+    pub fn close(&self) -> AureaResult<()> {
+        check(unsafe { ng_platform_notification_close(self.handle) })
+    }
+
+    /// Register a callback for this notification's action and close events.
+    pub fn on_event<F>(&self, callback: F)
+    where
+        F: Fn(WindowEvent) + Send + Sync + 'static,
+    {
+        self.event_queue.register_callback(Arc::new(callback));
+    }
+
+    /// Pump native events and return the actions and closing reported for
+    /// this notification since the last call, after passing them to its
+    /// [`on_event`](Self::on_event) callbacks. A windowless tool can wait for
+    /// the user's answer by polling until `NotificationClosed` arrives.
+    pub fn poll_events(&self) -> Vec<WindowEvent> {
+        unsafe { ng_platform_poll_events() };
+        self.event_queue.process_events()
+    }
+}
+
+impl Drop for NotificationHandle {
+    fn drop(&mut self) {
+        unregister_event_queue(self.handle);
+        unsafe { ng_platform_notification_release(self.handle) };
+    }
+}
+
+/// C strings backing an `NGNotificationOptions` for the length of one call.
+struct RawNotification<'a> {
+    title: CString,
+    body: CString,
+    icon: Option<&'a Image>,
+    /// Owns the strings behind `id_ptrs` and `label_ptrs`.
+    _actions: Vec<(CString, CString)>,
+    id_ptrs: Vec<*const c_char>,
+    label_ptrs: Vec<*const c_char>,
+    urgency: Urgency,
+}
+
+impl<'a> RawNotification<'a> {
+    fn new(notification: &'a Notification) -> AureaResult<Self> {
+        let c_string = |s: &str| CString::new(s).map_err(|_| AureaError::ElementOperationFailed);
+        if notification
+            .icon
+            .as_ref()
+            .is_some_and(|icon| !is_rgba_image(icon))
+        {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let actions = notification
+            .actions
+            .iter()
+            .map(|(id, label)| Ok((c_string(id)?, c_string(label)?)))
+            .collect::<AureaResult<Vec<_>>>()?;
+        let id_ptrs = actions.iter().map(|(id, _)| id.as_ptr()).collect();
+        let label_ptrs = actions.iter().map(|(_, label)| label.as_ptr()).collect();
+        Ok(Self {
+            title: c_string(&notification.title)?,
+            body: c_string(&notification.body)?,
+            icon: notification.icon.as_ref(),
+            _actions: actions,
+            id_ptrs,
+            label_ptrs,
+            urgency: notification.urgency,
+        })
+    }
+
+    fn options(&self) -> NGNotificationOptions {
+        let (icon_rgba, icon_width, icon_height) = self.icon.map_or((ptr::null(), 0, 0), |icon| {
+            (icon.data.as_ptr(), icon.width, icon.height)
+        });
+        NGNotificationOptions {
+            title: self.title.as_ptr(),
+            body: self.body.as_ptr(),
+            icon_rgba,
+            icon_width,
+            icon_height,
+            action_ids: self.id_ptrs.as_ptr(),
+            action_labels: self.label_ptrs.as_ptr(),
+            action_count: c_int::try_from(self.id_ptrs.len()).unwrap_or(c_int::MAX),
+            urgency: self.urgency.to_raw(),
+            timeout_ms: -1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn options_carry_actions_in_order() {
+        let notification = Notification::new("Build finished")
+            .body("3 warnings")
+            .actions([("open", "Open log"), ("rerun", "Run again")])
+            .urgency(Urgency::Critical);
+        let raw = RawNotification::new(&notification).unwrap();
+        let options = raw.options();
+
+        assert_eq!(options.action_count, 2);
+        assert_eq!(options.urgency, 2);
+        assert!(options.icon_rgba.is_null());
+        let label = unsafe { CStr::from_ptr(*options.action_labels.add(1)) };
+        assert_eq!(label.to_str().unwrap(), "Run again");
+
+        let broken = Notification::new("a\0b");
+        assert!(RawNotification::new(&broken).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    mod session_bus {
+        use super::*;
+        use crate::NotificationCloseReason;
+        use crate::test_bus::SessionBus;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        /// An `org.freedesktop.Notifications` server that prints each Notify
+        /// call, then reports the "open" action and a dismissal for it.
+        const MOCK_SERVER: &str = r#"from gi.repository import Gio, GLib
+
+XML = """<node><interface name="org.freedesktop.Notifications">
+<method name="Notify"><arg type="s"/><arg type="u"/><arg type="s"/><arg type="s"/><arg type="s"/>
+<arg type="as"/><arg type="a{sv}"/><arg type="i"/><arg type="u" direction="out"/></method>
+<method name="CloseNotification"><arg type="u"/></method>
+</interface></node>"""
+PATH = "/org/freedesktop/Notifications"
+NAME = "org.freedesktop.Notifications"
+
+def call(connection, sender, path, interface, method, params, invocation):
+    if method != "Notify":
+        invocation.return_value(None)
+        return
+    print(params.print_(True), flush=True)
+    invocation.return_value(GLib.Variant("(u)", (7,)))
+    connection.emit_signal(None, PATH, NAME, "ActionInvoked", GLib.Variant("(us)", (7, "open")))
+    connection.emit_signal(None, PATH, NAME, "NotificationClosed", GLib.Variant("(uu)", (7, 2)))
+
+def acquired(connection, name):
+    info = Gio.DBusNodeInfo.new_for_xml(XML).interfaces[0]
+    connection.register_object(PATH, info, call, None, None)
+
+Gio.bus_own_name(Gio.BusType.SESSION, NAME, 0, acquired, None, None)
+GLib.MainLoop().run()
+"#;
+
+        fn has_pygobject() -> bool {
+            Command::new("python3")
+                .args(["-c", "import gi"])
+                .status()
+                .is_ok_and(|status| status.success())
+        }
+
+        #[test]
+        fn server_receives_notify_and_reports_back() {
+            let Some(bus) = SessionBus::start() else {
+                return;
+            };
+            if !has_pygobject() {
+                return;
+            }
+            let mut server = Command::new("python3")
+                .args(["-c", MOCK_SERVER])
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap();
+            assert!(bus.pump_until(|| bus.names().contains("'org.freedesktop.Notifications'")));
+
+            let handle = Notification::new("Build finished")
+                .body("3 warnings")
+                .actions([("open", "Open log")])
+                .urgency(Urgency::Critical)
+                .show()
+                .unwrap();
+            let mut notify = String::new();
+            let stdout = server.stdout.take().unwrap();
+            BufReader::new(stdout).read_line(&mut notify).unwrap();
+            assert!(notify.contains("'Build finished', '3 warnings', ['open', 'Open log']"));
+            assert!(notify.contains("'urgency': <byte 0x02>"));
+
+            let mut events = Vec::new();
+            assert!(bus.pump_until(|| {
+                events.extend(handle.poll_events());
+                events.len() >= 2
+            }));
+            assert!(matches!(
+                &events[..],
+                [
+                    WindowEvent::NotificationAction { action },
+                    WindowEvent::NotificationClosed {
+                        reason: NotificationCloseReason::Dismissed,
+                    },
+                ] if action == "open"
+            ));
+            let _ = server.kill();
+            let _ = server.wait();
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::clipboard::ClipboardSelection;
+use crate::sync::lock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+type ClipboardListener = Arc<dyn Fn(ClipboardSelection) + Send + Sync>;
+
+static CLIPBOARD_LISTENERS: LazyLock<Mutex<Vec<(u64, ClipboardSelection, ClipboardListener)>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+static NEXT_LISTENER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Returns the id that [`unregister_clipboard_listener`] takes.
+pub fn register_clipboard_listener(
+    selection: ClipboardSelection,
+    callback: impl Fn(ClipboardSelection) + Send + Sync + 'static,
+) -> u64 {
+    let id = NEXT_LISTENER_ID.fetch_add(1, Ordering::Relaxed);
+    lock(&CLIPBOARD_LISTENERS).push((id, selection, Arc::new(callback)));
+    id
+}
+
+/// Returns `false` if the listener was already removed.
+pub fn unregister_clipboard_listener(id: u64) -> bool {
+    let mut listeners = lock(&CLIPBOARD_LISTENERS);
+    let before = listeners.len();
+    listeners.retain(|(listener, _, _)| *listener != id);
+    listeners.len() != before
+}
+
+/// Listeners run without the registry locked, so they may register more.
+pub fn invoke_clipboard_listeners(selection: ClipboardSelection) {
+    let listeners: Vec<ClipboardListener> = lock(&CLIPBOARD_LISTENERS)
+        .iter()
+        .filter(|(_, watched, _)| *watched == selection)
+        .map(|(_, _, listener)| Arc::clone(listener))
+        .collect();
+    for listener in listeners {
+        listener(selection);
+    }
+}
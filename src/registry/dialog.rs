@@ -0,0 +1,46 @@
+use crate::render::{Color, Font};
+use crate::sync::lock;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+
+/// What a native dialog reports when it closes.
+#[derive(Debug, Clone)]
+pub enum DialogResult {
+    /// Chosen paths; empty when cancelled.
+    Files(Vec<PathBuf>),
+    /// Index of the pressed button, `None` when dismissed.
+    Button(Option<usize>),
+    Color(Option<Color>),
+    Font(Option<Font>),
+}
+
+type DialogCallback = Box<dyn FnOnce(DialogResult) + Send>;
+
+static NEXT_REQUEST_ID: AtomicU32 = AtomicU32::new(1);
+static DIALOG_CALLBACKS: LazyLock<Mutex<HashMap<u32, DialogCallback>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Store `callback` until the dialog with the returned request id closes.
+pub fn register_dialog_callback(callback: impl FnOnce(DialogResult) + Send + 'static) -> u32 {
+    let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    lock(&DIALOG_CALLBACKS).insert(id, Box::new(callback));
+    id
+}
+
+/// Drop the callback for a dialog that failed to open.
+pub fn cancel_dialog_callback(id: u32) {
+    lock(&DIALOG_CALLBACKS).remove(&id);
+}
+
+pub fn invoke_dialog_callback(id: u32, result: DialogResult) {
+    let callback = lock(&DIALOG_CALLBACKS).remove(&id);
+    if let Some(callback) = callback {
+        callback(result);
+    }
+}
@@ -0,0 +1,30 @@
+use crate::sync::lock;
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+/// Receives the address of the newly focused native element, or 0.
+type NativeFocusCallback = Arc<dyn Fn(usize) + Send + Sync>;
+
+static NATIVE_FOCUS_CALLBACKS: LazyLock<Mutex<HashMap<usize, NativeFocusCallback>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub fn register_native_focus_callback(
+    window: usize,
+    callback: impl Fn(usize) + Send + Sync + 'static,
+) {
+    lock(&NATIVE_FOCUS_CALLBACKS).insert(window, Arc::new(callback));
+}
+
+pub fn unregister_native_focus_callback(window: usize) {
+    lock(&NATIVE_FOCUS_CALLBACKS).remove(&window);
+}
+
+/// The callback runs without the registry locked.
+pub fn invoke_native_focus_callback(window: usize, element: usize) {
+    let callback = lock(&NATIVE_FOCUS_CALLBACKS).get(&window).cloned();
+    if let Some(callback) = callback {
+        callback(element);
+    }
+}
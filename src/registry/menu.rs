@@ -1,10 +1,10 @@
 use crate::sync::lock;
 use std::{
     collections::HashMap,
-    sync::{LazyLock, Mutex},
+    sync::{Arc, LazyLock, Mutex},
 };
 
-type MenuCallback = Box<dyn Fn() + Send + Sync>;
+type MenuCallback = Arc<dyn Fn() + Send + Sync>;
 
 static MENU_ITEM_ID: LazyLock<Mutex<u32>> = LazyLock::new(|| Mutex::new(1));
 static MENU_CALLBACKS: LazyLock<Mutex<HashMap<u32, MenuCallback>>> =
@@ -18,12 +18,17 @@ pub fn next_menu_item_id() -> u32 {
 
 pub fn register_menu_callback(id: u32, callback: impl Fn() + Send + Sync + 'static) {
     let mut callbacks = lock(&MENU_CALLBACKS);
-    callbacks.insert(id, Box::new(callback));
+    callbacks.insert(id, Arc::new(callback));
+}
+
+pub fn unregister_menu_callback(id: u32) {
+    lock(&MENU_CALLBACKS).remove(&id);
 }
 
 pub fn invoke_menu_callback(id: u32) {
-    let callbacks = lock(&MENU_CALLBACKS);
-    if let Some(callback) = callbacks.get(&id) {
+    // Run outside the lock so a callback can add, update or remove items.
+    let callback = lock(&MENU_CALLBACKS).get(&id).cloned();
+    if let Some(callback) = callback {
         callback();
     }
 }
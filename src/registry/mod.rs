@@ -1,4 +1,7 @@
+pub mod clipboard;
 pub mod custom;
+pub mod dialog;
 pub mod elements;
+pub mod focus;
 pub mod menu;
 pub mod window;
@@ -27,6 +27,26 @@ pub fn register_event_queue(handle: *mut c_void, queue: &Arc<EventQueue>) {
     by_handle.insert(handle as usize, Arc::downgrade(queue));
 }
 
+/// Create the event queue for a native `handle`: events the platform pushes
+/// for the handle land in it, and it is processed along with every other
+/// queue. Pair with [`unregister_event_queue`] when the handle goes away.
+pub fn register_handle_event_queue(handle: *mut c_void) -> Arc<EventQueue> {
+    let queue = Arc::new(EventQueue::new());
+    register_global_event_queue(&queue);
+    register_event_queue(handle, &queue);
+    queue
+}
+
+/// Whether `queue` is still the one registered for `handle`, so the object
+/// it was created with has not been dropped, even if the handle's address
+/// has since been reused.
+pub fn is_event_queue_registered(handle: *mut c_void, queue: &Weak<EventQueue>) -> bool {
+    let by_handle = lock(&WINDOW_QUEUE_BY_HANDLE);
+    by_handle
+        .get(&(handle as usize))
+        .is_some_and(|registered| registered.ptr_eq(queue) && registered.strong_count() > 0)
+}
+
 pub fn unregister_event_queue(handle: *mut c_void) {
     let mut by_handle = lock(&WINDOW_QUEUE_BY_HANDLE);
     by_handle.remove(&(handle as usize));
@@ -73,7 +93,20 @@ pub fn push_window_event(handle: *mut c_void, event: WindowEvent) {
     };
 
     if let Some(queue) = queue {
-        queue.push(event);
+        queue.push_platform(event);
+    }
+}
+
+/// Push `event` to every registered queue, for changes that concern the
+/// whole application rather than one window.
+pub fn broadcast_window_event(event: &WindowEvent) {
+    let queues: Vec<_> = {
+        let mut queues = lock(&WINDOW_EVENT_QUEUES);
+        queues.retain(|weak| weak.strong_count() > 0);
+        queues.iter().filter_map(Weak::upgrade).collect()
+    };
+    for queue in queues {
+        queue.push_platform(event.clone());
     }
 }
 
@@ -1,17 +1,19 @@
 use crate::elements::Element;
 use crate::ffi::*;
+use crate::menu::ContextMenu;
 use crate::sync::lock;
 use crate::view::{DamageRegion, FrameScheduler};
 use crate::{AureaError, AureaResult};
-use aurea_foundation::CapabilityChecker;
 use aurea_foundation::Platform;
+use aurea_foundation::{CapabilityChecker, CursorIcon};
 use aurea_render::{
     ClickCallback, Color, CpuRasterizer, DrawingContext, GpuRasterizer, HoverCallback,
-    InteractionRegistry, InteractiveId, Point, Rect, Renderer, RendererBackend, Surface,
-    SurfaceInfo,
+    InteractionRegistry, InteractiveId, Paint, PaintStyle, Point, Rect, Renderer, RendererBackend,
+    Surface, SurfaceInfo,
 };
 use aurea_runtime::{FrameInfo, TickerId};
 use std::collections::HashMap;
+use std::ffi::CString;
 #[cfg(feature = "wgpu")]
 use std::mem::transmute;
 use std::os::raw::c_void;
@@ -25,6 +27,11 @@ mod runtime;
 /// reads canvas properties (size, background_color, etc.).
 pub type DrawCallback = Arc<dyn Fn(&mut dyn DrawingContext) -> AureaResult<()> + Send + Sync>;
 
+/// Draws the focus indicator for the focused shape, given its id and its
+/// bounds in logical pixels as of the previous frame.
+pub type FocusRingCallback =
+    Arc<dyn Fn(&mut dyn DrawingContext, InteractiveId, Rect) -> AureaResult<()> + Send + Sync>;
+
 /// All per-frame mutable canvas properties in one lock.
 /// Renderer lives in a separate Arc<Mutex<>> so render_frame can release this
 /// lock before invoking the draw callback.
@@ -40,6 +47,12 @@ pub(crate) struct CanvasState {
     /// compute how much of the IOSurface double-buffer needs refreshing this
     /// frame (back surface is always 2 frames stale, so we union N and N-1).
     pub prev_frame_damage: Option<Rect>,
+    /// Cursor last applied to the window from shape hover.
+    pub cursor: CursorIcon,
+    /// Shape holding keyboard focus, set by the window's focus manager.
+    pub focused_item: Option<InteractiveId>,
+    /// Replaces [`draw_focus_ring`] when set.
+    pub focus_ring: Option<FocusRingCallback>,
 }
 
 /// Global handle → state map so a redraw can be requested given only the raw
@@ -85,6 +98,41 @@ pub fn request_canvas_redraw(handle: usize) {
     }
 }
 
+/// Record which shape of the canvas identified by `handle` has keyboard
+/// focus and redraw it so the focus ring follows.
+pub(crate) fn set_canvas_focused_item(handle: usize, id: Option<InteractiveId>) {
+    let state = lock(&CANVAS_STATES).get(&handle).cloned();
+    let Some(state) = state else {
+        return;
+    };
+    let changed = {
+        let mut st = lock(&state);
+        let changed = st.focused_item != id;
+        st.focused_item = id;
+        changed
+    };
+    if changed {
+        request_canvas_redraw(handle);
+    }
+}
+
+/// The default focus ring: a 2px outline just outside `bounds`.
+pub fn draw_focus_ring(ctx: &mut dyn DrawingContext, bounds: Rect) -> AureaResult<()> {
+    let paint = Paint::new()
+        .color(Color::rgb(53, 132, 228))
+        .style(PaintStyle::Stroke)
+        .stroke_width(2.0);
+    ctx.draw_rect(
+        Rect::new(
+            bounds.x - 3.0,
+            bounds.y - 3.0,
+            bounds.width + 6.0,
+            bounds.height + 6.0,
+        ),
+        &paint,
+    )
+}
+
 /// Unregisters the canvas from the scheduler and tears down the renderer when
 /// the *last* `Canvas` clone is dropped.
 struct CanvasCleanup {
@@ -128,6 +176,7 @@ pub struct Canvas {
     pub(crate) renderer: Arc<Mutex<Option<Box<dyn Renderer>>>>,
     pub(crate) backend: RendererBackend,
     interaction_registry: Arc<InteractionRegistry>,
+    context_menus: Arc<Mutex<HashMap<InteractiveId, ContextMenu>>>,
     #[allow(dead_code)]
     platform: Platform,
     #[allow(dead_code)]
@@ -257,6 +306,9 @@ impl Canvas {
             draw_callback: None,
             needs_redraw: false,
             prev_frame_damage: None,
+            cursor: CursorIcon::Default,
+            focused_item: None,
+            focus_ring: None,
         }));
         let renderer_arc = Arc::new(Mutex::new(renderer));
         let interaction_registry = Arc::new(InteractionRegistry::new());
@@ -267,6 +319,7 @@ impl Canvas {
             renderer: renderer_arc.clone(),
             backend,
             interaction_registry,
+            context_menus: Arc::new(Mutex::new(HashMap::new())),
             platform,
             capabilities,
             _cleanup: Arc::new(CanvasCleanup {
@@ -505,6 +558,60 @@ impl Canvas {
         Ok(())
     }
 
+    /// Declare the cursor shown while the pointer is over an interactive
+    /// shape. Takes effect through [`Self::handle_hover`].
+    pub fn on_cursor(&self, id: InteractiveId, cursor: CursorIcon) -> AureaResult<()> {
+        self.interaction_registry.register_cursor(id, cursor);
+        Ok(())
+    }
+
+    /// Bind a context menu to an interactive shape, replacing any previous
+    /// one. Shown through [`Self::handle_context_menu`].
+    pub fn on_context_menu(&self, id: InteractiveId, menu: ContextMenu) -> AureaResult<()> {
+        lock(&self.context_menus).insert(id, menu);
+        Ok(())
+    }
+
+    /// Replace the default focus ring drawn around the focused shape. The
+    /// callback runs after the draw callback, so it paints on top.
+    pub fn set_focus_ring<F>(&self, callback: F)
+    where
+        F: Fn(&mut dyn DrawingContext, InteractiveId, Rect) -> AureaResult<()>
+            + Send
+            + Sync
+            + 'static,
+    {
+        lock(&self.state).focus_ring = Some(Arc::new(callback));
+        self.invalidate_all();
+    }
+
+    /// The shape holding keyboard focus, for draw callbacks that style it
+    /// themselves.
+    pub fn focused_item(&self) -> Option<InteractiveId> {
+        lock(&self.state).focused_item
+    }
+
+    /// Handle a secondary-button click at the given coordinates by showing the
+    /// menu bound to the topmost shape under it. Returns whether a menu was
+    /// shown. `x` and `y` are in logical (point) coordinates.
+    pub fn handle_context_menu(&self, x: f32, y: f32) -> AureaResult<bool> {
+        let sf = self.scale_factor();
+        let point = Point::new(x * sf, y * sf);
+        let target = {
+            let r = lock(&self.renderer);
+            let Some(display_list) = r.as_ref().and_then(|renderer| renderer.display_list()) else {
+                return Ok(false);
+            };
+            InteractionRegistry::topmost_at(display_list, point)
+        };
+        let menus = lock(&self.context_menus);
+        let Some(menu) = target.and_then(|id| menus.get(&id)) else {
+            return Ok(false);
+        };
+        menu.show_at(self.handle, x, y)?;
+        Ok(true)
+    }
+
     /// Handle a mouse/touch click event at the given coordinates.
     /// `x` and `y` are in logical (point) coordinates.
     pub fn handle_click(&self, x: f32, y: f32) -> AureaResult<()> {
@@ -524,12 +631,39 @@ impl Canvas {
     pub fn handle_hover(&self, x: f32, y: f32) -> AureaResult<()> {
         let sf = self.scale_factor();
         let point = Point::new(x * sf, y * sf);
-        let r = lock(&self.renderer);
-        if let Some(ref renderer) = *r
-            && let Some(display_list) = renderer.display_list()
-        {
-            return self.interaction_registry.handle_hover(display_list, point);
+        let cursor = {
+            let r = lock(&self.renderer);
+            let Some(display_list) = r.as_ref().and_then(|renderer| renderer.display_list()) else {
+                return Ok(());
+            };
+            self.interaction_registry
+                .handle_hover(display_list, point)?;
+            self.interaction_registry
+                .has_cursors()
+                .then(|| self.interaction_registry.cursor_at(display_list, point))
+        };
+        // Applied after the renderer lock is released; state is locked next.
+        match cursor {
+            Some(cursor) => self.apply_hover_cursor(cursor),
+            None => Ok(()),
+        }
+    }
+
+    fn apply_hover_cursor(&self, cursor: CursorIcon) -> AureaResult<()> {
+        let mut state = lock(&self.state);
+        if state.cursor == cursor {
+            return Ok(());
+        }
+        let window = unsafe { ng_platform_canvas_get_window(self.handle) };
+        if window.is_null() {
+            return Ok(());
+        }
+        let name =
+            CString::new(cursor.css_name()).map_err(|_| AureaError::ElementOperationFailed)?;
+        if unsafe { ng_platform_window_set_cursor_icon(window, name.as_ptr()) } != 0 {
+            return Err(AureaError::ElementOperationFailed);
         }
+        state.cursor = cursor;
         Ok(())
     }
 }
@@ -1,14 +1,15 @@
 use crate::ffi::*;
-use crate::render::canvas::{Canvas, CanvasState, ensure_canvas_renderer};
+use crate::render::canvas::{Canvas, CanvasState, draw_focus_ring, ensure_canvas_renderer};
 use crate::render::{Surface, SurfaceInfo};
 use crate::sync::lock;
 use crate::view::FrameScheduler;
 use crate::{AureaError, AureaResult};
 use aurea_render::Rect;
-use aurea_render::{CURRENT_BUFFER, Renderer, RendererBackend};
+use aurea_render::{CURRENT_BUFFER, InteractionRegistry, Renderer, RendererBackend};
 use std::os::raw::c_void;
 use std::ptr::{copy_nonoverlapping, null_mut};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 /// Converts a non-negative, pre-clamped `f32` row coordinate to `usize`.
 /// `std` has no safe non-`as` float-to-int conversion; clippy's
@@ -143,36 +144,63 @@ fn render_frame(
     _backend: RendererBackend,
 ) -> AureaResult<()> {
     // 1. Snapshot what we need, then release the state lock.
-    let (damage_rect, draw_callback, bg_color) = {
+    let (damage_rect, draw_callback, bg_color, focused, focus_ring, scale) = {
         let mut st = lock(state);
         let damage = st.damage.take();
         let cb = st.draw_callback.clone(); // Arc clone — O(1), no deep copy
         let bg = st.background_color;
-        (damage, cb, bg)
+        (
+            damage,
+            cb,
+            bg,
+            st.focused_item,
+            st.focus_ring.clone(),
+            st.scale_factor.max(f32::EPSILON),
+        )
     };
 
     // 2. Render under renderer lock only; grab last_frame_damage before releasing.
+    let render_start = Instant::now();
     let current_damage = {
         let mut r = lock(renderer);
         if let Some(ref mut r) = *r {
+            // The ring follows where the shape was last drawn.
+            let ring = focused.and_then(|id| {
+                let bounds = InteractionRegistry::bounds_of(r.display_list()?, id)?;
+                let logical = Rect::new(
+                    bounds.x / scale,
+                    bounds.y / scale,
+                    bounds.width / scale,
+                    bounds.height / scale,
+                );
+                Some((id, logical))
+            });
             r.set_damage(damage_rect);
             let mut ctx = r.begin_frame()?;
             ctx.clear(bg_color)?;
             if let Some(ref cb) = draw_callback {
                 cb(ctx.as_mut())?; // state lock NOT held here
             }
+            if let Some((id, bounds)) = ring {
+                match focus_ring {
+                    Some(ref ring) => ring(ctx.as_mut(), id, bounds)?,
+                    None => draw_focus_ring(ctx.as_mut(), bounds)?,
+                }
+            }
             r.end_frame()?;
             r.last_frame_damage()
         } else {
             None
         }
     };
+    let render_time = render_start.elapsed();
 
     // 3. Push buffer to platform (CURRENT_BUFFER is thread-local; no lock needed).
     #[cfg(feature = "zengpu")]
     let publishes_cpu_buffer = _backend != RendererBackend::ZenGpu;
     #[cfg(not(feature = "zengpu"))]
     let publishes_cpu_buffer = true;
+    let present_start = Instant::now();
     if publishes_cpu_buffer
         && let Some((ptr, size, w, h)) = CURRENT_BUFFER.with(|buf| *buf.borrow())
         && !ptr.is_null()
@@ -191,6 +219,7 @@ fn render_frame(
             publish_cpu_buffer(handle, ptr, size, w, h, refresh);
         }
     }
+    FrameScheduler::record_canvas_timing(handle, render_time, present_start.elapsed());
 
     Ok(())
 }
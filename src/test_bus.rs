@@ -0,0 +1,121 @@
+//! A private D-Bus session bus for tests of the session-bus backends (tray
+//! icons and notifications). Tests skip themselves when `dbus-daemon`,
+//! `gdbus` or a display is missing.
+
+use crate::ffi::{ng_platform_init, ng_platform_poll_events};
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Serialises bus tests: the bus address is process-wide and GTK is pumped
+/// from the test thread.
+static BUS_LOCK: Mutex<()> = Mutex::new(());
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A `dbus-daemon --session` that the process's session-bus address points
+/// at until it is dropped.
+pub(crate) struct SessionBus {
+    daemon: Child,
+    address: String,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl SessionBus {
+    pub(crate) fn start() -> Option<Self> {
+        if env::var_os("DISPLAY").is_none() && env::var_os("WAYLAND_DISPLAY").is_none() {
+            return None;
+        }
+        let lock = BUS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        if Command::new("gdbus").arg("help").output().is_err() || unsafe { ng_platform_init() } != 0
+        {
+            return None;
+        }
+        let mut daemon = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let mut address = String::new();
+        let stdout = daemon.stdout.take()?;
+        BufReader::new(stdout).read_line(&mut address).ok()?;
+        let address = address.trim().to_string();
+        // SAFETY: bus tests hold BUS_LOCK, and no other test reads the
+        // session-bus address.
+        unsafe { env::set_var("DBUS_SESSION_BUS_ADDRESS", &address) };
+        Some(Self {
+            daemon,
+            address,
+            _lock: lock,
+        })
+    }
+
+    /// Run `gdbus` against the bus while pumping native events, so that
+    /// in-process services can answer; its output, or `None` on failure.
+    pub(crate) fn gdbus(&self, command: &str, args: &[&str]) -> Option<String> {
+        let mut child = Command::new("gdbus")
+            .args([command, "--address", &self.address])
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let started = Instant::now();
+        while child.try_wait().ok()?.is_none() {
+            if started.elapsed() > TIMEOUT {
+                let _ = child.kill();
+                return None;
+            }
+            pump();
+        }
+        let output = child.wait_with_output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Pump native events until `done` returns true; false on timeout.
+    pub(crate) fn pump_until(&self, mut done: impl FnMut() -> bool) -> bool {
+        let started = Instant::now();
+        while !done() {
+            if started.elapsed() > TIMEOUT {
+                return false;
+            }
+            pump();
+        }
+        true
+    }
+
+    /// Names currently owned on the bus.
+    pub(crate) fn names(&self) -> String {
+        self.gdbus(
+            "call",
+            &[
+                "--dest",
+                "org.freedesktop.DBus",
+                "--object-path",
+                "/org/freedesktop/DBus",
+                "--method",
+                "org.freedesktop.DBus.ListNames",
+            ],
+        )
+        .unwrap_or_default()
+    }
+}
+
+impl Drop for SessionBus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+    }
+}
+
+fn pump() {
+    unsafe { ng_platform_poll_events() };
+    thread::sleep(Duration::from_millis(5));
+}
@@ -0,0 +1,250 @@
+//! System tray (status) icons.
+//!
+//! A [`TrayIcon`] shows an RGBA8 [`Image`] in the desktop's notification
+//! area, optionally with a [`ContextMenu`]. Clicks arrive as
+//! [`WindowEvent::TrayClicked`] and [`WindowEvent::TrayDoubleClicked`] on the
+//! icon's own event queue, so they reach [`TrayIcon::on_event`] callbacks
+//! whenever window events are processed.
+//!
+//! On Linux the icon is a StatusNotifierItem on the session bus, which needs
+//! a host such as the KDE panel or an AppIndicator extension to be visible.
+
+use crate::ffi::*;
+use crate::menu::ContextMenu;
+use crate::registry::window::{register_handle_event_queue, unregister_event_queue};
+use crate::render::Image;
+use crate::window::events::EventQueue;
+use crate::window::{MouseButton, WindowEvent, is_rgba_image};
+use crate::{AureaError, AureaResult, Capability, CapabilityChecker};
+use std::ffi::CString;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+use std::sync::Arc;
+
+/// An icon in the system tray. Removed from the tray on drop.
+pub struct TrayIcon {
+    handle: *mut c_void,
+    event_queue: Arc<EventQueue>,
+    menu: Option<ContextMenu>,
+}
+
+impl TrayIcon {
+    pub fn new(icon: &Image, tooltip: &str) -> AureaResult<Self> {
+        if !CapabilityChecker::new().has(Capability::SystemTray) || !is_rgba_image(icon) {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let tooltip = CString::new(tooltip).map_err(|_| AureaError::ElementOperationFailed)?;
+        let handle = unsafe {
+            ng_platform_tray_create(
+                icon.data.as_ptr(),
+                icon.width,
+                icon.height,
+                tooltip.as_ptr(),
+            )
+        };
+        if handle.is_null() {
+            return Err(AureaError::ElementOperationFailed);
+        }
+
+        let event_queue = register_handle_event_queue(handle);
+        Ok(Self {
+            handle,
+            event_queue,
+            menu: None,
+        })
+    }
+
+    pub fn set_icon(&self, icon: &Image) -> AureaResult<()> {
+        if !is_rgba_image(icon) {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let result = unsafe {
+            ng_platform_tray_set_icon(self.handle, icon.data.as_ptr(), icon.width, icon.height)
+        };
+        check(result)
+    }
+
+    pub fn set_tooltip(&self, tooltip: &str) -> AureaResult<()> {
+        let tooltip = CString::new(tooltip).map_err(|_| AureaError::ElementOperationFailed)?;
+        check(unsafe { ng_platform_tray_set_tooltip(self.handle, tooltip.as_ptr()) })
+    }
+
+    /// Offer `menu` on secondary click, or remove the menu with `None`. The
+    /// icon keeps the menu alive; its items stay editable through the
+    /// [`crate::MenuItem`] handles returned while building it.
+    pub fn set_menu(&mut self, menu: Option<ContextMenu>) -> AureaResult<()> {
+        let raw = menu.as_ref().map_or(ptr::null_mut(), |menu| menu.handle);
+        check(unsafe { ng_platform_tray_set_menu(self.handle, raw) })?;
+        self.menu = menu;
+        Ok(())
+    }
+
+    /// Register a callback for this icon's click events.
+    pub fn on_event<F>(&self, callback: F)
+    where
+        F: Fn(WindowEvent) + Send + Sync + 'static,
+    {
+        self.event_queue.register_callback(Arc::new(callback));
+    }
+
+    /// Pump native events and return the clicks on this icon, after passing
+    /// them to its [`on_event`](Self::on_event) callbacks. Only tray-only
+    /// applications need this: a running window event loop already
+    /// processes the icon's queue.
+    pub fn poll_events(&self) -> Vec<WindowEvent> {
+        unsafe { ng_platform_poll_events() };
+        self.event_queue.process_events()
+    }
+}
+
+impl Drop for TrayIcon {
+    fn drop(&mut self) {
+        unregister_event_queue(self.handle);
+        unsafe { ng_platform_tray_destroy(self.handle) };
+    }
+}
+
+/// Events for one native tray click: a `TrayClicked`, followed by a
+/// `TrayDoubleClicked` when it completes a double-click.
+pub(crate) fn tray_events(
+    button: c_int,
+    click_count: c_int,
+    x: i32,
+    y: i32,
+) -> impl Iterator<Item = WindowEvent> {
+    let button = u8::try_from(button.max(0)).unwrap_or(u8::MAX);
+    let click = WindowEvent::TrayClicked {
+        button: MouseButton::from_raw(button),
+        x,
+        y,
+    };
+    let double = (click_count == 2).then_some(WindowEvent::TrayDoubleClicked { x, y });
+    [click].into_iter().chain(double)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_click_adds_a_double_click() {
+        let events: Vec<_> = tray_events(0, 2, 1900, 4).collect();
+        assert!(matches!(
+            events[..],
+            [
+                WindowEvent::TrayClicked {
+                    button: MouseButton::Left,
+                    x: 1900,
+                    y: 4,
+                },
+                WindowEvent::TrayDoubleClicked { x: 1900, y: 4 },
+            ]
+        ));
+
+        let events: Vec<_> = tray_events(1, 1, 0, 0).collect();
+        assert!(matches!(
+            events[..],
+            [WindowEvent::TrayClicked {
+                button: MouseButton::Right,
+                ..
+            }]
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    mod session_bus {
+        use super::*;
+        use crate::test_bus::SessionBus;
+        use std::process;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        fn icon() -> Image {
+            Image::new(1, 1, vec![0xff; 4])
+        }
+
+        /// Bus name of this process's icon once it has been acquired.
+        fn item_name(bus: &SessionBus) -> Option<String> {
+            let mut name = None;
+            let prefix = format!("org.kde.StatusNotifierItem-{}-", process::id());
+            bus.pump_until(|| {
+                let names = bus.names();
+                name = names.find(&prefix).and_then(|start| {
+                    let end = start + names[start..].find('\'')?;
+                    Some(names[start..end].to_string())
+                });
+                name.is_some()
+            });
+            name
+        }
+
+        fn call(bus: &SessionBus, dest: &str, path: &str, method: &str, args: &[&str]) -> String {
+            let mut argv = vec![
+                "--dest",
+                dest,
+                "--object-path",
+                path,
+                "--method",
+                method,
+                "--",
+            ];
+            argv.extend_from_slice(args);
+            bus.gdbus("call", &argv).unwrap()
+        }
+
+        #[test]
+        fn activate_reports_a_click() {
+            let Some(bus) = SessionBus::start() else {
+                return;
+            };
+            let tray = TrayIcon::new(&icon(), "Aurea").unwrap();
+            let name = item_name(&bus).unwrap();
+
+            let method = "org.kde.StatusNotifierItem.Activate";
+            call(&bus, &name, "/StatusNotifierItem", method, &["10", "20"]);
+            assert!(matches!(
+                tray.poll_events()[..],
+                [WindowEvent::TrayClicked {
+                    button: MouseButton::Left,
+                    x: 10,
+                    y: 20,
+                }]
+            ));
+        }
+
+        #[test]
+        fn menu_is_served_over_dbusmenu() {
+            let Some(bus) = SessionBus::start() else {
+                return;
+            };
+            let mut tray = TrayIcon::new(&icon(), "Aurea").unwrap();
+            let clicked = Arc::new(AtomicBool::new(false));
+            let mut menu = ContextMenu::new().unwrap();
+            let flag = Arc::clone(&clicked);
+            menu.add_item("Quit", move || flag.store(true, Ordering::SeqCst))
+                .unwrap();
+            tray.set_menu(Some(menu)).unwrap();
+            let name = item_name(&bus).unwrap();
+
+            let method = "com.canonical.dbusmenu.GetLayout";
+            let layout = call(&bus, &name, "/MenuBar", method, &["0", "-1", "[]"]);
+            assert!(layout.contains("'label': <'Quit'>"));
+            let id = layout
+                .split("[<(")
+                .nth(1)
+                .unwrap()
+                .split(',')
+                .next()
+                .unwrap();
+
+            let method = "com.canonical.dbusmenu.Event";
+            call(
+                &bus,
+                &name,
+                "/MenuBar",
+                method,
+                &[id, "clicked", "<0>", "0"],
+            );
+            assert!(clicked.load(Ordering::SeqCst));
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ffi::ng_platform_frame_idle;
 use crate::window::{process_all_window_events, process_all_window_updates};
+use aurea_runtime::Executor;
 pub use aurea_runtime::FrameScheduler;
 use std::io::{Write, stderr};
 use std::panic::catch_unwind;
@@ -14,6 +15,8 @@ use std::panic::catch_unwind;
 pub unsafe extern "C" fn ng_process_frames() {
     let result = catch_unwind(|| {
         process_all_window_events();
+        // Tasks run before updates so values they publish are visible this pass.
+        Executor::run_ready();
         process_all_window_updates();
         if let Err(e) = FrameScheduler::process_frames() {
             log::warn!("Frame processing error: {:?}", e);
@@ -29,7 +32,13 @@ pub unsafe extern "C" fn ng_process_frames() {
         Write::flush(&mut stderr()).ok();
     }
 
-    if !FrameScheduler::is_scheduled() {
+    // Work woken during this pass already requested another one; idling the
+    // platform now would drop that wake-up on display-link backends. The
+    // same goes for a delayed wake-up the platform cannot time.
+    if !FrameScheduler::is_scheduled()
+        && !Executor::has_ready()
+        && !FrameScheduler::polls_for_wake()
+    {
         unsafe { ng_platform_frame_idle() };
     }
 }
@@ -0,0 +1,367 @@
+use super::events::EventQueue;
+use super::state::raw_size;
+use super::{Window, WindowId, WindowType, init_platform, is_rgba_image};
+use crate::ffi::{NGWindowOptions, ng_platform_create_window_with_options};
+use crate::registry::window::is_event_queue_registered;
+use crate::render::Image;
+use crate::{AureaError, AureaResult};
+use log::info;
+use std::ffi::CString;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+use std::sync::{Arc, Weak};
+
+/// `NG_WINDOW_OPTIONS_VERSION` in `common/platform_api.h`.
+const OPTIONS_VERSION: u32 = 1;
+
+const POSITION_DEFAULT: u32 = 0;
+const POSITION_EXPLICIT: u32 = 1;
+const POSITION_CENTERED: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Default,
+    At(i32, i32),
+    Centered,
+}
+
+/// The parent window, remembered without borrowing it. Its event queue
+/// tells whether the window is still open when the child is built.
+#[derive(Debug, Clone)]
+struct Parent {
+    id: WindowId,
+    queue: Weak<EventQueue>,
+}
+
+impl Parent {
+    /// The parent's native handle, or an error once it has been dropped.
+    fn handle(&self) -> AureaResult<*mut c_void> {
+        let handle = self.id.0 as *mut c_void;
+        if !is_event_queue_registered(handle, &self.queue) {
+            return Err(AureaError::WindowCreationFailed);
+        }
+        Ok(handle)
+    }
+}
+
+/// Configures a [`Window`] before creating it. Every option is applied to the
+/// native window before it is first shown, so it appears in its final place
+/// and shape without flicker.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use aurea::{Window, WindowType};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let main = Window::builder()
+///     .title("Editor")
+///     .size(1024, 768)
+///     .min_size(640, 480)
+///     .app_id("org.example.Editor")
+///     .build()?;
+/// let find = Window::builder()
+///     .title("Find")
+///     .size(360, 120)
+///     .window_type(WindowType::Dialog)
+///     .parent(&main)
+///     .centered()
+///     .modal(true)
+///     .resizable(false)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowBuilder {
+    title: String,
+    width: i32,
+    height: i32,
+    position: Position,
+    min_size: Option<(u32, u32)>,
+    max_size: Option<(u32, u32)>,
+    resizable: bool,
+    decorations: bool,
+    icon: Option<Image>,
+    visible: bool,
+    window_type: WindowType,
+    parent: Option<Parent>,
+    modal: bool,
+    transparent: bool,
+    app_id: Option<String>,
+}
+
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            width: 800,
+            height: 600,
+            position: Position::Default,
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            decorations: true,
+            icon: None,
+            visible: true,
+            window_type: WindowType::Normal,
+            parent: None,
+            modal: false,
+            transparent: false,
+            app_id: None,
+        }
+    }
+}
+
+impl WindowBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn size(mut self, width: i32, height: i32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Place the window's top-left corner at `(x, y)` in screen coordinates.
+    /// Wayland compositors choose the position themselves and ignore this.
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.position = Position::At(x, y);
+        self
+    }
+
+    /// Centre the window over its [`parent`](Self::parent), or over the
+    /// monitor when it has none.
+    pub fn centered(mut self) -> Self {
+        self.position = Position::Centered;
+        self
+    }
+
+    /// Smallest size the user can resize to, in logical pixels.
+    pub fn min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    /// Largest size the user can resize to, in logical pixels.
+    pub fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Show or hide the title bar and borders.
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Window icon as tightly packed RGBA8 pixels.
+    pub fn icon(mut self, icon: Image) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Create the window hidden; call [`Window::show`] once its content is
+    /// in place.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    pub fn window_type(mut self, window_type: WindowType) -> Self {
+        self.window_type = window_type;
+        self
+    }
+
+    /// Keep the window above `parent`, minimise it along with the parent and
+    /// close it when the parent closes. Building fails if `parent` has been
+    /// dropped by then.
+    pub fn parent(mut self, parent: &Window) -> Self {
+        self.parent = Some(Parent {
+            id: parent.id(),
+            queue: Arc::downgrade(&parent.event_queue),
+        });
+        self
+    }
+
+    /// Block input to the parent, and to the parent's other child windows,
+    /// while this window is shown; unrelated windows keep working. Without a
+    /// parent, input to every other window of the application is blocked.
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    /// Give the window an alpha channel; see [`Window::set_transparent`].
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    /// Application identifier the desktop uses to group windows and find
+    /// the `.desktop` file: the Wayland app ID and X11 `WM_CLASS` on Linux.
+    /// It applies to the whole process, so set the same ID on every window.
+    pub fn app_id(mut self, app_id: impl Into<String>) -> Self {
+        self.app_id = Some(app_id.into());
+        self
+    }
+
+    pub fn build(self) -> AureaResult<Window> {
+        init_platform()?;
+
+        info!("Creating window: {}x{}", self.width, self.height);
+
+        let raw = RawWindow::new(&self)?;
+        let handle = unsafe { ng_platform_create_window_with_options(&raw.options(&self)) };
+        let mut window = Window::from_native(handle, &self.title, self.window_type)?;
+        window.parent = self.parent.map(|parent| parent.id);
+        window.modal = self.modal;
+        Ok(window)
+    }
+}
+
+/// C strings backing an `NGWindowOptions` for the length of one call.
+struct RawWindow {
+    title: CString,
+    app_id: Option<CString>,
+    min_size: (c_int, c_int),
+    max_size: (c_int, c_int),
+    parent: *mut c_void,
+}
+
+impl RawWindow {
+    fn new(builder: &WindowBuilder) -> AureaResult<Self> {
+        if builder
+            .icon
+            .as_ref()
+            .is_some_and(|icon| !is_rgba_image(icon))
+        {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let app_id = builder
+            .app_id
+            .as_deref()
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| AureaError::ElementOperationFailed)?;
+        Ok(Self {
+            title: CString::new(builder.title.as_str()).map_err(|_| AureaError::InvalidTitle)?,
+            app_id,
+            min_size: raw_size(builder.min_size)?,
+            max_size: raw_size(builder.max_size)?,
+            parent: builder
+                .parent
+                .as_ref()
+                .map_or(Ok(ptr::null_mut()), Parent::handle)?,
+        })
+    }
+
+    fn options(&self, builder: &WindowBuilder) -> NGWindowOptions {
+        let (position, x, y) = match builder.position {
+            Position::Default => (POSITION_DEFAULT, 0, 0),
+            Position::At(x, y) => (POSITION_EXPLICIT, x, y),
+            Position::Centered => (POSITION_CENTERED, 0, 0),
+        };
+        let (icon_rgba, icon_width, icon_height) =
+            builder.icon.as_ref().map_or((ptr::null(), 0, 0), |icon| {
+                (icon.data.as_ptr(), icon.width, icon.height)
+            });
+        NGWindowOptions {
+            version: OPTIONS_VERSION,
+            title: self.title.as_ptr(),
+            width: builder.width,
+            height: builder.height,
+            position,
+            x,
+            y,
+            min_width: self.min_size.0,
+            min_height: self.min_size.1,
+            max_width: self.max_size.0,
+            max_height: self.max_size.1,
+            resizable: c_int::from(builder.resizable),
+            decorated: c_int::from(builder.decorations),
+            visible: c_int::from(builder.visible),
+            transparent: c_int::from(builder.transparent),
+            window_type: builder.window_type.to_raw(),
+            parent: self.parent,
+            modal: c_int::from(builder.modal),
+            icon_rgba,
+            icon_width,
+            icon_height,
+            app_id: self.app_id.as_ref().map_or(ptr::null(), |id| id.as_ptr()),
+        }
+    }
+}
+
+impl Window {
+    pub fn builder() -> WindowBuilder {
+        WindowBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::window::{register_event_queue, unregister_event_queue};
+
+    #[test]
+    fn options_carry_every_setting() {
+        let builder = WindowBuilder::new()
+            .title("Find")
+            .size(360, 120)
+            .centered()
+            .min_size(200, 100)
+            .resizable(false)
+            .visible(false)
+            .window_type(WindowType::Dialog)
+            .app_id("org.example.Find");
+        let raw = RawWindow::new(&builder).unwrap();
+        let options = raw.options(&builder);
+        assert_eq!(options.version, OPTIONS_VERSION);
+        assert_eq!((options.width, options.height), (360, 120));
+        assert_eq!(options.position, POSITION_CENTERED);
+        assert_eq!((options.min_width, options.min_height), (200, 100));
+        assert_eq!((options.max_width, options.max_height), (0, 0));
+        assert_eq!((options.resizable, options.decorated), (0, 1));
+        assert_eq!((options.visible, options.window_type), (0, 5));
+        assert!(options.parent.is_null() && options.icon_rgba.is_null());
+        assert!(!options.app_id.is_null());
+    }
+
+    #[test]
+    fn closed_parent_is_rejected() {
+        let queue = Arc::new(EventQueue::new());
+        let handle = Arc::as_ptr(&queue).cast_mut().cast::<c_void>();
+        register_event_queue(handle, &queue);
+        let mut builder = WindowBuilder::new();
+        builder.parent = Some(Parent {
+            id: WindowId::from_handle(handle),
+            queue: Arc::downgrade(&queue),
+        });
+        assert_eq!(RawWindow::new(&builder).unwrap().parent, handle);
+
+        // Another window now lives at the same address.
+        let other = Arc::new(EventQueue::new());
+        register_event_queue(handle, &other);
+        drop(queue);
+        assert!(RawWindow::new(&builder).is_err());
+        unregister_event_queue(handle);
+    }
+
+    #[test]
+    fn malformed_icon_is_rejected() {
+        let bad_icon = WindowBuilder::new().icon(Image::new(2, 2, vec![0; 4]));
+        assert!(RawWindow::new(&bad_icon).is_err());
+    }
+}
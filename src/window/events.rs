@@ -1,7 +1,15 @@
 //! Window event system for external event loop integration
 
-pub use aurea_foundation::{EventCallback, KeyCode, Modifiers, MouseButton, WindowEvent};
-pub use aurea_runtime::EventQueue;
+pub use aurea_foundation::{
+    AccessibilityAction, DragData, DropEffect, EventCallback, FocusTarget, KeyCode, KeyLocation,
+    LogicalKey, Modifiers, MouseButton, NotificationCloseReason, PhysicalKey, PointerButtons,
+    PointerEvent, PointerKind, PointerPhase, ScrollDeltaKind, ScrollPhase, TouchpadPhase,
+    WindowEvent,
+};
+pub use aurea_runtime::{
+    EventHandlerHandle, EventPhase, EventQueue, EventRecording, EventRouter, HandlerId, Phase,
+    Propagation, RecordedEvent, RecordingFormat, ReplayHandle, ReplaySpeed, RoutedEvent, ScopeId,
+};
 
 #[cfg(test)]
 mod tests {
@@ -35,6 +43,29 @@ mod tests {
         assert_eq!(lock(&received).len(), 1);
     }
 
+    #[test]
+    fn routed_stop_hides_event_from_flat_callbacks() {
+        let queue = EventQueue::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let rec = Arc::clone(&received);
+        queue.register_callback(Arc::new(move |e| {
+            lock(&rec).push(e);
+        }));
+        let root = queue.router().root();
+        queue.router().on_bubble(root, |routed| match routed.event {
+            WindowEvent::CloseRequested => Propagation::Stop,
+            _ => Propagation::Continue,
+        });
+
+        queue.push(WindowEvent::CloseRequested);
+        queue.push(WindowEvent::Focused);
+        let processed = queue.process_events();
+        assert_eq!(processed.len(), 2, "stopped events are still returned");
+        let received = lock(&received);
+        assert_eq!(received.len(), 1);
+        assert!(matches!(received[0], WindowEvent::Focused));
+    }
+
     #[test]
     fn modifiers_from_bits_and_is_any() {
         let none = Modifiers::from_bits(0);
@@ -48,6 +79,12 @@ mod tests {
         let all = Modifiers::from_bits(0b1111);
         assert!(all.is_any());
         assert!(all.shift && all.ctrl && all.alt && all.meta);
+
+        // Lock states alone are not held modifiers.
+        let locks = Modifiers::from_bits(0b11_0000);
+        assert!(locks.caps_lock && locks.num_lock);
+        assert!(!locks.is_any());
+        assert_eq!(locks.to_bits(), 0b11_0000);
     }
 
     #[test]
@@ -62,7 +99,11 @@ mod tests {
         let mods = Modifiers::from_bits(0b0010);
         queue.push(WindowEvent::KeyInput {
             key: KeyCode::A,
+            physical_key: PhysicalKey::KeyQ,
+            logical_key: LogicalKey::Character("a".into()),
+            location: KeyLocation::Standard,
             pressed: true,
+            repeat: false,
             modifiers: mods,
         });
         let out = queue.pop_all();
@@ -71,6 +112,7 @@ mod tests {
             &out[0],
             WindowEvent::KeyInput {
                 key: KeyCode::A,
+                physical_key: PhysicalKey::KeyQ,
                 pressed: true,
                 ..
             }
@@ -115,6 +157,8 @@ mod tests {
             delta_x: 1.0,
             delta_y: -2.0,
             modifiers: Modifiers::default(),
+            delta_kind: ScrollDeltaKind::Line,
+            phase: ScrollPhase::Discrete,
         });
         let out = queue.pop_all();
         assert_eq!(out.len(), 1);
@@ -129,6 +173,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn event_queue_merges_wheel_only_within_a_phase() {
+        let queue = EventQueue::new();
+        let wheel = |delta_y, phase| WindowEvent::MouseWheel {
+            delta_x: 0.0,
+            delta_y,
+            modifiers: Modifiers::default(),
+            delta_kind: ScrollDeltaKind::Pixel,
+            phase,
+        };
+        queue.push(wheel(1.0, ScrollPhase::Began));
+        queue.push(wheel(2.0, ScrollPhase::Changed));
+        queue.push(wheel(3.0, ScrollPhase::Changed));
+        queue.push(wheel(0.0, ScrollPhase::Ended));
+        let deltas: Vec<_> = queue
+            .pop_all()
+            .into_iter()
+            .map(|event| match event {
+                WindowEvent::MouseWheel { delta_y, phase, .. } => (delta_y, phase),
+                _ => unreachable!("only wheel events were pushed"),
+            })
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![
+                (1.0, ScrollPhase::Began),
+                (5.0, ScrollPhase::Changed),
+                (0.0, ScrollPhase::Ended),
+            ]
+        );
+    }
+
     #[test]
     fn event_queue_text_input() {
         let queue = EventQueue::new();
@@ -0,0 +1,471 @@
+//! Keyboard focus and Tab traversal within a window.
+
+use super::events::{EventHandlerHandle, EventQueue, Propagation, ScopeId};
+use super::{FocusTarget, KeyCode, PhysicalKey, WindowEvent};
+use crate::elements::Element;
+use crate::ffi::{
+    ng_platform_element_focus, ng_platform_element_set_focus_target,
+    ng_platform_window_focus_keeps_tab, ng_platform_window_set_focus_traversal,
+};
+use crate::registry::focus::{register_native_focus_callback, unregister_native_focus_callback};
+use crate::render::{Canvas, InteractiveId, set_canvas_focused_item};
+use crate::sync::lock;
+use crate::{AureaError, AureaResult};
+use std::mem::{replace, take};
+use std::os::raw::c_void;
+use std::sync::{Arc, Mutex, Weak};
+
+/// A registered target and the router scope its key events go to.
+struct Entry {
+    target: FocusTarget,
+    scope: ScopeId,
+}
+
+#[derive(Default)]
+struct FocusState {
+    /// Traversal order.
+    entries: Vec<Entry>,
+    current: Option<FocusTarget>,
+    /// Whether the platform has handed Tab over to us.
+    managed: bool,
+}
+
+impl FocusState {
+    fn scope_of(&self, target: FocusTarget) -> Option<ScopeId> {
+        self.entries
+            .iter()
+            .find(|entry| entry.target == target)
+            .map(|entry| entry.scope)
+    }
+}
+
+struct Shared {
+    window: usize,
+    event_queue: Arc<EventQueue>,
+    state: Mutex<FocusState>,
+}
+
+/// Tracks which registered element or canvas shape has keyboard focus.
+///
+/// Once something is registered, Tab and Shift-Tab move focus through the
+/// registered targets in order instead of through the toolkit's own focus
+/// chain, except that an unregistered text editor with focus keeps Tab as
+/// input. Focus changes arrive as [`WindowEvent::FocusGained`] and
+/// [`WindowEvent::FocusLost`]; while a target has focus, keyboard, text and
+/// IME events are routed to its [`Self::scope`], where handlers added with
+/// [`Self::on_key`] see them first and can consume Tab themselves.
+///
+/// A focused canvas shape gets a focus ring after the canvas' draw
+/// callback; see [`Canvas::set_focus_ring`].
+///
+/// ```rust,no_run
+/// use aurea::Window;
+/// use aurea::elements::TextField;
+/// use aurea::render::{Canvas, InteractiveId, RendererBackend};
+/// use aurea::window::events::Propagation;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let window = Window::new("Form", 400, 300)?;
+/// let name = TextField::new()?;
+/// let canvas = Canvas::new(400, 200, RendererBackend::Cpu)?;
+///
+/// let focus = window.focus_manager();
+/// focus.register_element(&name);
+/// let knob = focus.register_item(&canvas, InteractiveId(1));
+/// focus.on_key(knob, |event| {
+///     println!("knob key: {event:?}");
+///     Propagation::Stop
+/// });
+/// focus.focus(knob)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FocusManager {
+    shared: Arc<Shared>,
+    tab_handler: EventHandlerHandle,
+}
+
+impl FocusManager {
+    pub(crate) fn new(window: *mut c_void, event_queue: &Arc<EventQueue>) -> Self {
+        let shared = Arc::new(Shared {
+            window: window as usize,
+            event_queue: Arc::clone(event_queue),
+            state: Mutex::new(FocusState::default()),
+        });
+
+        // On the root's bubble phase, so a focused target can consume Tab.
+        // A text view we do not traverse to keeps Tab as input.
+        let weak: Weak<Shared> = Arc::downgrade(&shared);
+        let router = event_queue.router();
+        let tab_handler = router.on_bubble(router.root(), move |routed| {
+            match (weak.upgrade(), tab_direction(routed.event)) {
+                (Some(shared), Some(backward))
+                    if !shared.focus_keeps_tab() && shared.traverse(backward) =>
+                {
+                    Propagation::Stop
+                }
+                _ => Propagation::Continue,
+            }
+        });
+
+        let weak = Arc::downgrade(&shared);
+        register_native_focus_callback(window as usize, move |element| {
+            if let Some(shared) = weak.upgrade() {
+                shared.native_focus_changed(element);
+            }
+        });
+
+        Self {
+            shared,
+            tab_handler,
+        }
+    }
+
+    /// Add a native element to the end of the traversal order.
+    pub fn register_element<E: Element + ?Sized>(&self, element: &E) -> FocusTarget {
+        unsafe { ng_platform_element_set_focus_target(element.handle(), 1) };
+        self.register(FocusTarget::Element(element.handle() as usize))
+    }
+
+    /// Add the shape drawn with `id` on `canvas` to the end of the traversal
+    /// order.
+    pub fn register_item(&self, canvas: &Canvas, id: InteractiveId) -> FocusTarget {
+        self.register(FocusTarget::CanvasItem {
+            canvas: canvas.handle as usize,
+            id: id.0,
+        })
+    }
+
+    fn register(&self, target: FocusTarget) -> FocusTarget {
+        let router = self.shared.event_queue.router();
+        let take_over = {
+            let mut state = lock(&self.shared.state);
+            if state.scope_of(target).is_none()
+                && let Some(scope) = router.create_scope(router.root())
+            {
+                state.entries.push(Entry { target, scope });
+            }
+            !replace(&mut state.managed, true)
+        };
+        if take_over {
+            unsafe {
+                ng_platform_window_set_focus_traversal(self.shared.window as *mut c_void, 1);
+            }
+        }
+        target
+    }
+
+    /// Remove `target`, taking focus away from it if it had it.
+    pub fn unregister(&self, target: FocusTarget) {
+        let (entry, had_focus) = {
+            let mut state = lock(&self.shared.state);
+            let Some(index) = state.entries.iter().position(|e| e.target == target) else {
+                return;
+            };
+            let had_focus = state.current == Some(target);
+            if had_focus {
+                state.current = None;
+            }
+            (state.entries.remove(index), had_focus)
+        };
+        self.shared.event_queue.router().remove_scope(entry.scope);
+        if let FocusTarget::Element(element) = target {
+            unsafe { ng_platform_element_set_focus_target(element as *mut c_void, 0) };
+        }
+        if had_focus {
+            self.shared.announce(Some(target), None);
+        }
+    }
+
+    /// Put the listed targets first, in the given order; the rest follow in
+    /// their previous order. Unregistered targets are ignored.
+    pub fn set_tab_order(&self, order: &[FocusTarget]) {
+        let mut state = lock(&self.shared.state);
+        let mut rest = take(&mut state.entries);
+        let mut ordered = Vec::with_capacity(rest.len());
+        for target in order {
+            if let Some(index) = rest.iter().position(|e| e.target == *target) {
+                ordered.push(rest.remove(index));
+            }
+        }
+        ordered.extend(rest);
+        state.entries = ordered;
+    }
+
+    /// Registered targets in traversal order.
+    pub fn tab_order(&self) -> Vec<FocusTarget> {
+        lock(&self.shared.state)
+            .entries
+            .iter()
+            .map(|entry| entry.target)
+            .collect()
+    }
+
+    /// Give `target` keyboard focus, moving platform focus to its element or
+    /// canvas.
+    pub fn focus(&self, target: FocusTarget) -> AureaResult<()> {
+        self.shared.focus(Some(target))
+    }
+
+    /// Take focus away from every registered target. Platform focus stays
+    /// where it is.
+    pub fn clear_focus(&self) -> AureaResult<()> {
+        self.shared.focus(None)
+    }
+
+    /// Move to the next target, as Tab does. Returns whether focus moved.
+    pub fn focus_next(&self) -> bool {
+        self.shared.traverse(false)
+    }
+
+    /// Move to the previous target, as Shift-Tab does.
+    pub fn focus_previous(&self) -> bool {
+        self.shared.traverse(true)
+    }
+
+    pub fn focused(&self) -> Option<FocusTarget> {
+        lock(&self.shared.state).current
+    }
+
+    pub fn is_focused(&self, target: FocusTarget) -> bool {
+        self.focused() == Some(target)
+    }
+
+    /// The event router scope that receives keyboard, text and IME events
+    /// while `target` has focus.
+    pub fn scope(&self, target: FocusTarget) -> Option<ScopeId> {
+        lock(&self.shared.state).scope_of(target)
+    }
+
+    /// Handle the events routed to `target` while it has focus. Returning
+    /// [`Propagation::Stop`] keeps an event from the window's other handlers
+    /// and from Tab traversal. `None` if `target` is not registered.
+    pub fn on_key<F>(&self, target: FocusTarget, handler: F) -> Option<EventHandlerHandle>
+    where
+        F: Fn(&WindowEvent) -> Propagation + Send + Sync + 'static,
+    {
+        let scope = self.scope(target)?;
+        let router = self.shared.event_queue.router();
+        Some(router.on_bubble(scope, move |routed| handler(routed.event)))
+    }
+}
+
+impl Drop for FocusManager {
+    fn drop(&mut self) {
+        // The native window may already be gone; only Rust state is undone.
+        unregister_native_focus_callback(self.shared.window);
+        self.tab_handler.unregister();
+    }
+}
+
+impl Shared {
+    /// Focus state changes before the platform call, so the native focus
+    /// notification it triggers finds the target already current; the
+    /// change is announced only once the platform call succeeds.
+    fn focus(&self, target: Option<FocusTarget>) -> AureaResult<()> {
+        let router = self.event_queue.router();
+        let (previous, previous_scope) = {
+            let mut state = lock(&self.state);
+            let scope = match target {
+                Some(target) => Some(
+                    state
+                        .scope_of(target)
+                        .ok_or(AureaError::ElementOperationFailed)?,
+                ),
+                None => None,
+            };
+            let previous_scope = router.focus();
+            router.set_focus(scope);
+            (replace(&mut state.current, target), previous_scope)
+        };
+        if let Some(next) = target
+            && unsafe { ng_platform_element_focus(next.native_handle() as *mut c_void) } != 0
+        {
+            let mut state = lock(&self.state);
+            if state.current == target {
+                state.current = previous;
+                router.set_focus(previous_scope);
+            }
+            return Err(AureaError::ElementOperationFailed);
+        }
+        if previous != target {
+            self.announce(previous, target);
+        }
+        Ok(())
+    }
+
+    fn focus_keeps_tab(&self) -> bool {
+        unsafe { ng_platform_window_focus_keeps_tab(self.window as *mut c_void) == 1 }
+    }
+
+    fn traverse(&self, backward: bool) -> bool {
+        let next = {
+            let state = lock(&self.state);
+            let position = state
+                .current
+                .and_then(|current| state.entries.iter().position(|e| e.target == current));
+            step(position, state.entries.len(), backward).map(|index| state.entries[index].target)
+        };
+        next.is_some_and(|next| self.focus(Some(next)).is_ok())
+    }
+
+    /// The user or the toolkit moved platform focus, e.g. by clicking a
+    /// text field.
+    fn native_focus_changed(&self, element: usize) {
+        let (previous, next) = {
+            let mut state = lock(&self.state);
+            if state
+                .current
+                .is_some_and(|current| current.native_handle() == element)
+            {
+                return;
+            }
+            let target = FocusTarget::Element(element);
+            let scope = state.scope_of(target);
+            self.event_queue.router().set_focus(scope);
+            let next = scope.map(|_| target);
+            (replace(&mut state.current, next), next)
+        };
+        if previous != next {
+            self.announce(previous, next);
+        }
+    }
+
+    fn announce(&self, previous: Option<FocusTarget>, next: Option<FocusTarget>) {
+        if let Some(FocusTarget::CanvasItem { canvas, .. }) = previous {
+            set_canvas_focused_item(canvas, None);
+        }
+        if let Some(FocusTarget::CanvasItem { canvas, id }) = next {
+            set_canvas_focused_item(canvas, Some(InteractiveId(id)));
+        }
+        if let Some(target) = previous {
+            self.event_queue.push(WindowEvent::FocusLost { target });
+        }
+        if let Some(target) = next {
+            self.event_queue.push(WindowEvent::FocusGained { target });
+        }
+    }
+}
+
+/// `Some(backward)` for an unmodified Tab or Shift-Tab press.
+fn tab_direction(event: &WindowEvent) -> Option<bool> {
+    match event {
+        WindowEvent::KeyInput {
+            key,
+            physical_key,
+            pressed: true,
+            modifiers,
+            ..
+        } if (*key == KeyCode::Tab || *physical_key == PhysicalKey::Tab)
+            && !(modifiers.ctrl || modifiers.alt || modifiers.meta) =>
+        {
+            Some(modifiers.shift)
+        }
+        _ => None,
+    }
+}
+
+/// Index of the target after (or before) `position`, wrapping around. With
+/// nothing focused, Tab starts at the first target and Shift-Tab at the last.
+fn step(position: Option<usize>, len: usize, backward: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    Some(match (position, backward) {
+        (Some(index), false) => (index + 1) % len,
+        (Some(index), true) => (index + len - 1) % len,
+        (None, false) => 0,
+        (None, true) => len - 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::{KeyLocation, LogicalKey, Modifiers};
+    use std::ptr;
+
+    fn tab(shift: bool, ctrl: bool) -> WindowEvent {
+        WindowEvent::KeyInput {
+            key: KeyCode::Tab,
+            physical_key: PhysicalKey::Tab,
+            logical_key: LogicalKey::Named(KeyCode::Tab),
+            location: KeyLocation::Standard,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers {
+                shift,
+                ctrl,
+                ..Modifiers::default()
+            },
+        }
+    }
+
+    #[test]
+    fn tab_wraps_through_the_order() {
+        assert_eq!(tab_direction(&tab(false, false)), Some(false));
+        assert_eq!(tab_direction(&tab(true, false)), Some(true));
+        assert_eq!(tab_direction(&tab(false, true)), None);
+
+        assert_eq!(step(None, 3, false), Some(0));
+        assert_eq!(step(None, 3, true), Some(2));
+        assert_eq!(step(Some(2), 3, false), Some(0));
+        assert_eq!(step(Some(0), 3, true), Some(2));
+        assert_eq!(step(None, 0, false), None);
+    }
+
+    #[test]
+    fn key_events_follow_focus() {
+        let queue = Arc::new(EventQueue::new());
+        let manager = FocusManager::new(ptr::null_mut(), &queue);
+        let field = manager.register(FocusTarget::Element(0x10));
+        let knob = manager.register(FocusTarget::CanvasItem {
+            canvas: 0x20,
+            id: 7,
+        });
+        let button = manager.register(FocusTarget::Element(0x30));
+        manager.register(field);
+        manager.set_tab_order(&[button, knob]);
+        assert_eq!(manager.tab_order(), [button, knob, field]);
+
+        // Key events follow focus into the target's scope.
+        lock(&manager.shared.state).current = Some(knob);
+        queue.router().set_focus(manager.scope(knob));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        manager.on_key(knob, move |event| {
+            lock(&log).push(event.clone());
+            Propagation::Stop
+        });
+        queue.push(tab(false, false));
+        queue.process_events();
+        assert_eq!(lock(&seen).len(), 1);
+        assert_eq!(manager.focused(), Some(knob));
+
+        manager.unregister(knob);
+        assert_eq!(manager.focused(), None);
+        assert!(matches!(
+            queue.pop_all()[..],
+            [WindowEvent::FocusLost { target }] if target == knob
+        ));
+    }
+
+    #[test]
+    fn failed_focus_changes_nothing() {
+        let queue = Arc::new(EventQueue::new());
+        let manager = FocusManager::new(ptr::null_mut(), &queue);
+        let knob = manager.register(FocusTarget::CanvasItem {
+            canvas: 0x20,
+            id: 7,
+        });
+        // Every platform rejects a null element.
+        let missing = manager.register(FocusTarget::Element(0));
+        lock(&manager.shared.state).current = Some(knob);
+        queue.router().set_focus(manager.scope(knob));
+
+        assert!(manager.focus(missing).is_err());
+        assert_eq!(manager.focused(), Some(knob));
+        assert_eq!(queue.router().focus(), manager.scope(knob));
+        assert!(queue.pop_all().is_empty());
+    }
+}
@@ -32,10 +32,14 @@ impl WindowManager {
         windows.push(window);
     }
 
-    /// Unregister a window from the manager
+    /// Unregister a window from the manager, along with its child windows.
+    /// The platform closes those with their parent; this only releases the
+    /// manager's references, so dropping them afterwards is safe.
     pub fn unregister(&self, window_handle: *mut c_void) {
         let mut windows = lock(&self.windows);
-        windows.retain(|w| w.handle != window_handle);
+        let links: Vec<_> = windows.iter().map(|w| (w.id(), w.parent())).collect();
+        let closing = descendants(&links, WindowId::from_handle(window_handle));
+        windows.retain(|w| w.handle != window_handle && !closing.contains(&w.id()));
     }
 
     /// Get all registered windows
@@ -56,6 +60,23 @@ impl WindowManager {
         windows.iter().find(|w| w.handle == handle).cloned()
     }
 
+    /// The registered windows built with `id` as their parent.
+    pub fn children(&self, id: WindowId) -> Vec<Arc<Window>> {
+        let windows = lock(&self.windows);
+        windows
+            .iter()
+            .filter(|w| w.parent() == Some(id))
+            .cloned()
+            .collect()
+    }
+
+    /// The registered parent of the window `id`.
+    pub fn parent(&self, id: WindowId) -> Option<Arc<Window>> {
+        let windows = lock(&self.windows);
+        let parent = windows.iter().find(|w| w.id() == id)?.parent()?;
+        windows.iter().find(|w| w.id() == parent).cloned()
+    }
+
     /// Process events for all registered windows
     pub fn poll_all_events(&self) -> Vec<(WindowId, WindowEvent)> {
         unsafe {
@@ -86,3 +107,39 @@ impl Default for WindowManager {
         Self::new()
     }
 }
+
+/// Every window below `root` in the `(window, parent)` hierarchy.
+fn descendants(links: &[(WindowId, Option<WindowId>)], root: WindowId) -> Vec<WindowId> {
+    let mut found = Vec::new();
+    let mut pending = vec![root];
+    while let Some(parent) = pending.pop() {
+        for (child, _) in links.iter().filter(|(_, p)| *p == Some(parent)) {
+            if !found.contains(child) && *child != root {
+                found.push(*child);
+                pending.push(*child);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descendants_cover_nested_children_only() {
+        let id = WindowId::from_raw;
+        let links = [
+            (id(1), None),
+            (id(2), Some(id(1))),
+            (id(3), Some(id(2))),
+            (id(4), Some(id(1))),
+            (id(5), None),
+        ];
+        let mut closing = descendants(&links, id(1));
+        closing.sort_by_key(|w| w.0);
+        assert_eq!(closing, [id(2), id(3), id(4)]);
+        assert!(descendants(&links, id(5)).is_empty());
+    }
+}
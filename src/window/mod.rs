@@ -1,10 +1,28 @@
 //! Window management, events, and lifecycle integration.
 
+mod builder;
 pub mod events;
+mod focus;
 mod manager;
-
-pub use events::{EventCallback, KeyCode, Modifiers, MouseButton, WindowEvent};
+mod monitor;
+mod session;
+mod state;
+
+pub use aurea_foundation::CursorIcon;
+pub use builder::WindowBuilder;
+pub use events::{
+    AccessibilityAction, DragData, DropEffect, EventCallback, FocusTarget, KeyCode, KeyLocation,
+    LogicalKey, Modifiers, MouseButton, NotificationCloseReason, PhysicalKey, PointerButtons,
+    PointerEvent, PointerKind, PointerPhase, ScrollDeltaKind, ScrollPhase, TouchpadPhase,
+    WindowEvent,
+};
+pub use focus::FocusManager;
 pub use manager::WindowManager;
+pub use monitor::Monitor;
+use session::NormalGeometry;
+pub use session::WindowState;
+pub(crate) use state::state_event;
+use state::window_frame;
 
 /// Window type for different window behaviors
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,6 +41,19 @@ pub enum WindowType {
     Dialog,
 }
 
+impl WindowType {
+    fn to_raw(self) -> i32 {
+        match self {
+            Self::Normal => 0,
+            Self::Popup => 1,
+            Self::Tool => 2,
+            Self::Utility => 3,
+            Self::Sheet => 4,
+            Self::Dialog => 5,
+        }
+    }
+}
+
 /// Stable window identifier derived from the native handle.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct WindowId(usize);
@@ -49,24 +80,24 @@ pub enum CursorGrabMode {
     Locked = 2,
 }
 
+use crate::accessibility::Accessibility;
 use crate::elements::Element;
 use crate::ffi::*;
 use crate::ffi::{
     ng_platform_free_clipboard_text, ng_platform_get_clipboard_text, ng_platform_request_frame,
-    ng_platform_set_clipboard_text,
+    ng_platform_request_frame_after, ng_platform_set_clipboard_text,
 };
 #[cfg(feature = "wgpu")]
 use crate::integration::NativeWindowHandle;
 use crate::lifecycle::{
     LifecycleEvent, register_lifecycle_callback, unregister_lifecycle_callback,
 };
-use crate::menu::MenuBar;
+use crate::menu::{ContextMenu, MenuBar};
 use crate::registry::window::{
-    process_window_updates, register_event_queue, register_global_event_queue,
-    register_update_callback, register_update_callbacks, unregister_event_queue,
-    unregister_update_callbacks,
+    process_window_updates, register_handle_event_queue, register_update_callback,
+    register_update_callbacks, unregister_event_queue, unregister_update_callbacks,
 };
-use crate::render::Rect;
+use crate::render::{Image, Rect};
 #[cfg(feature = "zengpu")]
 use crate::render::{ZenGpuContext, ZenGpuRenderer};
 use crate::sync::lock;
@@ -78,7 +109,8 @@ use aurea_foundation::{Capability, CapabilityChecker};
 use std::result::Result as StdResult;
 use std::{
     ffi::{CStr, CString},
-    os::raw::c_void,
+    os::raw::{c_char, c_void},
+    ptr,
     sync::{
         Arc, Mutex, Once,
         atomic::{AtomicUsize, Ordering},
@@ -91,8 +123,45 @@ use std::{
 /// `UnregisterClassA` on Windows) out from under them.
 static WINDOW_COUNT: AtomicUsize = AtomicUsize::new(0);
 
+/// Check the native library's ABI and initialise the platform, once.
+fn init_platform() -> AureaResult<()> {
+    const AUREA_FFI_ABI_VERSION: i32 = 2;
+
+    static INIT: Once = Once::new();
+    let mut error = None;
+
+    INIT.call_once(|| {
+        let got = unsafe { ng_platform_get_abi_version() };
+        if got != AUREA_FFI_ABI_VERSION {
+            error = Some(AureaError::AbiVersionMismatch {
+                expected: AUREA_FFI_ABI_VERSION,
+                got,
+            });
+            return;
+        }
+        if unsafe { ng_platform_init() } != 0 {
+            error = Some(AureaError::PlatformError(1));
+            return;
+        }
+        FrameScheduler::set_request_frame_hook(|| {
+            unsafe { ng_platform_request_frame() };
+        });
+        // Without a timed wake-up the scheduler polls until deadlines pass.
+        if unsafe { ng_platform_can_request_frame_after() } != 0 {
+            FrameScheduler::set_request_frame_after_hook(|delay| {
+                // Round up so the platform never wakes before the deadline.
+                let ms = u32::try_from(delay.as_micros().div_ceil(1000)).unwrap_or(u32::MAX);
+                unsafe { ng_platform_request_frame_after(ms) };
+            });
+        }
+    });
+
+    error.map_or(Ok(()), Err)
+}
+
 pub use crate::registry::window::{
-    process_all_window_events, process_all_window_updates, push_window_event,
+    broadcast_window_event, process_all_window_events, process_all_window_updates,
+    push_window_event,
 };
 
 use log::info;
@@ -108,6 +177,11 @@ pub struct Window {
     scale_factor: Mutex<f32>,
     event_queue: Arc<events::EventQueue>,
     window_type: WindowType,
+    parent: Option<WindowId>,
+    modal: bool,
+    accessibility: Accessibility,
+    focus: FocusManager,
+    normal_geometry: Arc<NormalGeometry>,
 }
 
 impl Window {
@@ -117,70 +191,51 @@ impl Window {
     }
 
     /// Create a new window with the specified type.
+    ///
+    /// The window is shown straight away; use [`Window::builder`] to set its
+    /// position, size limits, icon and the like before it first appears.
     pub fn with_type(
         title: &str,
         width: i32,
         height: i32,
         window_type: WindowType,
     ) -> AureaResult<Self> {
-        const AUREA_FFI_ABI_VERSION: i32 = 2;
-
-        static INIT: Once = Once::new();
-        let mut error = None;
-
-        INIT.call_once(|| {
-            let got = unsafe { ng_platform_get_abi_version() };
-            if got != AUREA_FFI_ABI_VERSION {
-                error = Some(AureaError::AbiVersionMismatch {
-                    expected: AUREA_FFI_ABI_VERSION,
-                    got,
-                });
-                return;
-            }
-            if unsafe { ng_platform_init() } != 0 {
-                error = Some(AureaError::PlatformError(1));
-                return;
-            }
-            FrameScheduler::set_request_frame_hook(|| {
-                unsafe { ng_platform_request_frame() };
-            });
-        });
-
-        if let Some(err) = error {
-            return Err(err);
-        }
-
-        let platform = Platform::current();
-        let capabilities = CapabilityChecker::new();
+        init_platform()?;
 
         info!("Creating window: {}x{}", width, height);
 
-        let title = CString::new(title).map_err(|_| AureaError::InvalidTitle)?;
-        let window_type_int = match window_type {
-            WindowType::Normal => 0,
-            WindowType::Popup => 1,
-            WindowType::Tool => 2,
-            WindowType::Utility => 3,
-            WindowType::Sheet => 4,
-            WindowType::Dialog => 5,
-        };
+        let c_title = CString::new(title).map_err(|_| AureaError::InvalidTitle)?;
         let handle = unsafe {
-            ng_platform_create_window_with_type(title.as_ptr(), width, height, window_type_int)
+            ng_platform_create_window_with_type(
+                c_title.as_ptr(),
+                width,
+                height,
+                window_type.to_raw(),
+            )
         };
+        Self::from_native(handle, title, window_type)
+    }
 
+    /// Wrap a freshly created native window: hook up its event queue,
+    /// lifecycle and scale callbacks, accessibility tree and focus manager.
+    fn from_native(handle: *mut c_void, title: &str, window_type: WindowType) -> AureaResult<Self> {
         if handle.is_null() {
             return Err(AureaError::WindowCreationFailed);
         }
 
+        let platform = Platform::current();
+        let capabilities = CapabilityChecker::new();
         let scale_factor = unsafe { ng_platform_get_scale_factor(handle) };
-        let event_queue = Arc::new(events::EventQueue::new());
 
-        register_global_event_queue(&event_queue);
-        register_event_queue(handle, &event_queue);
+        let event_queue = register_handle_event_queue(handle);
         register_update_callbacks(handle);
 
+        let normal_geometry = Arc::new(NormalGeometry::default());
+        normal_geometry.track(handle);
+
         // Register lifecycle bridge
         let eq_clone = event_queue.clone();
+        let normal = Arc::clone(&normal_geometry);
         let handle_usize = handle as usize;
         register_lifecycle_callback(
             handle,
@@ -196,6 +251,7 @@ impl Window {
                         unsafe {
                             ng_platform_window_get_position(handle_ptr, &mut x, &mut y);
                         }
+                        normal.track(handle_ptr);
                         eq_clone.push(WindowEvent::Moved { x, y });
                     }
                     LifecycleEvent::WindowResized => {
@@ -204,6 +260,7 @@ impl Window {
                         unsafe {
                             ng_platform_window_get_size(handle_ptr, &mut w, &mut h);
                         }
+                        normal.track(handle_ptr);
                         eq_clone.push(WindowEvent::Resized {
                             width: w.cast_unsigned(),
                             height: h.cast_unsigned(),
@@ -233,6 +290,11 @@ impl Window {
 
         WINDOW_COUNT.fetch_add(1, Ordering::Relaxed);
 
+        let accessibility = Accessibility::new(title, &event_queue);
+        if let Some((outer, content)) = window_frame(handle) {
+            accessibility.set_window_bounds(outer, content);
+        }
+
         Ok(Self {
             handle,
             menu_bar: None,
@@ -241,8 +303,13 @@ impl Window {
             capabilities,
             damage: Mutex::new(DamageRegion::new(16)),
             scale_factor: Mutex::new(scale_factor),
-            event_queue,
             window_type,
+            parent: None,
+            modal: false,
+            accessibility,
+            focus: FocusManager::new(handle, &event_queue),
+            event_queue,
+            normal_geometry,
         })
     }
 
@@ -282,6 +349,13 @@ impl Window {
         Ok(MenuBar::new(handle))
     }
 
+    /// Pop up `menu` at `(x, y)`, in logical points from the window's top-left.
+    ///
+    /// Returns once the menu is shown; chosen items run their callbacks.
+    pub fn show_context_menu(&self, menu: &ContextMenu, x: f32, y: f32) -> AureaResult<()> {
+        menu.show_at(self.handle, x, y)
+    }
+
     pub fn platform(&self) -> Platform {
         self.platform
     }
@@ -300,6 +374,27 @@ impl Window {
         WindowId::from_handle(self.handle)
     }
 
+    /// The window this one was built on top of with
+    /// [`WindowBuilder::parent`].
+    pub fn parent(&self) -> Option<WindowId> {
+        self.parent
+    }
+
+    pub fn is_modal(&self) -> bool {
+        self.modal
+    }
+
+    /// The accessibility tree describing this window's canvas content.
+    pub fn accessibility(&self) -> &Accessibility {
+        &self.accessibility
+    }
+
+    /// Keyboard focus and Tab traversal among this window's elements and
+    /// canvas shapes.
+    pub fn focus_manager(&self) -> &FocusManager {
+        &self.focus
+    }
+
     pub fn run(&self) -> AureaResult<()> {
         let result = unsafe { ng_platform_run() };
         if result != 0 {
@@ -575,8 +670,26 @@ impl Window {
         // Process events through callbacks and return them for manual processing
         let events = self.event_queue.process_events();
         for event in &events {
-            if let WindowEvent::ScaleFactorChanged { scale_factor } = event {
-                *lock(&self.scale_factor) = *scale_factor;
+            match event {
+                WindowEvent::ScaleFactorChanged { scale_factor } => {
+                    *lock(&self.scale_factor) = *scale_factor;
+                }
+                WindowEvent::Moved { .. } | WindowEvent::Resized { .. } => {
+                    if let Some((outer, content)) = window_frame(self.handle) {
+                        self.accessibility.set_window_bounds(outer, content);
+                    }
+                }
+                WindowEvent::Focused => self.accessibility.set_window_focused(true),
+                WindowEvent::Unfocused => self.accessibility.set_window_focused(false),
+                WindowEvent::FocusGained { target } => {
+                    self.accessibility.set_focus(Some(*target));
+                }
+                WindowEvent::FocusLost { target }
+                    if self.accessibility.focus() == Some(*target) =>
+                {
+                    self.accessibility.set_focus(None);
+                }
+                _ => {}
             }
         }
         events
@@ -646,6 +759,67 @@ impl Window {
         self.event_queue.register_callback(Arc::new(callback));
     }
 
+    /// Routing layer for this window's events.
+    ///
+    /// Routed handlers can be given a priority, attached to nested scopes
+    /// and return [`Propagation::Stop`](events::Propagation::Stop) to consume
+    /// an event; consumed events never reach [`Self::on_event`] callbacks.
+    ///
+    /// ```rust,no_run
+    /// use aurea::Window;
+    /// use aurea::window::events::{Phase, Propagation};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let window = Window::new("App", 800, 600)?;
+    /// let router = window.event_router();
+    /// let handle = router.add_handler(router.root(), Phase::Capture, 10, |routed| {
+    ///     match routed.event {
+    ///         aurea::WindowEvent::KeyInput { .. } => Propagation::Stop,
+    ///         _ => Propagation::Continue,
+    ///     }
+    /// });
+    /// handle.unregister();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn event_router(&self) -> &events::EventRouter {
+        self.event_queue.router()
+    }
+
+    /// Start recording this window's events for later replay.
+    pub fn start_recording(&self) {
+        self.event_queue.start_recording();
+    }
+
+    /// Stop recording and return the captured event stream, if recording.
+    ///
+    /// ```rust,no_run
+    /// use aurea::Window;
+    /// use aurea::window::events::RecordingFormat;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let window = Window::new("App", 800, 600)?;
+    /// window.start_recording();
+    /// // ... interact with the window ...
+    /// if let Some(recording) = window.stop_recording() {
+    ///     recording.save("session.json", RecordingFormat::Json)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stop_recording(&self) -> Option<events::EventRecording> {
+        self.event_queue.stop_recording()
+    }
+
+    /// Replay a recording into this window's event queue from the event loop.
+    pub fn replay_events(
+        &self,
+        recording: &events::EventRecording,
+        speed: events::ReplaySpeed,
+    ) -> events::ReplayHandle {
+        recording.replay(self.event_queue.clone(), speed)
+    }
+
     /// Request the window to close
     ///
     /// This sends a close request to the window. The window may emit a
@@ -686,6 +860,7 @@ impl Window {
         unsafe {
             ng_platform_window_set_title(self.handle, title_cstr.as_ptr());
         }
+        self.accessibility.set_title(title);
         Ok(())
     }
 
@@ -793,6 +968,134 @@ impl Window {
         Ok(())
     }
 
+    /// Set the cursor shown while the pointer is over this window.
+    pub fn set_cursor(&self, icon: CursorIcon) -> AureaResult<()> {
+        let name = CString::new(icon.css_name()).map_err(|_| AureaError::ElementOperationFailed)?;
+        let result = unsafe { ng_platform_window_set_cursor_icon(self.handle, name.as_ptr()) };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+
+    /// Use an RGBA8 image as the cursor. `hotspot` is the pixel within the
+    /// image that tracks the pointer position.
+    ///
+    /// The image is taken to be in device pixels, like Canvas content, so it
+    /// appears at its natural size on high-DPI displays. Call
+    /// [`Self::set_cursor`] to go back to a standard shape.
+    pub fn set_custom_cursor(&self, image: &Image, hotspot: (u32, u32)) -> AureaResult<()> {
+        let (Ok(hotspot_x), Ok(hotspot_y)) = (i32::try_from(hotspot.0), i32::try_from(hotspot.1))
+        else {
+            return Err(AureaError::ElementOperationFailed);
+        };
+        if !is_rgba_image(image) || hotspot.0 >= image.width || hotspot.1 >= image.height {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let result = unsafe {
+            ng_platform_window_set_custom_cursor(
+                self.handle,
+                image.data.as_ptr(),
+                image.width,
+                image.height,
+                hotspot_x,
+                hotspot_y,
+            )
+        };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+
+    /// Answer the drag currently over this window, usually from a
+    /// [`WindowEvent::DragEntered`] or [`WindowEvent::DragOver`] handler.
+    ///
+    /// Every new drag starts rejected ([`DropEffect::None`]); the effect
+    /// chosen here is shown by the pointer and reported with
+    /// [`WindowEvent::Dropped`].
+    pub fn set_drop_effect(&self, effect: DropEffect) -> AureaResult<()> {
+        let result = unsafe { ng_platform_window_set_drop_effect(self.handle, effect.to_raw()) };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+
+    /// Drag `data` out of this window, e.g. content picked from a Canvas.
+    ///
+    /// Call while a mouse button is held, typically from a `MouseMove`
+    /// handler once the pointer has travelled a few pixels. `preview` is an
+    /// RGBA8 image in device pixels that follows the pointer; pass an empty
+    /// image for the platform default.
+    pub fn start_drag(&self, data: DragData, preview: &Image) -> AureaResult<()> {
+        let (kind, items) = data.to_raw();
+        let items = items
+            .into_iter()
+            .map(CString::new)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| AureaError::ElementOperationFailed)?;
+        let pointers: Vec<*const c_char> = items.iter().map(|item| item.as_ptr()).collect();
+        let count =
+            i32::try_from(pointers.len()).map_err(|_| AureaError::ElementOperationFailed)?;
+        let has_preview = preview.width != 0 && preview.height != 0;
+        if count == 0 || (has_preview && !is_rgba_image(preview)) {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        let result = unsafe {
+            ng_platform_window_start_drag(
+                self.handle,
+                kind,
+                pointers.as_ptr(),
+                count,
+                if has_preview {
+                    preview.data.as_ptr()
+                } else {
+                    ptr::null()
+                },
+                preview.width,
+                preview.height,
+            )
+        };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+
+    /// Allow the platform input method to compose text for this window.
+    ///
+    /// While allowed, composition is reported through
+    /// [`WindowEvent::ImePreedit`] and [`WindowEvent::ImeCommit`] instead of
+    /// [`WindowEvent::TextInput`], and keys the input method consumes do not
+    /// arrive as [`WindowEvent::KeyInput`]. A focused native text widget
+    /// keeps its own input method. Disabled by default.
+    pub fn set_ime_allowed(&self, allowed: bool) -> AureaResult<()> {
+        let result = unsafe { ng_platform_window_set_ime_allowed(self.handle, i32::from(allowed)) };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+
+    /// Tell the input method where the text cursor is, in logical window
+    /// coordinates, so the candidate window can be placed next to it.
+    pub fn set_ime_cursor_area(&self, area: Rect) -> AureaResult<()> {
+        let result = unsafe {
+            ng_platform_window_set_ime_cursor_area(
+                self.handle,
+                area.x,
+                area.y,
+                area.width,
+                area.height,
+            )
+        };
+        if result != 0 {
+            return Err(AureaError::ElementOperationFailed);
+        }
+        Ok(())
+    }
+
     /// Get the native window handle
     pub fn handle(&self) -> *mut c_void {
         self.handle
@@ -803,6 +1106,10 @@ impl Window {
         unsafe {
             ng_platform_window_show(self.handle);
         }
+        // Windows built hidden have no frame until now.
+        if let Some((outer, content)) = window_frame(self.handle) {
+            self.accessibility.set_window_bounds(outer, content);
+        }
     }
 
     /// Hide the window (without destroying it)
@@ -835,11 +1142,20 @@ impl Drop for Window {
     }
 }
 
+/// A non-empty image whose data holds exactly `width * height` RGBA8 pixels.
+pub(crate) fn is_rgba_image(image: &Image) -> bool {
+    let expected = (image.width as usize)
+        .checked_mul(image.height as usize)
+        .and_then(|pixels| pixels.checked_mul(4));
+    image.width != 0 && image.height != 0 && expected == Some(image.data.len())
+}
+
 unsafe impl Send for Window {}
 unsafe impl Sync for Window {}
 
 /// Read the OS clipboard as a UTF-8 string.
 /// Returns `None` if the clipboard is empty or does not contain text.
+/// See [`crate::clipboard::Clipboard`] for other content types.
 pub fn clipboard_text() -> Option<String> {
     let ptr = unsafe { ng_platform_get_clipboard_text() };
     if ptr.is_null() {
@@ -0,0 +1,125 @@
+use crate::ffi::{
+    NGMonitorInfo, ng_platform_monitor_count, ng_platform_monitor_info, ng_platform_primary_monitor,
+};
+use crate::render::Rect;
+use std::ffi::CStr;
+use std::mem;
+use std::os::raw::c_int;
+
+/// A snapshot of a display, identified by its position in the platform's
+/// monitor list.
+///
+/// Indices and geometry change when displays are connected, removed or
+/// rearranged, so look monitors up again after
+/// [`WindowEvent::MonitorsChanged`](super::WindowEvent::MonitorsChanged)
+/// rather than keeping them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    index: u32,
+    name: String,
+    bounds: Rect,
+    work_area: Rect,
+    scale_factor: f32,
+    refresh_rate: Option<f32>,
+}
+
+impl Monitor {
+    /// Every connected display, in platform order.
+    pub fn all() -> Vec<Self> {
+        let count = unsafe { ng_platform_monitor_count() };
+        (0..count).filter_map(Self::from_raw).collect()
+    }
+
+    /// The primary display, or the first one where the platform has no
+    /// notion of a primary display (Wayland).
+    pub fn primary() -> Option<Self> {
+        Self::from_raw(unsafe { ng_platform_primary_monitor() })
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// A human-readable name such as the panel model; may be empty.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The display's area in logical pixels, in the desktop's coordinate
+    /// space shared by all monitors.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// The part of [`Self::bounds`] not covered by panels and docks, where
+    /// windows and popups should be placed.
+    pub fn work_area(&self) -> Rect {
+        self.work_area
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Refresh rate in Hz, when the platform reports one.
+    pub fn refresh_rate(&self) -> Option<f32> {
+        self.refresh_rate
+    }
+
+    /// A platform monitor index; negative or stale indices give `None`.
+    pub(crate) fn from_raw(index: c_int) -> Option<Self> {
+        let mut info: NGMonitorInfo = unsafe { mem::zeroed() };
+        if index < 0 || unsafe { ng_platform_monitor_info(index, &mut info) } != 0 {
+            return None;
+        }
+        Self::from_info(index, &info)
+    }
+
+    fn from_info(index: c_int, info: &NGMonitorInfo) -> Option<Self> {
+        let index = u32::try_from(index).ok()?;
+        let rect = |x: c_int, y: c_int, width: c_int, height: c_int| {
+            Rect::new(x as f32, y as f32, width as f32, height as f32)
+        };
+        let name = unsafe { CStr::from_ptr(info.name.as_ptr()) };
+        Some(Self {
+            index,
+            name: name.to_string_lossy().into_owned(),
+            bounds: rect(info.x, info.y, info.width, info.height),
+            work_area: rect(info.work_x, info.work_y, info.work_width, info.work_height),
+            scale_factor: info.scale_factor,
+            refresh_rate: (info.refresh_millihertz > 0)
+                .then(|| info.refresh_millihertz as f32 / 1000.0),
+        })
+    }
+
+    /// Inverse of [`Self::from_raw`]. Out-of-range indices become an index
+    /// no platform has, so the call fails rather than picking a default.
+    pub(crate) fn to_raw(&self) -> c_int {
+        c_int::try_from(self.index).unwrap_or(c_int::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn info_converts_to_logical_geometry_and_hertz() {
+        let mut info: NGMonitorInfo = unsafe { mem::zeroed() };
+        for (slot, byte) in info.name.iter_mut().zip(b"DELL U2720Q") {
+            *slot = *byte as _;
+        }
+        (info.width, info.height) = (2560, 1440);
+        (info.work_y, info.work_width, info.work_height) = (32, 2560, 1408);
+        info.scale_factor = 2.0;
+        info.refresh_millihertz = 59_951;
+
+        let monitor = Monitor::from_info(1, &info).unwrap();
+        assert_eq!(monitor.name(), "DELL U2720Q");
+        assert_eq!(monitor.work_area(), Rect::new(0.0, 32.0, 2560.0, 1408.0));
+        assert_eq!(monitor.refresh_rate(), Some(59.951));
+
+        info.refresh_millihertz = 0;
+        assert_eq!(Monitor::from_info(1, &info).unwrap().refresh_rate(), None);
+    }
+}
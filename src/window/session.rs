@@ -0,0 +1,331 @@
+//! Saving and restoring window geometry across runs.
+
+use super::state::has_normal_geometry;
+use super::{Monitor, Window};
+use crate::ffi::{ng_platform_window_get_position, ng_platform_window_get_size};
+use crate::render::Rect;
+use crate::sync::lock;
+use crate::{AureaError, AureaResult};
+use std::fmt::{self, Display, Formatter};
+use std::os::raw::c_void;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// A snapshot of a window's placement, taken with [`Window::save_state`]
+/// and applied with [`Window::restore_state`].
+///
+/// Position and size are in logical pixels, so they carry over between
+/// monitors of different scale. The text form written by `Display` and read
+/// by `FromStr` is one `key=value` pair per line:
+///
+/// ```text
+/// x=120
+/// y=80
+/// width=1280
+/// height=800
+/// maximized=false
+/// fullscreen=false
+/// monitor=DELL U2720Q
+/// scale_factor=2
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    /// Name of the monitor the window was on, used to bring it back there
+    /// when the layout has changed.
+    pub monitor: Option<String>,
+    /// Scale factor of that monitor when saved; see [`Self::placement`].
+    pub scale_factor: f32,
+}
+
+impl WindowState {
+    /// Where the window should go on `monitors`, as `(x, y, width, height)`.
+    ///
+    /// The window stays on the monitor it overlaps most. When it overlaps
+    /// none, it moves to the monitor it was saved on, or the first one. If
+    /// that is the monitor it was saved on and the monitor's scale factor has
+    /// changed since, the window's offset in the work area is rescaled so it
+    /// keeps its place on the panel; like a live scale change, this keeps its
+    /// logical size. It is then shrunk and moved to fit the work area.
+    pub fn placement(&self, monitors: &[Monitor]) -> (i32, i32, u32, u32) {
+        let areas: Vec<Rect> = monitors.iter().map(Monitor::work_area).collect();
+        let saved_on = monitors
+            .iter()
+            .position(|monitor| Some(monitor.name()) == self.monitor.as_deref());
+        let rect = Rect::new(
+            self.x as f32,
+            self.y as f32,
+            self.width as f32,
+            self.height as f32,
+        );
+        let Some(target) = target_area(rect, &areas, saved_on) else {
+            return (self.x, self.y, self.width, self.height);
+        };
+        let area = areas[target];
+        let rect = match saved_on {
+            Some(saved) if saved == target => rescale_offset(
+                rect,
+                area,
+                self.scale_factor / monitors[saved].scale_factor(),
+            ),
+            _ => rect,
+        };
+        fit_to(rect, area)
+    }
+
+    fn invalid(msg: impl Into<String>) -> AureaError {
+        AureaError::InvalidWindowState(msg.into())
+    }
+}
+
+/// Index of the work area `rect` belongs on.
+fn target_area(rect: Rect, areas: &[Rect], saved_on: Option<usize>) -> Option<usize> {
+    let overlap = |area: &Rect| {
+        let width = (rect.x + rect.width).min(area.x + area.width) - rect.x.max(area.x);
+        let height = (rect.y + rect.height).min(area.y + area.height) - rect.y.max(area.y);
+        width.max(0.0) * height.max(0.0)
+    };
+    areas
+        .iter()
+        .map(overlap)
+        .enumerate()
+        .filter(|(_, overlap)| *overlap > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+        .or_else(|| {
+            let index = saved_on.unwrap_or(0);
+            (index < areas.len()).then_some(index)
+        })
+}
+
+/// Scale `rect`'s offset from the top-left of `area` by `ratio`, the saved
+/// scale factor over the current one.
+fn rescale_offset(rect: Rect, area: Rect, ratio: f32) -> Rect {
+    if !ratio.is_finite() || ratio <= 0.0 {
+        return rect;
+    }
+    Rect::new(
+        area.x + (rect.x - area.x) * ratio,
+        area.y + (rect.y - area.y) * ratio,
+        rect.width,
+        rect.height,
+    )
+}
+
+// Both rects hold whole logical pixels that came from integers, and the
+// clamps keep the results within the inputs' range.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn fit_to(rect: Rect, area: Rect) -> (i32, i32, u32, u32) {
+    let width = rect.width.min(area.width);
+    let height = rect.height.min(area.height);
+    let x = rect.x.clamp(area.x, area.x + area.width - width);
+    let y = rect.y.clamp(area.y, area.y + area.height - height);
+    (x as i32, y as i32, width as u32, height as u32)
+}
+
+impl Display for WindowState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "x={}", self.x)?;
+        writeln!(f, "y={}", self.y)?;
+        writeln!(f, "width={}", self.width)?;
+        writeln!(f, "height={}", self.height)?;
+        writeln!(f, "maximized={}", self.maximized)?;
+        writeln!(f, "fullscreen={}", self.fullscreen)?;
+        if let Some(monitor) = &self.monitor {
+            writeln!(f, "monitor={}", monitor)?;
+        }
+        writeln!(f, "scale_factor={}", self.scale_factor)
+    }
+}
+
+impl FromStr for WindowState {
+    type Err = AureaError;
+
+    /// Unknown keys are skipped so newer versions can add fields; missing
+    /// geometry is an error.
+    fn from_str(text: &str) -> AureaResult<Self> {
+        fn parse<T: FromStr>(key: &str, value: &str) -> AureaResult<T> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| WindowState::invalid(format!("bad value for {key}: {value}")))
+        }
+
+        let (mut x, mut y, mut width, mut height) = (None, None, None, None);
+        let mut state = WindowState {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            maximized: false,
+            fullscreen: false,
+            monitor: None,
+            scale_factor: 1.0,
+        };
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| Self::invalid(format!("expected key=value, got {line}")))?;
+            match key.trim() {
+                "x" => x = Some(parse(key, value)?),
+                "y" => y = Some(parse(key, value)?),
+                "width" => width = Some(parse(key, value)?),
+                "height" => height = Some(parse(key, value)?),
+                "maximized" => state.maximized = parse(key, value)?,
+                "fullscreen" => state.fullscreen = parse(key, value)?,
+                "monitor" => state.monitor = Some(value.to_owned()),
+                "scale_factor" => state.scale_factor = parse(key, value)?,
+                _ => {}
+            }
+        }
+        let missing = |key: &str| Self::invalid(format!("missing {key}"));
+        state.x = x.ok_or_else(|| missing("x"))?;
+        state.y = y.ok_or_else(|| missing("y"))?;
+        state.width = width.ok_or_else(|| missing("width"))?;
+        state.height = height.ok_or_else(|| missing("height"))?;
+        Ok(state)
+    }
+}
+
+/// A window's geometry the last time it was neither minimized, maximized
+/// nor fullscreen: the geometry it returns to, and what
+/// [`Window::save_state`] records.
+#[derive(Debug, Default)]
+pub(super) struct NormalGeometry(Mutex<Option<(i32, i32, u32, u32)>>);
+
+impl NormalGeometry {
+    /// Record `handle`'s geometry unless it is in one of those states; called
+    /// when the window is created and whenever it moves or resizes.
+    pub(super) fn track(&self, handle: *mut c_void) {
+        if !has_normal_geometry(handle) {
+            return;
+        }
+        let (mut x, mut y, mut width, mut height) = (0, 0, 0, 0);
+        unsafe {
+            ng_platform_window_get_position(handle, &mut x, &mut y);
+            ng_platform_window_get_size(handle, &mut width, &mut height);
+        }
+        *lock(&self.0) = Some((x, y, width.cast_unsigned(), height.cast_unsigned()));
+    }
+
+    fn get(&self) -> Option<(i32, i32, u32, u32)> {
+        *lock(&self.0)
+    }
+}
+
+impl Window {
+    /// Snapshot the window's placement. A maximized or fullscreen window
+    /// records the geometry it had before, which it returns to when it is
+    /// restored, along with the flag.
+    pub fn save_state(&self) -> WindowState {
+        let (x, y, width, height) = self.normal_geometry.get().unwrap_or_else(|| {
+            let (x, y) = self.position();
+            let (width, height) = self.size();
+            (x, y, width, height)
+        });
+        let monitor = self.current_monitor();
+        WindowState {
+            x,
+            y,
+            width,
+            height,
+            maximized: self.is_maximized(),
+            fullscreen: self.is_fullscreen(),
+            scale_factor: monitor
+                .as_ref()
+                .map_or_else(|| self.scale_factor(), Monitor::scale_factor),
+            monitor: monitor.map(|monitor| monitor.name().to_owned()),
+        }
+    }
+
+    /// Move and resize the window to `state`, fitted to the monitors
+    /// connected now (see [`WindowState::placement`]), then maximize or
+    /// enter fullscreen as recorded.
+    pub fn restore_state(&self, state: &WindowState) -> AureaResult<()> {
+        let monitors = Monitor::all();
+        let (x, y, width, height) = state.placement(&monitors);
+        if self.is_fullscreen() {
+            self.set_fullscreen(None)?;
+        }
+        if self.is_maximized() {
+            self.set_maximized(false)?;
+        }
+        self.set_size(width, height);
+        self.set_position(x, y);
+
+        if state.fullscreen {
+            // The monitor the window now sits on, not necessarily the saved one.
+            self.set_fullscreen(self.current_monitor())
+        } else if state.maximized {
+            self.set_maximized(true)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(x: i32, y: i32, width: u32, height: u32) -> WindowState {
+        WindowState {
+            x,
+            y,
+            width,
+            height,
+            maximized: true,
+            fullscreen: false,
+            monitor: Some("Built-in Retina Display".to_owned()),
+            scale_factor: 2.0,
+        }
+    }
+
+    #[test]
+    fn text_form_round_trips() {
+        let saved = state(-1800, 40, 1200, 900);
+        let text = saved.to_string();
+        assert_eq!(text.parse::<WindowState>().unwrap(), saved);
+
+        let with_extra = format!("{text}future_key=1\n");
+        assert_eq!(with_extra.parse::<WindowState>().unwrap(), saved);
+        assert!("x=1\ny=2\nwidth=3".parse::<WindowState>().is_err());
+        assert!(
+            "x=1\ny=2\nwidth=3\nheight=wide"
+                .parse::<WindowState>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn windows_off_screen_move_to_a_connected_monitor() {
+        let laptop = Rect::new(0.0, 32.0, 1440.0, 868.0);
+        let external = Rect::new(1440.0, 0.0, 2560.0, 1440.0);
+
+        // Saved on a monitor left of the laptop that has since gone away.
+        let rect = Rect::new(-1800.0, 40.0, 1200.0, 900.0);
+        assert_eq!(target_area(rect, &[laptop, external], Some(1)), Some(1));
+        assert_eq!(target_area(rect, &[laptop, external], None), Some(0));
+        assert_eq!(fit_to(rect, laptop), (0, 32, 1200, 868));
+
+        // Mostly on the external monitor: stays there.
+        let rect = Rect::new(1300.0, 100.0, 800.0, 600.0);
+        assert_eq!(target_area(rect, &[laptop, external], Some(0)), Some(1));
+        assert_eq!(target_area(rect, &[], Some(0)), None);
+    }
+
+    #[test]
+    fn rescaled_monitor_keeps_the_window_in_place() {
+        // Saved at 1x on a 4K panel, now running at 2x.
+        let area = Rect::new(0.0, 0.0, 1920.0, 1080.0);
+        let rect = Rect::new(2000.0, 1000.0, 800.0, 400.0);
+        let moved = rescale_offset(rect, area, 1.0 / 2.0);
+        assert_eq!(fit_to(moved, area), (1000, 500, 800, 400));
+        assert_eq!(rescale_offset(rect, area, f32::NAN), rect);
+    }
+}
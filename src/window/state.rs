@@ -0,0 +1,192 @@
+use super::{Monitor, Window, WindowEvent};
+use crate::ffi::*;
+use crate::render::Rect;
+use crate::{AureaError, AureaResult};
+use std::mem;
+use std::os::raw::{c_int, c_void};
+
+// `NG_WINDOW_STATE_*` in `common/platform_api.h`.
+const MINIMIZED: u32 = 1;
+const MAXIMIZED: u32 = 2;
+const FULLSCREEN: u32 = 4;
+const ALWAYS_ON_TOP: u32 = 8;
+const RESIZABLE: u32 = 16;
+const DECORATED: u32 = 32;
+
+/// The event for one reported state flag. Minimizing is reported through
+/// the lifecycle callback instead.
+pub(crate) fn state_event(flag: u32, enabled: bool) -> Option<WindowEvent> {
+    let event = match (flag, enabled) {
+        (MAXIMIZED, true) => WindowEvent::Maximized,
+        (MAXIMIZED, false) => WindowEvent::Unmaximized,
+        (FULLSCREEN, true) => WindowEvent::EnteredFullscreen,
+        (FULLSCREEN, false) => WindowEvent::ExitedFullscreen,
+        (ALWAYS_ON_TOP, always_on_top) => WindowEvent::AlwaysOnTopChanged { always_on_top },
+        (RESIZABLE, resizable) => WindowEvent::ResizableChanged { resizable },
+        (DECORATED, decorated) => WindowEvent::DecorationsChanged { decorated },
+        _ => return None,
+    };
+    Some(event)
+}
+
+/// The window's outer frame and content rect on the desktop, once it has been
+/// realized.
+pub(super) fn window_frame(handle: *mut c_void) -> Option<(Rect, Rect)> {
+    let mut frame: NGWindowFrame = unsafe { mem::zeroed() };
+    check(unsafe { ng_platform_window_get_frame(handle, &mut frame) }).ok()?;
+    let rect = |x: c_int, y: c_int, width: c_int, height: c_int| {
+        Rect::new(x as f32, y as f32, width as f32, height as f32)
+    };
+    Some((
+        rect(frame.x, frame.y, frame.width, frame.height),
+        rect(
+            frame.content_x,
+            frame.content_y,
+            frame.content_width,
+            frame.content_height,
+        ),
+    ))
+}
+
+/// Whether `handle` has geometry of its own, that is, it is not minimized,
+/// maximized or fullscreen.
+pub(super) fn has_normal_geometry(handle: *mut c_void) -> bool {
+    unsafe { ng_platform_window_get_state(handle) & (MINIMIZED | MAXIMIZED | FULLSCREEN) == 0 }
+}
+
+/// A size limit for the platform; `None` is 0x0, which removes it.
+pub(super) fn raw_size(size: Option<(u32, u32)>) -> AureaResult<(c_int, c_int)> {
+    let (width, height) = size.unwrap_or((0, 0));
+    match (c_int::try_from(width), c_int::try_from(height)) {
+        (Ok(width), Ok(height)) => Ok((width, height)),
+        _ => Err(AureaError::ElementOperationFailed),
+    }
+}
+
+/// Window states: fullscreen, maximized, minimized, size limits, and
+/// decorations. Each change, whether requested here or made by the user,
+/// arrives as a [`WindowEvent`] once the window manager applies it.
+impl Window {
+    fn has_state(&self, flag: u32) -> bool {
+        unsafe { ng_platform_window_get_state(self.handle) & flag != 0 }
+    }
+
+    /// Cover `monitor` with the window, or leave fullscreen with `None`.
+    pub fn set_fullscreen(&self, monitor: Option<Monitor>) -> AureaResult<()> {
+        let result = unsafe {
+            match monitor {
+                Some(monitor) => {
+                    ng_platform_window_set_fullscreen(self.handle, 1, monitor.to_raw())
+                }
+                None => ng_platform_window_set_fullscreen(self.handle, 0, -1),
+            }
+        };
+        check(result)
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.has_state(FULLSCREEN)
+    }
+
+    pub fn set_maximized(&self, maximized: bool) -> AureaResult<()> {
+        check(unsafe { ng_platform_window_set_maximized(self.handle, c_int::from(maximized)) })
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        self.has_state(MAXIMIZED)
+    }
+
+    /// Minimize the window, or restore it. Reported as
+    /// [`WindowEvent::Minimized`] and [`WindowEvent::Restored`].
+    pub fn set_minimized(&self, minimized: bool) -> AureaResult<()> {
+        check(unsafe { ng_platform_window_set_minimized(self.handle, c_int::from(minimized)) })
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        self.has_state(MINIMIZED)
+    }
+
+    /// Smallest size the user can resize to, in logical pixels; `None`
+    /// removes the limit.
+    pub fn set_min_size(&self, size: Option<(u32, u32)>) -> AureaResult<()> {
+        let (width, height) = raw_size(size)?;
+        check(unsafe { ng_platform_window_set_min_size(self.handle, width, height) })
+    }
+
+    /// Largest size the user can resize to, in logical pixels; `None`
+    /// removes the limit. A larger window shrinks and reports
+    /// [`WindowEvent::Resized`].
+    pub fn set_max_size(&self, size: Option<(u32, u32)>) -> AureaResult<()> {
+        let (width, height) = raw_size(size)?;
+        check(unsafe { ng_platform_window_set_max_size(self.handle, width, height) })
+    }
+
+    pub fn set_resizable(&self, resizable: bool) -> AureaResult<()> {
+        check(unsafe { ng_platform_window_set_resizable(self.handle, c_int::from(resizable)) })
+    }
+
+    pub fn is_resizable(&self) -> bool {
+        self.has_state(RESIZABLE)
+    }
+
+    /// Keep the window above others. This is a hint: some window managers
+    /// and Wayland compositors ignore it, in which case no
+    /// [`WindowEvent::AlwaysOnTopChanged`] follows.
+    pub fn set_always_on_top(&self, always_on_top: bool) -> AureaResult<()> {
+        let result = unsafe {
+            ng_platform_window_set_always_on_top(self.handle, c_int::from(always_on_top))
+        };
+        check(result)
+    }
+
+    pub fn is_always_on_top(&self) -> bool {
+        self.has_state(ALWAYS_ON_TOP)
+    }
+
+    /// Show or hide the title bar and borders.
+    pub fn set_decorations(&self, decorated: bool) -> AureaResult<()> {
+        check(unsafe { ng_platform_window_set_decorations(self.handle, c_int::from(decorated)) })
+    }
+
+    pub fn has_decorations(&self) -> bool {
+        self.has_state(DECORATED)
+    }
+
+    /// Give the window an alpha channel, so a Canvas whose background
+    /// colour is translucent shows the desktop behind it.
+    ///
+    /// Fails when the display offers no alpha-capable surface. The native
+    /// surface is recreated, so call this before creating GPU renderers.
+    pub fn set_transparent(&self, transparent: bool) -> AureaResult<()> {
+        check(unsafe { ng_platform_window_set_transparent(self.handle, c_int::from(transparent)) })
+    }
+
+    /// The monitor holding most of the window.
+    pub fn current_monitor(&self) -> Option<Monitor> {
+        Monitor::from_raw(unsafe { ng_platform_window_current_monitor(self.handle) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_flags_map_to_events() {
+        assert!(matches!(
+            state_event(MAXIMIZED, false),
+            Some(WindowEvent::Unmaximized)
+        ));
+        assert!(matches!(
+            state_event(FULLSCREEN, true),
+            Some(WindowEvent::EnteredFullscreen)
+        ));
+        assert!(matches!(
+            state_event(DECORATED, false),
+            Some(WindowEvent::DecorationsChanged { decorated: false })
+        ));
+        assert!(state_event(MINIMIZED, true).is_none());
+        assert!(raw_size(Some((u32::MAX, 1))).is_err());
+        assert_eq!(raw_size(None).unwrap(), (0, 0));
+    }
+}